@@ -0,0 +1,50 @@
+//! Signing throughput, run with cargo bench and compare against
+//! cargo bench --features global-context to see the cost of building a signing context
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use deep_space::fixtures::sign_doc_vectors;
+use deep_space::PrivateKey;
+use sha2::{Digest, Sha256};
+
+fn key_derivation(c: &mut Criterion) {
+    let vector = &sign_doc_vectors()[0];
+    c.bench_function("from_hd_wallet_path", |b| {
+        b.iter(|| {
+            PrivateKey::from_hd_wallet_path(
+                black_box(&vector.hd_path),
+                black_box(&vector.mnemonic),
+                "",
+            )
+            .unwrap()
+        })
+    });
+    let key = vector.private_key();
+    c.bench_function("to_address", |b| {
+        b.iter(|| black_box(key).to_address(&vector.prefix).unwrap())
+    });
+}
+
+fn sign_doc_hashing(c: &mut Criterion) {
+    let sign_doc = sign_doc_vectors()[0].sign_doc_bytes();
+    c.bench_function("sign_doc_sha256", |b| {
+        b.iter(|| Sha256::digest(black_box(&sign_doc)))
+    });
+}
+
+fn signing(c: &mut Criterion) {
+    let vector = &sign_doc_vectors()[0];
+    let key = vector.private_key();
+    let sign_doc = vector.sign_doc_bytes();
+    c.bench_function("sign_bytes", |b| {
+        b.iter(|| key.sign_bytes(black_box(&sign_doc)).unwrap())
+    });
+    let messages = vector.messages();
+    c.bench_function("sign_std_msg", |b| {
+        b.iter(|| {
+            key.sign_std_msg(&messages, vector.message_args(), vector.memo.clone())
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, key_derivation, sign_doc_hashing, signing);
+criterion_main!(benches);
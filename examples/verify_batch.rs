@@ -0,0 +1,41 @@
+//! Compares verifying signatures one by one to verify_batch, run with
+//! cargo run --release --example verify_batch
+extern crate deep_space;
+use deep_space::public_key::verify_batch;
+use deep_space::PrivateKey;
+use std::time::Instant;
+
+const SIGNATURES: usize = 20_000;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("Signing {} messages", SIGNATURES);
+    let mut items = Vec::with_capacity(SIGNATURES);
+    for i in 0..SIGNATURES {
+        let private_key = PrivateKey::from_secret(&(i as u64).to_be_bytes());
+        let public_key = private_key.to_public_key("cosmospub")?;
+        let message = format!("sign doc {}", i).into_bytes();
+        let signature = private_key.sign_bytes(&message)?;
+        items.push((message, signature, public_key));
+    }
+
+    let start = Instant::now();
+    let sequential: Vec<bool> = items
+        .iter()
+        .map(|(message, signature, key)| key.verify(message, signature))
+        .collect();
+    let sequential_time = start.elapsed();
+
+    let start = Instant::now();
+    let batch = verify_batch(&items);
+    let batch_time = start.elapsed();
+
+    assert_eq!(sequential, batch);
+    assert!(batch.iter().all(|valid| *valid));
+    println!("Sequential: {:?}", sequential_time);
+    println!("Batch: {:?}", batch_time);
+    println!(
+        "Speedup: {:.2}x",
+        sequential_time.as_secs_f64() / batch_time.as_secs_f64()
+    );
+    Ok(())
+}
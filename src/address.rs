@@ -58,6 +58,34 @@ impl Address {
         Ok(())
     }
 
+    /// Returns a copy of this address with another prefix, for example the osmo address of
+    /// the same account as a cosmos address
+    pub fn with_prefix<T: Into<String>>(&self, prefix: T) -> Result<Address, AddressError> {
+        Address::from_bytes(self.bytes, prefix)
+    }
+
+    /// Returns true if both addresses have the same bytes, regardless of their prefix.
+    /// Addresses derived from the same key with coin type 118 are the same account on
+    /// every chain, equality also compares the prefix.
+    pub fn same_account(&self, other: &Address) -> bool {
+        self.bytes == other.bytes
+    }
+
+    /// Parses two addresses in any format FromStr accepts and compares their bytes, see
+    /// same_account
+    pub fn same_account_str(a: &str, b: &str) -> Result<bool, AddressError> {
+        let a: Address = a.parse()?;
+        let b: Address = b.parse()?;
+        Ok(a.same_account(&b))
+    }
+
+    /// Parses an address in any format FromStr accepts and encodes it with the prefix, so
+    /// addresses from different chains can be stored and compared as strings
+    pub fn normalize(address: &str, prefix: &str) -> Result<String, AddressError> {
+        let address: Address = address.parse()?;
+        address.to_bech32(prefix)
+    }
+
     /// Obtain a bech32 encoded address with a given prefix.
     ///
     /// * `hrp` - A prefix for bech32 encoding. The convention for addresses
@@ -160,3 +188,22 @@ fn test_parse() {
         .parse()
         .unwrap();
 }
+
+#[test]
+fn test_same_account() {
+    let cosmos: Address = "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp"
+        .parse()
+        .unwrap();
+    let osmo = cosmos.with_prefix("osmo").unwrap();
+    assert_ne!(cosmos, osmo);
+    assert!(cosmos.same_account(&osmo));
+    assert_eq!(osmo.get_prefix(), "osmo");
+    assert!(!cosmos.same_account(&Address::from_bytes([0; 20], "cosmos").unwrap()));
+
+    let normalized = Address::normalize(&osmo.to_string(), "cosmos").unwrap();
+    assert_eq!(normalized, cosmos.to_string());
+    assert!(Address::same_account_str(&normalized, &osmo.to_string()).unwrap());
+    let hex = "67f7050ce9d1bc434af124c82c7b16e8c0d3f4ba";
+    assert!(Address::same_account_str(hex, &normalized).unwrap());
+    assert!(Address::same_account_str("osmo1invalid", &normalized).is_err());
+}
@@ -0,0 +1,212 @@
+//! Legacy Amino JSON rendering of transactions. Older explorers, Ledger flows and chains that
+//! still expose the pre Stargate REST endpoints expect a StdTx in Amino JSON rather than the
+//! protobuf Tx this crate builds. Messages are rendered by the Amino JSON encoders registered
+//! in a MsgRegistry, see MsgRegistry::register_amino_json.
+//!
+//! ```ignore
+//! let tx = private_key.get_signed_tx(&messages, args, "")?;
+//! let std_tx = std_tx_json(&tx, &MsgRegistry::with_defaults())?;
+//! ```
+
+use crate::any::AnyExt;
+use crate::error::CosmosGrpcError;
+use crate::registry::MsgRegistry;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::Input;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::Output;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as ProtoSecp256k1Pubkey;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use serde_json::json;
+use serde_json::Value;
+
+pub const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// Renders a compressed secp256k1 public key the way Amino JSON and CosmJS expect it
+pub fn secp256k1_pubkey_json(key: &[u8]) -> Value {
+    json!({ "type": "tendermint/PubKeySecp256k1", "value": base64::encode(key) })
+}
+
+/// Renders coins as Amino JSON, amounts are strings
+pub fn coins_json(coins: &[ProtoCoin]) -> Value {
+    Value::Array(coins.iter().map(coin_json).collect())
+}
+
+pub fn coin_json(coin: &ProtoCoin) -> Value {
+    json!({ "denom": coin.denom, "amount": coin.amount })
+}
+
+/// Renders an optional coin, an absent coin is rendered as null
+fn option_coin_json(coin: &Option<ProtoCoin>) -> Value {
+    coin.as_ref().map(coin_json).unwrap_or(Value::Null)
+}
+
+pub(crate) fn msg_send_json(msg: &MsgSend) -> Value {
+    json!({
+        "from_address": msg.from_address,
+        "to_address": msg.to_address,
+        "amount": coins_json(&msg.amount),
+    })
+}
+
+pub(crate) fn msg_multi_send_json(msg: &MsgMultiSend) -> Value {
+    let input = |i: &Input| json!({ "address": i.address, "coins": coins_json(&i.coins) });
+    let output = |o: &Output| json!({ "address": o.address, "coins": coins_json(&o.coins) });
+    json!({
+        "inputs": msg.inputs.iter().map(input).collect::<Vec<Value>>(),
+        "outputs": msg.outputs.iter().map(output).collect::<Vec<Value>>(),
+    })
+}
+
+pub(crate) fn msg_delegate_json(msg: &MsgDelegate) -> Value {
+    json!({
+        "delegator_address": msg.delegator_address,
+        "validator_address": msg.validator_address,
+        "amount": option_coin_json(&msg.amount),
+    })
+}
+
+pub(crate) fn msg_undelegate_json(msg: &MsgUndelegate) -> Value {
+    json!({
+        "delegator_address": msg.delegator_address,
+        "validator_address": msg.validator_address,
+        "amount": option_coin_json(&msg.amount),
+    })
+}
+
+pub(crate) fn msg_begin_redelegate_json(msg: &MsgBeginRedelegate) -> Value {
+    json!({
+        "delegator_address": msg.delegator_address,
+        "validator_src_address": msg.validator_src_address,
+        "validator_dst_address": msg.validator_dst_address,
+        "amount": option_coin_json(&msg.amount),
+    })
+}
+
+pub(crate) fn msg_withdraw_delegator_reward_json(msg: &MsgWithdrawDelegatorReward) -> Value {
+    json!({
+        "delegator_address": msg.delegator_address,
+        "validator_address": msg.validator_address,
+    })
+}
+
+pub(crate) fn msg_deposit_json(msg: &MsgDeposit) -> Value {
+    json!({
+        "proposal_id": msg.proposal_id.to_string(),
+        "depositor": msg.depositor,
+        "amount": coins_json(&msg.amount),
+    })
+}
+
+pub(crate) fn msg_vote_json(msg: &MsgVote) -> Value {
+    json!({
+        "proposal_id": msg.proposal_id.to_string(),
+        "voter": msg.voter,
+        "option": msg.option,
+    })
+}
+
+/// Renders a signed transaction as a legacy Amino JSON StdTx. Every message type must have an
+/// Amino JSON encoder registered and every signer must use a secp256k1 key.
+pub fn std_tx_json(tx: &Tx, registry: &MsgRegistry) -> Result<Value, CosmosGrpcError> {
+    let body = match &tx.body {
+        Some(body) => body,
+        None => return Err(CosmosGrpcError::BadInput("Tx has no body".to_string())),
+    };
+    let auth_info = match &tx.auth_info {
+        Some(auth_info) => auth_info,
+        None => return Err(CosmosGrpcError::BadInput("Tx has no auth info".to_string())),
+    };
+    let msgs = body
+        .messages
+        .iter()
+        .map(|m| registry.amino_json(m))
+        .collect::<Result<Vec<Value>, CosmosGrpcError>>()?;
+    let fee = match &auth_info.fee {
+        Some(fee) => json!({
+            "amount": coins_json(&fee.amount),
+            "gas": fee.gas_limit.to_string(),
+        }),
+        None => json!({ "amount": [], "gas": "0" }),
+    };
+    if auth_info.signer_infos.len() != tx.signatures.len() {
+        return Err(CosmosGrpcError::BadInput(
+            "Tx has a different number of signers and signatures".to_string(),
+        ));
+    }
+    let mut signatures = Vec::new();
+    for (signer, signature) in auth_info.signer_infos.iter().zip(tx.signatures.iter()) {
+        let pub_key = match &signer.public_key {
+            Some(key) if key.is::<ProtoSecp256k1Pubkey>() => {
+                key.unpack::<ProtoSecp256k1Pubkey>()?.key
+            }
+            Some(key) => {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "Amino JSON does not support {} keys",
+                    key.type_url
+                )))
+            }
+            None => return Err(CosmosGrpcError::BadInput("Signer has no key".to_string())),
+        };
+        signatures.push(json!({
+            "pub_key": secp256k1_pubkey_json(&pub_key),
+            "signature": base64::encode(signature),
+        }));
+    }
+    Ok(json!({
+        "type": "cosmos-sdk/StdTx",
+        "value": {
+            "msg": msgs,
+            "fee": fee,
+            "signatures": signatures,
+            "memo": body.memo,
+            "timeout_height": body.timeout_height.to_string(),
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sign_doc_vectors;
+
+    #[test]
+    fn test_std_tx_json() {
+        let vector = &sign_doc_vectors()[0];
+        let key = vector.private_key();
+        let tx = key
+            .get_signed_tx(
+                &vector.messages(),
+                vector.message_args(),
+                vector.memo.clone(),
+            )
+            .unwrap();
+        let std_tx = std_tx_json(&tx, &MsgRegistry::with_defaults()).unwrap();
+        assert_eq!(std_tx["type"], "cosmos-sdk/StdTx");
+        let value = &std_tx["value"];
+        assert_eq!(value["msg"][0]["type"], "cosmos-sdk/MsgSend");
+        assert_eq!(value["msg"][0]["value"]["from_address"], vector.address);
+        assert_eq!(value["msg"][0]["value"]["to_address"], vector.to_address);
+        assert_eq!(value["fee"]["gas"], vector.gas_limit.to_string());
+        assert_eq!(value["memo"], vector.memo);
+        let public_key = key.to_public_key("cosmospub").unwrap();
+        assert_eq!(
+            value["signatures"][0]["pub_key"]["value"],
+            base64::encode(public_key.as_bytes())
+        );
+        assert_eq!(
+            value["signatures"][0]["signature"],
+            base64::encode(vector.signature_bytes())
+        );
+
+        // message types without an encoder can not be rendered
+        assert!(std_tx_json(&tx, &MsgRegistry::new()).is_err());
+    }
+}
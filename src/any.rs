@@ -0,0 +1,200 @@
+//! Unpacking of protobuf Any values. Types with a fixed type url implement TypeUrl and can
+//! be unpacked directly, values whose type is only known at runtime are decoded through a
+//! MsgRegistry.
+//!
+//! ```ignore
+//! if any.is::<StakeAuthorization>() {
+//!     let stake: StakeAuthorization = any.unpack()?;
+//! }
+//! let decoded = msg.unpack_dynamic(&registry)?;
+//! ```
+
+use crate::amino::SECP256K1_PUBKEY_TYPE_URL;
+use crate::client::accounts::BASE_ACCOUNT_TYPE_URL;
+use crate::client::accounts::CONTINUOUS_VESTING_ACCOUNT_TYPE_URL;
+use crate::client::accounts::DELAYED_VESTING_ACCOUNT_TYPE_URL;
+use crate::client::accounts::ETH_ACCOUNT_TYPE_URL;
+use crate::client::accounts::MODULE_ACCOUNT_TYPE_URL;
+use crate::client::accounts::PERIODIC_VESTING_ACCOUNT_TYPE_URL;
+use crate::client::accounts::PERMANENT_LOCKED_ACCOUNT_TYPE_URL;
+use crate::client::authz::GENERIC_AUTHORIZATION_TYPE_URL;
+use crate::client::authz::MSG_EXEC_TYPE_URL;
+use crate::client::authz::MSG_GRANT_TYPE_URL;
+use crate::client::authz::MSG_REVOKE_TYPE_URL;
+use crate::client::authz::SEND_AUTHORIZATION_TYPE_URL;
+use crate::client::authz::STAKE_AUTHORIZATION_TYPE_URL;
+use crate::client::bank::MSG_MULTI_SEND_TYPE_URL;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::distribution::MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL;
+use crate::client::feegrant::ALLOWED_MSG_ALLOWANCE_TYPE_URL;
+use crate::client::feegrant::BASIC_ALLOWANCE_TYPE_URL;
+use crate::client::feegrant::PERIODIC_ALLOWANCE_TYPE_URL;
+use crate::client::gov::MSG_DEPOSIT_TYPE_URL;
+use crate::client::gov::MSG_SUBMIT_PROPOSAL_TYPE_URL;
+use crate::client::gov::MSG_VOTE_TYPE_URL;
+use crate::client::ibc::MSG_TRANSFER_TYPE_URL;
+use crate::client::ibc::TENDERMINT_CLIENT_STATE_TYPE_URL;
+use crate::client::staking::ED25519_PUBKEY_TYPE_URL;
+use crate::client::staking::MSG_BEGIN_REDELEGATE_TYPE_URL;
+use crate::client::staking::MSG_DELEGATE_TYPE_URL;
+use crate::client::staking::MSG_UNDELEGATE_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::authz::v1beta1::GenericAuthorization;
+use crate::proto::cosmos::authz::v1beta1::MsgExec;
+use crate::proto::cosmos::authz::v1beta1::MsgGrant;
+use crate::proto::cosmos::authz::v1beta1::MsgRevoke;
+use crate::proto::cosmos::bank::v1beta1::SendAuthorization;
+use crate::proto::cosmos::feegrant::v1beta1::AllowedMsgAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::BasicAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::PeriodicAllowance;
+use crate::proto::cosmos::staking::v1beta1::StakeAuthorization;
+use crate::proto::cosmos::vesting::v1beta1::PermanentLockedAccount;
+use crate::proto::ethermint::types::v1::EthAccount;
+use crate::proto::ibc::applications::transfer::v1::MsgTransfer;
+use crate::proto::ibc::lightclients::tendermint::v1::ClientState as TendermintClientState;
+use crate::registry::DecodedMsg;
+use crate::Msg;
+use crate::MsgRegistry;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::ModuleAccount;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::crypto::ed25519::PubKey as Ed25519PubKey;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as ProtoSecp256k1Pubkey;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::ContinuousVestingAccount;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::DelayedVestingAccount;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::PeriodicVestingAccount;
+use prost::Message;
+use prost_types::Any;
+
+pub const TX_TYPE_URL: &str = "/cosmos.tx.v1beta1.Tx";
+
+/// A prost type that is always packed into an Any under the same type url, implement this
+/// for chain specific types to unpack them with AnyExt::unpack
+pub trait TypeUrl: Message + Default {
+    const TYPE_URL: &'static str;
+}
+
+macro_rules! impl_type_url {
+    ($($ty:ty => $url:expr,)*) => {
+        $(
+            impl TypeUrl for $ty {
+                const TYPE_URL: &'static str = $url;
+            }
+        )*
+    };
+}
+
+impl_type_url! {
+    BaseAccount => BASE_ACCOUNT_TYPE_URL,
+    ModuleAccount => MODULE_ACCOUNT_TYPE_URL,
+    ContinuousVestingAccount => CONTINUOUS_VESTING_ACCOUNT_TYPE_URL,
+    DelayedVestingAccount => DELAYED_VESTING_ACCOUNT_TYPE_URL,
+    PeriodicVestingAccount => PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+    PermanentLockedAccount => PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+    EthAccount => ETH_ACCOUNT_TYPE_URL,
+    GenericAuthorization => GENERIC_AUTHORIZATION_TYPE_URL,
+    SendAuthorization => SEND_AUTHORIZATION_TYPE_URL,
+    StakeAuthorization => STAKE_AUTHORIZATION_TYPE_URL,
+    BasicAllowance => BASIC_ALLOWANCE_TYPE_URL,
+    PeriodicAllowance => PERIODIC_ALLOWANCE_TYPE_URL,
+    AllowedMsgAllowance => ALLOWED_MSG_ALLOWANCE_TYPE_URL,
+    MsgSend => MSG_SEND_TYPE_URL,
+    MsgMultiSend => MSG_MULTI_SEND_TYPE_URL,
+    MsgDelegate => MSG_DELEGATE_TYPE_URL,
+    MsgUndelegate => MSG_UNDELEGATE_TYPE_URL,
+    MsgBeginRedelegate => MSG_BEGIN_REDELEGATE_TYPE_URL,
+    MsgWithdrawDelegatorReward => MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL,
+    MsgSubmitProposal => MSG_SUBMIT_PROPOSAL_TYPE_URL,
+    MsgDeposit => MSG_DEPOSIT_TYPE_URL,
+    MsgVote => MSG_VOTE_TYPE_URL,
+    MsgGrant => MSG_GRANT_TYPE_URL,
+    MsgRevoke => MSG_REVOKE_TYPE_URL,
+    MsgExec => MSG_EXEC_TYPE_URL,
+    MsgTransfer => MSG_TRANSFER_TYPE_URL,
+    ProtoSecp256k1Pubkey => SECP256K1_PUBKEY_TYPE_URL,
+    Ed25519PubKey => ED25519_PUBKEY_TYPE_URL,
+    TendermintClientState => TENDERMINT_CLIENT_STATE_TYPE_URL,
+    Tx => TX_TYPE_URL,
+}
+
+/// Helpers for values packed into an Any, implemented for Any and Msg
+pub trait AnyExt {
+    fn as_any(&self) -> &Any;
+
+    /// Returns true if the value is packed under the type url of T
+    fn is<T: TypeUrl>(&self) -> bool {
+        self.as_any().type_url == T::TYPE_URL
+    }
+
+    /// Decodes the value as T, returns BadInput if it is packed under another type url
+    fn unpack<T: TypeUrl>(&self) -> Result<T, CosmosGrpcError> {
+        let any = self.as_any();
+        if !self.is::<T>() {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Expected {} but got {}",
+                T::TYPE_URL,
+                any.type_url
+            )));
+        }
+        T::decode(any.value.as_slice()).map_err(|error| CosmosGrpcError::DecodeError { error })
+    }
+
+    /// Decodes the value using the type the registry has for its type url
+    fn unpack_dynamic(&self, registry: &MsgRegistry) -> Result<DecodedMsg, CosmosGrpcError> {
+        registry.decode(self.as_any())
+    }
+}
+
+impl AnyExt for Any {
+    fn as_any(&self) -> &Any {
+        self
+    }
+}
+
+impl AnyExt for Msg {
+    fn as_any(&self) -> &Any {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::authz::Authorization;
+
+    #[test]
+    fn test_unpack() {
+        let send = MsgSend {
+            from_address: "cosmos1sender".to_string(),
+            to_address: "cosmos1receiver".to_string(),
+            amount: Vec::new(),
+        };
+        let msg = Msg::new(MSG_SEND_TYPE_URL, send.clone());
+        assert!(msg.is::<MsgSend>());
+        assert_eq!(msg.unpack::<MsgSend>().unwrap(), send);
+        assert!(msg.unpack::<MsgMultiSend>().is_err());
+
+        let decoded = msg.unpack_dynamic(&MsgRegistry::with_defaults()).unwrap();
+        assert_eq!(decoded.downcast_ref::<MsgSend>(), Some(&send));
+        assert!(msg.unpack_dynamic(&MsgRegistry::new()).is_err());
+
+        let generic = Authorization::Generic {
+            msg_type_url: MSG_VOTE_TYPE_URL.to_string(),
+        }
+        .to_any();
+        assert!(!generic.is::<StakeAuthorization>());
+        assert_eq!(
+            generic.unpack::<GenericAuthorization>().unwrap().msg,
+            MSG_VOTE_TYPE_URL
+        );
+    }
+}
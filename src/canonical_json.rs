@@ -0,0 +1,129 @@
+//! The canonical JSON encoding the Cosmos SDK signs for legacy Amino JSON sign docs and for
+//! ADR-036 off chain messages. The signed bytes are compared byte for byte so the encoding
+//! must match what the SDK produces with sdk.MustSortJSON: keys sorted at every level, no
+//! whitespace, and &, <, > and the unicode line separators escaped the way Go escapes them.
+//!
+//! ```ignore
+//! let doc = adr036_sign_doc(&signer, b"login nonce 1234");
+//! let signature = private_key.sign_bytes(&canonical_json(&doc))?;
+//! ```
+
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Encodes a value as canonical JSON. 64 bit integers are strings in Amino JSON and must
+/// already be rendered as such, numbers are written as they are and floats have no
+/// canonical form.
+pub fn canonical_json(value: &Value) -> Vec<u8> {
+    // serializing a Value can not fail, all keys are strings
+    let json = serde_json::to_string(&sort_keys(value)).unwrap();
+    json.replace('&', "\\u0026")
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029")
+        .into_bytes()
+}
+
+/// Sorts the keys of every object in the value. serde_json maps are already ordered unless
+/// preserve_order is enabled by some other crate in the build, sort explicitly so the
+/// signed bytes never depend on feature unification.
+pub fn sort_keys(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let mut sorted = Map::new();
+            for (k, v) in entries {
+                sorted.insert(k.clone(), sort_keys(v));
+            }
+            Value::Object(sorted)
+        }
+        Value::Array(values) => Value::Array(values.iter().map(sort_keys).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Removes the fields Amino leaves out of message values because of omitempty, fields that
+/// are null, false, zero, an empty string or an empty array. Empty objects are kept as Go
+/// does not omit structs. Only apply this to message values, the sign doc itself always
+/// contains its memo and fee amount.
+pub fn omit_empty(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut kept = Map::new();
+            for (k, v) in map {
+                let v = omit_empty(v);
+                if !is_empty(&v) {
+                    kept.insert(k.clone(), v);
+                }
+            }
+            Value::Object(kept)
+        }
+        Value::Array(values) => Value::Array(values.iter().map(omit_empty).collect()),
+        other => other.clone(),
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(b) => !b,
+        Value::Number(n) => n.as_f64() == Some(0.0),
+        Value::String(s) => s.is_empty(),
+        Value::Array(values) => values.is_empty(),
+        Value::Object(_) => false,
+    }
+}
+
+/// The ADR-036 sign doc for signing arbitrary data off chain, as Keplr signArbitrary builds
+/// it. Sign canonical_json of the result, the signature is verified against the same doc.
+pub fn adr036_sign_doc(signer: &str, data: &[u8]) -> Value {
+    json!({
+        "chain_id": "",
+        "account_number": "0",
+        "sequence": "0",
+        "fee": { "gas": "0", "amount": [] },
+        "msgs": [{
+            "type": "sign/MsgSignData",
+            "value": { "signer": signer, "data": base64::encode(data) },
+        }],
+        "memo": "",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_json() {
+        let value = json!({
+            "b": [{ "z": 1, "a": null }],
+            "a": "x < y & \u{2028}",
+            "c": { "amount": [], "gas": "0" },
+        });
+        assert_eq!(
+            String::from_utf8(canonical_json(&value)).unwrap(),
+            concat!(
+                r#"{"a":"x \u003c y \u0026 \u2028","b":[{"a":null,"z":1}],"#,
+                r#""c":{"amount":[],"gas":"0"}}"#
+            )
+        );
+        assert_eq!(
+            omit_empty(&value),
+            json!({ "b": [{ "z": 1 }], "a": "x < y & \u{2028}", "c": { "gas": "0" } })
+        );
+
+        let doc = adr036_sign_doc("cosmos1signer", b"hello");
+        assert_eq!(
+            String::from_utf8(canonical_json(&doc)).unwrap(),
+            concat!(
+                r#"{"account_number":"0","chain_id":"","fee":{"amount":[],"gas":"0"},"memo":"","#,
+                r#""msgs":[{"type":"sign/MsgSignData","value":{"data":"aGVsbG8=","#,
+                r#""signer":"cosmos1signer"}}],"sequence":"0"}"#
+            )
+        );
+    }
+}
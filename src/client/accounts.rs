@@ -0,0 +1,691 @@
+//! Decoding of the account types returned by the auth module. Accounts are returned packed
+//! into an Any and chains may use any account type, the types used by the Cosmos SDK and
+//! Ethermint are decoded by default and chains with custom account types can register a
+//! decoder that extracts the embedded BaseAccount.
+
+use crate::any::AnyExt;
+#[cfg(feature = "sdk-0_46")]
+use crate::client::paginate::paginate;
+#[cfg(feature = "sdk-0_46")]
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::bank::v1beta1::QuerySpendableBalancesRequest;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::bank::v1beta1::QuerySpendableBalancesResponse;
+use crate::proto::cosmos::vesting::v1beta1::PermanentLockedAccount;
+use crate::proto::ethermint::types::v1::EthAccount;
+use crate::Address;
+use crate::Coin;
+use crate::Dec;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::ModuleAccount;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryAccountRequest;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryAccountResponse;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::BaseVestingAccount;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::ContinuousVestingAccount;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::DelayedVestingAccount;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::PeriodicVestingAccount;
+#[cfg(feature = "sdk-0_46")]
+use futures::TryStreamExt;
+use num256::Uint256;
+use prost::DecodeError;
+use prost_types::Any;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tonic::Code as GrpcCode;
+
+pub const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+pub const MODULE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.ModuleAccount";
+pub const CONTINUOUS_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.ContinuousVestingAccount";
+pub const DELAYED_VESTING_ACCOUNT_TYPE_URL: &str = "/cosmos.vesting.v1beta1.DelayedVestingAccount";
+pub const PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PeriodicVestingAccount";
+pub const PERMANENT_LOCKED_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.PermanentLockedAccount";
+pub const ETH_ACCOUNT_TYPE_URL: &str = "/ethermint.types.v1.EthAccount";
+
+/// A decoded account
+#[derive(Debug, Clone, PartialEq)]
+pub enum Account {
+    Base(BaseAccount),
+    Module(ModuleAccount),
+    ContinuousVesting(ContinuousVestingAccount),
+    DelayedVesting(DelayedVestingAccount),
+    PeriodicVesting(PeriodicVestingAccount),
+    PermanentLocked(PermanentLockedAccount),
+    Eth(EthAccount),
+    /// An account decoded by a decoder registered with an AccountRegistry, value
+    /// is the undecoded account
+    Custom {
+        type_url: String,
+        base: BaseAccount,
+        value: Vec<u8>,
+    },
+}
+
+impl Account {
+    /// Returns the BaseAccount embedded in this account, which contains the account number
+    /// and sequence required for signing
+    pub fn base_account(&self) -> Option<&BaseAccount> {
+        match self {
+            Account::Base(account) => Some(account),
+            Account::Module(account) => account.base_account.as_ref(),
+            Account::ContinuousVesting(account) => base_of_vesting(&account.base_vesting_account),
+            Account::DelayedVesting(account) => base_of_vesting(&account.base_vesting_account),
+            Account::PeriodicVesting(account) => base_of_vesting(&account.base_vesting_account),
+            Account::PermanentLocked(account) => base_of_vesting(&account.base_vesting_account),
+            Account::Eth(account) => account.base_account.as_ref(),
+            Account::Custom { base, .. } => Some(base),
+        }
+    }
+
+    /// Returns the vesting state of this account if it is a vesting account
+    pub fn base_vesting_account(&self) -> Option<&BaseVestingAccount> {
+        match self {
+            Account::ContinuousVesting(account) => account.base_vesting_account.as_ref(),
+            Account::DelayedVesting(account) => account.base_vesting_account.as_ref(),
+            Account::PeriodicVesting(account) => account.base_vesting_account.as_ref(),
+            Account::PermanentLocked(account) => account.base_vesting_account.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Vesting schedule calculations, these follow the Cosmos SDK vesting account semantics. Non
+/// vesting accounts have nothing vested, vesting or locked.
+impl Account {
+    /// The coins that have vested as of the provided time
+    pub fn vested_coins(&self, time: SystemTime) -> Vec<Coin> {
+        let bva = match self.base_vesting_account() {
+            Some(bva) => bva,
+            None => return Vec::new(),
+        };
+        let now = unix_seconds(time);
+        let original = &bva.original_vesting;
+        match self {
+            Account::ContinuousVesting(account) => {
+                if now <= account.start_time {
+                    Vec::new()
+                } else if now >= bva.end_time {
+                    to_coins(coin_map(original))
+                } else {
+                    let elapsed = (now - account.start_time) as u64;
+                    let duration = (bva.end_time - account.start_time) as u64;
+                    let vested = coin_map(original)
+                        .into_iter()
+                        .map(|(denom, amount)| (denom, vested_amount(amount, elapsed, duration)))
+                        .collect();
+                    to_coins(vested)
+                }
+            }
+            Account::DelayedVesting(_) => {
+                if now >= bva.end_time {
+                    to_coins(coin_map(original))
+                } else {
+                    Vec::new()
+                }
+            }
+            Account::PeriodicVesting(account) => {
+                if now <= account.start_time {
+                    return Vec::new();
+                } else if now >= bva.end_time {
+                    return to_coins(coin_map(original));
+                }
+                let mut vested = BTreeMap::new();
+                let mut period_start = account.start_time;
+                for period in account.vesting_periods.iter() {
+                    if now - period_start < period.length {
+                        break;
+                    }
+                    add_coins(&mut vested, &period.amount);
+                    period_start += period.length;
+                }
+                to_coins(vested)
+            }
+            // permanently locked accounts never vest
+            _ => Vec::new(),
+        }
+    }
+
+    /// The coins that have not yet vested as of the provided time
+    pub fn vesting_coins(&self, time: SystemTime) -> Vec<Coin> {
+        match self.base_vesting_account() {
+            Some(bva) => to_coins(saturating_sub(
+                coin_map(&bva.original_vesting),
+                &coin_map_from(&self.vested_coins(time)),
+            )),
+            None => Vec::new(),
+        }
+    }
+
+    /// The coins that can not be transferred as of the provided time, this is the
+    /// vesting amount minus any vesting coins that have been delegated
+    pub fn locked_coins(&self, time: SystemTime) -> Vec<Coin> {
+        match self.base_vesting_account() {
+            Some(bva) => to_coins(saturating_sub(
+                coin_map_from(&self.vesting_coins(time)),
+                &coin_map(&bva.delegated_vesting),
+            )),
+            None => Vec::new(),
+        }
+    }
+
+    /// The coins of the provided balance that can be transferred as of the provided time,
+    /// like the Cosmos SDK nothing is spendable if any locked denom exceeds the balance
+    pub fn spendable_coins(&self, balance: &[Coin], time: SystemTime) -> Vec<Coin> {
+        let balance = coin_map_from(balance);
+        let locked = coin_map_from(&self.locked_coins(time));
+        for (denom, amount) in locked.iter() {
+            match balance.get(denom) {
+                Some(available) if available >= amount => {}
+                _ => return Vec::new(),
+            }
+        }
+        to_coins(saturating_sub(balance, &locked))
+    }
+}
+
+/// The balance of an account split into the coins that can be sent right now and those
+/// that can not, see Contact::get_spendable_balance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendableBalance {
+    /// every coin held by the account
+    pub total: Vec<Coin>,
+    pub spendable: Vec<Coin>,
+    /// coins held but not spendable, such as unvested coins
+    pub locked: Vec<Coin>,
+}
+
+impl SpendableBalance {
+    /// Splits a balance using the vesting schedule of the account at the provided time,
+    /// accounts that do not exist yet or do not vest can spend their whole balance
+    pub fn from_vesting(account: Option<&Account>, balance: Vec<Coin>, time: SystemTime) -> Self {
+        let spendable = match account {
+            Some(account) => account.spendable_coins(&balance, time),
+            None => balance.clone(),
+        };
+        SpendableBalance::from_spendable(balance, spendable)
+    }
+
+    /// Splits a balance into the provided spendable coins and the remainder
+    pub fn from_spendable(balance: Vec<Coin>, spendable: Vec<Coin>) -> Self {
+        let locked = to_coins(saturating_sub(
+            coin_map_from(&balance),
+            &coin_map_from(&spendable),
+        ));
+        SpendableBalance {
+            total: balance,
+            spendable,
+            locked,
+        }
+    }
+
+    /// The spendable amount of a denom, zero if none
+    pub fn spendable_of(&self, denom: &str) -> Uint256 {
+        self.spendable
+            .iter()
+            .filter(|c| c.denom == denom)
+            .fold(0u64.into(), |total, c| total + c.amount.clone())
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => since_epoch.as_secs() as i64,
+        Err(before_epoch) => -(before_epoch.duration().as_secs() as i64),
+    }
+}
+
+fn add_coins(map: &mut BTreeMap<String, Uint256>, coins: &[ProtoCoin]) {
+    for coin in coins {
+        // amounts returned by the chain are always valid integers
+        let amount: Uint256 = coin.amount.parse().unwrap_or_else(|_| 0u64.into());
+        let total = map.entry(coin.denom.clone()).or_insert_with(|| 0u64.into());
+        *total = total.clone() + amount;
+    }
+}
+
+fn coin_map(coins: &[ProtoCoin]) -> BTreeMap<String, Uint256> {
+    let mut map = BTreeMap::new();
+    add_coins(&mut map, coins);
+    map
+}
+
+fn coin_map_from(coins: &[Coin]) -> BTreeMap<String, Uint256> {
+    let mut map: BTreeMap<String, Uint256> = BTreeMap::new();
+    for coin in coins {
+        let total = map.entry(coin.denom.clone()).or_insert_with(|| 0u64.into());
+        *total = total.clone() + coin.amount.clone();
+    }
+    map
+}
+
+/// Subtracts b from a per denom, going no lower than zero
+fn saturating_sub(
+    mut a: BTreeMap<String, Uint256>,
+    b: &BTreeMap<String, Uint256>,
+) -> BTreeMap<String, Uint256> {
+    for (denom, amount) in a.iter_mut() {
+        if let Some(sub) = b.get(denom) {
+            if *sub >= *amount {
+                *amount = 0u64.into();
+            } else {
+                *amount = amount.clone() - sub.clone();
+            }
+        }
+    }
+    a
+}
+
+fn to_coins(map: BTreeMap<String, Uint256>) -> Vec<Coin> {
+    map.into_iter()
+        .filter(|(_, amount)| *amount > 0u64.into())
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect()
+}
+
+/// The vested part of amount after elapsed of duration, computed with the SDK decimal
+/// arithmetic: the vested fraction is rounded to 18 decimals, multiplied with the amount and
+/// the product rounded half to even to an integer. For large amounts the rounding of the
+/// fraction moves the result away from the exact amount * elapsed / duration.
+fn vested_amount(amount: Uint256, elapsed: u64, duration: u64) -> Uint256 {
+    let fraction = match Dec::from(elapsed).quo(&Dec::from(duration)) {
+        Some(fraction) => fraction,
+        None => return amount,
+    };
+    Dec::from(amount)
+        .mul(&fraction)
+        .round_int()
+        .to_string()
+        .parse()
+        .unwrap()
+}
+
+fn base_of_vesting(account: &Option<BaseVestingAccount>) -> Option<&BaseAccount> {
+    account.as_ref().and_then(|a| a.base_account.as_ref())
+}
+
+/// Extracts the BaseAccount from the bytes of a custom account type
+pub type AccountDecoder = fn(&[u8]) -> Result<BaseAccount, DecodeError>;
+
+/// Decodes accounts, custom account types can be added with register
+#[derive(Clone, Default)]
+pub struct AccountRegistry {
+    custom: HashMap<String, AccountDecoder>,
+}
+
+impl AccountRegistry {
+    pub fn new() -> Self {
+        AccountRegistry::default()
+    }
+
+    /// Registers a decoder for a custom account type, custom decoders take priority over
+    /// the built in account types
+    pub fn register(&mut self, type_url: impl Into<String>, decoder: AccountDecoder) {
+        self.custom.insert(type_url.into(), decoder);
+    }
+
+    /// Decodes an account, returns InvalidAccount if the type is not known
+    pub fn decode(&self, any: &Any) -> Result<Account, CosmosGrpcError> {
+        if let Some(decoder) = self.custom.get(&any.type_url) {
+            return Ok(Account::Custom {
+                type_url: any.type_url.clone(),
+                base: decoder(&any.value)?,
+                value: any.value.clone(),
+            });
+        }
+        let account = match any.type_url.as_str() {
+            BASE_ACCOUNT_TYPE_URL => Account::Base(any.unpack()?),
+            MODULE_ACCOUNT_TYPE_URL => Account::Module(any.unpack()?),
+            CONTINUOUS_VESTING_ACCOUNT_TYPE_URL => Account::ContinuousVesting(any.unpack()?),
+            DELAYED_VESTING_ACCOUNT_TYPE_URL => Account::DelayedVesting(any.unpack()?),
+            PERIODIC_VESTING_ACCOUNT_TYPE_URL => Account::PeriodicVesting(any.unpack()?),
+            PERMANENT_LOCKED_ACCOUNT_TYPE_URL => Account::PermanentLocked(any.unpack()?),
+            ETH_ACCOUNT_TYPE_URL => Account::Eth(any.unpack()?),
+            _ => {
+                return Err(CosmosGrpcError::InvalidAccount {
+                    type_url: any.type_url.clone(),
+                })
+            }
+        };
+        Ok(account)
+    }
+}
+
+/// Nodes report missing accounts with NotFound, some chains wrap the error so that only the
+/// message is left
+fn is_account_not_found(error: &CosmosGrpcError) -> bool {
+    match error {
+        CosmosGrpcError::RequestError { error, .. } => match error.code() {
+            GrpcCode::NotFound => true,
+            GrpcCode::Unknown => error.message().contains("not found"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl Contact {
+    /// Returns true if the chain has an account for the address and false if it does not,
+    /// which is the case until the address first receives tokens. Failures to reach or
+    /// query the node are returned as errors rather than reported as a missing account.
+    pub async fn account_exists(&self, address: Address) -> Result<bool, CosmosGrpcError> {
+        match self.get_account(address).await {
+            Ok(_) | Err(CosmosGrpcError::InvalidAccount { .. }) => Ok(true),
+            Err(CosmosGrpcError::AccountNotFound { .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets and decodes the account of the provided address using the default account
+    /// types, returns AccountNotFound if the account does not exist
+    pub async fn get_account(&self, address: Address) -> Result<Account, CosmosGrpcError> {
+        self.get_account_with(address, &AccountRegistry::default())
+            .await
+    }
+
+    /// Gets and decodes the account of the provided address using the provided registry
+    /// for custom account types, returns AccountNotFound if the account does not exist
+    pub async fn get_account_with(
+        &self,
+        address: Address,
+        registry: &AccountRegistry,
+    ) -> Result<Account, CosmosGrpcError> {
+        let address = address.to_bech32(&self.inner.chain_prefix).unwrap();
+        let res: Result<QueryAccountResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.auth.v1beta1.Query/Account",
+                QueryAccountRequest {
+                    address: address.clone(),
+                },
+            )
+            .await;
+        match res {
+            Ok(account) => match account.account {
+                Some(value) => registry.decode(&value),
+                None => Err(CosmosGrpcError::BadResponse("No account?".to_string())),
+            },
+            Err(e) if is_account_not_found(&e) => Err(CosmosGrpcError::AccountNotFound { address }),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets the coins the node reports as spendable for the address, this accounts for
+    /// vesting and any chain specific locks. Requires Cosmos SDK v0.46 or later on the chain.
+    #[cfg(feature = "sdk-0_46")]
+    pub async fn get_spendable_balances(
+        &self,
+        address: Address,
+    ) -> Result<Vec<Coin>, CosmosGrpcError> {
+        let address = address.to_bech32(&self.inner.chain_prefix).unwrap();
+        let balances: Vec<ProtoCoin> = paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QuerySpendableBalancesRequest {
+                address: address.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QuerySpendableBalancesResponse = self
+                    .grpc_unary("/cosmos.bank.v1beta1.Query/SpendableBalances", request)
+                    .await?;
+                Ok((res.balances, res.pagination))
+            }
+        })
+        .try_collect()
+        .await?;
+        let mut coins = Vec::new();
+        for coin in balances {
+            coins.push(Coin::from_proto(coin).map_err(CosmosGrpcError::BadResponse)?);
+        }
+        Ok(coins)
+    }
+
+    /// Gets how much of its balance the address can send right now. Where the node supports
+    /// the SpendableBalances query its answer is used, as it includes locks specific to the
+    /// chain. Otherwise the vesting schedule of the account is evaluated at the latest block
+    /// time, which is what the chain checks sends against, rather than the local clock.
+    pub async fn get_spendable_balance(
+        &self,
+        address: Address,
+    ) -> Result<SpendableBalance, CosmosGrpcError> {
+        let balance = self.get_balances(address).await?;
+        #[cfg(feature = "sdk-0_46")]
+        match self.get_spendable_balances(address).await {
+            Ok(spendable) => return Ok(SpendableBalance::from_spendable(balance, spendable)),
+            Err(e) if e.grpc_code() == Some(GrpcCode::Unimplemented) => {}
+            Err(e) => return Err(e),
+        }
+        let account = match self.get_account(address).await {
+            Ok(account) => Some(account),
+            Err(CosmosGrpcError::AccountNotFound { .. }) => None,
+            Err(e) => return Err(e),
+        };
+        let (_, time) = self.latest_height_and_time().await?;
+        Ok(SpendableBalance::from_vesting(
+            account.as_ref(),
+            balance,
+            time,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::encode_any;
+    use prost::Message;
+    use std::time::Duration;
+
+    fn base() -> BaseAccount {
+        BaseAccount {
+            address: "cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a".to_string(),
+            pub_key: None,
+            account_number: 5,
+            sequence: 7,
+        }
+    }
+
+    #[test]
+    fn test_decode_accounts() {
+        let registry = AccountRegistry::new();
+        let vesting = DelayedVestingAccount {
+            base_vesting_account: Some(BaseVestingAccount {
+                base_account: Some(base()),
+                original_vesting: Vec::new(),
+                delegated_free: Vec::new(),
+                delegated_vesting: Vec::new(),
+                end_time: 100,
+            }),
+        };
+        let decoded = registry
+            .decode(&encode_any(
+                vesting,
+                DELAYED_VESTING_ACCOUNT_TYPE_URL.to_string(),
+            ))
+            .unwrap();
+        assert_eq!(decoded.base_account().unwrap().sequence, 7);
+        assert_eq!(decoded.base_vesting_account().unwrap().end_time, 100);
+
+        let eth = EthAccount {
+            base_account: Some(base()),
+            code_hash: String::new(),
+        };
+        let decoded = registry
+            .decode(&encode_any(eth, ETH_ACCOUNT_TYPE_URL.to_string()))
+            .unwrap();
+        assert_eq!(decoded.base_account().unwrap().account_number, 5);
+
+        let custom = encode_any(base(), "/custom.v1.Account".to_string());
+        assert!(registry.decode(&custom).is_err());
+        let mut registry = registry;
+        registry.register("/custom.v1.Account", |bytes| BaseAccount::decode(bytes));
+        assert_eq!(
+            registry.decode(&custom).unwrap().base_account(),
+            Some(&base())
+        );
+    }
+
+    fn uatom(amount: u64) -> Vec<ProtoCoin> {
+        vec![ProtoCoin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }]
+    }
+
+    fn bva(original: u64, delegated_vesting: u64, end_time: i64) -> Option<BaseVestingAccount> {
+        Some(BaseVestingAccount {
+            base_account: Some(base()),
+            original_vesting: uatom(original),
+            delegated_free: Vec::new(),
+            delegated_vesting: if delegated_vesting > 0 {
+                uatom(delegated_vesting)
+            } else {
+                Vec::new()
+            },
+            end_time,
+        })
+    }
+
+    fn at(seconds: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds)
+    }
+
+    fn amount(coins: Vec<Coin>) -> u64 {
+        match coins.first() {
+            Some(coin) => coin.amount.to_string().parse().unwrap(),
+            None => 0,
+        }
+    }
+
+    #[test]
+    fn test_continuous_vesting() {
+        let account = Account::ContinuousVesting(ContinuousVestingAccount {
+            base_vesting_account: bva(1000, 300, 200),
+            start_time: 100,
+        });
+        assert_eq!(amount(account.vested_coins(at(50))), 0);
+        assert_eq!(amount(account.vested_coins(at(125))), 250);
+        assert_eq!(amount(account.vesting_coins(at(125))), 750);
+        // 300 of the vesting coins are delegated and so not locked in the balance
+        assert_eq!(amount(account.locked_coins(at(125))), 450);
+        assert_eq!(amount(account.vested_coins(at(300))), 1000);
+        assert_eq!(amount(account.locked_coins(at(300))), 0);
+
+        let balance = vec![Coin {
+            denom: "uatom".to_string(),
+            amount: 700u64.into(),
+        }];
+        assert_eq!(amount(account.spendable_coins(&balance, at(125))), 250);
+        assert_eq!(amount(account.spendable_coins(&balance[..0], at(125))), 0);
+
+        let split = SpendableBalance::from_vesting(Some(&account), balance.clone(), at(125));
+        assert_eq!(split.spendable_of("uatom"), 250u64.into());
+        assert_eq!(amount(split.locked), 450);
+        let unknown = SpendableBalance::from_vesting(None, balance, at(125));
+        assert_eq!(unknown.spendable_of("uatom"), 700u64.into());
+        assert!(unknown.locked.is_empty());
+    }
+
+    #[test]
+    fn test_periodic_delayed_and_locked_vesting() {
+        let period = |length, amount| cosmos_sdk_proto::cosmos::vesting::v1beta1::Period {
+            length,
+            amount: uatom(amount),
+        };
+        let periodic = Account::PeriodicVesting(PeriodicVestingAccount {
+            base_vesting_account: bva(600, 0, 160),
+            start_time: 100,
+            vesting_periods: vec![period(30, 100), period(30, 500)],
+        });
+        assert_eq!(amount(periodic.vested_coins(at(129))), 0);
+        assert_eq!(amount(periodic.vested_coins(at(130))), 100);
+        assert_eq!(amount(periodic.vested_coins(at(159))), 100);
+        assert_eq!(amount(periodic.vested_coins(at(160))), 600);
+
+        let delayed = Account::DelayedVesting(DelayedVestingAccount {
+            base_vesting_account: bva(1000, 0, 200),
+        });
+        assert_eq!(amount(delayed.locked_coins(at(199))), 1000);
+        assert_eq!(amount(delayed.locked_coins(at(200))), 0);
+
+        let locked = Account::PermanentLocked(PermanentLockedAccount {
+            base_vesting_account: bva(1000, 0, 0),
+        });
+        assert_eq!(amount(locked.locked_coins(at(u32::MAX as u64))), 1000);
+
+        assert_eq!(vested_amount(5u64.into(), 1, 2), 2u64.into());
+        assert_eq!(vested_amount(7u64.into(), 1, 2), 4u64.into());
+    }
+
+    #[test]
+    fn test_vested_amount_matches_sdk() {
+        // 10^24 of an 18 decimal token, the SDK rounds the vested fraction to
+        // 0.333333333333333333 and 0.666666666666666667 before multiplying
+        let amount: Uint256 = "1000000000000000000000000".parse().unwrap();
+        assert_eq!(
+            vested_amount(amount.clone(), 1, 3),
+            "333333333333333333000000".parse().unwrap()
+        );
+        assert_eq!(
+            vested_amount(amount, 2, 3),
+            "666666666666666667000000".parse().unwrap()
+        );
+        assert_eq!(vested_amount(1000u64.into(), 25, 100), 250u64.into());
+    }
+
+    #[actix_rt::test]
+    async fn test_account_exists() {
+        let file = std::env::temp_dir().join(format!(
+            "deep_space_accounts_{}.json",
+            rand::random::<u64>()
+        ));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        let path = "/cosmos.auth.v1beta1.Query/Account";
+        let request = |address: Address| {
+            let mut bytes = Vec::new();
+            QueryAccountRequest {
+                address: address.to_bech32("cosmos").unwrap(),
+            }
+            .encode(&mut bytes)
+            .unwrap();
+            bytes
+        };
+        let existing = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let new = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let unreachable = Address::from_bytes([2; 20], "cosmos").unwrap();
+        let mut response = Vec::new();
+        QueryAccountResponse {
+            account: Some(encode_any(base(), BASE_ACCOUNT_TYPE_URL.to_string())),
+        }
+        .encode(&mut response)
+        .unwrap();
+        tape.save(path, &request(existing), Ok(&response)).unwrap();
+        let not_found = tonic::Status::not_found(format!("account {} not found", new));
+        for _ in 0..2 {
+            tape.save(path, &request(new), Err(&not_found)).unwrap();
+        }
+        let unavailable = tonic::Status::unavailable("connection reset");
+        tape.save(path, &request(unreachable), Err(&unavailable))
+            .unwrap();
+
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert!(contact.account_exists(existing).await.unwrap());
+        assert!(!contact.account_exists(new).await.unwrap());
+        assert!(contact.account_exists(unreachable).await.is_err());
+        match contact.get_account(new).await {
+            Err(CosmosGrpcError::AccountNotFound { address }) => {
+                assert_eq!(address, new.to_string())
+            }
+            other => panic!("Expected AccountNotFound, got {:?}", other),
+        }
+    }
+}
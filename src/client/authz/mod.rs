@@ -0,0 +1,333 @@
+//! Contains utility functions for granting, revoking, and executing authz authorizations,
+//! these allow one account (the grantee) to send messages on behalf of another (the granter)
+
+use crate::client::bank::MSG_SEND_TYPE_URL;
+#[cfg(feature = "sdk-0_46")]
+use crate::client::paginate::paginate;
+#[cfg(feature = "sdk-0_46")]
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::client::staking::MSG_BEGIN_REDELEGATE_TYPE_URL;
+use crate::client::staking::MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL;
+use crate::client::staking::MSG_DELEGATE_TYPE_URL;
+use crate::client::staking::MSG_UNDELEGATE_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::authz::v1beta1::GenericAuthorization;
+use crate::proto::cosmos::authz::v1beta1::Grant;
+#[cfg(feature = "sdk-0_46")]
+pub use crate::proto::cosmos::authz::v1beta1::GrantAuthorization;
+use crate::proto::cosmos::authz::v1beta1::MsgExec;
+use crate::proto::cosmos::authz::v1beta1::MsgGrant;
+use crate::proto::cosmos::authz::v1beta1::MsgRevoke;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::authz::v1beta1::QueryGranteeGrantsRequest;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::authz::v1beta1::QueryGranteeGrantsResponse;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::authz::v1beta1::QueryGranterGrantsRequest;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::authz::v1beta1::QueryGranterGrantsResponse;
+use crate::proto::cosmos::authz::v1beta1::QueryGrantsRequest;
+use crate::proto::cosmos::authz::v1beta1::QueryGrantsResponse;
+use crate::proto::cosmos::bank::v1beta1::SendAuthorization;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::staking::v1beta1::stake_authorization::ValidatorList;
+use crate::proto::cosmos::staking::v1beta1::stake_authorization::Validators;
+pub use crate::proto::cosmos::staking::v1beta1::AuthorizationType;
+use crate::proto::cosmos::staking::v1beta1::StakeAuthorization;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Msg;
+use crate::PrivateKey;
+#[cfg(feature = "sdk-0_46")]
+use futures::Stream;
+use prost_types::Any;
+use std::time::Duration;
+use std::time::SystemTime;
+
+mod rotation;
+
+pub use rotation::*;
+
+pub const MSG_GRANT_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgGrant";
+pub const MSG_REVOKE_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgRevoke";
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+pub const SEND_AUTHORIZATION_TYPE_URL: &str = "/cosmos.bank.v1beta1.SendAuthorization";
+pub const STAKE_AUTHORIZATION_TYPE_URL: &str = "/cosmos.staking.v1beta1.StakeAuthorization";
+
+/// The validators a StakeAuthorization applies to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StakeValidators {
+    /// The grantee may only delegate to these validators
+    Allow(Vec<Address>),
+    /// The grantee may delegate to any validator except these
+    Deny(Vec<Address>),
+}
+
+/// The permissions given to a grantee by an authz grant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Authorization {
+    /// Unrestricted permission to send any message with the provided type url
+    /// on behalf of the granter
+    Generic { msg_type_url: String },
+    /// Permission to send up to spend_limit from the granter, optionally only to the
+    /// addresses in allow_list. The allow list requires Cosmos SDK v0.47 or later.
+    Send {
+        spend_limit: Vec<Coin>,
+        allow_list: Vec<Address>,
+    },
+    /// Permission to delegate, undelegate, or redelegate up to max_tokens (or unlimited
+    /// if None) on behalf of the granter
+    Stake {
+        max_tokens: Option<Coin>,
+        validators: StakeValidators,
+        authorization_type: AuthorizationType,
+    },
+}
+
+impl Authorization {
+    /// Returns the type url of the message this authorization allows the grantee to send,
+    /// this is the msg_type_url used to revoke the grant
+    pub fn msg_type_url(&self) -> String {
+        match self {
+            Authorization::Generic { msg_type_url } => msg_type_url.clone(),
+            Authorization::Send { .. } => MSG_SEND_TYPE_URL.to_string(),
+            Authorization::Stake {
+                authorization_type, ..
+            } => match authorization_type {
+                AuthorizationType::Delegate => MSG_DELEGATE_TYPE_URL,
+                AuthorizationType::Undelegate => MSG_UNDELEGATE_TYPE_URL,
+                AuthorizationType::Redelegate => MSG_BEGIN_REDELEGATE_TYPE_URL,
+                AuthorizationType::CancelUnbondingDelegation => {
+                    MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL
+                }
+                AuthorizationType::Unspecified => "",
+            }
+            .to_string(),
+        }
+    }
+
+    /// Packs this authorization into the Any used by the Grant type
+    pub fn to_any(&self) -> Any {
+        match self {
+            Authorization::Generic { msg_type_url } => encode_any(
+                GenericAuthorization {
+                    msg: msg_type_url.clone(),
+                },
+                GENERIC_AUTHORIZATION_TYPE_URL.to_string(),
+            ),
+            Authorization::Send {
+                spend_limit,
+                allow_list,
+            } => encode_any(
+                SendAuthorization {
+                    spend_limit: spend_limit.iter().map(|c| c.clone().into()).collect(),
+                    allow_list: allow_list.iter().map(|a| a.to_string()).collect(),
+                },
+                SEND_AUTHORIZATION_TYPE_URL.to_string(),
+            ),
+            Authorization::Stake {
+                max_tokens,
+                validators,
+                authorization_type,
+            } => {
+                let validators = match validators {
+                    StakeValidators::Allow(list) => Validators::AllowList(ValidatorList {
+                        address: list.iter().map(|a| a.to_string()).collect(),
+                    }),
+                    StakeValidators::Deny(list) => Validators::DenyList(ValidatorList {
+                        address: list.iter().map(|a| a.to_string()).collect(),
+                    }),
+                };
+                encode_any(
+                    StakeAuthorization {
+                        max_tokens: max_tokens.clone().map(|c| c.into()),
+                        authorization_type: (*authorization_type).into(),
+                        validators: Some(validators),
+                    },
+                    STAKE_AUTHORIZATION_TYPE_URL.to_string(),
+                )
+            }
+        }
+    }
+}
+
+/// Builds a MsgGrant giving the grantee the provided authorization over the granters
+/// account until the expiration time. Chains before Cosmos SDK v0.46 require an expiration.
+pub fn grant_msg(
+    granter: Address,
+    grantee: Address,
+    authorization: &Authorization,
+    expiration: Option<SystemTime>,
+) -> Msg {
+    let grant = MsgGrant {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+        grant: Some(Grant {
+            authorization: Some(authorization.to_any()),
+            expiration: expiration.map(|e| e.into()),
+        }),
+    };
+    Msg::new(MSG_GRANT_TYPE_URL, grant)
+}
+
+/// Builds a MsgRevoke removing any grant for msg_type_url from granter to grantee
+pub fn revoke_msg(granter: Address, grantee: Address, msg_type_url: impl Into<String>) -> Msg {
+    let revoke = MsgRevoke {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+        msg_type_url: msg_type_url.into(),
+    };
+    Msg::new(MSG_REVOKE_TYPE_URL, revoke)
+}
+
+/// Builds a MsgExec wrapping messages built by this crate, each message must have the
+/// granter as its signer and the grantee must hold a grant for the message type
+pub fn exec_msg(grantee: Address, msgs: Vec<Msg>) -> Msg {
+    let exec = MsgExec {
+        grantee: grantee.to_string(),
+        msgs: msgs.into_iter().map(|m| m.into()).collect(),
+    };
+    Msg::new(MSG_EXEC_TYPE_URL, exec)
+}
+
+impl Contact {
+    /// Gets the grants from granter to grantee, optionally filtered to a single message type
+    pub async fn get_authz_grants(
+        &self,
+        granter: Address,
+        grantee: Address,
+        msg_type_url: Option<String>,
+    ) -> Result<Vec<Grant>, CosmosGrpcError> {
+        let res: QueryGrantsResponse = self
+            .grpc_unary(
+                "/cosmos.authz.v1beta1.Query/Grants",
+                QueryGrantsRequest {
+                    granter: granter.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    grantee: grantee.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    msg_type_url: msg_type_url.unwrap_or_default(),
+                    pagination: None,
+                },
+            )
+            .await?;
+        Ok(res.grants)
+    }
+
+    /// Streams every grant given by the granter, pages are fetched as the stream is consumed.
+    /// Requires Cosmos SDK v0.46 or later on the chain.
+    #[cfg(feature = "sdk-0_46")]
+    pub fn get_granter_grants_stream(
+        &self,
+        granter: Address,
+    ) -> impl Stream<Item = Result<GrantAuthorization, CosmosGrpcError>> + '_ {
+        let granter = granter.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryGranterGrantsRequest {
+                granter: granter.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryGranterGrantsResponse = self
+                    .grpc_unary("/cosmos.authz.v1beta1.Query/GranterGrants", request)
+                    .await?;
+                Ok((res.grants, res.pagination))
+            }
+        })
+    }
+
+    /// Streams every grant held by the grantee, pages are fetched as the stream is consumed.
+    /// Requires Cosmos SDK v0.46 or later on the chain.
+    #[cfg(feature = "sdk-0_46")]
+    pub fn get_grantee_grants_stream(
+        &self,
+        grantee: Address,
+    ) -> impl Stream<Item = Result<GrantAuthorization, CosmosGrpcError>> + '_ {
+        let grantee = grantee.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryGranteeGrantsRequest {
+                grantee: grantee.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryGranteeGrantsResponse = self
+                    .grpc_unary("/cosmos.authz.v1beta1.Query/GranteeGrants", request)
+                    .await?;
+                Ok((res.grants, res.pagination))
+            }
+        })
+    }
+
+    /// Grants the grantee the provided authorization over the account of the provided
+    /// private key until the expiration time
+    pub async fn grant_authorization(
+        &self,
+        grantee: Address,
+        authorization: Authorization,
+        expiration: Option<SystemTime>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = grant_msg(our_address, grantee, &authorization, expiration);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Revokes the grant for msg_type_url from the account of the provided private key
+    /// to the grantee
+    pub async fn revoke_authorization(
+        &self,
+        grantee: Address,
+        msg_type_url: String,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = revoke_msg(our_address, grantee, msg_type_url);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Executes messages on behalf of their granters using grants held by the provided
+    /// private key
+    pub async fn exec_authorized(
+        &self,
+        msgs: Vec<Msg>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = exec_msg(our_address, msgs);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authorization_msg_type_url() {
+        let send = Authorization::Send {
+            spend_limit: Vec::new(),
+            allow_list: Vec::new(),
+        };
+        assert_eq!(send.msg_type_url(), MSG_SEND_TYPE_URL);
+        let stake = Authorization::Stake {
+            max_tokens: None,
+            validators: StakeValidators::Deny(Vec::new()),
+            authorization_type: AuthorizationType::Delegate,
+        };
+        assert_eq!(stake.msg_type_url(), MSG_DELEGATE_TYPE_URL);
+        assert_eq!(stake.to_any().type_url, STAKE_AUTHORIZATION_TYPE_URL);
+    }
+}
@@ -0,0 +1,257 @@
+//! Hot key rotation for bots that act for a granter account through authz. The grants and
+//! optionally the fee allowance held by the old hot key are copied to the new key, the new
+//! key is checked by simulating test messages through it, and only then are the grants of
+//! the old key revoked.
+
+use crate::any::AnyExt;
+use crate::client::authz::exec_msg;
+use crate::client::authz::revoke_msg;
+use crate::client::authz::Authorization;
+use crate::client::authz::StakeValidators;
+use crate::client::authz::GENERIC_AUTHORIZATION_TYPE_URL;
+use crate::client::authz::MSG_GRANT_TYPE_URL;
+use crate::client::authz::SEND_AUTHORIZATION_TYPE_URL;
+use crate::client::authz::STAKE_AUTHORIZATION_TYPE_URL;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::feegrant::revoke_allowance_msg;
+use crate::client::feegrant::MSG_GRANT_ALLOWANCE_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::authz::v1beta1::GenericAuthorization;
+use crate::proto::cosmos::authz::v1beta1::Grant;
+use crate::proto::cosmos::authz::v1beta1::MsgGrant;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::feegrant::v1beta1::Grant as FeeGrant;
+use crate::proto::cosmos::feegrant::v1beta1::MsgGrantAllowance;
+use crate::proto::cosmos::staking::v1beta1::AuthorizationType;
+use crate::proto::cosmos::staking::v1beta1::StakeAuthorization;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Msg;
+use crate::PrivateKey;
+use prost_types::Any;
+use std::time::Duration;
+
+/// What rotate_hot_key does besides copying the authz grants
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyRotationOptions {
+    /// also give the new key the fee allowance the old key holds from the granter
+    pub migrate_fee_allowance: bool,
+    /// messages with the granter as signer, each is simulated inside a MsgExec signed by
+    /// the new key to check that the grants work before the old key loses them
+    pub test_msgs: Vec<Msg>,
+    /// revoke the grants and fee allowance of the old key once the new key is verified
+    pub revoke_old: bool,
+    /// the fee paid by the granter for each transaction
+    pub fee: Coin,
+    pub wait_timeout: Duration,
+}
+
+/// The outcome of a successful rotate_hot_key
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyRotationReport {
+    /// the message types the new key was granted
+    pub msg_type_urls: Vec<String>,
+    pub fee_allowance_migrated: bool,
+    pub grant_tx: TxResponse,
+    /// None unless revoke_old was set
+    pub revoke_tx: Option<TxResponse>,
+}
+
+/// Returns the type url of the message an authorization packed in an Any allows
+pub fn authorization_msg_type_url(authorization: &Any) -> Result<String, CosmosGrpcError> {
+    match authorization.type_url.as_str() {
+        GENERIC_AUTHORIZATION_TYPE_URL => Ok(authorization.unpack::<GenericAuthorization>()?.msg),
+        SEND_AUTHORIZATION_TYPE_URL => Ok(MSG_SEND_TYPE_URL.to_string()),
+        STAKE_AUTHORIZATION_TYPE_URL => {
+            let stake: StakeAuthorization = authorization.unpack()?;
+            let authorization_type = AuthorizationType::from_i32(stake.authorization_type)
+                .unwrap_or(AuthorizationType::Unspecified);
+            Ok(Authorization::Stake {
+                max_tokens: None,
+                validators: StakeValidators::Allow(Vec::new()),
+                authorization_type,
+            }
+            .msg_type_url())
+        }
+        other => Err(CosmosGrpcError::BadInput(format!(
+            "Unknown authorization type {}",
+            other
+        ))),
+    }
+}
+
+/// Builds the messages giving the new key the grants, and optionally the fee allowance,
+/// that the old key holds from the granter. The authorizations and expirations are copied
+/// as they are.
+pub fn handoff_msgs(
+    granter: Address,
+    new_key: Address,
+    grants: &[Grant],
+    fee_allowance: Option<&FeeGrant>,
+) -> Vec<Msg> {
+    let mut msgs: Vec<Msg> = grants
+        .iter()
+        .map(|grant| {
+            let msg = MsgGrant {
+                granter: granter.to_string(),
+                grantee: new_key.to_string(),
+                grant: Some(grant.clone()),
+            };
+            Msg::new(MSG_GRANT_TYPE_URL, msg)
+        })
+        .collect();
+    if let Some(allowance) = fee_allowance {
+        let msg = MsgGrantAllowance {
+            granter: granter.to_string(),
+            grantee: new_key.to_string(),
+            allowance: allowance.allowance.clone(),
+        };
+        msgs.push(Msg::new(MSG_GRANT_ALLOWANCE_TYPE_URL, msg));
+    }
+    msgs
+}
+
+impl Contact {
+    /// Moves the authz grants held by old_key from the granter to new_key, see the module
+    /// documentation. Fails before revoking anything if the new key does not end up with
+    /// every grant or a test message fails simulation, the old key keeps working in
+    /// that case and the rotation can be retried.
+    pub async fn rotate_hot_key(
+        &self,
+        granter: PrivateKey,
+        old_key: Address,
+        new_key: PrivateKey,
+        options: KeyRotationOptions,
+    ) -> Result<KeyRotationReport, CosmosGrpcError> {
+        let granter_address = granter.to_address(&self.inner.chain_prefix)?;
+        let new_address = new_key.to_address(&self.inner.chain_prefix)?;
+        let grants = self
+            .get_authz_grants(granter_address, old_key, None)
+            .await?;
+        if grants.is_empty() {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "{} holds no grants from {}",
+                old_key, granter_address
+            )));
+        }
+        let mut msg_type_urls = Vec::new();
+        for grant in grants.iter() {
+            match &grant.authorization {
+                Some(authorization) => {
+                    msg_type_urls.push(authorization_msg_type_url(authorization)?)
+                }
+                None => return Err(CosmosGrpcError::BadResponse("Empty grant?".to_string())),
+            }
+        }
+        let fee_allowance = if options.migrate_fee_allowance {
+            self.get_fee_allowance(granter_address, old_key).await?
+        } else {
+            None
+        };
+
+        let msgs = handoff_msgs(
+            granter_address,
+            new_address,
+            &grants,
+            fee_allowance.as_ref(),
+        );
+        let grant_tx = self
+            .send_message(
+                &msgs,
+                None,
+                self.default_fee(Some(options.fee.clone()), &msgs),
+                granter,
+                Some(options.wait_timeout),
+            )
+            .await?;
+
+        for msg_type_url in msg_type_urls.iter() {
+            let granted = self
+                .get_authz_grants(granter_address, new_address, Some(msg_type_url.clone()))
+                .await?;
+            if granted.is_empty() {
+                return Err(CosmosGrpcError::BadResponse(format!(
+                    "New key was not granted {}",
+                    msg_type_url
+                )));
+            }
+        }
+        for msg in options.test_msgs.iter() {
+            let exec = exec_msg(new_address, vec![msg.clone()]);
+            self.preflight(&[exec], None, new_key).await?;
+        }
+
+        let revoke_tx = if options.revoke_old {
+            let mut revokes: Vec<Msg> = msg_type_urls
+                .iter()
+                .map(|url| revoke_msg(granter_address, old_key, url.clone()))
+                .collect();
+            if fee_allowance.is_some() {
+                revokes.push(revoke_allowance_msg(granter_address, old_key));
+            }
+            let fee = self.default_fee(Some(options.fee), &revokes);
+            let tx = self
+                .send_message(&revokes, None, fee, granter, Some(options.wait_timeout))
+                .await?;
+            Some(tx)
+        } else {
+            None
+        };
+
+        Ok(KeyRotationReport {
+            msg_type_urls,
+            fee_allowance_migrated: fee_allowance.is_some(),
+            grant_tx,
+            revoke_tx,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::staking::MSG_DELEGATE_TYPE_URL;
+
+    #[test]
+    fn test_handoff_msgs() {
+        let granter = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let new_key = Address::from_bytes([2; 20], "cosmos").unwrap();
+        let generic = Authorization::Generic {
+            msg_type_url: "/cosmos.gov.v1beta1.MsgVote".to_string(),
+        };
+        let stake = Authorization::Stake {
+            max_tokens: None,
+            validators: StakeValidators::Deny(Vec::new()),
+            authorization_type: AuthorizationType::Delegate,
+        };
+        let grants: Vec<Grant> = [&generic, &stake]
+            .iter()
+            .map(|authorization| Grant {
+                authorization: Some(authorization.to_any()),
+                expiration: None,
+            })
+            .collect();
+        let urls: Vec<String> = grants
+            .iter()
+            .map(|g| authorization_msg_type_url(g.authorization.as_ref().unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            urls,
+            vec!["/cosmos.gov.v1beta1.MsgVote", MSG_DELEGATE_TYPE_URL]
+        );
+
+        let allowance = FeeGrant {
+            granter: granter.to_string(),
+            grantee: "old".to_string(),
+            allowance: None,
+        };
+        let msgs = handoff_msgs(granter, new_key, &grants, Some(&allowance));
+        assert_eq!(msgs.len(), 3);
+        let grant = msgs[1].unpack::<MsgGrant>().unwrap();
+        assert_eq!(grant.grantee, new_key.to_string());
+        assert_eq!(grant.grant.unwrap(), grants[1]);
+        assert_eq!(msgs[2].0.type_url, MSG_GRANT_ALLOWANCE_TYPE_URL);
+        assert_eq!(handoff_msgs(granter, new_key, &grants, None).len(), 2);
+    }
+}
@@ -0,0 +1,484 @@
+//! Contains utility functions for building and sending Cosmos bank module messages
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::Input;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::Output;
+use num256::Uint256;
+use num_traits::CheckedAdd;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_MULTI_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgMultiSend";
+
+/// Base gas used by a MsgMultiSend plus the additional gas for each output, this is a
+/// conservative estimate, the actual cost depends on if the recipient accounts exist
+const MULTI_SEND_BASE_GAS: u64 = 100_000;
+const MULTI_SEND_GAS_PER_OUTPUT: u64 = 30_000;
+/// Gas used by each MsgSend when several are placed in one transaction
+const SEND_GAS: u64 = 80_000;
+
+/// Options for Contact::send_batch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchSendOptions {
+    /// The fee paid by each transaction sent
+    pub fee: Coin,
+    /// The largest gas limit any single transaction may use
+    pub max_gas_per_tx: u64,
+    /// If a single MsgMultiSend may be used, some chains disable MsgMultiSend
+    pub allow_multi_send: bool,
+    /// How long to wait for each transaction to enter the chain
+    pub wait_timeout: Duration,
+}
+
+/// How a group of payouts is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchKind {
+    /// All payouts in one MsgMultiSend
+    MultiSend,
+    /// One MsgSend per payout, all in one transaction
+    Sends,
+}
+
+/// What happened to the transaction a payout was sent in
+#[derive(Debug, Clone)]
+pub enum PayoutResult {
+    /// the transaction is in a block and succeeded
+    Included(TxResponse),
+    /// the transaction was not sent, was rejected or failed in the block, the payout was
+    /// not made
+    Failed(Arc<CosmosGrpcError>),
+    /// the node accepted the transaction but it was not in a block within the wait timeout,
+    /// it may still be included so the payout must not be sent again before checking
+    Unknown { tx_hash: String },
+}
+
+impl PayoutResult {
+    /// The result of a send that took elapsed, a transaction included with a nonzero code
+    /// failed
+    fn from_result(result: Result<TxResponse, CosmosGrpcError>, elapsed: Duration) -> Self {
+        match result {
+            Ok(response) if response.code != 0 => {
+                PayoutResult::Failed(Arc::new(CosmosGrpcError::TransactionFailed {
+                    tx: response,
+                    time: elapsed,
+                    sequence: None,
+                }))
+            }
+            Ok(response) => PayoutResult::Included(response),
+            Err(CosmosGrpcError::ConfirmationTimeout { tx_hash, .. }) => {
+                PayoutResult::Unknown { tx_hash }
+            }
+            Err(e) => PayoutResult::Failed(Arc::new(e)),
+        }
+    }
+
+    pub fn is_included(&self) -> bool {
+        matches!(self, PayoutResult::Included(_))
+    }
+
+    /// The hash of the transaction, None if it was never accepted by the node
+    pub fn tx_hash(&self) -> Option<&str> {
+        match self {
+            PayoutResult::Included(response) => Some(&response.txhash),
+            PayoutResult::Unknown { tx_hash } => Some(tx_hash),
+            PayoutResult::Failed(e) => match &**e {
+                CosmosGrpcError::TransactionFailed { tx, .. } if !tx.txhash.is_empty() => {
+                    Some(&tx.txhash)
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// The result of a single payout from Contact::send_batch, payouts sent in the same
+/// transaction share its result
+#[derive(Debug, Clone)]
+pub struct PayoutOutcome {
+    pub destination: Address,
+    pub coin: Coin,
+    pub result: PayoutResult,
+}
+
+/// Splits payouts into transactions, a single MsgMultiSend is used if allowed and it fits
+/// under the gas limit, otherwise payouts are sent as MsgSends split into as many
+/// transactions as needed. Returns the kind, payout index range and gas of each transaction.
+pub fn plan_batch(
+    payouts: usize,
+    max_gas_per_tx: u64,
+    allow_multi_send: bool,
+) -> Vec<(BatchKind, std::ops::Range<usize>, u64)> {
+    if payouts == 0 {
+        return Vec::new();
+    }
+    let multi_send_gas = MULTI_SEND_BASE_GAS + MULTI_SEND_GAS_PER_OUTPUT * payouts as u64;
+    if allow_multi_send && payouts > 1 && multi_send_gas <= max_gas_per_tx {
+        return vec![(BatchKind::MultiSend, 0..payouts, multi_send_gas)];
+    }
+    let per_tx = std::cmp::max(1, (max_gas_per_tx / SEND_GAS) as usize);
+    let mut plan = Vec::new();
+    let mut start = 0;
+    while start < payouts {
+        let end = std::cmp::min(start + per_tx, payouts);
+        plan.push((
+            BatchKind::Sends,
+            start..end,
+            SEND_GAS * (end - start) as u64,
+        ));
+        start = end;
+    }
+    plan
+}
+
+/// A builder for MsgMultiSend, which moves funds from one or more inputs to one or
+/// more outputs in a single message. The chain will reject any MsgMultiSend where the
+/// sum of the inputs does not exactly equal the sum of the outputs, so this is validated
+/// locally when the message is built. Note that starting with Cosmos SDK v0.46 only a
+/// single input is allowed.
+#[derive(Debug, Clone, Default)]
+pub struct MultiSend {
+    inputs: Vec<(Address, Vec<Coin>)>,
+    outputs: Vec<(Address, Vec<Coin>)>,
+}
+
+impl MultiSend {
+    pub fn new() -> Self {
+        MultiSend::default()
+    }
+
+    /// Adds an input, the provided address must sign the transaction
+    pub fn input(mut self, address: Address, coins: Vec<Coin>) -> Self {
+        self.inputs.push((address, coins));
+        self
+    }
+
+    /// Adds an output, the provided address will receive the coins
+    pub fn output(mut self, address: Address, coins: Vec<Coin>) -> Self {
+        self.outputs.push((address, coins));
+        self
+    }
+
+    /// Checks that there is at least one input and output, that no coin
+    /// amount is zero or repeated, and that the inputs equal the outputs
+    pub fn validate(&self) -> Result<(), CosmosGrpcError> {
+        if self.inputs.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "MultiSend requires at least one input".to_string(),
+            ));
+        }
+        if self.outputs.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "MultiSend requires at least one output".to_string(),
+            ));
+        }
+        let input_total = sum_coins(&self.inputs)?;
+        let output_total = sum_coins(&self.outputs)?;
+        if input_total != output_total {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "MultiSend inputs {:?} do not equal outputs {:?}",
+                input_total, output_total
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validates and builds the MsgMultiSend, coins for each input and output are sorted
+    /// by denom as required by the chain
+    pub fn build(self) -> Result<Msg, CosmosGrpcError> {
+        self.validate()?;
+        let multi_send = MsgMultiSend {
+            inputs: self
+                .inputs
+                .into_iter()
+                .map(|(address, coins)| Input {
+                    address: address.to_string(),
+                    coins: sorted_coins(coins),
+                })
+                .collect(),
+            outputs: self
+                .outputs
+                .into_iter()
+                .map(|(address, coins)| Output {
+                    address: address.to_string(),
+                    coins: sorted_coins(coins),
+                })
+                .collect(),
+        };
+        Ok(Msg::new(MSG_MULTI_SEND_TYPE_URL, multi_send))
+    }
+}
+
+/// Sums the coins for a set of inputs or outputs by denom, rejecting zero amounts
+/// and denoms repeated within a single entry
+fn sum_coins(
+    entries: &[(Address, Vec<Coin>)],
+) -> Result<BTreeMap<String, Uint256>, CosmosGrpcError> {
+    let mut totals: BTreeMap<String, Uint256> = BTreeMap::new();
+    for (address, coins) in entries {
+        if coins.is_empty() {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "MultiSend entry for {} has no coins",
+                address
+            )));
+        }
+        let mut seen = Vec::new();
+        for coin in coins {
+            if coin.amount == 0u64.into() {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "MultiSend entry for {} has a zero amount of {}",
+                    address, coin.denom
+                )));
+            }
+            if seen.contains(&&coin.denom) {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "MultiSend entry for {} repeats denom {}",
+                    address, coin.denom
+                )));
+            }
+            seen.push(&coin.denom);
+            let total = totals.entry(coin.denom.clone()).or_default();
+            *total = match total.checked_add(&coin.amount) {
+                Some(sum) => sum,
+                None => {
+                    return Err(CosmosGrpcError::BadInput(format!(
+                        "MultiSend total of {} overflows",
+                        coin.denom
+                    )))
+                }
+            };
+        }
+    }
+    Ok(totals)
+}
+
+/// Builds a MsgMultiSend from a single sender paying out to many destinations
+fn payout_multi_send_msg(
+    sender: Address,
+    payouts: &[(Address, Coin)],
+) -> Result<Msg, CosmosGrpcError> {
+    let mut input: BTreeMap<String, Uint256> = BTreeMap::new();
+    let mut builder = MultiSend::new();
+    for (destination, coin) in payouts.iter() {
+        let total = input.entry(coin.denom.clone()).or_default();
+        *total = match total.checked_add(&coin.amount) {
+            Some(sum) => sum,
+            None => {
+                return Err(CosmosGrpcError::BadInput(format!(
+                    "Payout total of {} overflows",
+                    coin.denom
+                )))
+            }
+        };
+        builder = builder.output(*destination, vec![coin.clone()]);
+    }
+    let input = input
+        .into_iter()
+        .map(|(denom, amount)| Coin { denom, amount })
+        .collect();
+    builder.input(sender, input).build()
+}
+
+fn sorted_coins(mut coins: Vec<Coin>) -> Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> {
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+    coins.into_iter().map(|c| c.into()).collect()
+}
+
+impl Contact {
+    /// Sends coins from the provided private key to many destinations using a single
+    /// MsgMultiSend, this is much cheaper than sending a transaction per destination
+    /// and is the recommended way to process batches of withdrawals.
+    pub async fn multi_send(
+        &self,
+        payouts: Vec<(Address, Coin)>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+
+        let msg = payout_multi_send_msg(our_address, &payouts)?;
+
+        // the gas of a MsgMultiSend grows with its outputs
+        let gas_limit = MULTI_SEND_BASE_GAS + MULTI_SEND_GAS_PER_OUTPUT * payouts.len() as u64;
+        let fee = self.fee_with_gas(Some(fee), gas_limit);
+
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Sends coins from the provided private key to many destinations, choosing between a
+    /// single MsgMultiSend, many MsgSends in one transaction, or many transactions depending
+    /// on the gas limits in the options, see plan_batch. Transactions are sent one at a time
+    /// and a failed transaction does not stop the remaining ones, the outcome of every payout
+    /// is returned in the order provided.
+    pub async fn send_batch(
+        &self,
+        payouts: Vec<(Address, Coin)>,
+        opts: BatchSendOptions,
+        private_key: PrivateKey,
+    ) -> Result<Vec<PayoutOutcome>, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let mut outcomes = Vec::new();
+        for (kind, range, gas_limit) in
+            plan_batch(payouts.len(), opts.max_gas_per_tx, opts.allow_multi_send)
+        {
+            let batch = &payouts[range];
+            let msgs = match kind {
+                BatchKind::MultiSend => vec![payout_multi_send_msg(our_address, batch)?],
+                BatchKind::Sends => batch
+                    .iter()
+                    .map(|(destination, coin)| {
+                        let send = MsgSend {
+                            from_address: our_address.to_string(),
+                            to_address: destination.to_string(),
+                            amount: vec![coin.clone().into()],
+                        };
+                        Msg::new(MSG_SEND_TYPE_URL, send)
+                    })
+                    .collect(),
+            };
+            let fee = self.fee_with_gas(Some(opts.fee.clone()), gas_limit);
+            let start = Instant::now();
+            let result = self
+                .send_message(&msgs, None, fee, private_key, Some(opts.wait_timeout))
+                .await;
+            let result = PayoutResult::from_result(result, start.elapsed());
+            for (destination, coin) in batch {
+                outcomes.push(PayoutOutcome {
+                    destination: *destination,
+                    coin: coin.clone(),
+                    result: result.clone(),
+                });
+            }
+        }
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(amount: u64, denom: &str) -> Coin {
+        Coin {
+            amount: amount.into(),
+            denom: denom.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_payout_result() {
+        let elapsed = Duration::from_secs(6);
+        let included = PayoutResult::from_result(
+            Ok(TxResponse {
+                txhash: "AB".to_string(),
+                ..TxResponse::default()
+            }),
+            elapsed,
+        );
+        assert!(included.is_included());
+        assert_eq!(included.tx_hash(), Some("AB"));
+
+        let unknown = PayoutResult::from_result(
+            Err(CosmosGrpcError::ConfirmationTimeout {
+                tx_hash: "CD".to_string(),
+                time: Duration::from_secs(60),
+            }),
+            elapsed,
+        );
+        assert!(!unknown.is_included());
+        assert_eq!(unknown.tx_hash(), Some("CD"));
+
+        let failed =
+            PayoutResult::from_result(Err(CosmosGrpcError::BadInput("no".to_string())), elapsed);
+        match &failed {
+            PayoutResult::Failed(e) => assert!(matches!(**e, CosmosGrpcError::BadInput(_))),
+            other => panic!("Expected Failed, got {:?}", other),
+        }
+        assert_eq!(failed.tx_hash(), None);
+
+        // included in a block but failed there, for example out of gas
+        let failed_on_chain = PayoutResult::from_result(
+            Ok(TxResponse {
+                txhash: "EF".to_string(),
+                height: 10,
+                code: 11,
+                raw_log: "out of gas".to_string(),
+                ..TxResponse::default()
+            }),
+            elapsed,
+        );
+        assert!(!failed_on_chain.is_included());
+        match &failed_on_chain {
+            PayoutResult::Failed(e) => match &**e {
+                CosmosGrpcError::TransactionFailed { tx, time, .. } => {
+                    assert_eq!((tx.code, *time), (11, elapsed));
+                }
+                other => panic!("Expected TransactionFailed, got {:?}", other),
+            },
+            other => panic!("Expected Failed, got {:?}", other),
+        }
+        assert_eq!(failed_on_chain.tx_hash(), Some("EF"));
+    }
+
+    #[test]
+    fn test_multi_send_validation() {
+        let a = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let b = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let c = Address::from_bytes([2; 20], "cosmos").unwrap();
+
+        let valid = MultiSend::new()
+            .input(a, vec![coin(100, "uatom"), coin(5, "ufoo")])
+            .output(b, vec![coin(60, "uatom")])
+            .output(c, vec![coin(40, "uatom"), coin(5, "ufoo")]);
+        assert!(valid.validate().is_ok());
+        assert!(valid.build().is_ok());
+
+        let unbalanced = MultiSend::new()
+            .input(a, vec![coin(100, "uatom")])
+            .output(b, vec![coin(99, "uatom")]);
+        assert!(unbalanced.validate().is_err());
+
+        let wrong_denom = MultiSend::new()
+            .input(a, vec![coin(100, "uatom")])
+            .output(b, vec![coin(100, "ufoo")]);
+        assert!(wrong_denom.validate().is_err());
+
+        let zero = MultiSend::new()
+            .input(a, vec![coin(0, "uatom")])
+            .output(b, vec![coin(0, "uatom")]);
+        assert!(zero.validate().is_err());
+
+        let no_outputs = MultiSend::new().input(a, vec![coin(1, "uatom")]);
+        assert!(no_outputs.validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_batch() {
+        assert!(plan_batch(0, 1_000_000, true).is_empty());
+
+        let single = plan_batch(10, 1_000_000, true);
+        assert_eq!(single, vec![(BatchKind::MultiSend, 0..10, 400_000)]);
+
+        // multi send not allowed, everything fits in one tx of sends
+        let sends = plan_batch(10, 1_000_000, false);
+        assert_eq!(sends, vec![(BatchKind::Sends, 0..10, 800_000)]);
+
+        // too large for one multi send, split into several txs
+        let split = plan_batch(31, 1_000_000, true);
+        assert_eq!(split.len(), 3);
+        assert_eq!(split[0], (BatchKind::Sends, 0..12, 960_000));
+        assert_eq!(split[2], (BatchKind::Sends, 24..31, 560_000));
+    }
+}
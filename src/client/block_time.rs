@@ -0,0 +1,237 @@
+//! Block time estimation, for converting between block heights and wall clock time when
+//! waiting on unbonding, upgrade heights or any other height based deadline
+
+use crate::client::types::LatestBlock;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::utils::parse_lowest_height;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetBlockByHeightRequest;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetBlockByHeightResponse;
+use std::convert::TryFrom;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tendermint_proto::types::Block;
+
+/// The number of blocks sampled by estimate_time_of_height
+pub const DEFAULT_BLOCK_TIME_WINDOW: u64 = 100;
+
+/// Returns the height and time of a block
+fn block_height_and_time(block: &Block) -> Result<(u64, SystemTime), CosmosGrpcError> {
+    let header = match &block.header {
+        Some(header) => header,
+        None => return Err(CosmosGrpcError::BadResponse("No block header?".to_string())),
+    };
+    let time = match &header.time {
+        Some(time) if time.seconds >= 0 && time.nanos >= 0 => {
+            UNIX_EPOCH + Duration::new(time.seconds as u64, time.nanos as u32)
+        }
+        _ => {
+            return Err(CosmosGrpcError::BadResponse(
+                "Invalid block time?".to_string(),
+            ))
+        }
+    };
+    match u64::try_from(header.height) {
+        Ok(height) => Ok((height, time)),
+        Err(_) => Err(CosmosGrpcError::BadResponse(
+            "Negative block height?".to_string(),
+        )),
+    }
+}
+
+/// The average interval between two sampled blocks, None if the blocks are not in order
+pub fn average_block_interval(
+    first: (u64, SystemTime),
+    last: (u64, SystemTime),
+) -> Option<Duration> {
+    let blocks = last.0.checked_sub(first.0)?;
+    if blocks == 0 {
+        return None;
+    }
+    let elapsed = last.1.duration_since(first.1).ok()?;
+    Some(elapsed / u32::try_from(blocks).ok()?)
+}
+
+impl Contact {
+    /// Gets the block at the provided height, returns HeightPruned if the node no longer
+    /// stores it
+    pub async fn get_block_by_height(&self, height: u64) -> Result<Block, CosmosGrpcError> {
+        let res: Result<GetBlockByHeightResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.base.tendermint.v1beta1.Service/GetBlockByHeight",
+                GetBlockByHeightRequest {
+                    height: height as i64,
+                },
+            )
+            .await;
+        match res {
+            Ok(res) => match res.block {
+                Some(block) => Ok(block),
+                None => Err(CosmosGrpcError::BadResponse("No block?".to_string())),
+            },
+            Err(e) => Err(self.check_pruned(height, e).await),
+        }
+    }
+
+    /// Gets the earliest height the node stores blocks for, this is 1 for archive nodes
+    /// and for chains that did not start from a genesis export
+    pub async fn get_earliest_height(&self) -> Result<u64, CosmosGrpcError> {
+        let res: Result<GetBlockByHeightResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.base.tendermint.v1beta1.Service/GetBlockByHeight",
+                GetBlockByHeightRequest { height: 1 },
+            )
+            .await;
+        match res {
+            Ok(_) => Ok(1),
+            Err(e) => {
+                let lowest = match &e {
+                    CosmosGrpcError::RequestError { error, .. } => {
+                        parse_lowest_height(error.message())
+                    }
+                    _ => None,
+                };
+                lowest.ok_or(e)
+            }
+        }
+    }
+
+    /// Converts the error of a query at a historical height into HeightPruned if the node
+    /// pruned the height, querying the earliest available height if the error does not
+    /// include it. Other errors are returned as is, as are pruning errors for heights the
+    /// node still has the block of, since the earliest state it keeps can not be queried.
+    pub(crate) async fn check_pruned(&self, height: u64, e: CosmosGrpcError) -> CosmosGrpcError {
+        let lowest = match &e {
+            CosmosGrpcError::RequestError { error, .. } if e.is_pruned_height() => {
+                parse_lowest_height(error.message())
+            }
+            _ => return e,
+        };
+        let earliest_available = match lowest {
+            Some(lowest) => lowest,
+            None => match self.get_earliest_height().await {
+                Ok(earliest) if earliest > height => earliest,
+                _ => return e,
+            },
+        };
+        CosmosGrpcError::HeightPruned {
+            height,
+            earliest_available,
+        }
+    }
+
+    /// Returns the height and time of the latest block, errors if the node is syncing
+    pub(crate) async fn latest_height_and_time(
+        &self,
+    ) -> Result<(u64, SystemTime), CosmosGrpcError> {
+        match self.get_latest_block().await? {
+            LatestBlock::Latest { block } => block_height_and_time(&block),
+            LatestBlock::Syncing { .. } => Err(CosmosGrpcError::BadResponse(
+                "Node is syncing, block times are not current".to_string(),
+            )),
+            LatestBlock::WaitingToStart => Err(CosmosGrpcError::BadResponse(
+                "Chain has not started".to_string(),
+            )),
+        }
+    }
+
+    /// Estimates the current block time as the average interval over the last window blocks
+    pub async fn estimate_block_time(&self, window: u64) -> Result<Duration, CosmosGrpcError> {
+        let latest = self.latest_height_and_time().await?;
+        self.block_time_before(latest, window).await
+    }
+
+    async fn block_time_before(
+        &self,
+        latest: (u64, SystemTime),
+        window: u64,
+    ) -> Result<Duration, CosmosGrpcError> {
+        let first_height = std::cmp::max(1, latest.0.saturating_sub(window));
+        let first = block_height_and_time(&self.get_block_by_height(first_height).await?)?;
+        match average_block_interval(first, latest) {
+            Some(interval) => Ok(interval),
+            None => Err(CosmosGrpcError::BadResponse(
+                "Not enough blocks to estimate block time".to_string(),
+            )),
+        }
+    }
+
+    /// Estimates when the chain will reach the provided height using the average block time
+    /// of the last DEFAULT_BLOCK_TIME_WINDOW blocks. For heights that have already been
+    /// reached the actual block time is returned.
+    pub async fn estimate_time_of_height(
+        &self,
+        height: u64,
+    ) -> Result<SystemTime, CosmosGrpcError> {
+        let latest = self.latest_height_and_time().await?;
+        if height <= latest.0 {
+            let block = self.get_block_by_height(height).await?;
+            return Ok(block_height_and_time(&block)?.1);
+        }
+        let interval = self
+            .block_time_before(latest, DEFAULT_BLOCK_TIME_WINDOW)
+            .await?;
+        let remaining = u32::try_from(height - latest.0).map_err(|_| {
+            CosmosGrpcError::BadInput(format!("Height {} is too far in the future", height))
+        })?;
+        Ok(latest.1 + interval * remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+    use tonic::Status;
+
+    #[test]
+    fn test_average_block_interval() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(
+            average_block_interval((100, at(1000)), (200, at(1600))),
+            Some(Duration::from_secs(6))
+        );
+        assert_eq!(
+            average_block_interval((200, at(1000)), (200, at(1000))),
+            None
+        );
+        assert_eq!(
+            average_block_interval((200, at(1000)), (100, at(1600))),
+            None
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_height_pruned() {
+        let file =
+            std::env::temp_dir().join(format!("deep_space_pruned_{}.json", rand::random::<u64>()));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        let path = "/cosmos.base.tendermint.v1beta1.Service/GetBlockByHeight";
+        let request = |height: i64| {
+            let mut bytes = Vec::new();
+            GetBlockByHeightRequest { height }
+                .encode(&mut bytes)
+                .unwrap();
+            bytes
+        };
+        let pruned = Status::unknown("height 5 is not available, lowest height is 1000");
+        tape.save(path, &request(5), Err(&pruned)).unwrap();
+        let pruned = Status::unknown("height 1 is not available, lowest height is 1000");
+        tape.save(path, &request(1), Err(&pruned)).unwrap();
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        match contact.get_block_by_height(5).await {
+            Err(CosmosGrpcError::HeightPruned {
+                height,
+                earliest_available,
+            }) => assert_eq!((height, earliest_available), (5, 1000)),
+            other => panic!("Expected HeightPruned, got {:?}", other),
+        }
+        assert_eq!(contact.get_earliest_height().await.unwrap(), 1000);
+    }
+}
@@ -0,0 +1,205 @@
+//! Background tracking of the latest block. Computing a timeout height or waiting for the
+//! next block normally costs a round trip to the node, a Contact with a running block watcher
+//! polls the latest block once per interval instead and serves those from a cache.
+//!
+//! ```ignore
+//! contact.start_block_watcher(Duration::from_secs(1));
+//! let height = contact.latest_height().await?;
+//! ```
+
+use crate::client::runtime;
+use crate::client::runtime::sleep;
+use crate::client::runtime::TaskHandle;
+use crate::client::types::ChainStatus;
+use crate::client::types::LatestBlock;
+use crate::client::Contact;
+use crate::client::ContactInner;
+use crate::error::CosmosGrpcError;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// The number of polling intervals after which a cached block is no longer served
+const STALE_INTERVALS: u32 = 3;
+
+/// The latest block as last seen by the block watcher
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedBlock {
+    pub height: u64,
+    /// the block time, None if the node did not provide a valid one
+    pub time: Option<SystemTime>,
+    pub chain_id: String,
+    /// when the block was fetched from the node
+    pub observed: Instant,
+}
+
+impl WatchedBlock {
+    fn from_latest(latest: LatestBlock) -> Option<Self> {
+        let header = match latest {
+            LatestBlock::Latest { block } => block.header?,
+            LatestBlock::Syncing { .. } | LatestBlock::WaitingToStart => return None,
+        };
+        let time = header
+            .time
+            .filter(|time| time.seconds >= 0 && time.nanos >= 0)
+            .map(|time| UNIX_EPOCH + Duration::new(time.seconds as u64, time.nanos as u32));
+        Some(WatchedBlock {
+            height: header.height as u64,
+            time,
+            chain_id: header.chain_id,
+            observed: Instant::now(),
+        })
+    }
+}
+
+/// The polling task of a Contact, aborted when the Contact is dropped
+pub(crate) struct BlockWatcher {
+    interval: Duration,
+    latest: Arc<Mutex<Option<WatchedBlock>>>,
+    task: TaskHandle,
+}
+
+impl Drop for BlockWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Polls the latest block until every Contact sharing the watched connection is dropped. Only
+/// a weak reference is held between polls so the task does not keep the Contact alive. The
+/// cache is cleared if the node is syncing, halted or unreachable so that readers fall back
+/// to querying the node and see the actual error.
+async fn watch(
+    contact: Weak<ContactInner>,
+    latest: Arc<Mutex<Option<WatchedBlock>>>,
+    interval: Duration,
+) {
+    loop {
+        let contact = match contact.upgrade() {
+            Some(inner) => Contact { inner },
+            None => return,
+        };
+        let block = contact.get_latest_block().await;
+        drop(contact);
+        *latest.lock().unwrap() = block.ok().and_then(WatchedBlock::from_latest);
+        sleep(interval).await;
+    }
+}
+
+impl Contact {
+    /// Starts polling the latest block every interval in the background, replacing any
+    /// watcher that is already running. The watcher is shared by all clones of this Contact
+    /// and stops when the last of them is dropped. Must be called from within a Tokio runtime.
+    pub fn start_block_watcher(&self, interval: Duration) {
+        let latest = Arc::new(Mutex::new(None));
+        let task = runtime::spawn(watch(Arc::downgrade(&self.inner), latest.clone(), interval));
+        *self.inner.watcher.lock().unwrap() = Some(BlockWatcher {
+            interval,
+            latest,
+            task,
+        });
+    }
+
+    /// Stops the block watcher, later calls query the node again
+    pub fn stop_block_watcher(&self) {
+        *self.inner.watcher.lock().unwrap() = None;
+    }
+
+    /// The latest block seen by the block watcher, None if no watcher is running, the node
+    /// is not producing blocks or the last successful poll is more than a few intervals old
+    pub fn cached_block(&self) -> Option<WatchedBlock> {
+        let watcher = self.inner.watcher.lock().unwrap();
+        let watcher = watcher.as_ref()?;
+        let block = watcher.latest.lock().unwrap().clone()?;
+        if block.observed.elapsed() > watcher.interval * STALE_INTERVALS {
+            None
+        } else {
+            Some(block)
+        }
+    }
+
+    /// The latest block height, served from the block watcher if it has a current block
+    /// and queried from the node otherwise
+    pub async fn latest_height(&self) -> Result<u64, CosmosGrpcError> {
+        if let Some(block) = self.cached_block() {
+            return Ok(block.height);
+        }
+        match self.get_chain_status().await? {
+            ChainStatus::Moving { block_height } => Ok(block_height),
+            ChainStatus::Syncing => Err(CosmosGrpcError::NodeNotSynced),
+            ChainStatus::WaitingToStart => Err(CosmosGrpcError::ChainNotRunning),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestBlockResponse;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetSyncingResponse;
+    use prost::Message;
+    use std::fs;
+    use tendermint_proto::types::Block;
+    use tendermint_proto::types::Header;
+
+    fn encode<M: Message>(message: M) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        message.encode(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[actix_rt::test]
+    async fn test_block_watcher_cache() {
+        let file =
+            std::env::temp_dir().join(format!("deep_space_watcher_{}.json", rand::random::<u64>()));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        tape.save(
+            "/cosmos.base.tendermint.v1beta1.Service/GetSyncing",
+            &[],
+            Ok(&encode(GetSyncingResponse { syncing: false })),
+        )
+        .unwrap();
+        let block = Block {
+            header: Some(Header {
+                chain_id: "cosmoshub-4".to_string(),
+                height: 42,
+                ..Header::default()
+            }),
+            ..Block::default()
+        };
+        tape.save(
+            "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+            &[],
+            Ok(&encode(GetLatestBlockResponse {
+                block_id: None,
+                block: Some(block),
+            })),
+        )
+        .unwrap();
+
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        fs::remove_file(&file).unwrap();
+        assert!(contact.cached_block().is_none());
+        contact.start_block_watcher(Duration::from_secs(60));
+        let start = Instant::now();
+        while contact.cached_block().is_none() && start.elapsed() < Duration::from_secs(5) {
+            sleep(Duration::from_millis(10)).await;
+        }
+        let block = contact.cached_block().unwrap();
+        assert_eq!(block.chain_id, "cosmoshub-4");
+        assert_eq!(block.time, None);
+        // every recorded response has been used, so this can only come from the cache
+        assert_eq!(contact.latest_height().await.unwrap(), 42);
+
+        contact.stop_block_watcher();
+        assert!(contact.cached_block().is_none());
+        assert!(contact.latest_height().await.is_err());
+    }
+}
@@ -0,0 +1,168 @@
+//! Synchronous wrappers around the main Contact operations for programs that do not use an
+//! async runtime, such as command line tools. Only compiled if the blocking feature is enabled.
+//!
+//! ```ignore
+//! let contact = BlockingContact::new("http://localhost:9090", Duration::from_secs(30), "cosmos")?;
+//! let balances = contact.get_balances(address)?;
+//! ```
+//!
+//! Each BlockingContact drives its own single threaded Tokio runtime, its methods must not be
+//! called from within an async context. Anything not wrapped here can be run with block_on.
+
+use crate::client::types::LatestBlock;
+use crate::client::ChainStatus;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+use crate::utils::GasDetails;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use std::future::Future;
+use std::time::Duration;
+use tokio::runtime::Builder;
+use tokio::runtime::Runtime;
+
+/// A Contact whose methods block until the request completes
+pub struct BlockingContact {
+    contact: Contact,
+    runtime: Runtime,
+}
+
+impl BlockingContact {
+    pub fn new(url: &str, timeout: Duration, chain_prefix: &str) -> Result<Self, CosmosGrpcError> {
+        BlockingContact::from_contact(Contact::new(url, timeout, chain_prefix)?)
+    }
+
+    /// Wraps a configured Contact, for example one with connection options set
+    pub fn from_contact(contact: Contact) -> Result<Self, CosmosGrpcError> {
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Failed to start runtime: {}", e)))?;
+        Ok(BlockingContact { contact, runtime })
+    }
+
+    /// The wrapped Contact, its async methods can be run with block_on
+    pub fn contact(&self) -> &Contact {
+        &self.contact
+    }
+
+    /// Runs a future to completion on the runtime of this BlockingContact
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    pub fn get_prefix(&self) -> String {
+        self.contact.get_prefix()
+    }
+
+    pub fn get_chain_status(&self) -> Result<ChainStatus, CosmosGrpcError> {
+        self.block_on(self.contact.get_chain_status())
+    }
+
+    pub fn get_latest_block(&self) -> Result<LatestBlock, CosmosGrpcError> {
+        self.block_on(self.contact.get_latest_block())
+    }
+
+    pub fn get_account_info(&self, address: Address) -> Result<BaseAccount, CosmosGrpcError> {
+        self.block_on(self.contact.get_account_info(address))
+    }
+
+    pub fn get_balances(&self, address: Address) -> Result<Vec<Coin>, CosmosGrpcError> {
+        self.block_on(self.contact.get_balances(address))
+    }
+
+    pub fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> Result<Option<Coin>, CosmosGrpcError> {
+        self.block_on(self.contact.get_balance(address, denom))
+    }
+
+    pub fn get_tx_by_hash(&self, txhash: String) -> Result<GetTxResponse, CosmosGrpcError> {
+        self.block_on(self.contact.get_tx_by_hash(txhash))
+    }
+
+    pub fn simulate_tx(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+        attribute_msgs: bool,
+    ) -> Result<GasDetails, CosmosGrpcError> {
+        self.block_on(
+            self.contact
+                .simulate_tx(messages, memo, private_key, attribute_msgs),
+        )
+    }
+
+    pub fn send_transaction(
+        &self,
+        msg: Vec<u8>,
+        mode: BroadcastMode,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        self.block_on(self.contact.send_transaction(msg, mode))
+    }
+
+    pub fn send_tokens(
+        &self,
+        coin: Coin,
+        fee: Option<Coin>,
+        destination: Address,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        self.block_on(
+            self.contact
+                .send_tokens(coin, fee, destination, private_key, wait_timeout),
+        )
+    }
+
+    pub fn send_message(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        self.block_on(
+            self.contact
+                .send_message(messages, memo, fee, private_key, wait_timeout),
+        )
+    }
+
+    pub fn wait_for_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        self.block_on(self.contact.wait_for_tx(response, timeout))
+    }
+
+    pub fn wait_for_next_block(&self, timeout: Duration) -> Result<(), CosmosGrpcError> {
+        self.block_on(self.contact.wait_for_next_block(timeout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocking_connection_error() {
+        // nothing listens on the discard port, the error is returned rather than hanging
+        let contact =
+            BlockingContact::new("http://127.0.0.1:9", Duration::from_secs(5), "cosmos").unwrap();
+        let address = PrivateKey::from_secret(&[1]).to_address("cosmos").unwrap();
+        let res = contact.get_balances(address);
+        assert!(res.unwrap_err().is_transient_network());
+    }
+}
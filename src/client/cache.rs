@@ -0,0 +1,215 @@
+//! An optional cache of query responses, for read heavy applications such as dashboards
+//! that make the same queries every few seconds. Responses are cached per method and
+//! request for the TTL configured for the method, methods without a TTL are never cached.
+
+use crate::client::Contact;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The default number of responses kept, see QueryCacheConfig::max_entries
+pub const DEFAULT_MAX_CACHE_ENTRIES: usize = 10_000;
+
+/// Which query methods are cached and for how long
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryCacheConfig {
+    ttls: HashMap<String, Duration>,
+    max_entries: usize,
+}
+
+impl Default for QueryCacheConfig {
+    fn default() -> Self {
+        QueryCacheConfig {
+            ttls: HashMap::new(),
+            max_entries: DEFAULT_MAX_CACHE_ENTRIES,
+        }
+    }
+}
+
+impl QueryCacheConfig {
+    /// A configuration that caches nothing until methods are added
+    pub fn new() -> Self {
+        QueryCacheConfig::default()
+    }
+
+    /// Caches the responses of a method, such as /cosmos.staking.v1beta1.Query/Validators,
+    /// for the provided TTL
+    pub fn method(mut self, path: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(path.into(), ttl);
+        self
+    }
+
+    /// The most responses kept at once, expired responses are dropped first when full
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Caches validators for a minute, module params for ten minutes and denom metadata for
+    /// an hour. Accounts are not included as they carry the sequence used for signing.
+    pub fn dashboard() -> Self {
+        let minute = Duration::from_secs(60);
+        let mut config = QueryCacheConfig::new()
+            .method("/cosmos.staking.v1beta1.Query/Validators", minute)
+            .method("/cosmos.staking.v1beta1.Query/Validator", minute)
+            .method("/cosmos.bank.v1beta1.Query/DenomMetadata", 60 * minute)
+            .method("/cosmos.bank.v1beta1.Query/DenomsMetadata", 60 * minute);
+        for module in [
+            "auth.v1beta1",
+            "bank.v1beta1",
+            "distribution.v1beta1",
+            "gov.v1beta1",
+            "mint.v1beta1",
+            "slashing.v1beta1",
+            "staking.v1beta1",
+        ]
+        .iter()
+        {
+            config = config.method(format!("/cosmos.{}.Query/Params", module), 10 * minute);
+        }
+        config
+    }
+
+    pub fn ttl(&self, path: &str) -> Option<Duration> {
+        self.ttls.get(path).copied()
+    }
+}
+
+struct CacheEntry {
+    expires: Instant,
+    response: Vec<u8>,
+}
+
+/// The cached responses shared by a Contact and its clones, keyed by method and encoded
+/// request
+pub(crate) struct QueryCache {
+    config: QueryCacheConfig,
+    entries: Mutex<HashMap<(String, Vec<u8>), CacheEntry>>,
+}
+
+impl QueryCache {
+    pub(crate) fn new(config: QueryCacheConfig) -> Self {
+        QueryCache {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn ttl(&self, path: &str) -> Option<Duration> {
+        self.config.ttl(path)
+    }
+
+    /// The cached response to a request if it has not expired
+    pub(crate) fn get(&self, path: &str, request: &[u8]) -> Option<Vec<u8>> {
+        let entries = self.entries.lock().unwrap();
+        match entries.get(&(path.to_string(), request.to_vec())) {
+            Some(entry) if entry.expires > Instant::now() => Some(entry.response.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, path: &str, request: Vec<u8>, response: Vec<u8>, ttl: Duration) {
+        if self.config.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= self.config.max_entries {
+            let now = Instant::now();
+            entries.retain(|_, entry| entry.expires > now);
+            if entries.len() >= self.config.max_entries {
+                entries.clear();
+            }
+        }
+        let entry = CacheEntry {
+            expires: Instant::now() + ttl,
+            response,
+        };
+        entries.insert((path.to_string(), request), entry);
+    }
+
+    fn invalidate(&self, path: Option<&str>) {
+        let mut entries = self.entries.lock().unwrap();
+        match path {
+            Some(path) => entries.retain(|(method, _), _| method != path),
+            None => entries.clear(),
+        }
+    }
+}
+
+impl Contact {
+    /// Caches the responses of the methods in the configuration, clones of the returned
+    /// Contact share the cache. The returned Contact does not share its connection with
+    /// clones of this one.
+    pub fn with_query_cache(self, config: QueryCacheConfig) -> Self {
+        let mut inner = self.unshared();
+        inner.cache = Some(Arc::new(QueryCache::new(config)));
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Drops every cached response, or only those of one method if a path is provided,
+    /// for example after sending a transaction that changes the cached state
+    pub fn invalidate_query_cache(&self, path: Option<&str>) {
+        if let Some(cache) = &self.inner.cache {
+            cache.invalidate(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::Params;
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryParamsRequest;
+    use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryParamsResponse;
+    use prost::Message;
+    use std::fs;
+
+    const PARAMS: &str = "/cosmos.auth.v1beta1.Query/Params";
+
+    #[actix_rt::test]
+    async fn test_query_cache() {
+        let file =
+            std::env::temp_dir().join(format!("deep_space_cache_{}.json", rand::random::<u64>()));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let params = QueryParamsResponse {
+            params: Some(Params {
+                max_memo_characters: 256,
+                ..Params::default()
+            }),
+        };
+        let mut response = Vec::new();
+        params.encode(&mut response).unwrap();
+        // the node is only asked once
+        recorder
+            .inner
+            .tape
+            .as_ref()
+            .unwrap()
+            .save(PARAMS, &[], Ok(&response))
+            .unwrap();
+
+        let config = QueryCacheConfig::dashboard();
+        assert_eq!(config.ttl(PARAMS), Some(Duration::from_secs(600)));
+        let contact = Contact::from_recording(&file, Duration::from_secs(1))
+            .unwrap()
+            .with_query_cache(config);
+        fs::remove_file(&file).unwrap();
+        for _ in 0..3 {
+            let res: QueryParamsResponse = contact
+                .grpc_unary(PARAMS, QueryParamsRequest {})
+                .await
+                .unwrap();
+            assert_eq!(res, params);
+        }
+        contact.invalidate_query_cache(Some(PARAMS));
+        let res: Result<QueryParamsResponse, _> =
+            contact.grpc_unary(PARAMS, QueryParamsRequest {}).await;
+        assert!(res.is_err());
+    }
+}
@@ -0,0 +1,296 @@
+//! Loads chain metadata from the [cosmos/chain-registry][1] `chain.json` format, so that the
+//! address prefix, HD path, fee tokens and gRPC endpoints of a chain do not need to be
+//! configured by hand. Only compiled if the chainregistry feature is enabled.
+//!
+//! No HTTP client is bundled, the json can be embedded with `include_str!`, read from a local
+//! checkout of the registry with [`ChainInfo::from_registry_dir`] or fetched by the caller
+//! and passed to [`ChainInfo::from_json`].
+//!
+//! [1]: https://github.com/cosmos/chain-registry
+
+use crate::client::gas_price::ChainRegistryGasPrice;
+use crate::client::gas_price::FeeToken;
+use crate::client::gas_price::GasPriceTier;
+use crate::client::Contact;
+use crate::coin_type::hd_path;
+use crate::coin_type::DEFAULT_COIN_TYPE;
+use crate::error::CosmosGrpcError;
+use crate::hardware_wallet::HdPath;
+use crate::Dec;
+use serde_json::Number;
+use std::path::Path;
+use std::time::Duration;
+
+/// The slip44 coin type used by most Cosmos chains
+pub const DEFAULT_SLIP44: u32 = DEFAULT_COIN_TYPE;
+
+#[derive(Deserialize)]
+struct RawChain {
+    chain_name: String,
+    #[serde(default)]
+    chain_id: String,
+    bech32_prefix: String,
+    slip44: Option<u32>,
+    #[serde(default)]
+    fees: RawFees,
+    #[serde(default)]
+    staking: RawStaking,
+    #[serde(default)]
+    apis: RawApis,
+}
+
+#[derive(Deserialize, Default)]
+struct RawFees {
+    #[serde(default)]
+    fee_tokens: Vec<RawFeeToken>,
+}
+
+#[derive(Deserialize)]
+struct RawFeeToken {
+    denom: String,
+    fixed_min_gas_price: Option<Number>,
+    low_gas_price: Option<Number>,
+    average_gas_price: Option<Number>,
+    high_gas_price: Option<Number>,
+}
+
+#[derive(Deserialize, Default)]
+struct RawStaking {
+    #[serde(default)]
+    staking_tokens: Vec<RawDenom>,
+}
+
+#[derive(Deserialize)]
+struct RawDenom {
+    denom: String,
+}
+
+#[derive(Deserialize, Default)]
+struct RawApis {
+    #[serde(default)]
+    grpc: Vec<RawEndpoint>,
+}
+
+#[derive(Deserialize)]
+struct RawEndpoint {
+    address: String,
+}
+
+/// Converts a json number such as 0.025 or 1e-5 into a Dec, json numbers in the registry
+/// are written by hand and may use either form
+fn number_to_dec(number: &Number) -> Result<Dec, CosmosGrpcError> {
+    let invalid = || CosmosGrpcError::BadInput(format!("Invalid gas price {}", number));
+    let value = number.to_string().to_lowercase();
+    let (mantissa, exponent) = match value.split_once('e') {
+        Some((mantissa, exponent)) => (
+            mantissa.to_string(),
+            exponent.parse::<i32>().map_err(|_| invalid())?,
+        ),
+        None => (value, 0),
+    };
+    let mut dec: Dec = mantissa.parse().map_err(|_| invalid())?;
+    let ten = Dec::from(10u64);
+    for _ in 0..exponent.abs() {
+        dec = if exponent > 0 {
+            dec.mul(&ten)
+        } else {
+            dec.quo(&ten).ok_or_else(invalid)?
+        };
+    }
+    Ok(dec)
+}
+
+/// Adds a scheme to registry gRPC addresses that do not have one, port 443 is assumed
+/// to be https and anything else plaintext
+fn normalize_grpc_url(address: &str) -> String {
+    let address = address.trim().trim_end_matches('/');
+    if address.starts_with("http://") || address.starts_with("https://") {
+        address.to_string()
+    } else if address.ends_with(":443") {
+        format!("https://{}", address)
+    } else {
+        format!("http://{}", address)
+    }
+}
+
+/// The chain metadata deep_space needs from a chain registry entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainInfo {
+    pub chain_name: String,
+    pub chain_id: String,
+    pub bech32_prefix: String,
+    /// the slip44 coin type used in the HD path, 60 for Ethermint chains
+    pub slip44: u32,
+    pub fee_tokens: Vec<FeeToken>,
+    pub staking_denoms: Vec<String>,
+    /// gRPC endpoints with a scheme, in the order listed in the registry
+    pub grpc_endpoints: Vec<String>,
+}
+
+impl ChainInfo {
+    /// Parses the contents of a chain registry chain.json file
+    pub fn from_json(json: &str) -> Result<ChainInfo, CosmosGrpcError> {
+        let raw: RawChain =
+            serde_json::from_str(json).map_err(|e| CosmosGrpcError::BadInput(e.to_string()))?;
+        let mut fee_tokens = Vec::new();
+        for token in raw.fees.fee_tokens {
+            let price = |price: Option<Number>| match price {
+                Some(price) => number_to_dec(&price).map(Some),
+                None => Ok(None),
+            };
+            fee_tokens.push(FeeToken {
+                denom: token.denom,
+                fixed_min_gas_price: price(token.fixed_min_gas_price)?,
+                low_gas_price: price(token.low_gas_price)?,
+                average_gas_price: price(token.average_gas_price)?,
+                high_gas_price: price(token.high_gas_price)?,
+            });
+        }
+        Ok(ChainInfo {
+            chain_name: raw.chain_name,
+            chain_id: raw.chain_id,
+            bech32_prefix: raw.bech32_prefix,
+            slip44: raw.slip44.unwrap_or(DEFAULT_SLIP44),
+            fee_tokens,
+            staking_denoms: raw
+                .staking
+                .staking_tokens
+                .into_iter()
+                .map(|t| t.denom)
+                .collect(),
+            grpc_endpoints: raw
+                .apis
+                .grpc
+                .iter()
+                .map(|e| normalize_grpc_url(&e.address))
+                .collect(),
+        })
+    }
+
+    /// Loads a chain by name from a local checkout of the chain registry repository
+    pub fn from_registry_dir(dir: &Path, chain_name: &str) -> Result<ChainInfo, CosmosGrpcError> {
+        let path = dir.join(chain_name).join("chain.json");
+        let json = std::fs::read_to_string(&path).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        ChainInfo::from_json(&json)
+    }
+
+    /// The HD path of the first key for this chain, pass its string form to
+    /// PrivateKey::from_hd_wallet_path
+    pub fn hd_path(&self) -> HdPath {
+        hd_path(self.slip44, 0)
+    }
+
+    /// The preferred fee denom, the first fee token listed
+    pub fn fee_denom(&self) -> Option<&str> {
+        self.fee_tokens.first().map(|t| t.denom.as_str())
+    }
+
+    /// A gas price oracle using the registry fee token prices
+    pub fn gas_price_oracle(&self, tier: GasPriceTier) -> ChainRegistryGasPrice {
+        ChainRegistryGasPrice::new(self.fee_tokens.clone(), tier)
+    }
+
+    /// Builds a Contact for the first listed gRPC endpoint with this chain's prefix and
+    /// coin type
+    pub fn contact(&self, timeout: Duration) -> Result<Contact, CosmosGrpcError> {
+        match self.grpc_endpoints.first() {
+            Some(url) => {
+                Ok(Contact::new(url, timeout, &self.bech32_prefix)?.with_coin_type(self.slip44))
+            }
+            None => Err(CosmosGrpcError::BadInput(format!(
+                "No gRPC endpoints listed for {}",
+                self.chain_name
+            ))),
+        }
+    }
+}
+
+impl Contact {
+    /// Loads a chain by name from a local checkout of the chain registry and builds a
+    /// Contact for it, the returned ChainInfo provides the HD path and fee defaults
+    pub fn from_chain_registry(
+        dir: &Path,
+        chain_name: &str,
+        timeout: Duration,
+    ) -> Result<(Contact, ChainInfo), CosmosGrpcError> {
+        let info = ChainInfo::from_registry_dir(dir, chain_name)?;
+        let contact = info.contact(timeout)?;
+        Ok((contact, info))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN_JSON: &str = r#"{
+        "$schema": "../chain.schema.json",
+        "chain_name": "evmos",
+        "chain_id": "evmos_9001-2",
+        "bech32_prefix": "evmos",
+        "slip44": 60,
+        "fees": {
+            "fee_tokens": [
+                {
+                    "denom": "aevmos",
+                    "fixed_min_gas_price": 25000000000,
+                    "low_gas_price": 20000000000,
+                    "average_gas_price": 25000000000,
+                    "high_gas_price": 4e10
+                }
+            ]
+        },
+        "staking": { "staking_tokens": [{ "denom": "aevmos" }] },
+        "apis": {
+            "grpc": [
+                { "address": "grpc.evmos.example:443", "provider": "a" },
+                { "address": "http://10.0.0.1:9090", "provider": "b" }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_chain_json() {
+        let info = ChainInfo::from_json(CHAIN_JSON).unwrap();
+        assert_eq!(info.bech32_prefix, "evmos");
+        assert_eq!(info.hd_path().to_string(), "m/44'/60'/0'/0/0");
+        assert_eq!(info.fee_denom(), Some("aevmos"));
+        assert_eq!(info.staking_denoms, vec!["aevmos".to_string()]);
+        assert_eq!(
+            info.fee_tokens[0].gas_price(GasPriceTier::High),
+            Some("40000000000".parse().unwrap())
+        );
+        // low is below the fixed minimum
+        assert_eq!(
+            info.fee_tokens[0].gas_price(GasPriceTier::Low),
+            Some("25000000000".parse().unwrap())
+        );
+        assert_eq!(
+            info.grpc_endpoints,
+            vec![
+                "https://grpc.evmos.example:443".to_string(),
+                "http://10.0.0.1:9090".to_string()
+            ]
+        );
+        assert_eq!(
+            info.contact(Duration::from_secs(30)).unwrap().get_prefix(),
+            "evmos"
+        );
+    }
+
+    #[test]
+    fn test_number_to_dec() {
+        let number = |json: &str| serde_json::from_str::<Number>(json).unwrap();
+        assert_eq!(
+            number_to_dec(&number("0.025")).unwrap(),
+            "0.025".parse().unwrap()
+        );
+        assert_eq!(
+            number_to_dec(&number("1e-5")).unwrap(),
+            "0.00001".parse().unwrap()
+        );
+        assert_eq!(number_to_dec(&number("3")).unwrap(), Dec::from(3u64));
+    }
+}
@@ -0,0 +1,111 @@
+//! A prost codec that enforces the message size limits configured on a Contact
+
+use bytes::Buf;
+use prost::Message;
+use std::marker::PhantomData;
+use tonic::codec::Codec;
+use tonic::codec::DecodeBuf;
+use tonic::codec::Decoder;
+use tonic::codec::EncodeBuf;
+use tonic::codec::Encoder;
+use tonic::Code;
+use tonic::Status;
+
+/// The maximum sizes of gRPC messages in bytes, None for no limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MessageLimits {
+    pub max_decoding_message_size: Option<usize>,
+    pub max_encoding_message_size: Option<usize>,
+}
+
+/// The error returned for a message over a limit, the same code tonic uses
+fn too_large(direction: &str, len: usize, limit: usize) -> Status {
+    Status::new(
+        Code::OutOfRange,
+        format!(
+            "{} message is {} bytes, the limit is {} bytes",
+            direction, len, limit
+        ),
+    )
+}
+
+pub(crate) struct LimitedProstCodec<T, U> {
+    limits: MessageLimits,
+    _pd: PhantomData<(T, U)>,
+}
+
+impl<T, U> LimitedProstCodec<T, U> {
+    pub fn new(limits: MessageLimits) -> Self {
+        LimitedProstCodec {
+            limits,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<T, U> Codec for LimitedProstCodec<T, U>
+where
+    T: Message + Send + 'static,
+    U: Message + Default + Send + 'static,
+{
+    type Encode = T;
+    type Decode = U;
+    type Encoder = LimitedEncoder<T>;
+    type Decoder = LimitedDecoder<U>;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        LimitedEncoder {
+            limit: self.limits.max_encoding_message_size,
+            _pd: PhantomData,
+        }
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        LimitedDecoder {
+            limit: self.limits.max_decoding_message_size,
+            _pd: PhantomData,
+        }
+    }
+}
+
+pub(crate) struct LimitedEncoder<T> {
+    limit: Option<usize>,
+    _pd: PhantomData<T>,
+}
+
+impl<T: Message> Encoder for LimitedEncoder<T> {
+    type Item = T;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, buf: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        let len = item.encoded_len();
+        match self.limit {
+            Some(limit) if len > limit => Err(too_large("Request", len, limit)),
+            _ => {
+                item.encode(buf)
+                    .expect("Message only errors if not enough space");
+                Ok(())
+            }
+        }
+    }
+}
+
+pub(crate) struct LimitedDecoder<U> {
+    limit: Option<usize>,
+    _pd: PhantomData<U>,
+}
+
+impl<U: Message + Default> Decoder for LimitedDecoder<U> {
+    type Item = U;
+    type Error = Status;
+
+    fn decode(&mut self, buf: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        let len = buf.remaining();
+        match self.limit {
+            Some(limit) if len > limit => Err(too_large("Response", len, limit)),
+            _ => Message::decode(buf)
+                .map(Some)
+                .map_err(|e| Status::new(Code::Internal, e.to_string())),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Named chain profiles loaded from a TOML or JSON file, so that services talking to many
+//! chains keep their endpoints, prefixes and fee settings in one place. Only compiled if the
+//! config feature is enabled.
+//!
+//! ```toml
+//! [chains.cosmoshub]
+//! grpc = "https://grpc.cosmos.example:443"
+//! prefix = "cosmos"
+//! denom = "uatom"
+//! gas_price = "0.025"
+//! timeout_secs = 30
+//!
+//! [chains.cosmoshub.tls]
+//! domain_name = "grpc.cosmos.example"
+//! ```
+//!
+//! ```ignore
+//! let config = Config::from_file(Path::new("chains.toml"))?;
+//! let contact = config.contact("cosmoshub")?;
+//! let fee = config.chain("cosmoshub")?.fee()?;
+//! ```
+
+use crate::client::gas_price::StaticGasPrice;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::Coin;
+use crate::Dec;
+use crate::DecCoin;
+use crate::Fee;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn default_gas_limit() -> u64 {
+    200_000
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// A set of named chains
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub chains: BTreeMap<String, ChainConfig>,
+}
+
+/// The connection and fee settings of a chain
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainConfig {
+    /// the gRPC endpoint including the scheme
+    pub grpc: String,
+    /// the bech32 address prefix
+    pub prefix: String,
+    /// the denom fees are paid in
+    pub denom: String,
+    /// the expected chain id, not checked against the node
+    #[serde(default)]
+    pub chain_id: Option<String>,
+    /// the gas price in denom as a decimal, fees are zero if not set
+    #[serde(default)]
+    pub gas_price: Option<String>,
+    /// the default gas limit used by fee()
+    #[serde(default = "default_gas_limit")]
+    pub gas_limit: u64,
+    /// the Contact timeout in seconds
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// TLS settings for https endpoints, requires the tls feature
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+}
+
+/// TLS settings of a chain, see TlsOptions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    #[serde(default)]
+    pub domain_name: Option<String>,
+    /// path to a PEM encoded certificate authority
+    #[serde(default)]
+    pub ca_certificate: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn from_toml(toml: &str) -> Result<Config, CosmosGrpcError> {
+        toml::from_str(toml)
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Invalid config: {}", e)))
+    }
+
+    pub fn from_json(json: &str) -> Result<Config, CosmosGrpcError> {
+        serde_json::from_str(json)
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Invalid config: {}", e)))
+    }
+
+    /// Loads a config file, files ending in .json are parsed as JSON and anything else as TOML
+    pub fn from_file(path: &Path) -> Result<Config, CosmosGrpcError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Config::from_json(&contents),
+            _ => Config::from_toml(&contents),
+        }
+    }
+
+    /// The settings of the named chain
+    pub fn chain(&self, name: &str) -> Result<&ChainConfig, CosmosGrpcError> {
+        self.chains
+            .get(name)
+            .ok_or_else(|| CosmosGrpcError::BadInput(format!("No chain named {} in config", name)))
+    }
+
+    /// Builds a Contact for the named chain
+    pub fn contact(&self, name: &str) -> Result<Contact, CosmosGrpcError> {
+        self.chain(name)?.contact()
+    }
+}
+
+impl ChainConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+
+    /// The configured gas price, None if no price is set
+    pub fn gas_price(&self) -> Result<Option<DecCoin>, CosmosGrpcError> {
+        match &self.gas_price {
+            Some(price) => {
+                let amount: Dec = price.parse().map_err(|_| {
+                    CosmosGrpcError::BadInput(format!("Invalid gas price {}", price))
+                })?;
+                Ok(Some(DecCoin {
+                    amount,
+                    denom: self.denom.clone(),
+                }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// A gas price oracle returning the configured gas price, for use with Contact::get_fee
+    pub fn gas_price_oracle(&self) -> Result<StaticGasPrice, CosmosGrpcError> {
+        Ok(StaticGasPrice::new(self.gas_price()?.into_iter().collect()))
+    }
+
+    /// The fee for the configured gas limit at the configured gas price
+    pub fn fee(&self) -> Result<Fee, CosmosGrpcError> {
+        self.fee_for_gas(self.gas_limit)
+    }
+
+    /// The fee for the provided gas limit at the configured gas price
+    pub fn fee_for_gas(&self, gas_limit: u64) -> Result<Fee, CosmosGrpcError> {
+        let amount = match self.gas_price()? {
+            Some(price) => price
+                .fee_for_gas(gas_limit)
+                .ok_or_else(|| CosmosGrpcError::BadInput(format!("Invalid gas price {}", price)))?,
+            None => Coin {
+                amount: 0u64.into(),
+                denom: self.denom.clone(),
+            },
+        };
+        Ok(Fee {
+            amount: vec![amount],
+            gas_limit,
+            granter: None,
+            payer: None,
+        })
+    }
+
+    /// Builds a Contact for this chain
+    pub fn contact(&self) -> Result<Contact, CosmosGrpcError> {
+        let contact = Contact::new(&self.grpc, self.timeout(), &self.prefix)?;
+        match &self.tls {
+            Some(tls) => with_tls(contact, tls),
+            None => Ok(contact),
+        }
+    }
+}
+
+#[cfg(feature = "tls")]
+fn with_tls(contact: Contact, tls: &TlsConfig) -> Result<Contact, CosmosGrpcError> {
+    let ca_certificate_pem = match &tls.ca_certificate {
+        Some(path) => Some(std::fs::read(path).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Failed to read {}: {}", path.display(), e))
+        })?),
+        None => None,
+    };
+    Ok(contact.with_tls(crate::client::TlsOptions {
+        domain_name: tls.domain_name.clone(),
+        ca_certificate_pem,
+    }))
+}
+
+#[cfg(not(feature = "tls"))]
+fn with_tls(_contact: Contact, _tls: &TlsConfig) -> Result<Contact, CosmosGrpcError> {
+    Err(CosmosGrpcError::BadInput(
+        "TLS settings require the tls feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOML: &str = r#"
+[chains.cosmoshub]
+grpc = "http://localhost:9090"
+prefix = "cosmos"
+denom = "uatom"
+gas_price = "0.025"
+
+[chains.local]
+grpc = "http://localhost:9091"
+prefix = "cosmos"
+denom = "stake"
+timeout_secs = 5
+"#;
+
+    #[test]
+    fn test_config() {
+        let config = Config::from_toml(TOML).unwrap();
+        let hub = config.chain("cosmoshub").unwrap();
+        assert_eq!(hub.gas_limit, 200_000);
+        assert_eq!(hub.fee().unwrap().amount[0], "5000uatom".parse().unwrap());
+        let contact = config.contact("local").unwrap();
+        assert_eq!(contact.get_timeout(), Duration::from_secs(5));
+        assert_eq!(
+            config.chain("local").unwrap().fee().unwrap().amount[0].denom,
+            "stake"
+        );
+        assert!(config.chain("osmosis").is_err());
+
+        let json = r#"{"chains": {"local": {"grpc": "http://localhost:9091", "prefix": "cosmos", "denom": "stake", "timeout_secs": 5}}}"#;
+        let from_json = Config::from_json(json).unwrap();
+        assert_eq!(from_json.chains["local"], config.chains["local"]);
+        // misspelled settings are rejected rather than silently ignored
+        assert!(Config::from_toml(
+            "[chains.a]\ngrpc = \"\"\nprefix = \"a\"\ndenom = \"a\"\ngas = 1"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//! Waiting for a confirmation depth after inclusion. On chains with instant finality a
+//! transaction in a block is final, but some consensus configurations can replace recent
+//! blocks. Callers that need more certainty can wait for a number of blocks to be produced
+//! on top of the inclusion block, the transaction is then looked up again to make sure it
+//! was not dropped in the meantime.
+//!
+//! ```ignore
+//! let events = contact.watch_tx(response, Duration::from_secs(60), 2);
+//! futures::pin_mut!(events);
+//! while let Some(event) = events.next().await {
+//!     println!("{:?}", event?);
+//! }
+//! ```
+
+use crate::client::runtime::sleep;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Fee;
+use crate::Msg;
+use crate::PrivateKey;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use std::time::Duration;
+use std::time::Instant;
+use tonic::Code;
+
+/// A step in the lifecycle of a broadcast transaction, see Contact::watch_tx
+#[derive(Debug, Clone, PartialEq)]
+pub enum TxLifecycleEvent {
+    /// the transaction was found in a block
+    Included { tx: TxResponse },
+    /// new blocks were produced on top of the inclusion block, depth is the number of
+    /// blocks on top of it as of height
+    Confirmation { height: u64, depth: u64 },
+    /// the transaction reached the requested depth and is still included, this is always
+    /// the last event
+    Confirmed { tx: TxResponse, depth: u64 },
+}
+
+enum WatchState {
+    Pending(TxResponse),
+    Included { tx: TxResponse, depth: u64 },
+    Done,
+}
+
+/// The number of blocks on top of the inclusion block, zero while the transaction is in the
+/// latest block. Confirmations everywhere in deep_space, including deposits, are this depth.
+pub fn confirmation_depth(inclusion_height: u64, latest_height: u64) -> u64 {
+    latest_height.saturating_sub(inclusion_height)
+}
+
+impl Contact {
+    /// Follows a broadcast transaction until it is included and required_depth blocks have
+    /// been produced on top of it, producing an event for each step. The stream ends after
+    /// the Confirmed event or the first error. A required_depth of zero confirms on inclusion.
+    pub fn watch_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+        required_depth: u64,
+    ) -> impl Stream<Item = Result<TxLifecycleEvent, CosmosGrpcError>> + '_ {
+        let start = Instant::now();
+        stream::unfold(WatchState::Pending(response), move |state| async move {
+            match state {
+                WatchState::Pending(response) => {
+                    let remaining = timeout.checked_sub(start.elapsed()).unwrap_or_default();
+                    match self.wait_for_tx(response, remaining).await {
+                        Ok(tx) => Some((
+                            Ok(TxLifecycleEvent::Included { tx: tx.clone() }),
+                            WatchState::Included { tx, depth: 0 },
+                        )),
+                        Err(e) => Some((Err(e), WatchState::Done)),
+                    }
+                }
+                WatchState::Included { tx, depth } if depth >= required_depth => {
+                    let event = if required_depth == 0 {
+                        Ok(TxLifecycleEvent::Confirmed { tx, depth })
+                    } else {
+                        self.check_still_included(tx)
+                            .await
+                            .map(|tx| TxLifecycleEvent::Confirmed { tx, depth })
+                    };
+                    Some((event, WatchState::Done))
+                }
+                WatchState::Included { tx, depth } => {
+                    match self
+                        .wait_for_depth(&tx, depth, required_depth, start, timeout)
+                        .await
+                    {
+                        Ok((height, depth)) => Some((
+                            Ok(TxLifecycleEvent::Confirmation { height, depth }),
+                            WatchState::Included { tx, depth },
+                        )),
+                        Err(e) => Some((Err(e), WatchState::Done)),
+                    }
+                }
+                WatchState::Done => None,
+            }
+        })
+    }
+
+    /// Waits for a broadcast transaction to be included and required_depth blocks to be
+    /// produced on top of it, returning the transaction as found after reaching the depth
+    pub async fn wait_for_confirmations(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+        required_depth: u64,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let events = self.watch_tx(response, timeout, required_depth);
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            if let TxLifecycleEvent::Confirmed { tx, .. } = event? {
+                return Ok(tx);
+            }
+        }
+        Err(CosmosGrpcError::BadResponse(
+            "Transaction watch ended without confirmation?".to_string(),
+        ))
+    }
+
+    /// Sends one or more messages like send_message() and waits for required_depth blocks
+    /// on top of the inclusion block before returning
+    pub async fn send_message_with_confirmations(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Duration,
+        required_depth: u64,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let start = Instant::now();
+        let response = self
+            .send_message(messages, memo, fee, private_key, None)
+            .await?;
+        let remaining = wait_timeout
+            .checked_sub(start.elapsed())
+            .unwrap_or_default();
+        self.wait_for_confirmations(response, remaining, required_depth)
+            .await
+    }
+
+    /// Polls the latest height until the depth of the transaction is greater than
+    /// last_depth, returning the height and the new depth
+    async fn wait_for_depth(
+        &self,
+        tx: &TxResponse,
+        last_depth: u64,
+        required_depth: u64,
+        start: Instant,
+        timeout: Duration,
+    ) -> Result<(u64, u64), CosmosGrpcError> {
+        let inclusion_height = tx.height as u64;
+        while start.elapsed() < timeout {
+            match self.latest_height().await {
+                Ok(height) if confirmation_depth(inclusion_height, height) > last_depth => {
+                    return Ok((height, confirmation_depth(inclusion_height, height)))
+                }
+                Ok(_) => {}
+                Err(CosmosGrpcError::NodeNotSynced) => return Err(CosmosGrpcError::NodeNotSynced),
+                Err(CosmosGrpcError::ChainNotRunning) => {
+                    return Err(CosmosGrpcError::ChainNotRunning)
+                }
+                // we don't want a single error to exit this loop early
+                Err(_) => {}
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        Err(CosmosGrpcError::ConfirmationDepthTimeout {
+            tx_hash: tx.txhash.clone(),
+            depth: last_depth,
+            required: required_depth,
+            time: timeout,
+        })
+    }
+
+    /// Looks the transaction up again, returning TxReorged if it is no longer found at the
+    /// height it was included at
+    async fn check_still_included(&self, tx: TxResponse) -> Result<TxResponse, CosmosGrpcError> {
+        let reorged = CosmosGrpcError::TxReorged {
+            tx_hash: tx.txhash.clone(),
+            height: tx.height as u64,
+        };
+        match self.get_tx_by_hash(tx.txhash.clone()).await {
+            Ok(status) => match status.tx_response {
+                Some(found) if found.height == tx.height => Ok(found),
+                _ => Err(reorged),
+            },
+            Err(e) if e.grpc_code() == Some(Code::NotFound) => Err(reorged),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestBlockResponse;
+    use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetSyncingResponse;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest;
+    use prost::Message;
+    use std::fs;
+    use tendermint_proto::types::Block;
+    use tendermint_proto::types::Commit;
+
+    fn encode<M: Message>(message: M) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        message.encode(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[actix_rt::test]
+    async fn test_watch_tx() {
+        let file = std::env::temp_dir().join(format!(
+            "deep_space_confirmations_{}.json",
+            rand::random::<u64>()
+        ));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        let tx = TxResponse {
+            height: 10,
+            txhash: "ABCD".to_string(),
+            ..TxResponse::default()
+        };
+        let get_tx = encode(GetTxRequest {
+            hash: "ABCD".to_string(),
+        });
+        let found = encode(GetTxResponse {
+            tx: None,
+            tx_response: Some(tx.clone()),
+        });
+        // once for inclusion and once for the check after reaching the depth
+        for _ in 0..2 {
+            tape.save("/cosmos.tx.v1beta1.Service/GetTx", &get_tx, Ok(&found))
+                .unwrap();
+        }
+        tape.save(
+            "/cosmos.base.tendermint.v1beta1.Service/GetSyncing",
+            &[],
+            Ok(&encode(GetSyncingResponse { syncing: false })),
+        )
+        .unwrap();
+        let block = Block {
+            last_commit: Some(Commit {
+                height: 12,
+                ..Commit::default()
+            }),
+            ..Block::default()
+        };
+        tape.save(
+            "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+            &[],
+            Ok(&encode(GetLatestBlockResponse {
+                block_id: None,
+                block: Some(block),
+            })),
+        )
+        .unwrap();
+
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        fs::remove_file(&file).unwrap();
+        let events: Vec<TxLifecycleEvent> = contact
+            .watch_tx(tx.clone(), Duration::from_secs(5), 2)
+            .map(|event| event.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            events,
+            vec![
+                TxLifecycleEvent::Included { tx: tx.clone() },
+                TxLifecycleEvent::Confirmation {
+                    height: 12,
+                    depth: 2
+                },
+                TxLifecycleEvent::Confirmed { tx, depth: 2 },
+            ]
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Event subscriptions for CosmWasm contracts. New transactions executing a contract are
+//! found by polling the transaction search and the events the contract emitted are decoded
+//! into one ContractExecution per transaction, as indexers and liquidation bots need.
+//! Only compiled if the cosmwasm feature is enabled.
+//!
+//! ```ignore
+//! let executions = contact.watch_contract_events(market_contract);
+//! futures::pin_mut!(executions);
+//! while let Some(execution) = executions.next().await {
+//!     for event in execution?.events {
+//!         if event.kind.as_deref() == Some("borrow") {
+//!             check_position(event.attribute("borrower")).await;
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::client::events::decode_event;
+use crate::client::events::split_event_at;
+use crate::client::events::tx_events;
+use crate::client::events::TypedEvent;
+use crate::client::runtime::sleep;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use std::time::Duration;
+
+/// The default interval between two searches for new executions, about one block
+pub const DEFAULT_CONTRACT_POLL_INTERVAL: Duration = Duration::from_secs(6);
+
+const CONTRACT_ADDRESS_KEY: &str = "_contract_address";
+
+/// An event emitted by a contract, the contract address is removed from the attributes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractEvent {
+    /// the type of a custom event with the wasm- prefix removed, None for the attributes
+    /// of the wasm event
+    pub kind: Option<String>,
+    pub attributes: Vec<(String, String)>,
+}
+
+impl ContractEvent {
+    /// The value of the first attribute with the provided key
+    pub fn attribute(&self, key: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// The events a contract emitted in one transaction, in the order they were emitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractExecution {
+    pub contract_address: String,
+    pub tx_hash: String,
+    pub height: u64,
+    pub events: Vec<ContractEvent>,
+}
+
+/// Decodes the wasm and custom wasm- events a contract emitted in a transaction, returns
+/// None if the transaction failed or the contract emitted nothing
+pub fn contract_execution(
+    response: &TxResponse,
+    contract_address: &str,
+) -> Result<Option<ContractExecution>, CosmosGrpcError> {
+    if response.code != 0 {
+        return Ok(None);
+    }
+    let mut events = Vec::new();
+    for event in tx_events(response).iter() {
+        if event.r#type == "wasm" {
+            for decoded in decode_event(event)? {
+                if let TypedEvent::Wasm(wasm) = decoded {
+                    if wasm.contract_address == contract_address {
+                        events.push(ContractEvent {
+                            kind: None,
+                            attributes: wasm.attributes,
+                        })
+                    }
+                }
+            }
+        } else if let Some(kind) = event.r#type.strip_prefix("wasm-") {
+            for attrs in split_event_at(event, CONTRACT_ADDRESS_KEY) {
+                let emitted = attrs
+                    .iter()
+                    .any(|(k, v)| k == CONTRACT_ADDRESS_KEY && v == contract_address);
+                if emitted {
+                    events.push(ContractEvent {
+                        kind: Some(kind.to_string()),
+                        attributes: attrs
+                            .into_iter()
+                            .filter(|(k, _)| k != CONTRACT_ADDRESS_KEY)
+                            .collect(),
+                    })
+                }
+            }
+        }
+    }
+    if events.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ContractExecution {
+        contract_address: contract_address.to_string(),
+        tx_hash: response.txhash.clone(),
+        height: response.height as u64,
+        events,
+    }))
+}
+
+impl Contact {
+    /// Streams the events of every transaction executing the contract from the next block
+    /// on, see watch_contract_events_from
+    pub fn watch_contract_events(
+        &self,
+        contract_address: impl Into<String>,
+    ) -> impl Stream<Item = Result<ContractExecution, CosmosGrpcError>> + '_ {
+        self.watch_contract_events_from(contract_address, None, DEFAULT_CONTRACT_POLL_INTERVAL)
+    }
+
+    /// Streams the events of every transaction executing the contract included at or after
+    /// from_height, or after the latest block if None, ordered by height. Transactions are
+    /// found through the wasm event, so only those in which the contract added attributes
+    /// to it are returned. The stream does not end, failed polls are returned as errors and
+    /// the same blocks are searched again after the next interval.
+    pub fn watch_contract_events_from(
+        &self,
+        contract_address: impl Into<String>,
+        from_height: Option<u64>,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<ContractExecution, CosmosGrpcError>> + '_ {
+        let contract_address = contract_address.into();
+        stream::unfold((from_height, true), move |(next_height, first)| {
+            let contract_address = contract_address.clone();
+            async move {
+                if !first {
+                    sleep(poll_interval).await;
+                }
+                let (executions, next_height) = match self
+                    .search_contract_executions(&contract_address, next_height)
+                    .await
+                {
+                    Ok((executions, next)) => (executions.into_iter().map(Ok).collect(), next),
+                    Err(e) => (vec![Err(e)], next_height),
+                };
+                Some((stream::iter(executions), (next_height, false)))
+            }
+        })
+        .flatten()
+    }
+
+    /// Finds the executions from from_height up to the latest block, returning them and the
+    /// height the next search starts at
+    async fn search_contract_executions(
+        &self,
+        contract_address: &str,
+        from_height: Option<u64>,
+    ) -> Result<(Vec<ContractExecution>, Option<u64>), CosmosGrpcError> {
+        let latest = self.latest_height().await?;
+        let from_height = match from_height {
+            Some(height) if height <= latest => height,
+            Some(height) => return Ok((Vec::new(), Some(height))),
+            None => return Ok((Vec::new(), Some(latest + 1))),
+        };
+        let events = vec![
+            format!("wasm.{}='{}'", CONTRACT_ADDRESS_KEY, contract_address),
+            format!("tx.height>={}", from_height),
+            format!("tx.height<={}", latest),
+        ];
+        let responses: Vec<TxResponse> =
+            self.get_txs_by_events_stream(events).try_collect().await?;
+        let mut executions = Vec::new();
+        for response in responses.iter() {
+            if let Some(execution) = contract_execution(response, contract_address)? {
+                executions.push(execution);
+            }
+        }
+        executions.sort_by_key(|execution| execution.height);
+        Ok((executions, Some(latest + 1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::StringEvent;
+
+    fn event(r#type: &str, attrs: &[(&str, &str)]) -> StringEvent {
+        StringEvent {
+            r#type: r#type.to_string(),
+            attributes: attrs
+                .iter()
+                .map(|(k, v)| Attribute {
+                    key: k.to_string(),
+                    value: v.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_contract_execution() {
+        // the market contract calls a token contract, both add to the wasm event
+        let wasm = event(
+            "wasm",
+            &[
+                ("_contract_address", "cosmos1market"),
+                ("action", "borrow"),
+                ("_contract_address", "cosmos1token"),
+                ("action", "transfer"),
+            ],
+        );
+        let custom = event(
+            "wasm-borrow",
+            &[
+                ("_contract_address", "cosmos1market"),
+                ("borrower", "cosmos1user"),
+                ("amount", "100"),
+            ],
+        );
+        let mut response = TxResponse {
+            height: 42,
+            txhash: "ABCD".to_string(),
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![event("message", &[("action", "execute")]), wasm, custom],
+            }],
+            ..TxResponse::default()
+        };
+
+        let execution = contract_execution(&response, "cosmos1market")
+            .unwrap()
+            .unwrap();
+        assert_eq!(execution.height, 42);
+        assert_eq!(execution.tx_hash, "ABCD");
+        assert_eq!(
+            execution.events,
+            vec![
+                ContractEvent {
+                    kind: None,
+                    attributes: vec![("action".to_string(), "borrow".to_string())],
+                },
+                ContractEvent {
+                    kind: Some("borrow".to_string()),
+                    attributes: vec![
+                        ("borrower".to_string(), "cosmos1user".to_string()),
+                        ("amount".to_string(), "100".to_string()),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(execution.events[1].attribute("amount"), Some("100"));
+
+        let token = contract_execution(&response, "cosmos1token")
+            .unwrap()
+            .unwrap();
+        assert_eq!(token.events.len(), 1);
+        assert!(contract_execution(&response, "cosmos1other")
+            .unwrap()
+            .is_none());
+        response.code = 5;
+        assert!(contract_execution(&response, "cosmos1market")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,200 @@
+//! The CosmosClient trait, the core query and transaction surface of Contact. Code written
+//! against this trait can be unit tested with [`MockCosmosClient`](crate::client::mock::MockCosmosClient)
+//! instead of a live node.
+
+use crate::client::types::ChainStatus;
+use crate::client::types::LatestBlock;
+use crate::client::Contact;
+use crate::client::MEMO;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+use crate::utils::GasDetails;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::MessageArgs;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::time::Duration;
+
+/// A Cosmos chain client, see the Contact methods of the same name for details. The trait is
+/// object safe so it can be used as `Box<dyn CosmosClient>` or `Arc<dyn CosmosClient>`.
+pub trait CosmosClient: Send + Sync {
+    /// The address prefix of the chain
+    fn get_prefix(&self) -> String;
+
+    fn get_chain_status(&self) -> BoxFuture<'_, Result<ChainStatus, CosmosGrpcError>>;
+
+    fn get_latest_block(&self) -> BoxFuture<'_, Result<LatestBlock, CosmosGrpcError>>;
+
+    fn get_account_info(
+        &self,
+        address: Address,
+    ) -> BoxFuture<'_, Result<BaseAccount, CosmosGrpcError>>;
+
+    fn get_balances(&self, address: Address) -> BoxFuture<'_, Result<Vec<Coin>, CosmosGrpcError>>;
+
+    fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> BoxFuture<'_, Result<Option<Coin>, CosmosGrpcError>>;
+
+    fn get_message_args(
+        &self,
+        our_address: Address,
+        fee: Fee,
+    ) -> BoxFuture<'_, Result<MessageArgs, CosmosGrpcError>>;
+
+    fn get_tx_by_hash(
+        &self,
+        txhash: String,
+    ) -> BoxFuture<'_, Result<GetTxResponse, CosmosGrpcError>>;
+
+    fn send_transaction(
+        &self,
+        msg: Vec<u8>,
+        mode: BroadcastMode,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>>;
+
+    fn send_message<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<TxResponse, CosmosGrpcError>>;
+
+    fn wait_for_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>>;
+
+    fn simulate_tx<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+        attribute_msgs: bool,
+    ) -> BoxFuture<'a, Result<GasDetails, CosmosGrpcError>>;
+}
+
+/// Signs and broadcasts messages using only the CosmosClient methods, this is the flow of
+/// Contact::send_message for clients that do not talk to a node
+pub(crate) async fn sign_and_send<C: CosmosClient + ?Sized>(
+    client: &C,
+    messages: &[Msg],
+    memo: Option<String>,
+    fee: Fee,
+    private_key: PrivateKey,
+    wait_timeout: Option<Duration>,
+) -> Result<TxResponse, CosmosGrpcError> {
+    if messages.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Can not send a transaction with no messages".to_string(),
+        ));
+    }
+    let our_address = private_key.to_address(&client.get_prefix()).unwrap();
+    let args = client.get_message_args(our_address, fee).await?;
+    let memo = memo.unwrap_or_else(|| MEMO.to_string());
+    let msg_bytes = private_key.sign_std_msg(messages, args, memo)?;
+    let response = client
+        .send_transaction(msg_bytes, BroadcastMode::Sync)
+        .await?;
+    match wait_timeout {
+        Some(timeout) => client.wait_for_tx(response, timeout).await,
+        None => Ok(response),
+    }
+}
+
+impl CosmosClient for Contact {
+    fn get_prefix(&self) -> String {
+        Contact::get_prefix(self)
+    }
+
+    fn get_chain_status(&self) -> BoxFuture<'_, Result<ChainStatus, CosmosGrpcError>> {
+        Contact::get_chain_status(self).boxed()
+    }
+
+    fn get_latest_block(&self) -> BoxFuture<'_, Result<LatestBlock, CosmosGrpcError>> {
+        Contact::get_latest_block(self).boxed()
+    }
+
+    fn get_account_info(
+        &self,
+        address: Address,
+    ) -> BoxFuture<'_, Result<BaseAccount, CosmosGrpcError>> {
+        Contact::get_account_info(self, address).boxed()
+    }
+
+    fn get_balances(&self, address: Address) -> BoxFuture<'_, Result<Vec<Coin>, CosmosGrpcError>> {
+        Contact::get_balances(self, address).boxed()
+    }
+
+    fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> BoxFuture<'_, Result<Option<Coin>, CosmosGrpcError>> {
+        Contact::get_balance(self, address, denom).boxed()
+    }
+
+    fn get_message_args(
+        &self,
+        our_address: Address,
+        fee: Fee,
+    ) -> BoxFuture<'_, Result<MessageArgs, CosmosGrpcError>> {
+        Contact::get_message_args(self, our_address, fee).boxed()
+    }
+
+    fn get_tx_by_hash(
+        &self,
+        txhash: String,
+    ) -> BoxFuture<'_, Result<GetTxResponse, CosmosGrpcError>> {
+        Contact::get_tx_by_hash(self, txhash).boxed()
+    }
+
+    fn send_transaction(
+        &self,
+        msg: Vec<u8>,
+        mode: BroadcastMode,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        Contact::send_transaction(self, msg, mode).boxed()
+    }
+
+    fn send_message<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<TxResponse, CosmosGrpcError>> {
+        Contact::send_message(self, messages, memo, fee, private_key, wait_timeout).boxed()
+    }
+
+    fn wait_for_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        Contact::wait_for_tx(self, response, timeout).boxed()
+    }
+
+    fn simulate_tx<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+        attribute_msgs: bool,
+    ) -> BoxFuture<'a, Result<GasDetails, CosmosGrpcError>> {
+        Contact::simulate_tx(self, messages, memo, private_key, attribute_msgs).boxed()
+    }
+}
@@ -0,0 +1,49 @@
+//! Contains utility functions for the crisis module, which allows any account to pay a
+//! constant fee to have an invariant checked, halting the chain if it is broken
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::crisis::v1beta1::MsgVerifyInvariant;
+use std::time::Duration;
+
+pub const MSG_VERIFY_INVARIANT_TYPE_URL: &str = "/cosmos.crisis.v1beta1.MsgVerifyInvariant";
+
+/// Builds a MsgVerifyInvariant for the invariant registered as module/route, for
+/// example bank/total-supply
+pub fn verify_invariant_msg(
+    sender: Address,
+    module_name: impl Into<String>,
+    route: impl Into<String>,
+) -> Msg {
+    let verify = MsgVerifyInvariant {
+        sender: sender.to_string(),
+        invariant_module_name: module_name.into(),
+        invariant_route: route.into(),
+    };
+    Msg::new(MSG_VERIFY_INVARIANT_TYPE_URL, verify)
+}
+
+impl Contact {
+    /// Checks an invariant from the provided private key, the crisis module charges its
+    /// constant fee in addition to the transaction fee. Invariant checks are expensive so
+    /// a large gas limit is used.
+    pub async fn verify_invariant(
+        &self,
+        module_name: String,
+        route: String,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = verify_invariant_msg(our_address, module_name, route);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
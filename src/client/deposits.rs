@@ -0,0 +1,251 @@
+//! Deposit detection for exchanges and custodians. Incoming bank transfers to a set of
+//! watched addresses are found through the transaction search and returned with the tx hash
+//! and memo, so deposits tagged with a memo can be credited to the right user. A transaction
+//! may contain several deposits, the tx hash and event index together identify one.
+//!
+//! ```ignore
+//! let deposits = contact.get_deposits_stream(vec![hot_wallet], start_height, 6);
+//! futures::pin_mut!(deposits);
+//! while let Some(deposit) = deposits.next().await {
+//!     let deposit = deposit?;
+//!     if deposit.confirmed {
+//!         credit(deposit.memo.trim(), &deposit.amount, &deposit.tx_hash, deposit.event_index);
+//!     } else {
+//!         show_pending(&deposit);
+//!     }
+//! }
+//! ```
+
+use crate::any::AnyExt;
+use crate::client::confirmations::confirmation_depth;
+use crate::client::events::decode_event;
+use crate::client::events::tx_message_events;
+use crate::client::events::TypedEvent;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+
+/// A transfer into a watched address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deposit {
+    pub sender: String,
+    pub recipient: String,
+    pub amount: Vec<Coin>,
+    pub tx_hash: String,
+    /// the memo of the transaction, empty if it has none
+    pub memo: String,
+    pub height: u64,
+    /// the index of the message that made the transfer
+    pub msg_index: u32,
+    /// the index of the transfer among the decoded events of the transaction, unique within
+    /// the transaction
+    pub event_index: u32,
+    /// the number of blocks on top of the deposit block when it was found, zero while it is
+    /// in the latest block, see confirmation_depth
+    pub confirmations: u64,
+    /// true if the confirmations reached the minimum the scan asked for
+    pub confirmed: bool,
+}
+
+impl Deposit {
+    /// The confirmations of the deposit once the chain has reached the given height
+    pub fn confirmations_at(&self, latest_height: u64) -> u64 {
+        confirmation_depth(self.height, latest_height)
+    }
+
+    /// Returns true if the memo is the provided tag, ignoring surrounding whitespace which
+    /// wallets frequently add
+    pub fn memo_matches(&self, tag: &str) -> bool {
+        !tag.trim().is_empty() && self.memo.trim() == tag.trim()
+    }
+}
+
+/// The memo of the transaction in a TxResponse, None if the response does not include the
+/// transaction or it can not be decoded
+pub fn tx_memo(response: &TxResponse) -> Option<String> {
+    let tx: Tx = response.tx.as_ref()?.unpack().ok()?;
+    Some(tx.body?.memo)
+}
+
+/// Extracts every transfer to one of the recipients from a transaction, deposits with fewer
+/// than min_confirmations are returned with confirmed false. Failed transactions contain no
+/// deposits.
+pub fn deposits_in_tx(
+    response: &TxResponse,
+    recipients: &[String],
+    latest_height: u64,
+    min_confirmations: u64,
+) -> Result<Vec<Deposit>, CosmosGrpcError> {
+    if response.code != 0 {
+        return Ok(Vec::new());
+    }
+    let memo = tx_memo(response).unwrap_or_default();
+    let height = response.height as u64;
+    let confirmations = confirmation_depth(height, latest_height);
+    let mut deposits = Vec::new();
+    let mut event_index = 0;
+    for (msg_index, event) in tx_message_events(response) {
+        for decoded in decode_event(&event)? {
+            if let TypedEvent::Transfer(transfer) = decoded {
+                if recipients.contains(&transfer.recipient) {
+                    deposits.push(Deposit {
+                        sender: transfer.sender,
+                        recipient: transfer.recipient,
+                        amount: transfer.amount,
+                        tx_hash: response.txhash.clone(),
+                        memo: memo.clone(),
+                        height,
+                        msg_index,
+                        event_index,
+                        confirmations,
+                        confirmed: confirmations >= min_confirmations,
+                    })
+                }
+            }
+            event_index += 1;
+        }
+    }
+    Ok(deposits)
+}
+
+impl Contact {
+    /// Streams every deposit to the provided addresses included at or after from_height,
+    /// ordered by address and then by height. The latest height is read once when the stream
+    /// is first polled, deposits with fewer than min_confirmations at that height are
+    /// returned with confirmed false and are returned again by a later scan.
+    pub fn get_deposits_stream(
+        &self,
+        addresses: Vec<Address>,
+        from_height: u64,
+        min_confirmations: u64,
+    ) -> impl Stream<Item = Result<Deposit, CosmosGrpcError>> + '_ {
+        let prefix = self.inner.chain_prefix.clone();
+        // chain prefix is validated as part of this client, so this can't panic
+        let recipients: Vec<String> = addresses
+            .iter()
+            .map(|a| a.to_bech32(&prefix).unwrap())
+            .collect();
+        stream::once(self.latest_height()).flat_map(move |latest| match latest {
+            Ok(latest) => self
+                .scan_deposits(recipients.clone(), from_height, latest, min_confirmations)
+                .left_stream(),
+            Err(e) => stream::once(async move { Err(e) }).right_stream(),
+        })
+    }
+
+    fn scan_deposits(
+        &self,
+        recipients: Vec<String>,
+        from_height: u64,
+        latest_height: u64,
+        min_confirmations: u64,
+    ) -> impl Stream<Item = Result<Deposit, CosmosGrpcError>> + '_ {
+        stream::iter(recipients).flat_map(move |recipient| {
+            let mut events = vec![format!("transfer.recipient='{}'", recipient)];
+            if from_height > 0 {
+                events.push(format!("tx.height>={}", from_height));
+            }
+            let recipients = vec![recipient];
+            self.get_txs_by_events_stream(events)
+                .flat_map(move |response| {
+                    let deposits = response.and_then(|tx| {
+                        deposits_in_tx(&tx, &recipients, latest_height, min_confirmations)
+                    });
+                    let deposits = match deposits {
+                        Ok(deposits) => deposits.into_iter().map(Ok).collect(),
+                        Err(e) => vec![Err(e)],
+                    };
+                    stream::iter(deposits)
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::encode_any;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::StringEvent;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+
+    fn attribute(key: &str, value: &str) -> Attribute {
+        Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_deposits_in_tx() {
+        let tx = Tx {
+            body: Some(TxBody {
+                memo: " 12345 ".to_string(),
+                ..TxBody::default()
+            }),
+            auth_info: None,
+            signatures: Vec::new(),
+        };
+        let transfer = StringEvent {
+            r#type: "transfer".to_string(),
+            attributes: vec![
+                attribute("recipient", "cosmos1exchange"),
+                attribute("sender", "cosmos1user"),
+                attribute("amount", "100uatom"),
+                attribute("recipient", "cosmos1other"),
+                attribute("sender", "cosmos1user"),
+                attribute("amount", "5uatom"),
+            ],
+        };
+        let mut response = TxResponse {
+            height: 10,
+            txhash: "ABCD".to_string(),
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![transfer],
+            }],
+            tx: Some(encode_any(tx, "/cosmos.tx.v1beta1.Tx".to_string())),
+            ..TxResponse::default()
+        };
+
+        let exchange = ["cosmos1exchange".to_string()];
+        let deposits = deposits_in_tx(&response, &exchange, 12, 6).unwrap();
+        assert_eq!(deposits.len(), 1);
+        let deposit = &deposits[0];
+        assert_eq!(deposit.sender, "cosmos1user");
+        assert_eq!(deposit.amount, vec!["100uatom".parse().unwrap()]);
+        assert_eq!(deposit.tx_hash, "ABCD");
+        assert_eq!((deposit.msg_index, deposit.event_index), (0, 0));
+        // blocks 11 and 12 are on top of the deposit block
+        assert_eq!(deposit.confirmations, 2);
+        // pending deposits are returned, not dropped
+        assert!(!deposit.confirmed);
+        assert!(deposits_in_tx(&response, &exchange, 12, 2).unwrap()[0].confirmed);
+        assert_eq!(
+            deposits_in_tx(&response, &exchange, 10, 0).unwrap()[0].confirmations,
+            0
+        );
+        // the second transfer of the merged event is the second event of the transaction
+        let other = ["cosmos1other".to_string()];
+        assert_eq!(
+            deposits_in_tx(&response, &other, 12, 1).unwrap()[0].event_index,
+            1
+        );
+        assert_eq!(deposit.confirmations_at(20), 10);
+        assert!(deposit.memo_matches("12345"));
+        assert!(!deposit.memo_matches("1234"));
+
+        response.code = 5;
+        assert!(deposits_in_tx(&response, &exchange, 12, 1)
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+//! Contains utility functions for withdrawing staking rewards and validator commission
+
+use crate::client::staking::operator_address;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgFundCommunityPool;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgSetWithdrawAddress;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawValidatorCommission;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegatorValidatorsRequest;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegatorValidatorsResponse;
+use std::time::Duration;
+
+mod restake;
+
+pub use restake::*;
+
+pub const MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward";
+pub const MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.MsgWithdrawValidatorCommission";
+pub const MSG_SET_WITHDRAW_ADDRESS_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress";
+pub const MSG_FUND_COMMUNITY_POOL_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.MsgFundCommunityPool";
+
+/// Gas allocated per withdraw message when claiming from many validators at once
+pub(crate) const GAS_PER_WITHDRAW: u64 = 150_000;
+
+/// Builds a MsgWithdrawDelegatorReward claiming the delegators rewards from one validator
+pub fn withdraw_delegator_reward_msg(delegator: Address, validator: Address) -> Msg {
+    let withdraw = MsgWithdrawDelegatorReward {
+        delegator_address: delegator.to_string(),
+        validator_address: validator.to_string(),
+    };
+    Msg::new(MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL, withdraw)
+}
+
+/// Builds a MsgWithdrawValidatorCommission, the validator address is the operator address
+pub fn withdraw_validator_commission_msg(validator: Address) -> Msg {
+    let withdraw = MsgWithdrawValidatorCommission {
+        validator_address: validator.to_string(),
+    };
+    Msg::new(MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL, withdraw)
+}
+
+/// Builds a MsgSetWithdrawAddress, future rewards for the delegator will be sent to the
+/// withdraw address
+pub fn set_withdraw_address_msg(delegator: Address, withdraw_address: Address) -> Msg {
+    let set = MsgSetWithdrawAddress {
+        delegator_address: delegator.to_string(),
+        withdraw_address: withdraw_address.to_string(),
+    };
+    Msg::new(MSG_SET_WITHDRAW_ADDRESS_TYPE_URL, set)
+}
+
+/// Builds a MsgFundCommunityPool depositing amount into the community pool
+pub fn fund_community_pool_msg(depositor: Address, amount: Vec<Coin>) -> Msg {
+    let fund = MsgFundCommunityPool {
+        amount: amount.into_iter().map(|c| c.into()).collect(),
+        depositor: depositor.to_string(),
+    };
+    Msg::new(MSG_FUND_COMMUNITY_POOL_TYPE_URL, fund)
+}
+
+impl Contact {
+    /// Gets the list of validators the provided address has delegated to
+    pub async fn get_delegator_validators(
+        &self,
+        delegator: Address,
+    ) -> Result<Vec<Address>, CosmosGrpcError> {
+        let res: QueryDelegatorValidatorsResponse = self
+            .grpc_unary(
+                "/cosmos.distribution.v1beta1.Query/DelegatorValidators",
+                QueryDelegatorValidatorsRequest {
+                    delegator_address: delegator.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        let mut validators = Vec::new();
+        for v in res.validators {
+            match v.parse() {
+                Ok(v) => validators.push(v),
+                Err(e) => return Err(CosmosGrpcError::BadResponse(e.to_string())),
+            }
+        }
+        Ok(validators)
+    }
+
+    /// Withdraws the staking rewards for the provided private key from a single validator
+    pub async fn withdraw_rewards(
+        &self,
+        validator_address: Address,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = withdraw_delegator_reward_msg(our_address, validator_address);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Withdraws the staking rewards for the provided private key from every validator it has
+    /// delegated to in a single transaction, gas is scaled by the number of validators
+    pub async fn withdraw_all_rewards(
+        &self,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let validators = self.get_delegator_validators(our_address).await?;
+        if validators.is_empty() {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "{} has no delegations to withdraw rewards from",
+                our_address
+            )));
+        }
+        let msgs: Vec<Msg> = validators
+            .into_iter()
+            .map(|v| withdraw_delegator_reward_msg(our_address, v))
+            .collect();
+        let fee = self.default_fee(Some(fee), &msgs);
+        self.send_message(&msgs, None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Withdraws the commission of the validator operated by the provided private key, the
+    /// validators self delegation rewards are withdrawn in the same transaction
+    pub async fn withdraw_validator_commission(
+        &self,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let operator = operator_address(our_address)?;
+        let msgs = [
+            withdraw_delegator_reward_msg(our_address, operator),
+            withdraw_validator_commission_msg(operator),
+        ];
+        let fee = self.default_fee(Some(fee), &msgs);
+        self.send_message(&msgs, None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Sets the address future staking rewards for the provided private key are sent to
+    pub async fn set_withdraw_address(
+        &self,
+        withdraw_address: Address,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = set_withdraw_address_msg(our_address, withdraw_address);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Deposits the provided coins into the community pool
+    pub async fn fund_community_pool(
+        &self,
+        amount: Vec<Coin>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = fund_community_pool_msg(our_address, amount);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
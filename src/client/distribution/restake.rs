@@ -0,0 +1,171 @@
+//! Claims pending staking rewards and delegates them back to the validators they were
+//! earned from, the standard auto-compounding loop.
+
+use crate::client::distribution::withdraw_delegator_reward_msg;
+use crate::client::distribution::GAS_PER_WITHDRAW;
+use crate::client::staking::delegate_msg;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::DecCoin;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin as ProtoDecCoin;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegationTotalRewardsRequest;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegationTotalRewardsResponse;
+use num256::Uint256;
+use std::convert::TryFrom;
+use std::time::Duration;
+
+/// Gas allocated for the delegate message paired with each withdraw when restaking
+const GAS_PER_RESTAKE_DELEGATE: u64 = 250_000;
+
+/// Converts a DecCoin into a Coin, truncating the fractional part. Over gRPC DecCoin
+/// amounts are the 18 decimal fixed point integer without a decimal point.
+pub fn truncate_dec_coin(coin: &ProtoDecCoin) -> Result<Coin, CosmosGrpcError> {
+    let truncated = DecCoin::try_from(coin.clone())
+        .ok()
+        .and_then(|dec_coin| dec_coin.truncate());
+    match truncated {
+        Some(coin) => Ok(coin),
+        None => Err(CosmosGrpcError::BadResponse(format!(
+            "Invalid DecCoin amount {}",
+            coin.amount
+        ))),
+    }
+}
+
+/// Plans the restake transactions, each entry of rewards is a validator and the claimable
+/// amount, validators below min_claim are skipped and the remaining are split into groups
+/// of withdraw and delegate message pairs that fit under max_gas_per_tx
+pub fn plan_restake(
+    delegator: Address,
+    rewards: Vec<(Address, Coin)>,
+    min_claim: Uint256,
+    max_gas_per_tx: u64,
+) -> Vec<(Vec<Msg>, u64)> {
+    let gas_per_pair = GAS_PER_WITHDRAW + GAS_PER_RESTAKE_DELEGATE;
+    let pairs_per_tx = std::cmp::max(1, (max_gas_per_tx / gas_per_pair) as usize);
+    let claimable: Vec<(Address, Coin)> = rewards
+        .into_iter()
+        .filter(|(_, reward)| reward.amount >= min_claim && reward.amount > 0u64.into())
+        .collect();
+    claimable
+        .chunks(pairs_per_tx)
+        .map(|chunk| {
+            let mut msgs = Vec::new();
+            for (validator, reward) in chunk {
+                msgs.push(withdraw_delegator_reward_msg(delegator, *validator));
+                msgs.push(delegate_msg(delegator, *validator, reward.clone()));
+            }
+            (msgs, gas_per_pair * chunk.len() as u64)
+        })
+        .collect()
+}
+
+impl Contact {
+    /// Gets the pending rewards in the provided denom for every validator the delegator
+    /// has delegated to, truncated to whole units
+    pub async fn get_pending_rewards(
+        &self,
+        delegator: Address,
+        denom: &str,
+    ) -> Result<Vec<(Address, Coin)>, CosmosGrpcError> {
+        let res: QueryDelegationTotalRewardsResponse = self
+            .grpc_unary(
+                "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+                QueryDelegationTotalRewardsRequest {
+                    delegator_address: delegator.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        let mut rewards = Vec::new();
+        for reward in res.rewards {
+            let validator = match reward.validator_address.parse() {
+                Ok(v) => v,
+                Err(e) => return Err(CosmosGrpcError::BadResponse(format!("{}", e))),
+            };
+            for coin in reward.reward.iter().filter(|c| c.denom == denom) {
+                rewards.push((validator, truncate_dec_coin(coin)?));
+            }
+        }
+        Ok(rewards)
+    }
+
+    /// Withdraws the staking rewards of the provided private key in the provided denom and
+    /// delegates them back to the validator they were earned from. Validators with less than
+    /// min_claim pending are skipped. Messages are split into as many transactions as needed
+    /// to stay under max_gas_per_tx, each paying the provided fee, and each transaction is
+    /// waited on before the next is sent. Returns an empty list if there is nothing to claim.
+    pub async fn claim_and_restake(
+        &self,
+        denom: &str,
+        min_claim: Uint256,
+        max_gas_per_tx: u64,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Duration,
+    ) -> Result<Vec<TxResponse>, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let rewards = self.get_pending_rewards(our_address, denom).await?;
+        let mut responses = Vec::new();
+        for (msgs, gas_limit) in plan_restake(our_address, rewards, min_claim, max_gas_per_tx) {
+            let fee = self.fee_with_gas(Some(fee.clone()), gas_limit);
+            let response = self
+                .send_message(&msgs, None, fee, private_key, Some(wait_timeout))
+                .await?;
+            responses.push(response);
+        }
+        Ok(responses)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(amount: u64) -> Coin {
+        Coin {
+            amount: amount.into(),
+            denom: "uatom".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_truncate_dec_coin() {
+        let dec = |amount: &str| ProtoDecCoin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        };
+        assert_eq!(
+            truncate_dec_coin(&dec("1500000000000000000000")).unwrap(),
+            coin(1500)
+        );
+        assert_eq!(truncate_dec_coin(&dec("999")).unwrap(), coin(0));
+        assert_eq!(truncate_dec_coin(&dec("12.75")).unwrap(), coin(12));
+        assert!(truncate_dec_coin(&dec("abc")).is_err());
+    }
+
+    #[test]
+    fn test_plan_restake() {
+        let delegator = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let validators: Vec<Address> = (1..=5u8)
+            .map(|i| Address::from_bytes([i; 20], "cosmosvaloper").unwrap())
+            .collect();
+        let rewards = vec![
+            (validators[0], coin(10)),
+            (validators[1], coin(1000)),
+            (validators[2], coin(2000)),
+            (validators[3], coin(3000)),
+            (validators[4], coin(99)),
+        ];
+        // room for two pairs per tx, two validators are below the minimum
+        let plan = plan_restake(delegator, rewards, 100u64.into(), 800_000);
+        assert_eq!(plan.len(), 2);
+        assert_eq!(plan[0].0.len(), 4);
+        assert_eq!(plan[1].0.len(), 2);
+        assert_eq!(plan[0].1, 800_000);
+    }
+}
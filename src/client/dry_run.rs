@@ -0,0 +1,157 @@
+//! Dry run mode for staging and CI. A Contact in dry run mode simulates every transaction it
+//! would broadcast instead of sending it, logs the result and returns a synthetic response,
+//! so the full path of a payout or trading system, including signing and transaction
+//! policies, can run against a real node without moving funds.
+//!
+//! ```ignore
+//! let contact = Contact::new(url, timeout, "cosmos")?;
+//! let contact = if staging { contact.with_dry_run() } else { contact };
+//! ```
+
+use crate::client::simulate::simulation_failure;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::SimulateRequest;
+use crate::utils::tx_hash;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse;
+use std::sync::Arc;
+
+/// The info field of every synthetic response, marks responses that were not broadcast
+pub const DRY_RUN_INFO: &str = "dry run";
+
+impl Contact {
+    /// Simulates transactions instead of broadcasting them, see the module documentation.
+    /// send_transaction returns a response with the hash and gas of the simulated
+    /// transaction, a height of zero and DRY_RUN_INFO as info, and wait_for_tx returns such
+    /// responses immediately. Transactions the chain would reject fail with SimulationFailed.
+    /// The returned Contact does not share its connection with clones of this one.
+    pub fn with_dry_run(self) -> Self {
+        let mut inner = self.unshared();
+        inner.dry_run = true;
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Returns true if transactions sent through this Contact are only simulated
+    pub fn is_dry_run(&self) -> bool {
+        self.inner.dry_run
+    }
+
+    /// Simulates an encoded TxRaw in place of broadcasting it
+    pub(crate) async fn dry_run_transaction(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let txhash = tx_hash(&tx_bytes);
+        let res: SimulateResponse = self
+            .grpc_unary(
+                "/cosmos.tx.v1beta1.Service/Simulate",
+                SimulateRequest { tx: None, tx_bytes },
+            )
+            .await
+            .map_err(simulation_failure)?;
+        let gas_info = res.gas_info.unwrap_or_default();
+        info!(
+            "Dry run of tx {} used {} of {} gas",
+            txhash, gas_info.gas_used, gas_info.gas_wanted
+        );
+        Ok(TxResponse {
+            txhash,
+            raw_log: res.result.map(|result| result.log).unwrap_or_default(),
+            info: DRY_RUN_INFO.to_string(),
+            gas_wanted: gas_info.gas_wanted as i64,
+            gas_used: gas_info.gas_used as i64,
+            ..TxResponse::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::private_key::MessageArgs;
+    use crate::Fee;
+    use crate::Msg;
+    use crate::PrivateKey;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::GasInfo;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+    use prost::Message;
+    use std::time::Duration;
+
+    #[actix_rt::test]
+    async fn test_dry_run() {
+        let key = PrivateKey::from_secret(b"dry run test key");
+        let args = MessageArgs {
+            sequence: 0,
+            fee: Fee {
+                amount: vec![],
+                gas_limit: 200_000,
+                granter: None,
+                payer: None,
+            },
+            timeout_height: 100,
+            chain_id: "testing".to_string(),
+            account_number: 1,
+        };
+        let msg = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        let tx_bytes = key.sign_std_msg(&[msg], args, "").unwrap();
+        let mut request = Vec::new();
+        SimulateRequest {
+            tx: None,
+            tx_bytes: tx_bytes.clone(),
+        }
+        .encode(&mut request)
+        .unwrap();
+        let mut response = Vec::new();
+        SimulateResponse {
+            gas_info: Some(GasInfo {
+                gas_wanted: 200_000,
+                gas_used: 61_000,
+            }),
+            result: None,
+        }
+        .encode(&mut response)
+        .unwrap();
+
+        let file =
+            std::env::temp_dir().join(format!("deep_space_dry_run_{}.json", rand::random::<u64>()));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        let path = "/cosmos.tx.v1beta1.Service/Simulate";
+        tape.save(path, &request, Ok(&response)).unwrap();
+        let rejected = tonic::Status::unknown("0uatom is smaller than 5uatom: insufficient funds");
+        tape.save(path, &request, Err(&rejected)).unwrap();
+        let contact = Contact::from_recording(&file, Duration::from_secs(1))
+            .unwrap()
+            .with_dry_run();
+        std::fs::remove_file(&file).unwrap();
+        assert!(contact.is_dry_run());
+
+        // nothing is broadcast, the only recorded calls are simulations
+        let res = contact
+            .send_transaction(tx_bytes.clone(), BroadcastMode::Sync)
+            .await
+            .unwrap();
+        assert_eq!(res.txhash, tx_hash(&tx_bytes));
+        assert_eq!(res.gas_used, 61_000);
+        assert_eq!(res.info, DRY_RUN_INFO);
+        let waited = contact
+            .wait_for_tx(res.clone(), Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(waited, res);
+        match contact
+            .send_transaction(tx_bytes, BroadcastMode::Sync)
+            .await
+        {
+            Err(CosmosGrpcError::SimulationFailed { .. }) => {}
+            other => panic!("Expected SimulationFailed, got {:?}", other),
+        }
+    }
+}
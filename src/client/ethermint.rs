@@ -0,0 +1,199 @@
+//! Support for pushing Ethereum transactions through the Cosmos gRPC broadcast path of
+//! Ethermint based chains. The Ethereum transaction must already be signed, it is wrapped
+//! into a MsgEthereumTx and placed in a Cosmos transaction with no Cosmos signatures, the
+//! ExtensionOptionsEthereumTx extension option and a fee matching the Ethereum gas terms.
+//! Only compiled if the ethermint feature is enabled.
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::ethermint::evm::v1::DynamicFeeTx;
+use crate::proto::ethermint::evm::v1::ExtensionOptionsEthereumTx;
+use crate::proto::ethermint::evm::v1::LegacyTx;
+use crate::proto::ethermint::evm::v1::MsgEthereumTx;
+use crate::utils::encode_any;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::AuthInfo;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use num256::Uint256;
+use num_traits::CheckedMul;
+use prost::Message;
+use prost_types::Any;
+use std::time::Duration;
+
+pub const MSG_ETHEREUM_TX_TYPE_URL: &str = "/ethermint.evm.v1.MsgEthereumTx";
+pub const LEGACY_TX_TYPE_URL: &str = "/ethermint.evm.v1.LegacyTx";
+pub const DYNAMIC_FEE_TX_TYPE_URL: &str = "/ethermint.evm.v1.DynamicFeeTx";
+pub const EXTENSION_OPTIONS_ETHEREUM_TX_TYPE_URL: &str =
+    "/ethermint.evm.v1.ExtensionOptionsEthereumTx";
+
+/// The data of a signed Ethereum transaction, amounts are decimal strings in the
+/// smallest unit of the EVM denom
+#[derive(Debug, Clone, PartialEq)]
+pub enum EthereumTxData {
+    Legacy(LegacyTx),
+    DynamicFee(DynamicFeeTx),
+}
+
+impl EthereumTxData {
+    /// The gas limit of the Ethereum transaction
+    pub fn gas(&self) -> u64 {
+        match self {
+            EthereumTxData::Legacy(tx) => tx.gas,
+            EthereumTxData::DynamicFee(tx) => tx.gas,
+        }
+    }
+
+    /// The maximum price paid per unit of gas, the gas price for legacy transactions
+    /// and the fee cap for dynamic fee transactions
+    pub fn max_gas_price(&self) -> Result<Uint256, CosmosGrpcError> {
+        let price = match self {
+            EthereumTxData::Legacy(tx) => &tx.gas_price,
+            EthereumTxData::DynamicFee(tx) => &tx.gas_fee_cap,
+        };
+        price
+            .parse()
+            .map_err(|_| CosmosGrpcError::BadInput(format!("Invalid gas price {}", price)))
+    }
+
+    pub fn to_any(&self) -> Any {
+        match self {
+            EthereumTxData::Legacy(tx) => encode_any(tx.clone(), LEGACY_TX_TYPE_URL.to_string()),
+            EthereumTxData::DynamicFee(tx) => {
+                encode_any(tx.clone(), DYNAMIC_FEE_TX_TYPE_URL.to_string())
+            }
+        }
+    }
+}
+
+/// Builds a MsgEthereumTx, hash is the 0x prefixed hex Ethereum transaction hash. The
+/// sender is left empty as it is recovered from the signature.
+pub fn ethereum_tx_msg(data: &EthereumTxData, hash: impl Into<String>) -> Msg {
+    let msg = MsgEthereumTx {
+        data: Some(data.to_any()),
+        size: 0.0,
+        hash: hash.into(),
+        from: String::new(),
+    };
+    Msg::new(MSG_ETHEREUM_TX_TYPE_URL, msg)
+}
+
+/// Returns the Cosmos fee Ethermint requires for the provided Ethereum transaction, the gas
+/// limit must equal the Ethereum gas limit and the amount must be the gas limit multiplied by
+/// the maximum gas price in the EVM denom
+pub fn ethereum_tx_fee(data: &EthereumTxData, evm_denom: &str) -> Result<Fee, CosmosGrpcError> {
+    let amount = match data
+        .max_gas_price()?
+        .checked_mul(&Uint256::from(data.gas()))
+    {
+        Some(amount) => amount,
+        None => {
+            return Err(CosmosGrpcError::BadInput(
+                "Gas price times gas limit overflows".to_string(),
+            ))
+        }
+    };
+    // Ethermint requires the gas limit of the Ethereum transaction and rejects a payer or
+    // granter, so neither the gas table nor the fee granter of a Contact apply
+    Ok(Fee {
+        amount: vec![Coin {
+            amount,
+            denom: evm_denom.to_string(),
+        }],
+        gas_limit: data.gas(),
+        ..Fee::default()
+    })
+}
+
+/// Encodes a Cosmos transaction containing a single MsgEthereumTx, ready to broadcast. The
+/// transaction has no signer infos or signatures as the Ethereum signature authenticates it.
+pub fn ethereum_tx_bytes(
+    data: &EthereumTxData,
+    hash: impl Into<String>,
+    evm_denom: &str,
+) -> Result<Vec<u8>, CosmosGrpcError> {
+    let body = TxBody {
+        messages: vec![ethereum_tx_msg(data, hash).into()],
+        memo: String::new(),
+        timeout_height: 0,
+        extension_options: vec![encode_any(
+            ExtensionOptionsEthereumTx {},
+            EXTENSION_OPTIONS_ETHEREUM_TX_TYPE_URL.to_string(),
+        )],
+        non_critical_extension_options: Vec::new(),
+    };
+    let auth_info = AuthInfo {
+        signer_infos: Vec::new(),
+        fee: Some(ethereum_tx_fee(data, evm_denom)?.into()),
+    };
+
+    let mut body_bytes = Vec::new();
+    body.encode(&mut body_bytes).unwrap();
+    let mut auth_info_bytes = Vec::new();
+    auth_info.encode(&mut auth_info_bytes).unwrap();
+    let tx_raw = TxRaw {
+        body_bytes,
+        auth_info_bytes,
+        signatures: Vec::new(),
+    };
+    let mut tx_bytes = Vec::new();
+    tx_raw.encode(&mut tx_bytes).unwrap();
+    Ok(tx_bytes)
+}
+
+impl Contact {
+    /// Broadcasts a signed Ethereum transaction using the Cosmos gRPC endpoint of an Ethermint
+    /// chain, if a wait_timeout is provided this will wait for the tx to enter the chain.
+    pub async fn send_ethereum_tx(
+        &self,
+        data: EthereumTxData,
+        hash: String,
+        evm_denom: &str,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let tx_bytes = ethereum_tx_bytes(&data, hash, evm_denom)?;
+        let response = self.send_transaction(tx_bytes, BroadcastMode::Sync).await?;
+        trace!("broadcasted! with response {:?}", response);
+        if let Some(time) = wait_timeout {
+            self.wait_for_tx(response, time).await
+        } else {
+            Ok(response)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ethereum_tx_fee() {
+        let data = EthereumTxData::DynamicFee(DynamicFeeTx {
+            gas_tip_cap: "1000000000".to_string(),
+            gas_fee_cap: "20000000000".to_string(),
+            gas: 21000,
+            ..Default::default()
+        });
+        let fee = ethereum_tx_fee(&data, "aevmos").unwrap();
+        assert_eq!(fee.gas_limit, 21000);
+        assert_eq!(fee.amount[0].amount, 420_000_000_000_000u64.into());
+        assert_eq!(fee.amount[0].denom, "aevmos");
+
+        let tx = TxRaw::decode(
+            ethereum_tx_bytes(&data, "0x00", "aevmos")
+                .unwrap()
+                .as_slice(),
+        )
+        .unwrap();
+        assert!(tx.signatures.is_empty());
+        let body = TxBody::decode(tx.body_bytes.as_slice()).unwrap();
+        assert_eq!(
+            body.extension_options[0].type_url,
+            EXTENSION_OPTIONS_ETHEREUM_TX_TYPE_URL
+        );
+    }
+}
@@ -0,0 +1,517 @@
+//! Typed decoding of the events emitted by common Cosmos SDK, CosmWasm and IBC modules.
+//! TxResponse logs provide events as lists of string attributes, and when a message emits
+//! several events of the same type they are merged into a single event with repeated keys.
+//! The decoders here split those merged events and parse the attributes into typed values.
+//! Addresses are left as strings since module and contract accounts may be 32 bytes long.
+
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Coin;
+#[cfg(feature = "sdk-0_50")]
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::StringEvent;
+
+/// A bank transfer between two accounts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferEvent {
+    pub sender: String,
+    pub recipient: String,
+    pub amount: Vec<Coin>,
+}
+
+/// Coins leaving an account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinSpentEvent {
+    pub spender: String,
+    pub amount: Vec<Coin>,
+}
+
+/// Coins entering an account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoinReceivedEvent {
+    pub receiver: String,
+    pub amount: Vec<Coin>,
+}
+
+/// A delegation to a validator, older chains emit the amount without a denom in which
+/// case the denom of the returned coin is empty
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegateEvent {
+    pub validator: String,
+    pub amount: Coin,
+    pub new_shares: String,
+}
+
+/// Staking rewards withdrawn from a validator, the delegator is only included in
+/// events emitted by Cosmos SDK v0.47 and later
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawRewardsEvent {
+    pub validator: String,
+    pub delegator: Option<String>,
+    pub amount: Vec<Coin>,
+}
+
+/// Attributes emitted by a CosmWasm contract, the contract address is removed from the
+/// attribute list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmEvent {
+    pub contract_address: String,
+    pub attributes: Vec<(String, String)>,
+}
+
+/// An IBC packet lifecycle event, the same attributes are emitted for sending, receiving
+/// and acknowledging a packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketEvent {
+    pub sequence: u64,
+    pub src_port: String,
+    pub src_channel: String,
+    pub dst_port: String,
+    pub dst_channel: String,
+    /// The packet data, not included in acknowledge_packet events
+    pub data: Option<String>,
+    pub timeout_height: String,
+    pub timeout_timestamp: u64,
+}
+
+/// An event decoded from a transaction, events of types without a decoder are returned
+/// as Other with their attributes unmodified
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedEvent {
+    Transfer(TransferEvent),
+    CoinSpent(CoinSpentEvent),
+    CoinReceived(CoinReceivedEvent),
+    Delegate(DelegateEvent),
+    WithdrawRewards(WithdrawRewardsEvent),
+    Wasm(WasmEvent),
+    SendPacket(PacketEvent),
+    RecvPacket(PacketEvent),
+    AcknowledgePacket(PacketEvent),
+    Other(StringEvent),
+}
+
+/// The events emitted by the messages of a transaction in order. Cosmos SDK v0.50 nodes
+/// leave the logs empty and return the events in TxResponse.events instead, those are
+/// read when there are no logs. Events there without a msg_index attribute come from the
+/// ante handler and are skipped, as they are not part of the logs either.
+pub fn tx_events(response: &TxResponse) -> Vec<StringEvent> {
+    tx_message_events(response)
+        .into_iter()
+        .map(|(_, event)| event)
+        .collect()
+}
+
+/// The events of tx_events, each with the index of the message that emitted it
+pub fn tx_message_events(response: &TxResponse) -> Vec<(u32, StringEvent)> {
+    if !response.logs.is_empty() {
+        return response
+            .logs
+            .iter()
+            .flat_map(|log| log.events.iter().map(move |e| (log.msg_index, e.clone())))
+            .collect();
+    }
+    message_events(response)
+}
+
+#[cfg(feature = "sdk-0_50")]
+fn message_events(response: &TxResponse) -> Vec<(u32, StringEvent)> {
+    let mut events = Vec::new();
+    for event in response.events.iter() {
+        let attributes: Vec<Attribute> = event
+            .attributes
+            .iter()
+            .map(|attr| Attribute {
+                key: String::from_utf8_lossy(&attr.key).into_owned(),
+                value: String::from_utf8_lossy(&attr.value).into_owned(),
+            })
+            .collect();
+        let msg_index = attributes
+            .iter()
+            .find(|attr| attr.key == "msg_index")
+            .and_then(|attr| attr.value.parse().ok());
+        let msg_index = match msg_index {
+            Some(msg_index) => msg_index,
+            None => continue,
+        };
+        events.push((
+            msg_index,
+            StringEvent {
+                r#type: event.r#type.clone(),
+                attributes: attributes
+                    .into_iter()
+                    .filter(|attr| attr.key != "msg_index")
+                    .collect(),
+            },
+        ));
+    }
+    events
+}
+
+#[cfg(not(feature = "sdk-0_50"))]
+fn message_events(_response: &TxResponse) -> Vec<(u32, StringEvent)> {
+    Vec::new()
+}
+
+/// Decodes every event emitted by the messages of a transaction in order
+pub fn decode_events(response: &TxResponse) -> Result<Vec<TypedEvent>, CosmosGrpcError> {
+    let mut events = Vec::new();
+    for event in tx_events(response).iter() {
+        events.extend(decode_event(event)?);
+    }
+    Ok(events)
+}
+
+/// Decodes a single event from a TxResponse log, this may produce several events if
+/// events of the same type were merged
+pub fn decode_event(event: &StringEvent) -> Result<Vec<TypedEvent>, CosmosGrpcError> {
+    let mut decoded = Vec::new();
+    match event.r#type.as_str() {
+        "transfer" => {
+            for attrs in split_event(event) {
+                decoded.push(TypedEvent::Transfer(TransferEvent {
+                    sender: attribute(&attrs, "sender")?,
+                    recipient: attribute(&attrs, "recipient")?,
+                    amount: parse_coins(&attribute(&attrs, "amount")?)?,
+                }))
+            }
+        }
+        "coin_spent" => {
+            for attrs in split_event(event) {
+                decoded.push(TypedEvent::CoinSpent(CoinSpentEvent {
+                    spender: attribute(&attrs, "spender")?,
+                    amount: parse_coins(&attribute(&attrs, "amount")?)?,
+                }))
+            }
+        }
+        "coin_received" => {
+            for attrs in split_event(event) {
+                decoded.push(TypedEvent::CoinReceived(CoinReceivedEvent {
+                    receiver: attribute(&attrs, "receiver")?,
+                    amount: parse_coins(&attribute(&attrs, "amount")?)?,
+                }))
+            }
+        }
+        "delegate" => {
+            for attrs in split_event(event) {
+                decoded.push(TypedEvent::Delegate(DelegateEvent {
+                    validator: attribute(&attrs, "validator")?,
+                    amount: parse_coin(&attribute(&attrs, "amount")?)?,
+                    new_shares: attribute(&attrs, "new_shares").unwrap_or_default(),
+                }))
+            }
+        }
+        "withdraw_rewards" => {
+            for attrs in split_event(event) {
+                decoded.push(TypedEvent::WithdrawRewards(WithdrawRewardsEvent {
+                    validator: attribute(&attrs, "validator")?,
+                    delegator: attribute(&attrs, "delegator").ok(),
+                    amount: parse_coins(&attribute(&attrs, "amount")?)?,
+                }))
+            }
+        }
+        "wasm" => {
+            for attrs in split_event_at(event, "_contract_address") {
+                let contract_address = attribute(&attrs, "_contract_address")?;
+                decoded.push(TypedEvent::Wasm(WasmEvent {
+                    contract_address,
+                    attributes: attrs
+                        .into_iter()
+                        .filter(|(k, _)| k != "_contract_address")
+                        .collect(),
+                }))
+            }
+        }
+        "send_packet" | "recv_packet" | "acknowledge_packet" => {
+            for attrs in split_event(event) {
+                let packet = packet_event(&attrs)?;
+                decoded.push(match event.r#type.as_str() {
+                    "send_packet" => TypedEvent::SendPacket(packet),
+                    "recv_packet" => TypedEvent::RecvPacket(packet),
+                    _ => TypedEvent::AcknowledgePacket(packet),
+                })
+            }
+        }
+        _ => decoded.push(TypedEvent::Other(event.clone())),
+    }
+    Ok(decoded)
+}
+
+fn packet_event(attrs: &[(String, String)]) -> Result<PacketEvent, CosmosGrpcError> {
+    Ok(PacketEvent {
+        sequence: parse_u64(&attribute(attrs, "packet_sequence")?)?,
+        src_port: attribute(attrs, "packet_src_port")?,
+        src_channel: attribute(attrs, "packet_src_channel")?,
+        dst_port: attribute(attrs, "packet_dst_port")?,
+        dst_channel: attribute(attrs, "packet_dst_channel")?,
+        data: attribute(attrs, "packet_data").ok(),
+        timeout_height: attribute(attrs, "packet_timeout_height").unwrap_or_default(),
+        timeout_timestamp: match attribute(attrs, "packet_timeout_timestamp") {
+            Ok(v) => parse_u64(&v)?,
+            Err(_) => 0,
+        },
+    })
+}
+
+/// Splits a merged event into the attributes of each original event. Events of the same
+/// type are merged by appending their attributes, so a new event is started whenever a key
+/// repeats. This does not depend on the order the module emits the attributes in.
+pub(crate) fn split_event(event: &StringEvent) -> Vec<Vec<(String, String)>> {
+    let mut split: Vec<Vec<(String, String)>> = Vec::new();
+    for attribute in event.attributes.iter() {
+        let repeated = match split.last() {
+            Some(attrs) => attrs.iter().any(|(k, _)| *k == attribute.key),
+            None => true,
+        };
+        if repeated {
+            split.push(Vec::new());
+        }
+        split
+            .last_mut()
+            .unwrap()
+            .push((attribute.key.clone(), attribute.value.clone()));
+    }
+    split
+}
+
+/// Splits a merged event at every occurrence of first_key, for events such as wasm whose
+/// attributes are chosen by a contract and may repeat within one event, but which always
+/// start with the same key
+pub(crate) fn split_event_at(event: &StringEvent, first_key: &str) -> Vec<Vec<(String, String)>> {
+    let mut split: Vec<Vec<(String, String)>> = Vec::new();
+    for attribute in event.attributes.iter() {
+        if attribute.key == first_key || split.is_empty() {
+            split.push(Vec::new());
+        }
+        split
+            .last_mut()
+            .unwrap()
+            .push((attribute.key.clone(), attribute.value.clone()));
+    }
+    split
+}
+
+fn attribute(attrs: &[(String, String)], key: &str) -> Result<String, CosmosGrpcError> {
+    match attrs.iter().find(|(k, _)| k == key) {
+        Some((_, v)) => Ok(v.clone()),
+        None => Err(CosmosGrpcError::BadResponse(format!(
+            "Event is missing attribute {}",
+            key
+        ))),
+    }
+}
+
+fn parse_u64(value: &str) -> Result<u64, CosmosGrpcError> {
+    value
+        .parse()
+        .map_err(|_| CosmosGrpcError::BadResponse(format!("Invalid event number {}", value)))
+}
+
+fn parse_coin(value: &str) -> Result<Coin, CosmosGrpcError> {
+    match value.parse() {
+        Ok(coin) => Ok(coin),
+        Err(e) => Err(CosmosGrpcError::BadResponse(format!(
+            "Invalid event coin {} {}",
+            value, e
+        ))),
+    }
+}
+
+/// Parses a comma separated coin list, an empty string is an empty list
+fn parse_coins(value: &str) -> Result<Vec<Coin>, CosmosGrpcError> {
+    if value.is_empty() {
+        return Ok(Vec::new());
+    }
+    value.split(',').map(parse_coin).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+
+    fn event(r#type: &str, attrs: &[(&str, &str)]) -> StringEvent {
+        StringEvent {
+            r#type: r#type.to_string(),
+            attributes: attrs
+                .iter()
+                .map(|(k, v)| Attribute {
+                    key: k.to_string(),
+                    value: v.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_decode_merged_transfers() {
+        let merged = event(
+            "transfer",
+            &[
+                ("recipient", "cosmos1a"),
+                ("sender", "cosmos1b"),
+                ("amount", "100uatom"),
+                ("recipient", "cosmos1c"),
+                ("sender", "cosmos1b"),
+                ("amount", "5uatom,7ufoo"),
+            ],
+        );
+        let decoded = decode_event(&merged).unwrap();
+        assert_eq!(decoded.len(), 2);
+        match &decoded[1] {
+            TypedEvent::Transfer(transfer) => {
+                assert_eq!(transfer.recipient, "cosmos1c");
+                assert_eq!(transfer.amount.len(), 2);
+                assert_eq!(transfer.amount[1].denom, "ufoo");
+            }
+            _ => panic!("Expected a transfer"),
+        }
+    }
+
+    #[test]
+    fn test_decode_merged_node_events() {
+        // attributes in the order Cosmos SDK v0.50 emits them, amount comes first
+        let rewards = event(
+            "withdraw_rewards",
+            &[
+                ("amount", "10uatom"),
+                ("validator", "cosmosvaloper1a"),
+                ("delegator", "cosmos1d"),
+                ("amount", ""),
+                ("validator", "cosmosvaloper1b"),
+                ("delegator", "cosmos1d"),
+            ],
+        );
+        assert_eq!(
+            decode_event(&rewards).unwrap(),
+            vec![
+                TypedEvent::WithdrawRewards(WithdrawRewardsEvent {
+                    validator: "cosmosvaloper1a".to_string(),
+                    delegator: Some("cosmos1d".to_string()),
+                    amount: vec!["10uatom".parse().unwrap()],
+                }),
+                TypedEvent::WithdrawRewards(WithdrawRewardsEvent {
+                    validator: "cosmosvaloper1b".to_string(),
+                    delegator: Some("cosmos1d".to_string()),
+                    amount: Vec::new(),
+                }),
+            ]
+        );
+
+        // a send_packet event in the order ibc-go v7 emits it, for two packets
+        let packet = |sequence: &'static str| {
+            vec![
+                ("packet_data", "{}"),
+                ("packet_data_hex", "7b7d"),
+                ("packet_timeout_height", "1-500"),
+                ("packet_timeout_timestamp", "1700000000000000000"),
+                ("packet_sequence", sequence),
+                ("packet_src_port", "icacontroller-cosmos1a"),
+                ("packet_src_channel", "channel-3"),
+                ("packet_dst_port", "icahost"),
+                ("packet_dst_channel", "channel-9"),
+                ("packet_channel_ordering", "ORDER_ORDERED"),
+                ("packet_connection", "connection-0"),
+                ("connection_id", "connection-0"),
+            ]
+        };
+        let mut attrs = packet("4");
+        attrs.extend(packet("5"));
+        let decoded = decode_event(&event("send_packet", &attrs)).unwrap();
+        assert_eq!(decoded.len(), 2);
+        match &decoded[1] {
+            TypedEvent::SendPacket(packet) => {
+                assert_eq!(packet.sequence, 5);
+                assert_eq!(packet.src_channel, "channel-3");
+                assert_eq!(packet.dst_port, "icahost");
+                assert_eq!(packet.data, Some("{}".to_string()));
+                assert_eq!(packet.timeout_timestamp, 1_700_000_000_000_000_000);
+            }
+            other => panic!("Expected a send packet, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_wasm_and_other() {
+        let wasm = event(
+            "wasm",
+            &[("_contract_address", "cosmos1contract"), ("action", "mint")],
+        );
+        assert_eq!(
+            decode_event(&wasm).unwrap(),
+            vec![TypedEvent::Wasm(WasmEvent {
+                contract_address: "cosmos1contract".to_string(),
+                attributes: vec![("action".to_string(), "mint".to_string())],
+            })]
+        );
+        let message = event("message", &[("action", "send")]);
+        assert_eq!(
+            decode_event(&message).unwrap(),
+            vec![TypedEvent::Other(message)]
+        );
+        let bad = event("transfer", &[("recipient", "cosmos1a")]);
+        assert!(decode_event(&bad).is_err());
+    }
+
+    #[cfg(feature = "sdk-0_50")]
+    #[test]
+    fn test_tx_events_without_logs() {
+        use tendermint_proto::abci::Event;
+        use tendermint_proto::abci::EventAttribute;
+
+        let abci_event = |r#type: &str, attrs: &[(&str, &str)]| Event {
+            r#type: r#type.to_string(),
+            attributes: attrs
+                .iter()
+                .map(|(k, v)| EventAttribute {
+                    key: k.as_bytes().to_vec(),
+                    value: v.as_bytes().to_vec(),
+                    index: true,
+                })
+                .collect(),
+        };
+        let response = TxResponse {
+            events: vec![
+                // the fee payment of the ante handler
+                abci_event(
+                    "transfer",
+                    &[
+                        ("recipient", "cosmos1feecollector"),
+                        ("sender", "cosmos1b"),
+                        ("amount", "5uatom"),
+                    ],
+                ),
+                abci_event(
+                    "transfer",
+                    &[
+                        ("recipient", "cosmos1a"),
+                        ("sender", "cosmos1b"),
+                        ("amount", "100uatom"),
+                        ("msg_index", "0"),
+                    ],
+                ),
+            ],
+            ..TxResponse::default()
+        };
+        assert!(response.logs.is_empty());
+        assert_eq!(tx_message_events(&response)[0].0, 0);
+        assert_eq!(
+            tx_events(&response),
+            vec![event(
+                "transfer",
+                &[
+                    ("recipient", "cosmos1a"),
+                    ("sender", "cosmos1b"),
+                    ("amount", "100uatom"),
+                ],
+            )]
+        );
+        assert_eq!(
+            decode_events(&response).unwrap(),
+            vec![TypedEvent::Transfer(TransferEvent {
+                sender: "cosmos1b".to_string(),
+                recipient: "cosmos1a".to_string(),
+                amount: vec!["100uatom".parse().unwrap()],
+            })]
+        );
+    }
+}
@@ -0,0 +1,63 @@
+//! Contains utility functions for the evidence module, which accepts evidence of validator
+//! misbehavior. Equivocation evidence is normally submitted automatically by Tendermint,
+//! these helpers exist for operator tooling and chains with custom evidence types.
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::evidence::v1beta1::Equivocation;
+use cosmos_sdk_proto::cosmos::evidence::v1beta1::MsgSubmitEvidence;
+use prost_types::Any;
+use std::time::Duration;
+use std::time::SystemTime;
+
+pub const MSG_SUBMIT_EVIDENCE_TYPE_URL: &str = "/cosmos.evidence.v1beta1.MsgSubmitEvidence";
+pub const EQUIVOCATION_TYPE_URL: &str = "/cosmos.evidence.v1beta1.Equivocation";
+
+/// Packs equivocation evidence, consensus_address is the bech32 validator consensus
+/// address (valcons prefix)
+pub fn equivocation_evidence(
+    height: i64,
+    time: SystemTime,
+    power: i64,
+    consensus_address: impl Into<String>,
+) -> Any {
+    let equivocation = Equivocation {
+        height,
+        time: Some(time.into()),
+        power,
+        consensus_address: consensus_address.into(),
+    };
+    encode_any(equivocation, EQUIVOCATION_TYPE_URL.to_string())
+}
+
+/// Builds a MsgSubmitEvidence, the evidence may be any type the chain has a handler for
+pub fn submit_evidence_msg(submitter: Address, evidence: Any) -> Msg {
+    let submit = MsgSubmitEvidence {
+        submitter: submitter.to_string(),
+        evidence: Some(evidence),
+    };
+    Msg::new(MSG_SUBMIT_EVIDENCE_TYPE_URL, submit)
+}
+
+impl Contact {
+    /// Submits evidence of misbehavior from the provided private key
+    pub async fn submit_evidence(
+        &self,
+        evidence: Any,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = submit_evidence_msg(our_address, evidence);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
@@ -0,0 +1,234 @@
+//! Fee spend limits for automated senders. A Contact with a fee budget refuses to broadcast
+//! a transaction once the fees it broadcast within the rolling window, plus the fee of the
+//! new transaction, would exceed the limit, so an error loop can not drain the fee account.
+//! A budget can be shared between Contacts, or the Contact of a Wallet, to cap the total.
+//!
+//! ```ignore
+//! let hourly = FeeBudget::new(Duration::from_secs(3600)).limit("5000000uatom".parse()?);
+//! let budget = Arc::new(hourly);
+//! let contact = contact.with_fee_budget(budget.clone());
+//! ```
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::utils::tx_fee;
+use crate::Coin;
+use num256::Uint256;
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A fee counted against the budget
+struct Spend {
+    id: u64,
+    at: Instant,
+    fee: Vec<Coin>,
+}
+
+#[derive(Default)]
+struct BudgetState {
+    /// oldest first
+    spends: VecDeque<Spend>,
+    next_id: u64,
+}
+
+/// A limit on the fees broadcast per denom within a rolling window, fees in denoms without
+/// a limit are not restricted
+pub struct FeeBudget {
+    window: Duration,
+    limits: BTreeMap<String, Uint256>,
+    state: Mutex<BudgetState>,
+}
+
+impl FeeBudget {
+    /// A budget over the provided window with no limits until they are added
+    pub fn new(window: Duration) -> Self {
+        FeeBudget {
+            window,
+            limits: BTreeMap::new(),
+            state: Mutex::new(BudgetState::default()),
+        }
+    }
+
+    /// Limits the fees paid in the denom of the coin to its amount per window
+    pub fn limit(mut self, limit: Coin) -> Self {
+        self.limits.insert(limit.denom, limit.amount);
+        self
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// The fees counted within the current window, per denom
+    pub fn spent(&self) -> Vec<Coin> {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        self.expire(&mut state, now);
+        let mut spent: BTreeMap<String, Uint256> = BTreeMap::new();
+        for coin in state.spends.iter().flat_map(|spend| spend.fee.iter()) {
+            let total = spent.entry(coin.denom.clone()).or_default();
+            *total = total.clone() + coin.amount.clone();
+        }
+        spent
+            .into_iter()
+            .map(|(denom, amount)| Coin { amount, denom })
+            .collect()
+    }
+
+    fn expire(&self, state: &mut BudgetState, now: Instant) {
+        while let Some(spend) = state.spends.front() {
+            if now.duration_since(spend.at) < self.window {
+                break;
+            }
+            state.spends.pop_front();
+        }
+    }
+
+    /// Counts a fee against the budget, failing without counting it if it does not fit.
+    /// Returns the id of the spend for release.
+    pub(crate) fn reserve(&self, fee: &[Coin]) -> Result<u64, CosmosGrpcError> {
+        self.reserve_at(fee, Instant::now())
+    }
+
+    fn reserve_at(&self, fee: &[Coin], now: Instant) -> Result<u64, CosmosGrpcError> {
+        let mut state = self.state.lock().unwrap();
+        self.expire(&mut state, now);
+        for coin in fee {
+            let limit = match self.limits.get(&coin.denom) {
+                Some(limit) => limit.clone(),
+                None => continue,
+            };
+            let spent = state
+                .spends
+                .iter()
+                .map(|spend| spend_of(spend, &coin.denom))
+                .fold(Uint256::default(), |total, amount| total + amount);
+            if spent.clone() + coin.amount.clone() <= limit {
+                continue;
+            }
+            // the time until enough of the spent fees leave the window for this fee to fit
+            let mut remaining = spent.clone();
+            let mut retry_after = self.window;
+            for spend in state.spends.iter() {
+                remaining = remaining - spend_of(spend, &coin.denom);
+                if remaining.clone() + coin.amount.clone() <= limit {
+                    retry_after = self.window - now.duration_since(spend.at);
+                    break;
+                }
+            }
+            return Err(CosmosGrpcError::FeeBudgetExceeded {
+                fee: coin.clone(),
+                spent: Coin {
+                    amount: spent,
+                    denom: coin.denom.clone(),
+                },
+                limit: Coin {
+                    amount: limit,
+                    denom: coin.denom.clone(),
+                },
+                window: self.window,
+                retry_after,
+            });
+        }
+        let id = state.next_id;
+        state.next_id += 1;
+        state.spends.push_back(Spend {
+            id,
+            at: now,
+            fee: fee.to_vec(),
+        });
+        Ok(id)
+    }
+
+    /// Removes a spend from the budget, for transactions the node rejected without
+    /// charging a fee
+    pub(crate) fn release(&self, id: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.spends.retain(|spend| spend.id != id);
+    }
+}
+
+fn spend_of(spend: &Spend, denom: &str) -> Uint256 {
+    spend
+        .fee
+        .iter()
+        .filter(|coin| coin.denom == denom)
+        .fold(Uint256::default(), |total, coin| {
+            total + coin.amount.clone()
+        })
+}
+
+/// Counts the fee of an encoded transaction against the budget
+pub(crate) fn reserve_tx_fee(budget: &FeeBudget, tx_bytes: &[u8]) -> Result<u64, CosmosGrpcError> {
+    let fee: Vec<Coin> = tx_fee(tx_bytes)
+        .map(|fee| fee.amount)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|coin| Coin::from_proto(coin).ok())
+        .collect();
+    budget.reserve(&fee)
+}
+
+impl Contact {
+    /// Refuses to broadcast transactions whose fee does not fit in the budget, see the
+    /// module documentation. Fees are counted when a transaction is broadcast and removed
+    /// again if the node rejects it, as rejected transactions pay no fee. The returned
+    /// Contact does not share its connection with clones of this one.
+    pub fn with_fee_budget(self, budget: Arc<FeeBudget>) -> Self {
+        let mut inner = self.unshared();
+        inner.fee_budget = Some(budget);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(coin: &str) -> Coin {
+        coin.parse().unwrap()
+    }
+
+    #[test]
+    fn test_fee_budget() {
+        let budget = FeeBudget::new(Duration::from_secs(60)).limit(coin("1000uatom"));
+        let start = Instant::now();
+        budget.reserve_at(&[coin("400uatom")], start).unwrap();
+        let second = budget
+            .reserve_at(&[coin("400uatom")], start + Duration::from_secs(10))
+            .unwrap();
+        // fees in other denoms are not limited
+        budget
+            .reserve_at(&[coin("9999ufoo")], start + Duration::from_secs(10))
+            .unwrap();
+        match budget.reserve_at(&[coin("300uatom")], start + Duration::from_secs(20)) {
+            Err(CosmosGrpcError::FeeBudgetExceeded {
+                spent, retry_after, ..
+            }) => {
+                assert_eq!(spent, coin("800uatom"));
+                // the first fee leaves the window 60 seconds after it was counted
+                assert_eq!(retry_after, Duration::from_secs(40));
+            }
+            other => panic!("Expected FeeBudgetExceeded, got {:?}", other),
+        }
+
+        // a rejected transaction is not counted
+        budget.release(second);
+        budget
+            .reserve_at(&[coin("300uatom")], start + Duration::from_secs(20))
+            .unwrap();
+        assert!(budget
+            .reserve_at(&[coin("301uatom")], start + Duration::from_secs(30))
+            .is_err());
+        // the first fee has left the window
+        budget
+            .reserve_at(&[coin("301uatom")], start + Duration::from_secs(60))
+            .unwrap();
+    }
+}
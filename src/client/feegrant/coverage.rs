@@ -0,0 +1,264 @@
+//! Checks that a fee allowance pays for a transaction before it is broadcast. A transaction
+//! whose granter can not pay is rejected by the ante handler with a generic fee allowance
+//! error, checking first names the limit that was hit.
+//!
+//! ```ignore
+//! let contact = contact.with_fee_granter(treasury);
+//! // every fee built by the Contact now names the treasury as granter and is checked
+//! if let Err(CosmosGrpcError::FeeGrantNotUsable { error, .. }) =
+//!     contact.send_message(&msgs, None, fee, key, None).await
+//! {
+//!     warn!("treasury allowance exhausted: {}", error);
+//! }
+//! ```
+
+use crate::any::AnyExt;
+use crate::error::CosmosGrpcError;
+use crate::error::FeeGrantError;
+use crate::proto::cosmos::feegrant::v1beta1::AllowedMsgAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::BasicAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::PeriodicAllowance;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Fee;
+use crate::Msg;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use num256::Uint256;
+use prost_types::Any;
+use prost_types::Timestamp;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+fn timestamp_to_time(time: &Timestamp) -> Option<SystemTime> {
+    if time.seconds < 0 || time.nanos < 0 {
+        return None;
+    }
+    Some(UNIX_EPOCH + Duration::new(time.seconds as u64, time.nanos as u32))
+}
+
+/// The coin of the fee's denom the limit has left, zero if the limit does not hold it
+fn remaining_of(limit: &[ProtoCoin], denom: &str) -> Result<Coin, FeeGrantError> {
+    let mut amount: Uint256 = 0u64.into();
+    for coin in limit.iter().filter(|coin| coin.denom == denom) {
+        amount = Coin::from_proto(coin.clone())
+            .map_err(FeeGrantError::InvalidAllowance)?
+            .amount;
+    }
+    Ok(Coin {
+        amount,
+        denom: denom.to_string(),
+    })
+}
+
+fn check_basic(
+    allowance: &BasicAllowance,
+    fee: &Fee,
+    now: SystemTime,
+) -> Result<(), FeeGrantError> {
+    if let Some(expiration) = allowance.expiration.as_ref().and_then(timestamp_to_time) {
+        if now > expiration {
+            return Err(FeeGrantError::Expired { expiration });
+        }
+    }
+    // an empty spend limit is unlimited
+    if allowance.spend_limit.is_empty() {
+        return Ok(());
+    }
+    for coin in fee.amount.iter() {
+        let remaining = remaining_of(&allowance.spend_limit, &coin.denom)?;
+        if coin.amount > remaining.amount {
+            return Err(FeeGrantError::SpendLimitExceeded {
+                fee: coin.clone(),
+                remaining,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn check_periodic(
+    allowance: &PeriodicAllowance,
+    fee: &Fee,
+    now: SystemTime,
+) -> Result<(), FeeGrantError> {
+    let period_reset = allowance.period_reset.as_ref().and_then(timestamp_to_time);
+    // the chain refills the period before deducting once the reset time has passed
+    let can_spend = match period_reset {
+        Some(reset) if now >= reset => &allowance.period_spend_limit,
+        _ => &allowance.period_can_spend,
+    };
+    for coin in fee.amount.iter() {
+        let remaining = remaining_of(can_spend, &coin.denom)?;
+        if coin.amount > remaining.amount {
+            return Err(FeeGrantError::PeriodLimitExceeded {
+                fee: coin.clone(),
+                remaining,
+                period_reset,
+            });
+        }
+    }
+    match allowance.basic.as_ref() {
+        Some(basic) => check_basic(basic, fee, now),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the allowance of a Grant pays the fee of a transaction containing the
+/// messages at the time now, the same way the feegrant module accepts it
+pub fn check_fee_allowance(
+    allowance: &Any,
+    messages: &[Msg],
+    fee: &Fee,
+    now: SystemTime,
+) -> Result<(), FeeGrantError> {
+    let invalid = |e: CosmosGrpcError| FeeGrantError::InvalidAllowance(e.to_string());
+    if allowance.is::<BasicAllowance>() {
+        check_basic(&allowance.unpack().map_err(invalid)?, fee, now)
+    } else if allowance.is::<PeriodicAllowance>() {
+        check_periodic(&allowance.unpack().map_err(invalid)?, fee, now)
+    } else if allowance.is::<AllowedMsgAllowance>() {
+        let allowed: AllowedMsgAllowance = allowance.unpack().map_err(invalid)?;
+        for msg in messages {
+            if !allowed.allowed_messages.iter().any(|m| m == msg.type_url()) {
+                return Err(FeeGrantError::MessageNotAllowed {
+                    type_url: msg.type_url().to_string(),
+                });
+            }
+        }
+        match allowed.allowance.as_ref() {
+            Some(inner) => check_fee_allowance(inner, messages, fee, now),
+            None => Err(FeeGrantError::InvalidAllowance(
+                "AllowedMsgAllowance without an allowance".to_string(),
+            )),
+        }
+    } else {
+        Err(FeeGrantError::UnknownAllowance {
+            type_url: allowance.type_url.clone(),
+        })
+    }
+}
+
+impl Contact {
+    /// Sets the account paying the fees of transactions built by this Contact, the fees of
+    /// the transaction helpers name it as granter and are checked against its allowance
+    /// before broadcasting. The returned Contact does not share its connection with clones
+    /// of this one.
+    pub fn with_fee_granter(self, granter: Address) -> Self {
+        let mut inner = self.unshared();
+        inner.fee_granter = Some(granter);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn fee_granter(&self) -> Option<Address> {
+        self.inner.fee_granter
+    }
+
+    /// Returns FeeGrantNotUsable if the fee names a granter whose allowance to the signer
+    /// does not pay for the messages, fees without a granter are not checked. Called before
+    /// signing.
+    pub(crate) async fn check_fee_grant(
+        &self,
+        signer: Address,
+        messages: &[Msg],
+        fee: &Fee,
+    ) -> Result<(), CosmosGrpcError> {
+        let granter = match fee.granter.as_ref() {
+            Some(granter) => granter,
+            None => return Ok(()),
+        };
+        let granter_address: Address = granter.parse().map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Invalid granter {}: {}", granter, e))
+        })?;
+        let not_usable = |error: FeeGrantError| CosmosGrpcError::FeeGrantNotUsable {
+            granter: granter.clone(),
+            error,
+        };
+        let grant = self.get_fee_allowance(granter_address, signer).await?;
+        match grant.and_then(|grant| grant.allowance) {
+            Some(allowance) => check_fee_allowance(&allowance, messages, fee, SystemTime::now())
+                .map_err(not_usable),
+            None => Err(not_usable(FeeGrantError::NoAllowance)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::client::feegrant::FeeAllowance;
+    use crate::client::feegrant::PERIODIC_ALLOWANCE_TYPE_URL;
+    use crate::client::gov::MSG_VOTE_TYPE_URL;
+    use crate::utils::encode_any;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    fn fee(amount: &str) -> Fee {
+        Fee {
+            amount: vec![amount.parse().unwrap()],
+            gas_limit: 200_000,
+            granter: Some("cosmos1granter".to_string()),
+            payer: None,
+        }
+    }
+
+    #[test]
+    fn test_check_fee_allowance() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let send = [Msg::new(MSG_SEND_TYPE_URL, MsgSend::default())];
+        let basic = FeeAllowance::Basic {
+            spend_limit: vec!["1000uatom".parse().unwrap()],
+            expiration: Some(now + Duration::from_secs(60)),
+        };
+        let any = basic.to_any().unwrap();
+        assert!(check_fee_allowance(&any, &send, &fee("1000uatom"), now).is_ok());
+        assert_eq!(
+            check_fee_allowance(&any, &send, &fee("1001uatom"), now),
+            Err(FeeGrantError::SpendLimitExceeded {
+                fee: "1001uatom".parse().unwrap(),
+                remaining: "1000uatom".parse().unwrap(),
+            })
+        );
+        assert!(matches!(
+            check_fee_allowance(&any, &send, &fee("1uosmo"), now),
+            Err(FeeGrantError::SpendLimitExceeded { .. })
+        ));
+        let later = now + Duration::from_secs(61);
+        assert!(matches!(
+            check_fee_allowance(&any, &send, &fee("1uatom"), later),
+            Err(FeeGrantError::Expired { .. })
+        ));
+
+        let allowed = FeeAllowance::AllowedMsg {
+            allowance: Box::new(basic),
+            allowed_messages: vec![MSG_VOTE_TYPE_URL.to_string()],
+        }
+        .to_any()
+        .unwrap();
+        assert_eq!(
+            check_fee_allowance(&allowed, &send, &fee("1uatom"), now),
+            Err(FeeGrantError::MessageNotAllowed {
+                type_url: MSG_SEND_TYPE_URL.to_string()
+            })
+        );
+
+        let periodic = PeriodicAllowance {
+            basic: Some(BasicAllowance::default()),
+            period: None,
+            period_spend_limit: vec!["100uatom".parse::<Coin>().unwrap().into()],
+            period_can_spend: vec!["10uatom".parse::<Coin>().unwrap().into()],
+            period_reset: Some((now + Duration::from_secs(60)).into()),
+        };
+        let any = encode_any(periodic, PERIODIC_ALLOWANCE_TYPE_URL.to_string());
+        assert!(matches!(
+            check_fee_allowance(&any, &send, &fee("50uatom"), now),
+            Err(FeeGrantError::PeriodLimitExceeded { .. })
+        ));
+        // the period has reset by then
+        assert!(check_fee_allowance(&any, &send, &fee("50uatom"), later).is_ok());
+    }
+}
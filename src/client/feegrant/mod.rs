@@ -0,0 +1,325 @@
+//! Contains utility functions for granting and revoking fee allowances, which allow one
+//! account (the granter) to pay the transaction fees of another (the grantee). The grantee
+//! uses an allowance by setting the granter field of the transaction Fee.
+
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::feegrant::v1beta1::AllowedMsgAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::BasicAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::Grant;
+use crate::proto::cosmos::feegrant::v1beta1::MsgGrantAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::MsgRevokeAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::PeriodicAllowance;
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowanceRequest;
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowanceResponse;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowancesByGranterRequest;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowancesByGranterResponse;
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowancesRequest;
+use crate::proto::cosmos::feegrant::v1beta1::QueryAllowancesResponse;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Fee;
+use crate::Msg;
+use crate::PrivateKey;
+use futures::Stream;
+use prost_types::Any;
+use std::time::Duration;
+use std::time::SystemTime;
+use tonic::Code as GrpcCode;
+
+mod coverage;
+
+pub use coverage::*;
+
+pub const MSG_GRANT_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgGrantAllowance";
+pub const MSG_REVOKE_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgRevokeAllowance";
+pub const BASIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+pub const PERIODIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.PeriodicAllowance";
+pub const ALLOWED_MSG_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.AllowedMsgAllowance";
+
+/// A fee allowance given by a granter to a grantee
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeeAllowance {
+    /// Allows the grantee to spend up to spend_limit (or unlimited if empty) on fees
+    /// until the optional expiration time
+    Basic {
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+    },
+    /// Same as Basic but additionally limits spending to period_spend_limit per period
+    Periodic {
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+        period: Duration,
+        period_spend_limit: Vec<Coin>,
+    },
+    /// Restricts the wrapped Basic or Periodic allowance so that it can only pay for
+    /// transactions containing the allowed message types
+    AllowedMsg {
+        allowance: Box<FeeAllowance>,
+        allowed_messages: Vec<String>,
+    },
+}
+
+impl FeeAllowance {
+    /// Packs this allowance into the Any used by MsgGrantAllowance
+    pub fn to_any(&self) -> Result<Any, CosmosGrpcError> {
+        match self {
+            FeeAllowance::Basic {
+                spend_limit,
+                expiration,
+            } => Ok(encode_any(
+                basic_allowance(spend_limit, expiration),
+                BASIC_ALLOWANCE_TYPE_URL.to_string(),
+            )),
+            FeeAllowance::Periodic {
+                spend_limit,
+                expiration,
+                period,
+                period_spend_limit,
+            } => {
+                let period_spend_limit: Vec<_> = period_spend_limit
+                    .iter()
+                    .map(|c| c.clone().into())
+                    .collect();
+                let periodic = PeriodicAllowance {
+                    basic: Some(basic_allowance(spend_limit, expiration)),
+                    period: Some((*period).into()),
+                    period_spend_limit: period_spend_limit.clone(),
+                    // the first period starts now with the full limit available
+                    period_can_spend: period_spend_limit,
+                    period_reset: Some((SystemTime::now() + *period).into()),
+                };
+                Ok(encode_any(
+                    periodic,
+                    PERIODIC_ALLOWANCE_TYPE_URL.to_string(),
+                ))
+            }
+            FeeAllowance::AllowedMsg {
+                allowance,
+                allowed_messages,
+            } => {
+                if let FeeAllowance::AllowedMsg { .. } = **allowance {
+                    return Err(CosmosGrpcError::BadInput(
+                        "AllowedMsg allowances can not be nested".to_string(),
+                    ));
+                }
+                if allowed_messages.is_empty() {
+                    return Err(CosmosGrpcError::BadInput(
+                        "AllowedMsg allowance requires at least one message type".to_string(),
+                    ));
+                }
+                let allowed = AllowedMsgAllowance {
+                    allowance: Some(allowance.to_any()?),
+                    allowed_messages: allowed_messages.clone(),
+                };
+                Ok(encode_any(
+                    allowed,
+                    ALLOWED_MSG_ALLOWANCE_TYPE_URL.to_string(),
+                ))
+            }
+        }
+    }
+}
+
+fn basic_allowance(spend_limit: &[Coin], expiration: &Option<SystemTime>) -> BasicAllowance {
+    BasicAllowance {
+        spend_limit: spend_limit.iter().map(|c| c.clone().into()).collect(),
+        expiration: expiration.map(|e| e.into()),
+    }
+}
+
+/// Builds a MsgGrantAllowance allowing the grantee to pay fees from the granters account
+pub fn grant_allowance_msg(
+    granter: Address,
+    grantee: Address,
+    allowance: &FeeAllowance,
+) -> Result<Msg, CosmosGrpcError> {
+    let grant = MsgGrantAllowance {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+        allowance: Some(allowance.to_any()?),
+    };
+    Ok(Msg::new(MSG_GRANT_ALLOWANCE_TYPE_URL, grant))
+}
+
+/// Builds a MsgRevokeAllowance removing the allowance from granter to grantee
+pub fn revoke_allowance_msg(granter: Address, grantee: Address) -> Msg {
+    let revoke = MsgRevokeAllowance {
+        granter: granter.to_string(),
+        grantee: grantee.to_string(),
+    };
+    Msg::new(MSG_REVOKE_ALLOWANCE_TYPE_URL, revoke)
+}
+
+/// Nodes report a missing allowance with NotFound, or depending on the SDK version with
+/// Unknown or Internal and only the feegrant error message to tell it apart
+fn is_allowance_not_found(error: &CosmosGrpcError) -> bool {
+    match error {
+        CosmosGrpcError::RequestError { error, .. } => match error.code() {
+            GrpcCode::NotFound => true,
+            GrpcCode::Unknown | GrpcCode::Internal => {
+                error.message().contains("fee-grant not found")
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl Contact {
+    /// Gets the fee allowance from granter to grantee, returns None if there is no allowance
+    pub async fn get_fee_allowance(
+        &self,
+        granter: Address,
+        grantee: Address,
+    ) -> Result<Option<Grant>, CosmosGrpcError> {
+        let res: Result<QueryAllowanceResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.feegrant.v1beta1.Query/Allowance",
+                QueryAllowanceRequest {
+                    granter: granter.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    grantee: grantee.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await;
+        match res {
+            Ok(res) => Ok(res.allowance),
+            Err(e) if is_allowance_not_found(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Streams every fee allowance held by the grantee, pages are fetched as the stream
+    /// is consumed
+    pub fn get_fee_allowances_stream(
+        &self,
+        grantee: Address,
+    ) -> impl Stream<Item = Result<Grant, CosmosGrpcError>> + '_ {
+        let grantee = grantee.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryAllowancesRequest {
+                grantee: grantee.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryAllowancesResponse = self
+                    .grpc_unary("/cosmos.feegrant.v1beta1.Query/Allowances", request)
+                    .await?;
+                Ok((res.allowances, res.pagination))
+            }
+        })
+    }
+
+    /// Streams every fee allowance given by the granter, pages are fetched as the stream
+    /// is consumed. Requires Cosmos SDK v0.46 or later on the chain.
+    #[cfg(feature = "sdk-0_46")]
+    pub fn get_fee_allowances_by_granter_stream(
+        &self,
+        granter: Address,
+    ) -> impl Stream<Item = Result<Grant, CosmosGrpcError>> + '_ {
+        let granter = granter.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryAllowancesByGranterRequest {
+                granter: granter.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryAllowancesByGranterResponse = self
+                    .grpc_unary(
+                        "/cosmos.feegrant.v1beta1.Query/AllowancesByGranter",
+                        request,
+                    )
+                    .await?;
+                Ok((res.allowances, res.pagination))
+            }
+        })
+    }
+
+    /// Grants the grantee an allowance to pay fees from the account of the provided private key
+    pub async fn grant_fee_allowance(
+        &self,
+        grantee: Address,
+        allowance: FeeAllowance,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = grant_allowance_msg(our_address, grantee, &allowance)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Revokes the fee allowance from the account of the provided private key to the grantee
+    pub async fn revoke_fee_allowance(
+        &self,
+        grantee: Address,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = revoke_allowance_msg(our_address, grantee);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allowed_msg_nesting() {
+        let basic = FeeAllowance::Basic {
+            spend_limit: Vec::new(),
+            expiration: None,
+        };
+        let allowed = FeeAllowance::AllowedMsg {
+            allowance: Box::new(basic),
+            allowed_messages: vec!["/cosmos.bank.v1beta1.MsgSend".to_string()],
+        };
+        assert!(allowed.to_any().is_ok());
+        let nested = FeeAllowance::AllowedMsg {
+            allowance: Box::new(allowed),
+            allowed_messages: vec!["/cosmos.bank.v1beta1.MsgSend".to_string()],
+        };
+        assert!(nested.to_any().is_err());
+    }
+
+    #[test]
+    fn test_allowance_not_found() {
+        let error = |code: GrpcCode, message: &str| CosmosGrpcError::RequestError {
+            endpoint: "http://localhost:9090".to_string(),
+            method: "/cosmos.feegrant.v1beta1.Query/Allowance".to_string(),
+            error: tonic::Status::new(code, message),
+        };
+        assert!(is_allowance_not_found(&error(GrpcCode::NotFound, "")));
+        // SDK v0.46 returns the keeper error as is, v0.50 wraps it as Internal
+        assert!(is_allowance_not_found(&error(
+            GrpcCode::Unknown,
+            "fee-grant not found: unknown request"
+        )));
+        assert!(is_allowance_not_found(&error(
+            GrpcCode::Internal,
+            "fee-grant not found"
+        )));
+        assert!(!is_allowance_not_found(&error(
+            GrpcCode::Internal,
+            "failed to load store"
+        )));
+        assert!(!is_allowance_not_found(&error(
+            GrpcCode::Unavailable,
+            "fee-grant not found"
+        )));
+    }
+}
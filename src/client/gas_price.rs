@@ -0,0 +1,841 @@
+//! Gas price oracles, pluggable strategies for choosing the gas price to pay on a chain so
+//! that fees do not need to be maintained by hand for every chain a deployment uses. Use
+//! [`Contact::get_fee`] to turn the price returned by any oracle into a [`Fee`], or
+//! [`Contact::with_auto_fee`] to have send_tokens and send_message price fees left empty.
+
+use crate::client::accounts::SpendableBalance;
+use crate::client::feegrant::check_fee_allowance;
+use crate::client::types::ChainStatus;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::error::FeeGrantError;
+use crate::proto::cosmos::base::node::v1beta1::ConfigRequest;
+use crate::proto::cosmos::base::node::v1beta1::ConfigResponse;
+use crate::proto::feemarket::feemarket::v1::GasPriceRequest;
+use crate::proto::feemarket::feemarket::v1::GasPriceResponse;
+use crate::proto::feemarket::feemarket::v1::GasPricesRequest;
+use crate::proto::feemarket::feemarket::v1::GasPricesResponse;
+use crate::proto::gaia::globalfee::v1beta1::QueryMinimumGasPricesRequest;
+use crate::proto::gaia::globalfee::v1beta1::QueryMinimumGasPricesResponse;
+use crate::utils::FundsInfo;
+use crate::Address;
+use crate::Coin;
+use crate::Dec;
+use crate::DecCoin;
+use crate::Fee;
+use cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin as ProtoDecCoin;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use num256::Uint256;
+use prost_types::Any;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// A source of gas prices, implement this to provide a custom pricing strategy
+pub trait GasPriceOracle: Send + Sync {
+    /// Returns the gas price to pay in the provided denom
+    fn gas_price<'a>(
+        &'a self,
+        contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>>;
+}
+
+/// How send_tokens and send_message price a fee without an amount, see
+/// Contact::with_auto_fee
+#[derive(Clone)]
+pub enum AutoFee {
+    /// pays in denom at the price returned by the oracle, see Contact::get_fee
+    Oracle {
+        oracle: Arc<dyn GasPriceOracle>,
+        denom: String,
+    },
+    /// pays in the cheapest denom the signer can afford, see Contact::get_affordable_fee
+    Affordable { unit_values: Vec<DecCoin> },
+}
+
+fn find_price(prices: &[DecCoin], denom: &str) -> Result<DecCoin, CosmosGrpcError> {
+    match prices.iter().find(|price| price.denom == denom) {
+        Some(price) => Ok(price.clone()),
+        None => Err(CosmosGrpcError::BadInput(format!(
+            "No gas price for {}",
+            denom
+        ))),
+    }
+}
+
+/// Always returns the configured gas prices
+#[derive(Debug, Clone)]
+pub struct StaticGasPrice {
+    prices: Vec<DecCoin>,
+}
+
+impl StaticGasPrice {
+    pub fn new(prices: Vec<DecCoin>) -> Self {
+        StaticGasPrice { prices }
+    }
+}
+
+impl GasPriceOracle for StaticGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        _contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        futures::future::ready(find_price(&self.prices, denom)).boxed()
+    }
+}
+
+/// Uses the minimum gas prices configured on the node the Contact is connected to, this
+/// is the lowest price that node will accept into its mempool. Requires Cosmos SDK v0.46+
+#[derive(Debug, Clone, Default)]
+pub struct NodeConfigGasPrice;
+
+impl GasPriceOracle for NodeConfigGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        async move {
+            let prices = contact.get_minimum_gas_prices().await?;
+            find_price(&prices, denom)
+        }
+        .boxed()
+    }
+}
+
+/// Uses the current base gas price of chains running the Skip feemarket module, scaled by a
+/// multiplier to leave room for the price to rise before the transaction is included
+#[derive(Debug, Clone)]
+pub struct FeeMarketGasPrice {
+    multiplier: Dec,
+}
+
+impl FeeMarketGasPrice {
+    pub fn new() -> Self {
+        FeeMarketGasPrice {
+            multiplier: Dec::one(),
+        }
+    }
+
+    pub fn with_multiplier(multiplier: Dec) -> Self {
+        FeeMarketGasPrice { multiplier }
+    }
+}
+
+impl Default for FeeMarketGasPrice {
+    fn default() -> Self {
+        FeeMarketGasPrice::new()
+    }
+}
+
+impl GasPriceOracle for FeeMarketGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        async move {
+            let price = contact.get_feemarket_gas_price(denom.to_string()).await?;
+            Ok(DecCoin {
+                amount: price.amount.mul(&self.multiplier),
+                denom: price.denom,
+            })
+        }
+        .boxed()
+    }
+}
+
+/// Which of the chain registry gas prices to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasPriceTier {
+    Low,
+    Average,
+    High,
+}
+
+/// A fee token as listed in the fees section of the cosmos/chain-registry format
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeeToken {
+    pub denom: String,
+    pub fixed_min_gas_price: Option<Dec>,
+    pub low_gas_price: Option<Dec>,
+    pub average_gas_price: Option<Dec>,
+    pub high_gas_price: Option<Dec>,
+}
+
+impl FeeToken {
+    /// Returns the gas price for the requested tier, falling back to the average, then the
+    /// low price if the tier is not listed, and never below the fixed minimum
+    pub fn gas_price(&self, tier: GasPriceTier) -> Option<Dec> {
+        let preferred = match tier {
+            GasPriceTier::Low => &self.low_gas_price,
+            GasPriceTier::Average => &self.average_gas_price,
+            GasPriceTier::High => &self.high_gas_price,
+        };
+        let price = preferred
+            .as_ref()
+            .or_else(|| self.average_gas_price.as_ref())
+            .or_else(|| self.low_gas_price.as_ref())
+            .cloned();
+        match (price, self.fixed_min_gas_price.clone()) {
+            (Some(price), Some(min)) => Some(std::cmp::max(price, min)),
+            (Some(price), None) => Some(price),
+            (None, min) => min,
+        }
+    }
+}
+
+/// Uses the default gas prices published for the chain in the cosmos/chain-registry
+#[derive(Debug, Clone)]
+pub struct ChainRegistryGasPrice {
+    fee_tokens: Vec<FeeToken>,
+    tier: GasPriceTier,
+}
+
+impl ChainRegistryGasPrice {
+    pub fn new(fee_tokens: Vec<FeeToken>, tier: GasPriceTier) -> Self {
+        ChainRegistryGasPrice { fee_tokens, tier }
+    }
+}
+
+impl GasPriceOracle for ChainRegistryGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        _contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        let price = self
+            .fee_tokens
+            .iter()
+            .find(|token| token.denom == denom)
+            .and_then(|token| token.gas_price(self.tier));
+        let result = match price {
+            Some(amount) => Ok(DecCoin::new(amount, denom.to_string())),
+            None => Err(CosmosGrpcError::BadInput(format!(
+                "No chain registry gas price for {}",
+                denom
+            ))),
+        };
+        futures::future::ready(result).boxed()
+    }
+}
+
+/// Uses a percentile of the gas prices paid by transactions in the most recent blocks,
+/// for example the 50th percentile is the median price recently paid
+#[derive(Debug, Clone)]
+pub struct RecentTxGasPrice {
+    blocks: u64,
+    percentile: u8,
+}
+
+impl RecentTxGasPrice {
+    /// Samples the provided number of recent blocks, percentile is capped at 100
+    pub fn new(blocks: u64, percentile: u8) -> Self {
+        RecentTxGasPrice {
+            blocks: std::cmp::max(blocks, 1),
+            percentile: std::cmp::min(percentile, 100),
+        }
+    }
+}
+
+/// Returns the gas prices paid in the provided denom by a set of transactions
+pub fn paid_gas_prices(txs: &[Tx], denom: &str) -> Vec<Dec> {
+    let mut prices = Vec::new();
+    for tx in txs {
+        let fee = match tx.auth_info.as_ref().and_then(|info| info.fee.as_ref()) {
+            Some(fee) if fee.gas_limit > 0 => fee,
+            _ => continue,
+        };
+        for coin in fee.amount.iter().filter(|coin| coin.denom == denom) {
+            if let Ok(coin) = Coin::from_proto(coin.clone()) {
+                if let Some(price) = Dec::from(coin.amount).quo(&Dec::from(fee.gas_limit)) {
+                    prices.push(price);
+                }
+            }
+        }
+    }
+    prices
+}
+
+/// Returns the value at the provided percentile (0 to 100) of a set of prices
+pub fn percentile_price(mut prices: Vec<Dec>, percentile: u8) -> Option<Dec> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort();
+    let percentile = std::cmp::min(percentile, 100) as usize;
+    let index = (prices.len() - 1) * percentile / 100;
+    Some(prices.swap_remove(index))
+}
+
+impl GasPriceOracle for RecentTxGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        async move {
+            let latest = match contact.get_chain_status().await? {
+                ChainStatus::Moving { block_height } => block_height,
+                _ => {
+                    return Err(CosmosGrpcError::BadResponse(
+                        "Node is not synced, can not sample recent transactions".to_string(),
+                    ))
+                }
+            };
+            let mut prices = Vec::new();
+            let first = latest.saturating_sub(self.blocks - 1);
+            for height in first..=latest {
+                let res = contact
+                    .get_txs_event(vec![format!("tx.height={}", height)], None)
+                    .await?;
+                prices.extend(paid_gas_prices(&res.txs, denom));
+            }
+            match percentile_price(prices, self.percentile) {
+                Some(amount) => Ok(DecCoin::new(amount, denom.to_string())),
+                None => Err(CosmosGrpcError::BadResponse(format!(
+                    "No recent transactions paid fees in {}",
+                    denom
+                ))),
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Tries each oracle in order returning the first price found, for example a feemarket
+/// oracle falling back to chain registry defaults on chains without the module
+pub struct FallbackGasPrice {
+    oracles: Vec<Box<dyn GasPriceOracle>>,
+}
+
+impl FallbackGasPrice {
+    pub fn new(oracles: Vec<Box<dyn GasPriceOracle>>) -> Self {
+        FallbackGasPrice { oracles }
+    }
+}
+
+impl GasPriceOracle for FallbackGasPrice {
+    fn gas_price<'a>(
+        &'a self,
+        contact: &'a Contact,
+        denom: &'a str,
+    ) -> BoxFuture<'a, Result<DecCoin, CosmosGrpcError>> {
+        async move {
+            let mut last_error =
+                CosmosGrpcError::BadInput("No gas price oracles configured".to_string());
+            for oracle in self.oracles.iter() {
+                match oracle.gas_price(contact, denom).await {
+                    Ok(price) => return Ok(price),
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(last_error)
+        }
+        .boxed()
+    }
+}
+
+/// A denom the chain accepts fees in, with the fee it requires for a gas limit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeOption {
+    pub gas_price: DecCoin,
+    pub fee: Fee,
+}
+
+/// Builds the fee for the provided gas limit in each of the accepted denoms, prices that
+/// can not produce a fee are skipped
+pub fn fee_options(prices: &[DecCoin], gas_limit: u64) -> Vec<FeeOption> {
+    prices
+        .iter()
+        .filter_map(|price| {
+            let amount = price.fee_for_gas(gas_limit)?;
+            Some(FeeOption {
+                gas_price: price.clone(),
+                fee: Fee {
+                    amount: vec![amount],
+                    gas_limit,
+                    granter: None,
+                    payer: None,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Picks the option that costs the least, unit_values holds the value of one base unit of
+/// each denom in a common reference such as USD. Denoms without a value are ignored.
+pub fn cheapest_fee_option(options: &[FeeOption], unit_values: &[DecCoin]) -> Option<FeeOption> {
+    options
+        .iter()
+        .filter_map(|option| {
+            let amount = option.fee.amount.first()?;
+            let value = unit_values.iter().find(|v| v.denom == amount.denom)?;
+            Some((Dec::from(amount.amount).mul(&value.amount), option))
+        })
+        .min_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, option)| option.clone())
+}
+
+/// The amount of denom an option needs, its fee plus the coins reserved for the messages
+fn required_amount(option: &FeeOption, reserved: &[Coin], denom: &str) -> Uint256 {
+    option
+        .fee
+        .amount
+        .iter()
+        .chain(reserved.iter())
+        .filter(|c| c.denom == denom)
+        .fold(0u64.into(), |total, c| total + c.amount.clone())
+}
+
+/// Keeps the options the account can pay out of its spendable balance while still holding
+/// the reserved coins, such as the amount of a send in the same transaction
+pub fn affordable_fee_options(
+    options: &[FeeOption],
+    balance: &SpendableBalance,
+    reserved: &[Coin],
+) -> Vec<FeeOption> {
+    options
+        .iter()
+        .filter(|option| {
+            option.fee.amount.iter().all(|coin| {
+                required_amount(option, reserved, &coin.denom) <= balance.spendable_of(&coin.denom)
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Picks a fee the account can afford, the cheapest according to unit_values if any of
+/// the affordable denoms has a value, otherwise the first in the order listed by the chain.
+/// If none is affordable the error reports the shortfall in the first listed denom.
+pub fn select_fee_option(
+    options: &[FeeOption],
+    balance: &SpendableBalance,
+    reserved: &[Coin],
+    unit_values: &[DecCoin],
+) -> Result<FeeOption, CosmosGrpcError> {
+    let affordable = affordable_fee_options(options, balance, reserved);
+    if let Some(option) = cheapest_fee_option(&affordable, unit_values) {
+        return Ok(option);
+    }
+    if let Some(option) = affordable.into_iter().next() {
+        return Ok(option);
+    }
+    match options
+        .first()
+        .and_then(|o| o.fee.amount.first().map(|c| (o, c)))
+    {
+        Some((option, coin)) => Err(CosmosGrpcError::InsufficientFunds {
+            funds_info: FundsInfo {
+                denom: coin.denom.clone(),
+                required: required_amount(option, reserved, &coin.denom),
+                available: balance.spendable_of(&coin.denom),
+            },
+        }),
+        None => Err(CosmosGrpcError::BadInput(
+            "No fee options to choose from".to_string(),
+        )),
+    }
+}
+
+/// Picks a fee the allowance of a fee granter covers, the cheapest according to unit_values
+/// if any of the covered denoms has a value, otherwise the first in the order listed by the
+/// chain. The messages are not known here, so an AllowedMsgAllowance is only checked for
+/// them when the transaction is sent. If no option is covered the error is the one of the
+/// first listed option.
+pub fn select_granted_fee_option(
+    options: &[FeeOption],
+    allowance: &Any,
+    unit_values: &[DecCoin],
+    now: SystemTime,
+) -> Result<FeeOption, FeeGrantError> {
+    let mut first_error = None;
+    let mut covered = Vec::new();
+    for option in options {
+        match check_fee_allowance(allowance, &[], &option.fee, now) {
+            Ok(()) => covered.push(option.clone()),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    if let Some(option) = cheapest_fee_option(&covered, unit_values) {
+        return Ok(option);
+    }
+    match covered.into_iter().next() {
+        Some(option) => Ok(option),
+        None => Err(first_error.unwrap_or_else(|| {
+            FeeGrantError::InvalidAllowance("No fee options to choose from".to_string())
+        })),
+    }
+}
+
+fn dec_coins_from_proto(prices: Vec<ProtoDecCoin>) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+    prices
+        .into_iter()
+        .map(|price| {
+            DecCoin::try_from(price).map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))
+        })
+        .collect()
+}
+
+impl Contact {
+    /// Gets the minimum gas prices configured on the connected node, requires
+    /// Cosmos SDK v0.46+
+    pub async fn get_minimum_gas_prices(&self) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let res: ConfigResponse = self
+            .grpc_unary("/cosmos.base.node.v1beta1.Service/Config", ConfigRequest {})
+            .await?;
+        let mut prices = Vec::new();
+        for price in res.minimum_gas_price.split(',').map(|p| p.trim()) {
+            if price.is_empty() {
+                continue;
+            }
+            match price.parse() {
+                Ok(price) => prices.push(price),
+                Err(e) => return Err(CosmosGrpcError::BadResponse(e)),
+            }
+        }
+        Ok(prices)
+    }
+
+    /// Gets the current base gas price in the provided denom from the feemarket module
+    pub async fn get_feemarket_gas_price(&self, denom: String) -> Result<DecCoin, CosmosGrpcError> {
+        let res: GasPriceResponse = self
+            .grpc_unary(
+                "/feemarket.feemarket.v1.Query/GasPrice",
+                GasPriceRequest { denom },
+            )
+            .await?;
+        match res.price {
+            Some(price) => {
+                DecCoin::try_from(price).map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))
+            }
+            None => Err(CosmosGrpcError::BadResponse("No gas price?".to_string())),
+        }
+    }
+
+    /// Gets the current base gas price of every denom accepted by the feemarket module
+    pub async fn get_feemarket_gas_prices(&self) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let res: GasPricesResponse = self
+            .grpc_unary(
+                "/feemarket.feemarket.v1.Query/GasPrices",
+                GasPricesRequest {},
+            )
+            .await?;
+        dec_coins_from_proto(res.prices)
+    }
+
+    /// Gets the chain wide minimum gas prices of the Gaia globalfee module
+    pub async fn get_global_fee_min_gas_prices(&self) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let res: QueryMinimumGasPricesResponse = self
+            .grpc_unary(
+                "/gaia.globalfee.v1beta1.Query/MinimumGasPrices",
+                QueryMinimumGasPricesRequest {},
+            )
+            .await?;
+        dec_coins_from_proto(res.minimum_gas_prices)
+    }
+
+    /// Gets the gas price of every denom the chain accepts fees in. The feemarket module
+    /// is tried first, then the globalfee module and finally the minimum gas prices of the
+    /// connected node, the first non empty list is returned.
+    pub async fn get_accepted_fee_prices(&self) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let mut last_error = match self.get_feemarket_gas_prices().await {
+            Ok(prices) if !prices.is_empty() => return Ok(prices),
+            Ok(_) => None,
+            Err(e) => Some(e),
+        };
+        match self.get_global_fee_min_gas_prices().await {
+            Ok(prices) if !prices.is_empty() => return Ok(prices),
+            Ok(_) => {}
+            Err(e) => last_error = Some(e),
+        }
+        match self.get_minimum_gas_prices().await {
+            Ok(prices) if !prices.is_empty() => Ok(prices),
+            Ok(_) => Err(last_error.unwrap_or_else(|| {
+                CosmosGrpcError::BadResponse("Chain does not list any fee denoms".to_string())
+            })),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Gets the fee for the provided gas limit in every denom the chain accepts fees in, the
+    /// fee granter of the Contact, if any, pays them
+    pub async fn get_fee_options(&self, gas_limit: u64) -> Result<Vec<FeeOption>, CosmosGrpcError> {
+        let prices = self.get_accepted_fee_prices().await?;
+        let granter = self.fee_with_gas(None, gas_limit).granter;
+        Ok(fee_options(&prices, gas_limit)
+            .into_iter()
+            .map(|mut option| {
+                option.fee.granter = granter.clone();
+                option
+            })
+            .collect())
+    }
+
+    /// Builds the cheapest fee for the provided gas limit among the denoms the chain accepts,
+    /// see cheapest_fee_option for the meaning of unit_values
+    pub async fn get_cheapest_fee(
+        &self,
+        gas_limit: u64,
+        unit_values: &[DecCoin],
+    ) -> Result<Fee, CosmosGrpcError> {
+        let options = self.get_fee_options(gas_limit).await?;
+        match cheapest_fee_option(&options, unit_values) {
+            Some(option) => Ok(option.fee),
+            None => Err(CosmosGrpcError::BadInput(
+                "No value provided for any accepted fee denom".to_string(),
+            )),
+        }
+    }
+
+    /// Builds a fee for the provided gas limit in a denom the chain accepts and the account
+    /// can pay out of its spendable balance, keeping the reserved coins for the messages of
+    /// the transaction. See select_fee_option for how the denom is chosen. If the Contact has
+    /// a fee granter the fee is chosen among those its allowance to the account covers
+    /// instead, see select_granted_fee_option.
+    pub async fn get_affordable_fee(
+        &self,
+        payer: Address,
+        gas_limit: u64,
+        reserved: &[Coin],
+        unit_values: &[DecCoin],
+    ) -> Result<Fee, CosmosGrpcError> {
+        let granter = match self.fee_granter() {
+            Some(granter) => granter,
+            None => {
+                let (options, balance) = futures::try_join!(
+                    self.get_fee_options(gas_limit),
+                    self.get_spendable_balance(payer)
+                )?;
+                return select_fee_option(&options, &balance, reserved, unit_values)
+                    .map(|option| option.fee);
+            }
+        };
+        let (options, grant) = futures::try_join!(
+            self.get_fee_options(gas_limit),
+            self.get_fee_allowance(granter, payer)
+        )?;
+        let not_usable = |error| CosmosGrpcError::FeeGrantNotUsable {
+            granter: granter.to_bech32(&self.inner.chain_prefix).unwrap(),
+            error,
+        };
+        let allowance = match grant.and_then(|grant| grant.allowance) {
+            Some(allowance) => allowance,
+            None => return Err(not_usable(FeeGrantError::NoAllowance)),
+        };
+        select_granted_fee_option(&options, &allowance, unit_values, SystemTime::now())
+            .map(|option| option.fee)
+            .map_err(not_usable)
+    }
+
+    /// Prices fees left without an amount by send_tokens and send_message. Without an auto
+    /// fee, the default, such fees are broadcast as is and only accepted by chains with no
+    /// minimum gas price. The returned Contact does not share its connection with clones of
+    /// this one.
+    pub fn with_auto_fee(self, auto_fee: AutoFee) -> Self {
+        let mut inner = self.unshared();
+        inner.auto_fee = Some(auto_fee);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Sets the amount of a fee without one using the auto fee of the Contact, fees with an
+    /// amount or without an auto fee are returned as is. Reserved are the coins the messages
+    /// of the transaction spend, an affordable fee is chosen from the rest of the balance.
+    pub async fn fill_fee(
+        &self,
+        payer: Address,
+        mut fee: Fee,
+        reserved: &[Coin],
+    ) -> Result<Fee, CosmosGrpcError> {
+        if !fee.amount.is_empty() {
+            return Ok(fee);
+        }
+        let priced = match &self.inner.auto_fee {
+            Some(AutoFee::Oracle { oracle, denom }) => {
+                self.get_fee(oracle.as_ref(), denom, fee.gas_limit).await?
+            }
+            Some(AutoFee::Affordable { unit_values }) => {
+                self.get_affordable_fee(payer, fee.gas_limit, reserved, unit_values)
+                    .await?
+            }
+            None => return Ok(fee),
+        };
+        fee.amount = priced.amount;
+        Ok(fee)
+    }
+
+    /// Builds a fee for the provided gas limit paid in denom, priced by the oracle
+    pub async fn get_fee(
+        &self,
+        oracle: &dyn GasPriceOracle,
+        denom: &str,
+        gas_limit: u64,
+    ) -> Result<Fee, CosmosGrpcError> {
+        let price = oracle.gas_price(self, denom).await?;
+        match price.fee_for_gas(gas_limit) {
+            Some(amount) => Ok(self.fee_with_gas(Some(amount), gas_limit)),
+            None => Err(CosmosGrpcError::BadResponse(format!(
+                "Invalid gas price {}",
+                price
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::Msg;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use std::time::Duration;
+
+    fn dec(value: &str) -> Dec {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_fee_token_tiers() {
+        let token = FeeToken {
+            denom: "uatom".to_string(),
+            fixed_min_gas_price: Some(dec("0.005")),
+            low_gas_price: Some(dec("0.001")),
+            average_gas_price: Some(dec("0.025")),
+            high_gas_price: None,
+        };
+        // low is below the fixed minimum, high is missing and falls back to average
+        assert_eq!(token.gas_price(GasPriceTier::Low), Some(dec("0.005")));
+        assert_eq!(token.gas_price(GasPriceTier::Average), Some(dec("0.025")));
+        assert_eq!(token.gas_price(GasPriceTier::High), Some(dec("0.025")));
+        assert_eq!(FeeToken::default().gas_price(GasPriceTier::High), None);
+    }
+
+    #[test]
+    fn test_percentile_price() {
+        let prices = vec![dec("0.5"), dec("0.1"), dec("0.3"), dec("0.2"), dec("0.4")];
+        assert_eq!(percentile_price(prices.clone(), 0), Some(dec("0.1")));
+        assert_eq!(percentile_price(prices.clone(), 50), Some(dec("0.3")));
+        assert_eq!(percentile_price(prices, 100), Some(dec("0.5")));
+        assert_eq!(percentile_price(Vec::new(), 50), None);
+    }
+
+    #[test]
+    fn test_cheapest_fee_option() {
+        let prices = vec![
+            "0.025uatom".parse().unwrap(),
+            "0.1uosmo".parse().unwrap(),
+            "1uusdc".parse().unwrap(),
+        ];
+        let options = fee_options(&prices, 200_000);
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].fee.amount, vec!["5000uatom".parse().unwrap()]);
+        assert_eq!(options[0].fee.gas_limit, 200_000);
+        assert_eq!(options[1].fee.amount, vec!["20000uosmo".parse().unwrap()]);
+
+        // 5000uatom costs 0.05, 20000uosmo 0.01 and uusdc has no known value
+        let values = vec![
+            "0.00001uatom".parse().unwrap(),
+            "0.0000005uosmo".parse().unwrap(),
+        ];
+        let cheapest = cheapest_fee_option(&options, &values).unwrap();
+        assert_eq!(cheapest.gas_price.denom, "uosmo");
+        assert_eq!(cheapest_fee_option(&options, &[]), None);
+    }
+
+    #[actix_rt::test]
+    async fn test_fill_fee() {
+        let contact =
+            Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos").unwrap();
+        let payer = Address::from_bytes([7; 20], "cosmos").unwrap();
+        let send = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        let empty = contact.fee_with_gas(None, 200_000);
+        // without an auto fee an empty fee is left as is
+        assert_eq!(
+            contact.fill_fee(payer, empty.clone(), &[]).await.unwrap(),
+            empty
+        );
+
+        let oracle = StaticGasPrice::new(vec!["0.025uatom".parse().unwrap()]);
+        let contact = contact.with_auto_fee(AutoFee::Oracle {
+            oracle: Arc::new(oracle),
+            denom: "uatom".to_string(),
+        });
+        let fee = contact.fill_fee(payer, empty, &[]).await.unwrap();
+        assert_eq!(fee.amount, vec!["5000uatom".parse().unwrap()]);
+        assert_eq!(fee.gas_limit, 200_000);
+        // a fee the caller priced is not changed
+        let paid = contact.default_fee(Some("1uatom".parse().unwrap()), &[send]);
+        assert_eq!(
+            contact.fill_fee(payer, paid.clone(), &[]).await.unwrap(),
+            paid
+        );
+    }
+
+    #[test]
+    fn test_select_fee_option() {
+        let prices = vec!["0.025uatom".parse().unwrap(), "0.1uosmo".parse().unwrap()];
+        let options = fee_options(&prices, 200_000);
+        let balance = SpendableBalance::from_spendable(
+            vec!["6000uatom".parse().unwrap(), "30000uosmo".parse().unwrap()],
+            vec!["6000uatom".parse().unwrap(), "30000uosmo".parse().unwrap()],
+        );
+        // both are affordable and there are no values, the first listed denom wins
+        let selected = select_fee_option(&options, &balance, &[], &[]).unwrap();
+        assert_eq!(selected.gas_price.denom, "uatom");
+        // sending 2000uatom leaves too little to pay the 5000uatom fee
+        let reserved = vec!["2000uatom".parse().unwrap()];
+        let selected = select_fee_option(&options, &balance, &reserved, &[]).unwrap();
+        assert_eq!(selected.gas_price.denom, "uosmo");
+
+        let poor = SpendableBalance::from_spendable(
+            vec!["6000uatom".parse().unwrap()],
+            vec!["1000uatom".parse().unwrap()],
+        );
+        match select_fee_option(&options, &poor, &[], &[]) {
+            Err(CosmosGrpcError::InsufficientFunds { funds_info }) => {
+                assert_eq!(funds_info.denom, "uatom");
+                assert_eq!(funds_info.required, 5000u64.into());
+                assert_eq!(funds_info.available, 1000u64.into());
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_select_granted_fee_option() {
+        use crate::client::feegrant::FeeAllowance;
+
+        let prices = vec!["0.025uatom".parse().unwrap(), "0.1uosmo".parse().unwrap()];
+        let options = fee_options(&prices, 200_000);
+        let now = SystemTime::now();
+        // the granter only pays in uosmo, the balance of the signer does not matter
+        let allowance = FeeAllowance::Basic {
+            spend_limit: vec!["30000uosmo".parse().unwrap()],
+            expiration: None,
+        }
+        .to_any()
+        .unwrap();
+        let selected = select_granted_fee_option(&options, &allowance, &[], now).unwrap();
+        assert_eq!(selected.gas_price.denom, "uosmo");
+
+        let small = FeeAllowance::Basic {
+            spend_limit: vec!["1000uatom".parse().unwrap()],
+            expiration: None,
+        }
+        .to_any()
+        .unwrap();
+        assert!(matches!(
+            select_granted_fee_option(&options, &small, &[], now),
+            Err(FeeGrantError::SpendLimitExceeded { .. })
+        ));
+    }
+}
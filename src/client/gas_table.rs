@@ -0,0 +1,279 @@
+//! Default gas limits per message type, used by the transaction helpers of Contact and by
+//! callers that can not or do not want to simulate. The gas of a transaction is a base
+//! amount for signature checks and fee deduction plus the gas of each message, messages
+//! that are not in the table are given the fallback amount.
+//!
+//! The default table keeps the gas limits the helpers used before the table existed, 500_000
+//! for most single message transactions. GasTable::tuned has tighter limits per message,
+//! for example 150_000 instead of 500_000 for a bank send, and must be opted into.
+//!
+//! ```ignore
+//! let table = GasTable::tuned()
+//!     .set(MSG_EXECUTE_CONTRACT_TYPE_URL, 1_200_000)
+//!     .set("/osmosis.gamm.v1beta1.MsgSwapExactAmountIn", 300_000);
+//! let contact = contact.with_gas_table(table);
+//! ```
+
+use crate::client::authz::MSG_EXEC_TYPE_URL;
+use crate::client::authz::MSG_GRANT_TYPE_URL;
+use crate::client::authz::MSG_REVOKE_TYPE_URL;
+use crate::client::bank::MSG_MULTI_SEND_TYPE_URL;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::crisis::MSG_VERIFY_INVARIANT_TYPE_URL;
+use crate::client::distribution::GAS_PER_WITHDRAW;
+use crate::client::distribution::MSG_FUND_COMMUNITY_POOL_TYPE_URL;
+use crate::client::distribution::MSG_SET_WITHDRAW_ADDRESS_TYPE_URL;
+use crate::client::distribution::MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL;
+use crate::client::distribution::MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL;
+use crate::client::gov::MSG_DEPOSIT_TYPE_URL;
+use crate::client::gov::MSG_SUBMIT_PROPOSAL_TYPE_URL;
+use crate::client::gov::MSG_VOTE_TYPE_URL;
+use crate::client::gov::MSG_VOTE_WEIGHTED_TYPE_URL;
+use crate::client::ibc::MSG_PAY_PACKET_FEE_ASYNC_TYPE_URL;
+use crate::client::ibc::MSG_PAY_PACKET_FEE_TYPE_URL;
+use crate::client::ibc::MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL;
+use crate::client::ibc::MSG_TRANSFER_TYPE_URL;
+use crate::client::ibc::MSG_UPDATE_CLIENT_TYPE_URL;
+use crate::client::ibc::MSG_UPGRADE_CLIENT_TYPE_URL;
+use crate::client::staking::MSG_BEGIN_REDELEGATE_TYPE_URL;
+use crate::client::staking::MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL;
+use crate::client::staking::MSG_DELEGATE_TYPE_URL;
+use crate::client::staking::MSG_UNDELEGATE_TYPE_URL;
+use crate::client::staking::MSG_UNJAIL_TYPE_URL;
+use crate::client::vesting::MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL;
+use crate::client::vesting::MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL;
+use crate::client::vesting::MSG_CREATE_VESTING_ACCOUNT_TYPE_URL;
+#[cfg(feature = "cosmwasm")]
+use crate::client::wasm::MSG_CLEAR_ADMIN_TYPE_URL;
+#[cfg(feature = "cosmwasm")]
+use crate::client::wasm::MSG_EXECUTE_CONTRACT_TYPE_URL;
+#[cfg(feature = "cosmwasm")]
+use crate::client::wasm::MSG_INSTANTIATE_CONTRACT_TYPE_URL;
+#[cfg(feature = "cosmwasm")]
+use crate::client::wasm::MSG_MIGRATE_CONTRACT_TYPE_URL;
+#[cfg(feature = "cosmwasm")]
+use crate::client::wasm::MSG_UPDATE_ADMIN_TYPE_URL;
+use crate::client::Contact;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The gas of a transaction before its messages, see GasTable::base
+pub const DEFAULT_BASE_GAS: u64 = 50_000;
+/// The gas of a message that is not in the table, the single default used before the table
+pub const DEFAULT_FALLBACK_GAS: u64 = 500_000;
+
+/// Gas limits per message type, see the module documentation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasTable {
+    base: u64,
+    fallback: u64,
+    gas: HashMap<String, u64>,
+}
+
+impl Default for GasTable {
+    /// The limits the transaction helpers used before the table, no base amount and the
+    /// fallback for every message the helpers did not give a different limit. A single
+    /// reward withdrawal uses the 150_000 per message of withdraw_all_rewards.
+    fn default() -> Self {
+        GasTable::empty()
+            .base(0)
+            .set(MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL, GAS_PER_WITHDRAW)
+            .set(MSG_CREATE_VESTING_ACCOUNT_TYPE_URL, 150_000)
+            .set(MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL, 150_000)
+            .set(MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL, 150_000)
+            .set(MSG_PAY_PACKET_FEE_ASYNC_TYPE_URL, 300_000)
+            .set(MSG_UPDATE_CLIENT_TYPE_URL, 1_000_000)
+            .set(MSG_UPGRADE_CLIENT_TYPE_URL, 1_000_000)
+            .set(MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL, 1_000_000)
+            .set(MSG_VERIFY_INVARIANT_TYPE_URL, 10_000_000)
+    }
+}
+
+#[cfg(feature = "cosmwasm")]
+fn wasm_defaults(table: GasTable) -> GasTable {
+    table
+        .set(MSG_EXECUTE_CONTRACT_TYPE_URL, 400_000)
+        .set(MSG_INSTANTIATE_CONTRACT_TYPE_URL, 500_000)
+        .set(MSG_MIGRATE_CONTRACT_TYPE_URL, 500_000)
+        .set(MSG_UPDATE_ADMIN_TYPE_URL, 150_000)
+        .set(MSG_CLEAR_ADMIN_TYPE_URL, 150_000)
+}
+
+#[cfg(not(feature = "cosmwasm"))]
+fn wasm_defaults(table: GasTable) -> GasTable {
+    table
+}
+
+impl GasTable {
+    /// Limits per message type with some headroom over what the messages use on a Cosmos
+    /// SDK chain so that they do not run out of gas as state grows. Most are well below the
+    /// default table, which lowers the fees of callers that price fees by gas.
+    pub fn tuned() -> Self {
+        wasm_defaults(
+            GasTable::empty()
+                .set(MSG_SEND_TYPE_URL, 100_000)
+                .set(MSG_MULTI_SEND_TYPE_URL, 200_000)
+                .set(MSG_DELEGATE_TYPE_URL, 250_000)
+                .set(MSG_UNDELEGATE_TYPE_URL, 300_000)
+                .set(MSG_BEGIN_REDELEGATE_TYPE_URL, 350_000)
+                .set(MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL, 250_000)
+                .set(MSG_UNJAIL_TYPE_URL, 150_000)
+                .set(MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL, GAS_PER_WITHDRAW)
+                .set(MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL, 150_000)
+                .set(MSG_SET_WITHDRAW_ADDRESS_TYPE_URL, 100_000)
+                .set(MSG_FUND_COMMUNITY_POOL_TYPE_URL, 100_000)
+                .set(MSG_VOTE_TYPE_URL, 100_000)
+                .set(MSG_VOTE_WEIGHTED_TYPE_URL, 100_000)
+                .set(MSG_DEPOSIT_TYPE_URL, 150_000)
+                .set(MSG_SUBMIT_PROPOSAL_TYPE_URL, 300_000)
+                .set(MSG_TRANSFER_TYPE_URL, 200_000)
+                .set(MSG_GRANT_TYPE_URL, 150_000)
+                .set(MSG_REVOKE_TYPE_URL, 100_000)
+                .set(MSG_EXEC_TYPE_URL, 300_000)
+                .set(MSG_CREATE_VESTING_ACCOUNT_TYPE_URL, 150_000)
+                .set(MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL, 150_000)
+                .set(MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL, 150_000)
+                .set(MSG_PAY_PACKET_FEE_TYPE_URL, 300_000)
+                .set(MSG_PAY_PACKET_FEE_ASYNC_TYPE_URL, 300_000)
+                .set(MSG_UPDATE_CLIENT_TYPE_URL, 1_000_000)
+                .set(MSG_UPGRADE_CLIENT_TYPE_URL, 1_000_000)
+                .set(MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL, 1_000_000)
+                // invariant checks iterate over the state of a module
+                .set(MSG_VERIFY_INVARIANT_TYPE_URL, 10_000_000),
+        )
+    }
+
+    /// A table without any message types, every message is given the fallback gas
+    pub fn empty() -> Self {
+        GasTable {
+            base: DEFAULT_BASE_GAS,
+            fallback: DEFAULT_FALLBACK_GAS,
+            gas: HashMap::new(),
+        }
+    }
+
+    /// Sets the gas of a message type, replacing the built in amount
+    pub fn set(mut self, type_url: impl Into<String>, gas: u64) -> Self {
+        self.gas.insert(type_url.into(), gas);
+        self
+    }
+
+    /// Sets the gas added once per transaction
+    pub fn base(mut self, gas: u64) -> Self {
+        self.base = gas;
+        self
+    }
+
+    /// Sets the gas of message types that are not in the table
+    pub fn fallback(mut self, gas: u64) -> Self {
+        self.fallback = gas;
+        self
+    }
+
+    /// The gas of one message of the type, not including the base
+    pub fn gas_for(&self, type_url: &str) -> u64 {
+        self.gas.get(type_url).copied().unwrap_or(self.fallback)
+    }
+
+    /// The gas limit of a transaction containing the messages
+    pub fn gas_limit(&self, messages: &[Msg]) -> u64 {
+        messages
+            .iter()
+            .fold(self.base, |total, msg| total + self.gas_for(msg.type_url()))
+    }
+}
+
+impl Contact {
+    /// Replaces the gas table used by the transaction helpers of this Contact and its
+    /// clones. The returned Contact does not share its connection with clones of this one.
+    pub fn with_gas_table(self, table: GasTable) -> Self {
+        let mut inner = self.unshared();
+        inner.gas_table = Arc::new(table);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    pub fn gas_table(&self) -> &GasTable {
+        &self.inner.gas_table
+    }
+
+    /// A fee paying the provided amount with the gas limit of the gas table for the
+    /// messages. A fee without an amount is priced when it is sent if the Contact has an auto
+    /// fee, see with_auto_fee. The fee granter of the Contact, if any, pays it.
+    pub fn default_fee(&self, amount: Option<Coin>, messages: &[Msg]) -> Fee {
+        self.fee_with_gas(amount, self.inner.gas_table.gas_limit(messages))
+    }
+
+    /// A fee paying the provided amount with the provided gas limit, for messages whose gas
+    /// depends on more than their type. The fee granter of the Contact, if any, pays it.
+    pub fn fee_with_gas(&self, amount: Option<Coin>, gas_limit: u64) -> Fee {
+        Fee {
+            amount: amount.into_iter().collect(),
+            gas_limit,
+            granter: self
+                .inner
+                .fee_granter
+                .map(|granter| granter.to_bech32(&self.inner.chain_prefix).unwrap()),
+            payer: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use std::time::Duration;
+
+    #[test]
+    fn test_fee_granter() {
+        let contact =
+            Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos").unwrap();
+        let send = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        let fee = contact.default_fee(Some("100uatom".parse().unwrap()), &[send.clone()]);
+        assert_eq!(fee.granter, None);
+
+        let granter = Address::from_bytes([7; 20], "cosmos").unwrap();
+        let contact = contact.with_fee_granter(granter);
+        let fee = contact.default_fee(Some("100uatom".parse().unwrap()), &[send]);
+        assert_eq!(fee.granter, Some(granter.to_string()));
+        assert_eq!(fee.gas_limit, 500_000);
+        let fee = contact.fee_with_gas(None, 80_000);
+        assert_eq!(fee.granter, Some(granter.to_string()));
+        assert!(fee.amount.is_empty());
+    }
+
+    #[test]
+    fn test_gas_table() {
+        let send = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        let unknown = Msg::new(
+            "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn",
+            MsgSend::default(),
+        );
+        // the limits of the helpers before the table
+        let table = GasTable::default();
+        assert_eq!(table.gas_limit(&[send.clone()]), 500_000);
+        assert_eq!(table.gas_limit(&[unknown.clone()]), DEFAULT_FALLBACK_GAS);
+        assert_eq!(table.gas_for(MSG_VERIFY_INVARIANT_TYPE_URL), 10_000_000);
+
+        let table = GasTable::tuned();
+        assert_eq!(table.gas_limit(&[send.clone()]), 150_000);
+        assert_eq!(table.gas_limit(&[send.clone(), send.clone()]), 250_000);
+        assert_eq!(
+            table.gas_limit(&[unknown.clone()]),
+            DEFAULT_BASE_GAS + DEFAULT_FALLBACK_GAS
+        );
+
+        let table = table
+            .set("/osmosis.gamm.v1beta1.MsgSwapExactAmountIn", 250_000)
+            .set(MSG_SEND_TYPE_URL, 80_000)
+            .base(20_000);
+        assert_eq!(table.gas_limit(&[send, unknown]), 350_000);
+        assert_eq!(GasTable::empty().gas_for(MSG_TRANSFER_TYPE_URL), 500_000);
+    }
+}
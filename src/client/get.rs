@@ -1,39 +1,116 @@
+use crate::client::paginate::paginate_by_offset;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::client::runtime::sleep;
 use crate::client::types::*;
 use crate::coin::Coin;
 use crate::coin::Fee;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxsEventRequest;
+use crate::proto::cosmos::tx::v1beta1::GetTxsEventResponse;
 use crate::{address::Address, private_key::MessageArgs};
 use crate::{client::Contact, error::CosmosGrpcError};
-use bytes::BytesMut;
-use cosmos_sdk_proto::cosmos::auth::v1beta1::{
-    query_client::QueryClient as AuthQueryClient, BaseAccount, QueryAccountRequest,
-};
-use cosmos_sdk_proto::cosmos::bank::v1beta1::query_client::QueryClient as BankQueryClient;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
 use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryAllBalancesRequest;
-use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient as TendermintServiceClient;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryAllBalancesResponse;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceRequest;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceResponse;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::QuerySupplyOfRequest;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::QuerySupplyOfResponse;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
 use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestBlockRequest;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestBlockResponse;
 use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetSyncingRequest;
-use cosmos_sdk_proto::cosmos::tx::v1beta1::service_client::ServiceClient as TxServiceClient;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetSyncingResponse;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest;
-use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse;
-use prost::Message;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
 use std::time::Duration;
 use std::time::Instant;
-use tokio::time::sleep;
-use tonic::Code as GrpcCode;
+
+/// The balance of a balance query, None if it is zero
+fn nonzero_balance(res: QueryBalanceResponse) -> Result<Option<Coin>, CosmosGrpcError> {
+    match res.balance {
+        Some(coin) => {
+            let coin = Coin::from_proto(coin).map_err(CosmosGrpcError::BadResponse)?;
+            if coin.amount == 0u64.into() {
+                Ok(None)
+            } else {
+                Ok(Some(coin))
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Builds a GetTxsEvent request for the transactions matching all of the events. Cosmos SDK
+/// v0.50 only reads the events from the query string and pages with page and limit, the
+/// offset and limit of the pagination are converted to those.
+#[cfg(feature = "sdk-0_50")]
+pub(crate) fn txs_event_request(
+    events: Vec<String>,
+    pagination: Option<PageRequest>,
+) -> GetTxsEventRequest {
+    let (page, limit) = match pagination {
+        Some(p) if p.limit > 0 => (p.offset / p.limit + 1, p.limit),
+        _ => (0, 0),
+    };
+    GetTxsEventRequest {
+        query: events.join(" AND "),
+        page,
+        limit,
+        ..GetTxsEventRequest::default()
+    }
+}
+
+/// Builds a GetTxsEvent request for the transactions matching all of the events
+#[cfg(not(feature = "sdk-0_50"))]
+pub(crate) fn txs_event_request(
+    events: Vec<String>,
+    pagination: Option<PageRequest>,
+) -> GetTxsEventRequest {
+    GetTxsEventRequest { events, pagination }
+}
+
+/// The number of transactions matching a search, Cosmos SDK v0.50 no longer returns the
+/// pagination
+#[cfg(feature = "sdk-0_50")]
+fn txs_event_total(res: &GetTxsEventResponse) -> u64 {
+    match &res.pagination {
+        Some(pagination) if res.total == 0 => pagination.total,
+        _ => res.total,
+    }
+}
+
+/// The number of transactions matching a search
+#[cfg(not(feature = "sdk-0_50"))]
+fn txs_event_total(res: &GetTxsEventResponse) -> u64 {
+    res.pagination.as_ref().map(|p| p.total).unwrap_or_default()
+}
 
 impl Contact {
     /// Gets the current chain status, returns an enum taking into account the various possible states
     /// of the chain and the requesting full node. In the common case this provides the block number
     pub async fn get_chain_status(&self) -> Result<ChainStatus, CosmosGrpcError> {
-        let mut grpc = TendermintServiceClient::connect(self.url.clone()).await?;
-        let syncing = grpc.get_syncing(GetSyncingRequest {}).await?.into_inner();
+        let syncing: GetSyncingResponse = self
+            .grpc_unary(
+                "/cosmos.base.tendermint.v1beta1.Service/GetSyncing",
+                GetSyncingRequest {},
+            )
+            .await?;
 
         if syncing.syncing {
             Ok(ChainStatus::Syncing)
         } else {
-            let block = grpc.get_latest_block(GetLatestBlockRequest {}).await;
+            let block: Result<GetLatestBlockResponse, CosmosGrpcError> = self
+                .grpc_unary(
+                    "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+                    GetLatestBlockRequest {},
+                )
+                .await;
             match block {
-                Ok(block) => match block.into_inner().block {
+                Ok(block) => match block.block {
                     Some(block) => match block.last_commit {
                         // for some reason the block height can be negative, we cast it to a u64 for the sake
                         // of logical bounds checking
@@ -48,13 +125,12 @@ impl Contact {
                 },
                 // if get syncing succeeded and this fails, it means there's 'no block' and
                 // we're waiting to start
-                Err(e) => {
-                    if e.message().contains("nil Block") {
-                        Ok(ChainStatus::WaitingToStart)
-                    } else {
-                        Err(e.into())
-                    }
+                Err(CosmosGrpcError::RequestError { error, .. })
+                    if error.message().contains("nil Block") =>
+                {
+                    Ok(ChainStatus::WaitingToStart)
                 }
+                Err(e) => Err(e),
             }
         }
     }
@@ -62,16 +138,21 @@ impl Contact {
     /// Gets the latest block from the node, taking into account the possibility that the chain is halted
     /// and also the possibility that the node is syncing
     pub async fn get_latest_block(&self) -> Result<LatestBlock, CosmosGrpcError> {
-        let mut grpc = TendermintServiceClient::connect(self.url.clone()).await?;
-        let syncing = grpc
-            .get_syncing(GetSyncingRequest {})
-            .await?
-            .into_inner()
-            .syncing;
-
-        let block = grpc.get_latest_block(GetLatestBlockRequest {}).await?;
-        let block = block.into_inner().block;
-        match block {
+        let syncing: GetSyncingResponse = self
+            .grpc_unary(
+                "/cosmos.base.tendermint.v1beta1.Service/GetSyncing",
+                GetSyncingRequest {},
+            )
+            .await?;
+        let syncing = syncing.syncing;
+
+        let block: GetLatestBlockResponse = self
+            .grpc_unary(
+                "/cosmos.base.tendermint.v1beta1.Service/GetLatestBlock",
+                GetLatestBlockRequest {},
+            )
+            .await?;
+        match block.block {
             Some(block) => {
                 if syncing {
                     Ok(LatestBlock::Syncing { block })
@@ -85,60 +166,130 @@ impl Contact {
 
     /// Gets account info for the provided Cosmos account using the accounts endpoint
     /// accounts do not have any info if they have no tokens or are otherwise never seen
-    /// before in this case we return the special error NoToken
+    /// before in this case we return the special error AccountNotFound. Vesting, module and
+    /// Ethermint accounts are supported, see get_account for the full account.
     pub async fn get_account_info(&self, address: Address) -> Result<BaseAccount, CosmosGrpcError> {
-        let mut agrpc = AuthQueryClient::connect(self.url.clone()).await?;
-        let res = agrpc
-            // todo detect chain prefix here
-            .account(QueryAccountRequest {
-                address: address.to_bech32(&self.chain_prefix).unwrap(),
-            })
-            .await;
-        match res {
-            Ok(account) => {
-                // null pointer if this fails to unwrap
-                let value = account.into_inner().account.unwrap();
-                let mut buf = BytesMut::with_capacity(value.value.len());
-                buf.extend_from_slice(&value.value);
-                let decoded: BaseAccount = BaseAccount::decode(buf)?;
-                Ok(decoded)
-            }
-            Err(e) => match e.code() {
-                GrpcCode::NotFound => Err(CosmosGrpcError::NoToken),
-                _ => Err(CosmosGrpcError::RequestError { error: e }),
-            },
+        let account = self.get_account(address).await?;
+        match account.base_account() {
+            Some(base) => Ok(base.clone()),
+            None => Err(CosmosGrpcError::BadResponse(
+                "Account has no base account?".to_string(),
+            )),
         }
     }
 
     // Gets a transaction using it's hash value, TODO should fail if the transaction isn't found
     pub async fn get_tx_by_hash(&self, txhash: String) -> Result<GetTxResponse, CosmosGrpcError> {
-        let mut txrpc = TxServiceClient::connect(self.url.clone()).await?;
-        let res = txrpc
-            .get_tx(GetTxRequest { hash: txhash })
-            .await?
-            .into_inner();
-        Ok(res)
+        self.grpc_unary(
+            "/cosmos.tx.v1beta1.Service/GetTx",
+            GetTxRequest { hash: txhash },
+        )
+        .await
+    }
+
+    /// Searches for the transactions matching all of the provided events, see
+    /// txs_event_request
+    pub(crate) async fn get_txs_event(
+        &self,
+        events: Vec<String>,
+        pagination: Option<PageRequest>,
+    ) -> Result<GetTxsEventResponse, CosmosGrpcError> {
+        self.grpc_unary(
+            "/cosmos.tx.v1beta1.Service/GetTxsEvent",
+            txs_event_request(events, pagination),
+        )
+        .await
+    }
+
+    /// Streams every transaction matching all of the provided events, for example
+    /// `message.sender='cosmos1...'`. Pages are fetched as the stream is consumed.
+    pub fn get_txs_by_events_stream(
+        &self,
+        events: Vec<String>,
+    ) -> impl Stream<Item = Result<TxResponse, CosmosGrpcError>> + '_ {
+        paginate_by_offset(DEFAULT_PAGE_SIZE, move |pagination| {
+            let events = events.clone();
+            async move {
+                let res = self.get_txs_event(events, Some(pagination)).await?;
+                let total = txs_event_total(&res);
+                Ok((res.tx_responses, total))
+            }
+        })
     }
 
     pub async fn get_balances(&self, address: Address) -> Result<Vec<Coin>, CosmosGrpcError> {
-        let mut bankrpc = BankQueryClient::connect(self.url.clone()).await?;
-        let res = bankrpc
-            .all_balances(QueryAllBalancesRequest {
-                // chain prefix is validated as part of this client, so this can't
-                // panic
-                address: address.to_bech32(&self.chain_prefix).unwrap(),
-                pagination: None,
-            })
-            .await?
-            .into_inner();
+        let res: QueryAllBalancesResponse = self
+            .grpc_unary(
+                "/cosmos.bank.v1beta1.Query/AllBalances",
+                QueryAllBalancesRequest {
+                    // chain prefix is validated as part of this client, so this can't
+                    // panic
+                    address: address.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    pagination: None,
+                },
+            )
+            .await?;
         let balances = res.balances;
         let mut ret = Vec::new();
         for value in balances {
-            ret.push(value.into());
+            ret.push(Coin::from_proto(value).map_err(CosmosGrpcError::BadResponse)?);
         }
         Ok(ret)
     }
 
+    /// Gets the balance of a single denom for an address, returns None if the address
+    /// holds none of the denom
+    pub async fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> Result<Option<Coin>, CosmosGrpcError> {
+        let res: QueryBalanceResponse = self
+            .grpc_unary(
+                "/cosmos.bank.v1beta1.Query/Balance",
+                QueryBalanceRequest {
+                    address: address.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    denom,
+                },
+            )
+            .await?;
+        nonzero_balance(res)
+    }
+
+    /// Gets the balance of a single denom for many addresses, returning the results in the
+    /// same order as the addresses. At most concurrency queries are in flight at once and
+    /// they share a single connection, so scanning thousands of addresses does not open
+    /// thousands of connections to the node. A failed query only fails its own entry.
+    pub async fn get_balances_many(
+        &self,
+        addresses: &[Address],
+        denom: &str,
+        concurrency: usize,
+    ) -> Result<Vec<Result<Option<Coin>, CosmosGrpcError>>, CosmosGrpcError> {
+        // every query goes through the shared connection of the Contact
+        let queries = addresses
+            .iter()
+            .map(|address| self.get_balance(*address, denom.to_string()));
+        Ok(stream::iter(queries)
+            .buffered(concurrency.max(1))
+            .collect()
+            .await)
+    }
+
+    /// Gets the total supply of a denom across all accounts
+    pub async fn get_supply_of(&self, denom: String) -> Result<Coin, CosmosGrpcError> {
+        let res: QuerySupplyOfResponse = self
+            .grpc_unary(
+                "/cosmos.bank.v1beta1.Query/SupplyOf",
+                QuerySupplyOfRequest { denom },
+            )
+            .await?;
+        match res.amount {
+            Some(coin) => Coin::from_proto(coin).map_err(CosmosGrpcError::BadResponse),
+            None => Err(CosmosGrpcError::BadResponse("No supply?".to_string())),
+        }
+    }
+
     /// Grabs an up to date MessageArgs structure for an address,
     /// provided a fee value to insert into the structure. The goal of
     /// this function is to be very minimal and make a lot of choices for
@@ -149,6 +300,15 @@ impl Contact {
         fee: Fee,
     ) -> Result<MessageArgs, CosmosGrpcError> {
         let account_info = self.get_account_info(our_address).await?;
+        if let Some(block) = self.cached_block() {
+            return Ok(MessageArgs {
+                sequence: account_info.sequence,
+                account_number: account_info.account_number,
+                chain_id: block.chain_id,
+                fee,
+                timeout_height: block.height + 100,
+            });
+        }
         let latest_block = self.get_latest_block().await?;
 
         match latest_block {
@@ -173,22 +333,22 @@ impl Contact {
     }
 
     /// Waits for the next block to be produced, useful if you want to wait for
-    /// an on chain event or some thing to change
+    /// an on chain event or some thing to change. Uses the block watcher if one is running.
     pub async fn wait_for_next_block(&self, timeout: Duration) -> Result<(), CosmosGrpcError> {
         let start = Instant::now();
         let mut last_height = None;
         while Instant::now() - start < timeout {
-            match (self.get_chain_status().await, last_height) {
-                (Ok(ChainStatus::Moving { block_height }), None) => {
-                    last_height = Some(block_height)
-                }
-                (Ok(ChainStatus::Moving { block_height }), Some(last_height)) => {
+            match (self.latest_height().await, last_height) {
+                (Ok(block_height), None) => last_height = Some(block_height),
+                (Ok(block_height), Some(last_height)) => {
                     if block_height > last_height {
                         return Ok(());
                     }
                 }
-                (Ok(ChainStatus::Syncing), _) => return Err(CosmosGrpcError::NodeNotSynced),
-                (Ok(ChainStatus::WaitingToStart), _) => {
+                (Err(CosmosGrpcError::NodeNotSynced), _) => {
+                    return Err(CosmosGrpcError::NodeNotSynced)
+                }
+                (Err(CosmosGrpcError::ChainNotRunning), _) => {
                     return Err(CosmosGrpcError::ChainNotRunning)
                 }
                 // we don't want a single error to exit this loop early
@@ -199,3 +359,106 @@ impl Contact {
         Err(CosmosGrpcError::NoBlockProduced { time: timeout })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse;
+    use prost::Message;
+
+    #[test]
+    fn test_txs_event_request() {
+        let events = vec![
+            "message.sender='cosmos1from'".to_string(),
+            "tx.height=5".to_string(),
+        ];
+        let pagination = PageRequest {
+            offset: 200,
+            limit: 100,
+            ..PageRequest::default()
+        };
+        let mut bytes = Vec::new();
+        txs_event_request(events.clone(), Some(pagination.clone()))
+            .encode(&mut bytes)
+            .unwrap();
+        let request = GetTxsEventRequest::decode(bytes.as_slice()).unwrap();
+
+        #[cfg(feature = "sdk-0_50")]
+        {
+            // v0.50 nodes reject requests without a query
+            assert_eq!(
+                request.query,
+                "message.sender='cosmos1from' AND tx.height=5"
+            );
+            assert_eq!((request.page, request.limit), (3, 100));
+            assert!(request.events.is_empty());
+            assert_eq!(request.pagination, None);
+        }
+        #[cfg(not(feature = "sdk-0_50"))]
+        {
+            assert_eq!(request.events, events);
+            assert_eq!(request.pagination, Some(pagination));
+        }
+    }
+
+    fn encode(msg: impl Message) -> Vec<u8> {
+        let mut buf = Vec::new();
+        msg.encode(&mut buf).unwrap();
+        buf
+    }
+
+    #[actix_rt::test]
+    async fn test_get_txs_by_events_stream() {
+        let events = vec!["message.sender='cosmos1from'".to_string()];
+        let file = std::env::temp_dir().join(format!(
+            "deep_space_txs_stream_{}.json",
+            rand::random::<u64>()
+        ));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        // 150 matching transactions, the node never returns a next key
+        for offset in [0u64, 100].iter() {
+            let request = txs_event_request(
+                events.clone(),
+                Some(PageRequest {
+                    offset: *offset,
+                    limit: DEFAULT_PAGE_SIZE,
+                    count_total: true,
+                    ..PageRequest::default()
+                }),
+            );
+            let count = if *offset == 0 { 100 } else { 50 };
+            let response = GetTxsEventResponse {
+                tx_responses: (0..count)
+                    .map(|i| TxResponse {
+                        txhash: (offset + i).to_string(),
+                        ..TxResponse::default()
+                    })
+                    .collect(),
+                pagination: Some(PageResponse {
+                    next_key: Vec::new(),
+                    total: 150,
+                }),
+                ..GetTxsEventResponse::default()
+            };
+            tape.save(
+                "/cosmos.tx.v1beta1.Service/GetTxsEvent",
+                &encode(request),
+                Ok(&encode(response)),
+            )
+            .unwrap();
+        }
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let txs: Vec<TxResponse> = contact
+            .get_txs_by_events_stream(events)
+            .map(|tx| tx.unwrap())
+            .collect()
+            .await;
+        assert_eq!(txs.len(), 150);
+        assert_eq!(txs[149].txhash, "149");
+    }
+}
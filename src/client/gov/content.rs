@@ -0,0 +1,86 @@
+//! Helpers for building the content of content based (v1beta1) governance proposals, the
+//! returned Any can be passed to create_gov_proposal() or wrapped with legacy_content_msg()
+//! for submission through the v1 governance module
+
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::CommunityPoolSpendProposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::TextProposal;
+use cosmos_sdk_proto::cosmos::params::v1beta1::ParamChange;
+use cosmos_sdk_proto::cosmos::params::v1beta1::ParameterChangeProposal;
+use cosmos_sdk_proto::cosmos::upgrade::v1beta1::Plan;
+use cosmos_sdk_proto::cosmos::upgrade::v1beta1::SoftwareUpgradeProposal;
+use prost_types::Any;
+
+pub const TEXT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1beta1.TextProposal";
+pub const PARAMETER_CHANGE_PROPOSAL_TYPE_URL: &str =
+    "/cosmos.params.v1beta1.ParameterChangeProposal";
+pub const SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL: &str =
+    "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal";
+pub const COMMUNITY_POOL_SPEND_PROPOSAL_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal";
+
+/// A proposal with no on chain effect, used for signaling
+pub fn text_proposal(title: impl Into<String>, description: impl Into<String>) -> Any {
+    let proposal = TextProposal {
+        title: title.into(),
+        description: description.into(),
+    };
+    encode_any(proposal, TEXT_PROPOSAL_TYPE_URL.to_string())
+}
+
+/// A proposal that changes one or more module parameters, each change value must be
+/// the JSON encoding of the new parameter value
+pub fn param_change_proposal(
+    title: impl Into<String>,
+    description: impl Into<String>,
+    changes: Vec<ParamChange>,
+) -> Any {
+    let proposal = ParameterChangeProposal {
+        title: title.into(),
+        description: description.into(),
+        changes,
+    };
+    encode_any(proposal, PARAMETER_CHANGE_PROPOSAL_TYPE_URL.to_string())
+}
+
+/// A proposal that halts the chain at the provided height so that it can be upgraded to
+/// the binary handling the upgrade with the provided name, info is typically a JSON object
+/// containing binary download links
+pub fn software_upgrade_proposal(
+    title: impl Into<String>,
+    description: impl Into<String>,
+    name: impl Into<String>,
+    height: u64,
+    info: impl Into<String>,
+) -> Any {
+    let proposal = SoftwareUpgradeProposal {
+        title: title.into(),
+        description: description.into(),
+        plan: Some(Plan {
+            name: name.into(),
+            time: None,
+            height: height as i64,
+            info: info.into(),
+            upgraded_client_state: None,
+        }),
+    };
+    encode_any(proposal, SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL.to_string())
+}
+
+/// A proposal that sends the provided amount from the community pool to the recipient
+pub fn community_pool_spend_proposal(
+    title: impl Into<String>,
+    description: impl Into<String>,
+    recipient: Address,
+    amount: Vec<Coin>,
+) -> Any {
+    let proposal = CommunityPoolSpendProposal {
+        title: title.into(),
+        description: description.into(),
+        recipient: recipient.to_string(),
+        amount: amount.into_iter().map(|c| c.into()).collect(),
+    };
+    encode_any(proposal, COMMUNITY_POOL_SPEND_PROPOSAL_TYPE_URL.to_string())
+}
@@ -1,33 +1,212 @@
 //! Contains utility functions for interacting with and modifying Cosmos validator staking status
 
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
 use crate::client::MEMO;
+use crate::decimal::Decimal;
 use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::gov::v1::MsgExecLegacyContent;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::gov::v1::MsgSubmitProposal as MsgSubmitProposalV1;
+use crate::proto::cosmos::gov::v1beta1::MsgVoteWeighted;
+use crate::proto::cosmos::gov::v1beta1::WeightedVoteOption;
+use crate::Address;
 use crate::Coin;
 use crate::Contact;
-use crate::Fee;
 use crate::Msg;
 use crate::PrivateKey;
-use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
-use cosmos_sdk_proto::cosmos::gov::v1beta1::query_client::QueryClient as GovQueryClient;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::Proposal;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalsRequest;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalsResponse;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use futures::Stream;
 use prost_types::Any;
+use sha2::{Digest, Sha256};
 use std::time::Duration;
 
+mod content;
+mod watcher;
+
+pub use content::*;
+pub use watcher::*;
+
+pub const MSG_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposal";
+pub const MSG_SUBMIT_PROPOSAL_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgSubmitProposal";
+pub const MSG_EXEC_LEGACY_CONTENT_TYPE_URL: &str = "/cosmos.gov.v1.MsgExecLegacyContent";
+pub const MSG_DEPOSIT_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgDeposit";
+pub const MSG_VOTE_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgVote";
+pub const MSG_VOTE_WEIGHTED_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgVoteWeighted";
+
+/// Returns the address of the governance module account, this is the authority for
+/// messages executed by v1 governance proposals
+pub fn gov_module_address(prefix: &str) -> Address {
+    let hash = Sha256::digest(b"gov");
+    // the prefix is validated by the caller and the slice is always 20 bytes
+    Address::from_slice(&hash[0..20], prefix).unwrap()
+}
+
+/// Builds a content based v1beta1 MsgSubmitProposal, see the content module for helpers
+/// to build common proposal types
+pub fn submit_proposal_msg(proposer: Address, content: Any, deposit: Vec<Coin>) -> Msg {
+    let proposal = MsgSubmitProposal {
+        proposer: proposer.to_string(),
+        content: Some(content),
+        initial_deposit: deposit.into_iter().map(|c| c.into()).collect(),
+    };
+    Msg::new(MSG_SUBMIT_PROPOSAL_TYPE_URL, proposal)
+}
+
+/// Builds a message based v1 MsgSubmitProposal, the messages will be executed by the
+/// governance module if the proposal passes so their authority or signer must be the
+/// gov_module_address(). Title and summary were added in Cosmos SDK v0.47, v0.46 chains
+/// reject them so they are left out unless the sdk-0_47 feature is enabled.
+#[cfg(feature = "sdk-0_46")]
+pub fn submit_proposal_v1_msg(
+    proposer: Address,
+    messages: Vec<Msg>,
+    deposit: Vec<Coin>,
+    metadata: impl Into<String>,
+    title: impl Into<String>,
+    summary: impl Into<String>,
+) -> Msg {
+    let (title, summary) = (title.into(), summary.into());
+    #[cfg(not(feature = "sdk-0_47"))]
+    let (title, summary) = {
+        if !title.is_empty() || !summary.is_empty() {
+            warn!("Proposal title and summary require Cosmos SDK v0.47, leaving them out");
+        }
+        (String::new(), String::new())
+    };
+    let proposal = MsgSubmitProposalV1 {
+        messages: messages.into_iter().map(|m| m.into()).collect(),
+        initial_deposit: deposit.into_iter().map(|c| c.into()).collect(),
+        proposer: proposer.to_string(),
+        metadata: metadata.into(),
+        title,
+        summary,
+    };
+    Msg::new(MSG_SUBMIT_PROPOSAL_V1_TYPE_URL, proposal)
+}
+
+/// Wraps content based proposal content into a MsgExecLegacyContent so that it can be
+/// included in a v1 MsgSubmitProposal
+#[cfg(feature = "sdk-0_46")]
+pub fn legacy_content_msg(content: Any, prefix: &str) -> Msg {
+    let exec = MsgExecLegacyContent {
+        content: Some(content),
+        authority: gov_module_address(prefix).to_string(),
+    };
+    Msg::new(MSG_EXEC_LEGACY_CONTENT_TYPE_URL, exec)
+}
+
+/// Builds a MsgDeposit adding amount to the deposit of a proposal
+pub fn deposit_msg(depositor: Address, proposal_id: u64, amount: Vec<Coin>) -> Msg {
+    let deposit = MsgDeposit {
+        proposal_id,
+        depositor: depositor.to_string(),
+        amount: amount.into_iter().map(|c| c.into()).collect(),
+    };
+    Msg::new(MSG_DEPOSIT_TYPE_URL, deposit)
+}
+
+/// Builds a MsgVote casting the voters full voting power for a single option
+pub fn vote_msg(voter: Address, proposal_id: u64, option: VoteOption) -> Msg {
+    let vote = MsgVote {
+        proposal_id,
+        voter: voter.to_string(),
+        option: option.into(),
+    };
+    Msg::new(MSG_VOTE_TYPE_URL, vote)
+}
+
+/// Builds a MsgVoteWeighted splitting the voters voting power between several options,
+/// each option may only appear once and the weights must add up to exactly one
+pub fn vote_weighted_msg(
+    voter: Address,
+    proposal_id: u64,
+    options: Vec<(VoteOption, Decimal)>,
+) -> Result<Msg, CosmosGrpcError> {
+    if options.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Weighted vote requires at least one option".to_string(),
+        ));
+    }
+    let one = Decimal::from(1u8);
+    let zero = Decimal::from(0u8);
+    let mut total: i128 = 0;
+    let mut seen = Vec::new();
+    for (option, weight) in options.iter() {
+        if seen.contains(option) {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Weighted vote repeats option {:?}",
+                option
+            )));
+        }
+        seen.push(*option);
+        if *weight <= zero || *weight > one {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Weighted vote option {:?} has invalid weight {}",
+                option, weight
+            )));
+        }
+        // the proto representation of a decimal is the integer value scaled by 10^18
+        // which we can sum without any loss of precision
+        total += weight.to_proto_string().parse::<i128>().unwrap();
+    }
+    if total.to_string() != one.to_proto_string() {
+        return Err(CosmosGrpcError::BadInput(
+            "Weighted vote option weights must add up to 1".to_string(),
+        ));
+    }
+    let vote = MsgVoteWeighted {
+        proposal_id,
+        voter: voter.to_string(),
+        options: options
+            .into_iter()
+            .map(|(option, weight)| WeightedVoteOption {
+                option: option.into(),
+                weight: weight.to_proto_string(),
+            })
+            .collect(),
+    };
+    Ok(Msg::new(MSG_VOTE_WEIGHTED_TYPE_URL, vote))
+}
+
 impl Contact {
     /// Gets a list of governance proposals, user provides filter items
     pub async fn get_governance_proposals(
         &self,
         filters: QueryProposalsRequest,
     ) -> Result<QueryProposalsResponse, CosmosGrpcError> {
-        let mut grpc = GovQueryClient::connect(self.url.clone()).await?;
-        let res = grpc.proposals(filters).await?.into_inner();
-        Ok(res)
+        self.grpc_unary("/cosmos.gov.v1beta1.Query/Proposals", filters)
+            .await
+    }
+
+    /// Streams every governance proposal matching the filters, the pagination of the
+    /// filters is ignored. Pages are fetched as the stream is consumed.
+    pub fn get_governance_proposals_stream(
+        &self,
+        filters: QueryProposalsRequest,
+    ) -> impl Stream<Item = Result<Proposal, CosmosGrpcError>> + '_ {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryProposalsRequest {
+                pagination: Some(pagination),
+                ..filters.clone()
+            };
+            async move {
+                let res: QueryProposalsResponse = self
+                    .grpc_unary("/cosmos.gov.v1beta1.Query/Proposals", request)
+                    .await?;
+                Ok((res.proposals, res.pagination))
+            }
+        })
     }
 
     /// Gets a list of all active governance proposals currently in the voting period
@@ -98,26 +277,23 @@ impl Contact {
         private_key: PrivateKey,
         wait_timeout: Option<Duration>,
     ) -> Result<TxResponse, CosmosGrpcError> {
-        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
         let vote = MsgVote {
             proposal_id,
             voter: our_address.to_string(),
             option: vote.into(),
         };
 
-        let fee = Fee {
-            amount: vec![fee],
-            gas_limit: 500_000u64,
-            granter: None,
-            payer: None,
-        };
-
-        let msg = Msg::new("/cosmos.gov.v1beta1.MsgVote", vote);
+        let msg = Msg::new(MSG_VOTE_TYPE_URL, vote);
 
+        let messages = [msg];
+        let fee = self.default_fee(Some(fee), &messages);
+        self.check_tx_policy(our_address, &messages, &fee, MEMO)?;
+        self.check_fee_grant(our_address, &messages, &fee).await?;
         let args = self.get_message_args(our_address, fee).await?;
         trace!("got optional tx info");
 
-        let msg_bytes = private_key.sign_std_msg(&[msg], args, MEMO)?;
+        let msg_bytes = private_key.sign_std_msg(&messages, args, MEMO)?;
 
         let response = self
             .send_transaction(msg_bytes, BroadcastMode::Sync)
@@ -140,26 +316,23 @@ impl Contact {
         private_key: PrivateKey,
         wait_timeout: Option<Duration>,
     ) -> Result<TxResponse, CosmosGrpcError> {
-        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
         let proposal = MsgSubmitProposal {
             proposer: our_address.to_string(),
             content: Some(content),
             initial_deposit: vec![deposit.into()],
         };
 
-        let fee = Fee {
-            amount: vec![fee],
-            gas_limit: 500_000u64,
-            granter: None,
-            payer: None,
-        };
-
-        let msg = Msg::new("/cosmos.gov.v1beta1.MsgSubmitProposal", proposal);
+        let msg = Msg::new(MSG_SUBMIT_PROPOSAL_TYPE_URL, proposal);
 
+        let messages = [msg];
+        let fee = self.default_fee(Some(fee), &messages);
+        self.check_tx_policy(our_address, &messages, &fee, MEMO)?;
+        self.check_fee_grant(our_address, &messages, &fee).await?;
         let args = self.get_message_args(our_address, fee).await?;
         trace!("got optional tx info");
 
-        let msg_bytes = private_key.sign_std_msg(&[msg], args, MEMO)?;
+        let msg_bytes = private_key.sign_std_msg(&messages, args, MEMO)?;
 
         let response = self
             .send_transaction(msg_bytes, BroadcastMode::Sync)
@@ -172,4 +345,98 @@ impl Contact {
             Ok(response)
         }
     }
+
+    /// Votes on a governance proposal splitting the voting power of the provided private key
+    /// between several options, the weights must add up to exactly one
+    pub async fn vote_weighted_on_gov_proposal(
+        &self,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Decimal)>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = vote_weighted_msg(our_address, proposal_id, options)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Adds to the deposit of a governance proposal in the deposit period, once the
+    /// deposit reaches the chains minimum deposit the proposal enters the voting period
+    pub async fn deposit_on_gov_proposal(
+        &self,
+        proposal_id: u64,
+        amount: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = deposit_msg(our_address, proposal_id, vec![amount]);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Provides an interface for submitting message based v1 governance proposals, requires
+    /// Cosmos SDK v0.46 or later. See submit_proposal_v1_msg() for details on the arguments.
+    #[cfg(feature = "sdk-0_46")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_gov_proposal_v1(
+        &self,
+        messages: Vec<Msg>,
+        deposit: Coin,
+        metadata: String,
+        title: String,
+        summary: String,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = submit_proposal_v1_msg(
+            our_address,
+            messages,
+            vec![deposit],
+            metadata,
+            title,
+            summary,
+        );
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gov_module_address() {
+        assert_eq!(
+            gov_module_address("cosmos").to_string(),
+            "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn"
+        );
+    }
+
+    #[test]
+    fn test_weighted_vote_validation() {
+        let voter = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let half: Decimal = "0.500000000000000000".parse().unwrap();
+        let quarter: Decimal = "0.250000000000000000".parse().unwrap();
+
+        let valid = vec![(VoteOption::Yes, half), (VoteOption::No, half)];
+        assert!(vote_weighted_msg(voter, 1, valid).is_ok());
+
+        let under = vec![(VoteOption::Yes, half), (VoteOption::No, quarter)];
+        assert!(vote_weighted_msg(voter, 1, under).is_err());
+
+        let repeated = vec![(VoteOption::Yes, half), (VoteOption::Yes, half)];
+        assert!(vote_weighted_msg(voter, 1, repeated).is_err());
+
+        assert!(vote_weighted_msg(voter, 1, Vec::new()).is_err());
+    }
 }
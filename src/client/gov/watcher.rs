@@ -0,0 +1,239 @@
+//! Governance proposal lifecycle tracking. The proposals of the chain are polled and each
+//! status change produces an event, so voting and notification bots do not have to diff
+//! proposal lists themselves.
+//!
+//! ```ignore
+//! let events = contact.watch_proposals(ProposalFilter::default());
+//! futures::pin_mut!(events);
+//! while let Some(event) = events.next().await {
+//!     if let ProposalEvent::VotingPeriod(proposal) = event? {
+//!         vote(proposal.proposal_id).await;
+//!     }
+//! }
+//! ```
+
+use crate::client::runtime::sleep;
+use crate::error::CosmosGrpcError;
+use crate::Contact;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::Proposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalsRequest;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// The default interval between two polls of the proposal list
+pub const DEFAULT_PROPOSAL_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A proposal entering a new stage of its lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProposalEvent {
+    DepositPeriod(Proposal),
+    VotingPeriod(Proposal),
+    Passed(Proposal),
+    Rejected(Proposal),
+    Failed(Proposal),
+    /// the proposal is no longer returned by the node, proposals that do not reach the
+    /// minimum deposit before the deposit period ends are deleted
+    Removed {
+        proposal_id: u64,
+    },
+}
+
+impl ProposalEvent {
+    fn new(status: ProposalStatus, proposal: Proposal) -> Option<Self> {
+        match status {
+            ProposalStatus::DepositPeriod => Some(ProposalEvent::DepositPeriod(proposal)),
+            ProposalStatus::VotingPeriod => Some(ProposalEvent::VotingPeriod(proposal)),
+            ProposalStatus::Passed => Some(ProposalEvent::Passed(proposal)),
+            ProposalStatus::Rejected => Some(ProposalEvent::Rejected(proposal)),
+            ProposalStatus::Failed => Some(ProposalEvent::Failed(proposal)),
+            ProposalStatus::Unspecified => None,
+        }
+    }
+
+    pub fn proposal_id(&self) -> u64 {
+        match self {
+            ProposalEvent::DepositPeriod(proposal)
+            | ProposalEvent::VotingPeriod(proposal)
+            | ProposalEvent::Passed(proposal)
+            | ProposalEvent::Rejected(proposal)
+            | ProposalEvent::Failed(proposal) => proposal.proposal_id,
+            ProposalEvent::Removed { proposal_id } => *proposal_id,
+        }
+    }
+}
+
+/// Selects the proposals and events watch_proposals reports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProposalFilter {
+    /// proposals with a lower id are ignored
+    pub min_proposal_id: u64,
+    /// only status changes to these statuses are reported, every change if empty
+    pub statuses: Vec<ProposalStatus>,
+    /// report the current status of the proposals that exist when watching starts, by
+    /// default only changes after the first poll are reported
+    pub include_existing: bool,
+    pub poll_interval: Duration,
+}
+
+impl Default for ProposalFilter {
+    fn default() -> Self {
+        ProposalFilter {
+            min_proposal_id: 0,
+            statuses: Vec::new(),
+            include_existing: false,
+            poll_interval: DEFAULT_PROPOSAL_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Remembers the last seen status of each proposal and turns proposal lists into events,
+/// this is the state of watch_proposals and can be used with any source of proposals
+#[derive(Debug, Clone)]
+pub struct ProposalTracker {
+    filter: ProposalFilter,
+    statuses: BTreeMap<u64, i32>,
+    initialized: bool,
+}
+
+impl ProposalTracker {
+    pub fn new(filter: ProposalFilter) -> Self {
+        ProposalTracker {
+            filter,
+            statuses: BTreeMap::new(),
+            initialized: false,
+        }
+    }
+
+    fn reported(&self, status: ProposalStatus) -> bool {
+        self.filter.statuses.is_empty() || self.filter.statuses.contains(&status)
+    }
+
+    /// Compares the full list of proposals against the previous one, returning an event
+    /// for each new proposal or status change in order of proposal id
+    pub fn update(&mut self, proposals: Vec<Proposal>) -> Vec<ProposalEvent> {
+        let report = self.initialized || self.filter.include_existing;
+        let mut events = Vec::new();
+        let mut seen = BTreeMap::new();
+        for proposal in proposals {
+            if proposal.proposal_id < self.filter.min_proposal_id {
+                continue;
+            }
+            let id = proposal.proposal_id;
+            let status = proposal.status;
+            seen.insert(id, status);
+            if !report || self.statuses.get(&id) == Some(&status) {
+                continue;
+            }
+            let status = ProposalStatus::from_i32(status).unwrap_or(ProposalStatus::Unspecified);
+            if !self.reported(status) {
+                continue;
+            }
+            if let Some(event) = ProposalEvent::new(status, proposal) {
+                events.push(event);
+            }
+        }
+        if self.initialized && self.filter.statuses.is_empty() {
+            for id in self.statuses.keys().filter(|id| !seen.contains_key(id)) {
+                events.push(ProposalEvent::Removed { proposal_id: *id });
+            }
+        }
+        events.sort_by_key(|event| event.proposal_id());
+        self.statuses = seen;
+        self.initialized = true;
+        events
+    }
+}
+
+impl Contact {
+    /// Polls the governance proposals of the chain and streams an event each time a
+    /// proposal matching the filter enters the deposit or voting period, passes, is
+    /// rejected or fails. The stream does not end, failed polls are returned as errors and
+    /// polling continues after the next interval.
+    pub fn watch_proposals(
+        &self,
+        filter: ProposalFilter,
+    ) -> impl Stream<Item = Result<ProposalEvent, CosmosGrpcError>> + '_ {
+        let interval = filter.poll_interval;
+        let tracker = ProposalTracker::new(filter);
+        stream::unfold((tracker, true), move |(mut tracker, first)| async move {
+            if !first {
+                sleep(interval).await;
+            }
+            let proposals: Result<Vec<Proposal>, CosmosGrpcError> = self
+                .get_governance_proposals_stream(QueryProposalsRequest::default())
+                .try_collect()
+                .await;
+            let events = match proposals {
+                Ok(proposals) => tracker.update(proposals).into_iter().map(Ok).collect(),
+                Err(e) => vec![Err(e)],
+            };
+            Some((stream::iter(events), (tracker, false)))
+        })
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proposal(proposal_id: u64, status: ProposalStatus) -> Proposal {
+        Proposal {
+            proposal_id,
+            status: status.into(),
+            ..Proposal::default()
+        }
+    }
+
+    #[test]
+    fn test_proposal_tracker() {
+        let mut tracker = ProposalTracker::new(ProposalFilter {
+            min_proposal_id: 2,
+            ..ProposalFilter::default()
+        });
+        // the first list is the baseline
+        let events = tracker.update(vec![
+            proposal(1, ProposalStatus::VotingPeriod),
+            proposal(2, ProposalStatus::DepositPeriod),
+            proposal(3, ProposalStatus::VotingPeriod),
+        ]);
+        assert!(events.is_empty());
+
+        let events = tracker.update(vec![
+            proposal(1, ProposalStatus::Passed),
+            proposal(3, ProposalStatus::Rejected),
+            proposal(4, ProposalStatus::DepositPeriod),
+        ]);
+        assert_eq!(
+            events,
+            vec![
+                ProposalEvent::Removed { proposal_id: 2 },
+                ProposalEvent::Rejected(proposal(3, ProposalStatus::Rejected)),
+                ProposalEvent::DepositPeriod(proposal(4, ProposalStatus::DepositPeriod)),
+            ]
+        );
+        // nothing changed
+        let events = tracker.update(vec![
+            proposal(3, ProposalStatus::Rejected),
+            proposal(4, ProposalStatus::DepositPeriod),
+        ]);
+        assert!(events.is_empty());
+
+        let mut voting = ProposalTracker::new(ProposalFilter {
+            statuses: vec![ProposalStatus::VotingPeriod],
+            include_existing: true,
+            ..ProposalFilter::default()
+        });
+        let events = voting.update(vec![
+            proposal(1, ProposalStatus::DepositPeriod),
+            proposal(2, ProposalStatus::VotingPeriod),
+        ]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].proposal_id(), 2);
+    }
+}
@@ -0,0 +1,437 @@
+//! Contains utility functions for the group module, which allows a set of weighted members
+//! to jointly control group policy accounts by submitting and voting on proposals. This is
+//! a more flexible replacement for multisig accounts. Group policy addresses are 32 bytes and
+//! so can not be represented by Address, they are passed as bech32 strings instead.
+
+use crate::client::events::tx_events;
+use crate::client::Contact;
+use crate::decimal::Decimal;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::group::v1::DecisionPolicyWindows;
+use crate::proto::cosmos::group::v1::Exec;
+use crate::proto::cosmos::group::v1::MemberRequest;
+use crate::proto::cosmos::group::v1::MsgCreateGroup;
+use crate::proto::cosmos::group::v1::MsgCreateGroupPolicy;
+use crate::proto::cosmos::group::v1::MsgCreateGroupWithPolicy;
+use crate::proto::cosmos::group::v1::MsgExec;
+use crate::proto::cosmos::group::v1::MsgSubmitProposal;
+use crate::proto::cosmos::group::v1::MsgVote;
+use crate::proto::cosmos::group::v1::MsgWithdrawProposal;
+use crate::proto::cosmos::group::v1::PercentageDecisionPolicy;
+use crate::proto::cosmos::group::v1::ThresholdDecisionPolicy;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use prost_types::Any;
+use std::time::Duration;
+
+pub use crate::proto::cosmos::group::v1::VoteOption;
+
+pub const MSG_CREATE_GROUP_TYPE_URL: &str = "/cosmos.group.v1.MsgCreateGroup";
+pub const MSG_CREATE_GROUP_POLICY_TYPE_URL: &str = "/cosmos.group.v1.MsgCreateGroupPolicy";
+pub const MSG_CREATE_GROUP_WITH_POLICY_TYPE_URL: &str = "/cosmos.group.v1.MsgCreateGroupWithPolicy";
+pub const MSG_GROUP_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.group.v1.MsgSubmitProposal";
+pub const MSG_GROUP_VOTE_TYPE_URL: &str = "/cosmos.group.v1.MsgVote";
+pub const MSG_GROUP_EXEC_TYPE_URL: &str = "/cosmos.group.v1.MsgExec";
+pub const MSG_GROUP_WITHDRAW_PROPOSAL_TYPE_URL: &str = "/cosmos.group.v1.MsgWithdrawProposal";
+pub const THRESHOLD_DECISION_POLICY_TYPE_URL: &str = "/cosmos.group.v1.ThresholdDecisionPolicy";
+pub const PERCENTAGE_DECISION_POLICY_TYPE_URL: &str = "/cosmos.group.v1.PercentageDecisionPolicy";
+
+/// A member of a group, weight is the members share of the voting power
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupMember {
+    pub address: Address,
+    pub weight: u64,
+    pub metadata: String,
+}
+
+/// The decision policy of a group policy account, determines when a proposal passes
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecisionPolicy {
+    /// Passes when the sum of the weights of yes votes is at least threshold
+    Threshold {
+        threshold: u64,
+        voting_period: Duration,
+        min_execution_period: Duration,
+    },
+    /// Passes when the yes votes are at least percentage of the total group weight,
+    /// percentage must be greater than zero and at most one
+    Percentage {
+        percentage: Decimal,
+        voting_period: Duration,
+        min_execution_period: Duration,
+    },
+}
+
+impl DecisionPolicy {
+    /// Packs this policy into the Any used by the group policy messages
+    pub fn to_any(&self) -> Result<Any, CosmosGrpcError> {
+        match self {
+            DecisionPolicy::Threshold {
+                threshold,
+                voting_period,
+                min_execution_period,
+            } => {
+                if *threshold == 0 {
+                    return Err(CosmosGrpcError::BadInput(
+                        "Group threshold must be greater than zero".to_string(),
+                    ));
+                }
+                let policy = ThresholdDecisionPolicy {
+                    threshold: threshold.to_string(),
+                    windows: Some(windows(*voting_period, *min_execution_period)),
+                };
+                Ok(encode_any(
+                    policy,
+                    THRESHOLD_DECISION_POLICY_TYPE_URL.to_string(),
+                ))
+            }
+            DecisionPolicy::Percentage {
+                percentage,
+                voting_period,
+                min_execution_period,
+            } => {
+                if *percentage <= Decimal::from(0u8) || *percentage > Decimal::from(1u8) {
+                    return Err(CosmosGrpcError::BadInput(format!(
+                        "Group percentage {} must be in (0, 1]",
+                        percentage
+                    )));
+                }
+                let policy = PercentageDecisionPolicy {
+                    percentage: percentage.to_string(),
+                    windows: Some(windows(*voting_period, *min_execution_period)),
+                };
+                Ok(encode_any(
+                    policy,
+                    PERCENTAGE_DECISION_POLICY_TYPE_URL.to_string(),
+                ))
+            }
+        }
+    }
+}
+
+fn windows(voting_period: Duration, min_execution_period: Duration) -> DecisionPolicyWindows {
+    DecisionPolicyWindows {
+        voting_period: Some(voting_period.into()),
+        min_execution_period: Some(min_execution_period.into()),
+    }
+}
+
+/// Checks that members are not empty, repeated or zero weighted
+fn member_requests(members: Vec<GroupMember>) -> Result<Vec<MemberRequest>, CosmosGrpcError> {
+    if members.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "A group requires at least one member".to_string(),
+        ));
+    }
+    let mut seen = Vec::new();
+    let mut requests = Vec::new();
+    for member in members {
+        if member.weight == 0 {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Group member {} has zero weight",
+                member.address
+            )));
+        }
+        if seen.contains(&member.address) {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Group member {} is repeated",
+                member.address
+            )));
+        }
+        seen.push(member.address);
+        requests.push(MemberRequest {
+            address: member.address.to_string(),
+            weight: member.weight.to_string(),
+            metadata: member.metadata,
+        });
+    }
+    Ok(requests)
+}
+
+fn exec_mode(exec: bool) -> i32 {
+    if exec {
+        Exec::Try.into()
+    } else {
+        Exec::Unspecified.into()
+    }
+}
+
+/// Builds a MsgCreateGroup, a group has no account of its own, group policies must be
+/// created to hold funds and execute proposals
+pub fn create_group_msg(
+    admin: Address,
+    members: Vec<GroupMember>,
+    metadata: impl Into<String>,
+) -> Result<Msg, CosmosGrpcError> {
+    let create = MsgCreateGroup {
+        admin: admin.to_string(),
+        members: member_requests(members)?,
+        metadata: metadata.into(),
+    };
+    Ok(Msg::new(MSG_CREATE_GROUP_TYPE_URL, create))
+}
+
+/// Builds a MsgCreateGroupPolicy creating a new account controlled by the group
+pub fn create_group_policy_msg(
+    admin: Address,
+    group_id: u64,
+    metadata: impl Into<String>,
+    policy: &DecisionPolicy,
+) -> Result<Msg, CosmosGrpcError> {
+    let create = MsgCreateGroupPolicy {
+        admin: admin.to_string(),
+        group_id,
+        metadata: metadata.into(),
+        decision_policy: Some(policy.to_any()?),
+    };
+    Ok(Msg::new(MSG_CREATE_GROUP_POLICY_TYPE_URL, create))
+}
+
+/// Builds a MsgCreateGroupWithPolicy creating a group and a single group policy account at
+/// once. If policy_as_admin is set the policy account becomes the admin of both, so that any
+/// future membership changes must be approved by the group itself.
+pub fn create_group_with_policy_msg(
+    admin: Address,
+    members: Vec<GroupMember>,
+    group_metadata: impl Into<String>,
+    policy_metadata: impl Into<String>,
+    policy_as_admin: bool,
+    policy: &DecisionPolicy,
+) -> Result<Msg, CosmosGrpcError> {
+    let create = MsgCreateGroupWithPolicy {
+        admin: admin.to_string(),
+        members: member_requests(members)?,
+        group_metadata: group_metadata.into(),
+        group_policy_metadata: policy_metadata.into(),
+        group_policy_as_admin: policy_as_admin,
+        decision_policy: Some(policy.to_any()?),
+    };
+    Ok(Msg::new(MSG_CREATE_GROUP_WITH_POLICY_TYPE_URL, create))
+}
+
+/// Builds a group MsgSubmitProposal, the messages will be executed by the group policy
+/// account if the proposal passes so their signer must be the group policy address. If
+/// exec is set execution is attempted immediately, counting the proposers as yes votes.
+pub fn submit_group_proposal_msg(
+    group_policy_address: impl Into<String>,
+    proposers: Vec<Address>,
+    messages: Vec<Msg>,
+    metadata: impl Into<String>,
+    exec: bool,
+) -> Result<Msg, CosmosGrpcError> {
+    if proposers.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Group proposals require at least one proposer".to_string(),
+        ));
+    }
+    let proposal = MsgSubmitProposal {
+        group_policy_address: group_policy_address.into(),
+        proposers: proposers.iter().map(|p| p.to_string()).collect(),
+        metadata: metadata.into(),
+        messages: messages.into_iter().map(|m| m.into()).collect(),
+        exec: exec_mode(exec),
+        title: String::new(),
+        summary: String::new(),
+    };
+    Ok(Msg::new(MSG_GROUP_SUBMIT_PROPOSAL_TYPE_URL, proposal))
+}
+
+/// Builds a group MsgVote, if exec is set execution is attempted after the vote is counted
+pub fn group_vote_msg(
+    voter: Address,
+    proposal_id: u64,
+    option: VoteOption,
+    metadata: impl Into<String>,
+    exec: bool,
+) -> Msg {
+    let vote = MsgVote {
+        proposal_id,
+        voter: voter.to_string(),
+        option: option.into(),
+        metadata: metadata.into(),
+        exec: exec_mode(exec),
+    };
+    Msg::new(MSG_GROUP_VOTE_TYPE_URL, vote)
+}
+
+/// Builds a group MsgExec, executing the messages of a passed proposal
+pub fn group_exec_msg(executor: Address, proposal_id: u64) -> Msg {
+    let exec = MsgExec {
+        proposal_id,
+        executor: executor.to_string(),
+    };
+    Msg::new(MSG_GROUP_EXEC_TYPE_URL, exec)
+}
+
+/// Builds a MsgWithdrawProposal, the address must be a proposer or the policy admin
+pub fn withdraw_group_proposal_msg(address: Address, proposal_id: u64) -> Msg {
+    let withdraw = MsgWithdrawProposal {
+        proposal_id,
+        address: address.to_string(),
+    };
+    Msg::new(MSG_GROUP_WITHDRAW_PROPOSAL_TYPE_URL, withdraw)
+}
+
+/// Finds an attribute of a typed group event, these are JSON encoded so the surrounding
+/// quotes are removed
+fn group_event_attribute(response: &TxResponse, event_type: &str, key: &str) -> Option<String> {
+    for event in tx_events(response).iter() {
+        if event.r#type != event_type {
+            continue;
+        }
+        for attribute in event.attributes.iter() {
+            if attribute.key == key {
+                return Some(attribute.value.trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Gets the id of the group created by a MsgCreateGroup or MsgCreateGroupWithPolicy
+pub fn created_group_id(response: &TxResponse) -> Option<u64> {
+    group_event_attribute(response, "cosmos.group.v1.EventCreateGroup", "group_id")
+        .and_then(|v| v.parse().ok())
+}
+
+/// Gets the address of the group policy created by a MsgCreateGroupPolicy or
+/// MsgCreateGroupWithPolicy
+pub fn created_group_policy_address(response: &TxResponse) -> Option<String> {
+    group_event_attribute(
+        response,
+        "cosmos.group.v1.EventCreateGroupPolicy",
+        "address",
+    )
+}
+
+/// Gets the id of the proposal created by a group MsgSubmitProposal
+pub fn submitted_group_proposal_id(response: &TxResponse) -> Option<u64> {
+    group_event_attribute(
+        response,
+        "cosmos.group.v1.EventSubmitProposal",
+        "proposal_id",
+    )
+    .and_then(|v| v.parse().ok())
+}
+
+impl Contact {
+    /// Creates a group administered by the provided private key along with a single group
+    /// policy account, the group id and policy address can be read from the returned
+    /// transaction using created_group_id and created_group_policy_address
+    pub async fn create_group_with_policy(
+        &self,
+        members: Vec<GroupMember>,
+        policy: DecisionPolicy,
+        policy_as_admin: bool,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg =
+            create_group_with_policy_msg(our_address, members, "", "", policy_as_admin, &policy)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Submits a group proposal from the provided private key, which must be a group member
+    pub async fn submit_group_proposal(
+        &self,
+        group_policy_address: String,
+        messages: Vec<Msg>,
+        exec: bool,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg =
+            submit_group_proposal_msg(group_policy_address, vec![our_address], messages, "", exec)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Votes on a group proposal from the provided private key
+    pub async fn vote_on_group_proposal(
+        &self,
+        proposal_id: u64,
+        option: VoteOption,
+        exec: bool,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = group_vote_msg(our_address, proposal_id, option, "", exec);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Executes a passed group proposal, any account may execute a proposal
+    pub async fn exec_group_proposal(
+        &self,
+        proposal_id: u64,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = group_exec_msg(our_address, proposal_id);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_validation() {
+        let a = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let b = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let member = |address, weight| GroupMember {
+            address,
+            weight,
+            metadata: String::new(),
+        };
+        let policy = DecisionPolicy::Threshold {
+            threshold: 2,
+            voting_period: Duration::from_secs(3600),
+            min_execution_period: Duration::from_secs(0),
+        };
+        assert!(create_group_with_policy_msg(
+            a,
+            vec![member(a, 1), member(b, 1)],
+            "",
+            "",
+            true,
+            &policy
+        )
+        .is_ok());
+        assert!(create_group_msg(a, vec![], "").is_err());
+        assert!(create_group_msg(a, vec![member(a, 0)], "").is_err());
+        assert!(create_group_msg(a, vec![member(a, 1), member(a, 1)], "").is_err());
+
+        let too_high = DecisionPolicy::Percentage {
+            percentage: "1.500000000000000000".parse().unwrap(),
+            voting_period: Duration::from_secs(3600),
+            min_execution_period: Duration::from_secs(0),
+        };
+        assert!(too_high.to_any().is_err());
+        let half = DecisionPolicy::Percentage {
+            percentage: "0.500000000000000000".parse().unwrap(),
+            voting_period: Duration::from_secs(3600),
+            min_execution_period: Duration::from_secs(0),
+        };
+        assert!(half.to_any().is_ok());
+    }
+}
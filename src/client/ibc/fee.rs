@@ -0,0 +1,262 @@
+//! ICS-29 fee middleware support, relayer fees can be attached to packets sent over channels
+//! that have fees enabled. To pay for a transfer include pay_packet_fee_msg() before the
+//! transfer_msg() in the same transaction, fees for packets that were already sent can be
+//! paid with pay_packet_fee_async_msg().
+
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::ibc::applications::fee::v1::Fee as ProtoPacketFee;
+use crate::proto::ibc::applications::fee::v1::MsgPayPacketFee;
+use crate::proto::ibc::applications::fee::v1::MsgPayPacketFeeAsync;
+use crate::proto::ibc::applications::fee::v1::MsgRegisterCounterpartyPayee;
+use crate::proto::ibc::applications::fee::v1::PacketFee;
+use crate::proto::ibc::applications::fee::v1::QueryCounterpartyPayeeRequest;
+use crate::proto::ibc::applications::fee::v1::QueryCounterpartyPayeeResponse;
+use crate::proto::ibc::applications::fee::v1::QueryFeeEnabledChannelRequest;
+use crate::proto::ibc::applications::fee::v1::QueryFeeEnabledChannelResponse;
+use crate::proto::ibc::applications::fee::v1::QueryIncentivizedPacketRequest;
+use crate::proto::ibc::applications::fee::v1::QueryIncentivizedPacketResponse;
+use crate::proto::ibc::applications::fee::v1::QueryIncentivizedPacketsForChannelRequest;
+use crate::proto::ibc::applications::fee::v1::QueryIncentivizedPacketsForChannelResponse;
+use crate::proto::ibc::applications::fee::v1::QueryPayeeRequest;
+use crate::proto::ibc::applications::fee::v1::QueryPayeeResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use futures::Stream;
+use std::time::Duration;
+
+pub use crate::proto::ibc::applications::fee::v1::IdentifiedPacketFees;
+pub use crate::proto::ibc::core::channel::v1::PacketId;
+
+pub const MSG_PAY_PACKET_FEE_TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFee";
+pub const MSG_PAY_PACKET_FEE_ASYNC_TYPE_URL: &str = "/ibc.applications.fee.v1.MsgPayPacketFeeAsync";
+pub const MSG_REGISTER_COUNTERPARTY_PAYEE_TYPE_URL: &str =
+    "/ibc.applications.fee.v1.MsgRegisterCounterpartyPayee";
+
+/// The fees paid to relayers for a packet, the receive fee goes to the relayer of the packet,
+/// the ack fee to the relayer of the acknowledgement and the timeout fee to the relayer of
+/// the timeout. Only one of the ack and timeout fees is ever paid, the rest is refunded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PacketFees {
+    pub recv_fee: Vec<Coin>,
+    pub ack_fee: Vec<Coin>,
+    pub timeout_fee: Vec<Coin>,
+}
+
+impl From<PacketFees> for ProtoPacketFee {
+    fn from(value: PacketFees) -> Self {
+        ProtoPacketFee {
+            recv_fee: value.recv_fee.into_iter().map(|c| c.into()).collect(),
+            ack_fee: value.ack_fee.into_iter().map(|c| c.into()).collect(),
+            timeout_fee: value.timeout_fee.into_iter().map(|c| c.into()).collect(),
+        }
+    }
+}
+
+/// Builds a MsgPayPacketFee paying for the next packet sent over the channel by the signer,
+/// unspent fees are refunded to the signer. If relayers is empty any relayer may be paid.
+pub fn pay_packet_fee_msg(
+    signer: Address,
+    port: impl Into<String>,
+    channel: impl Into<String>,
+    fees: PacketFees,
+    relayers: Vec<String>,
+) -> Msg {
+    let pay = MsgPayPacketFee {
+        fee: Some(fees.into()),
+        source_port_id: port.into(),
+        source_channel_id: channel.into(),
+        signer: signer.to_string(),
+        relayers,
+    };
+    Msg::new(MSG_PAY_PACKET_FEE_TYPE_URL, pay)
+}
+
+/// Builds a MsgPayPacketFeeAsync adding fees to a packet that has already been sent, unspent
+/// fees are refunded to the refund address
+pub fn pay_packet_fee_async_msg(
+    refund_address: Address,
+    packet_id: PacketId,
+    fees: PacketFees,
+    relayers: Vec<String>,
+) -> Msg {
+    let pay = MsgPayPacketFeeAsync {
+        packet_id: Some(packet_id),
+        packet_fee: Some(PacketFee {
+            fee: Some(fees.into()),
+            refund_address: refund_address.to_string(),
+            relayers,
+        }),
+    };
+    Msg::new(MSG_PAY_PACKET_FEE_ASYNC_TYPE_URL, pay)
+}
+
+/// Builds a MsgRegisterCounterpartyPayee, registering the address on the counterparty chain
+/// the relayer is paid receive fees at
+pub fn register_counterparty_payee_msg(
+    relayer: Address,
+    port: impl Into<String>,
+    channel: impl Into<String>,
+    counterparty_payee: impl Into<String>,
+) -> Msg {
+    let register = MsgRegisterCounterpartyPayee {
+        port_id: port.into(),
+        channel_id: channel.into(),
+        relayer: relayer.to_string(),
+        counterparty_payee: counterparty_payee.into(),
+    };
+    Msg::new(MSG_REGISTER_COUNTERPARTY_PAYEE_TYPE_URL, register)
+}
+
+impl Contact {
+    /// Returns true if the fee middleware is enabled on the channel
+    pub async fn is_fee_enabled_channel(
+        &self,
+        port: String,
+        channel: String,
+    ) -> Result<bool, CosmosGrpcError> {
+        let res: QueryFeeEnabledChannelResponse = self
+            .grpc_unary(
+                "/ibc.applications.fee.v1.Query/FeeEnabledChannel",
+                QueryFeeEnabledChannelRequest {
+                    port_id: port,
+                    channel_id: channel,
+                },
+            )
+            .await?;
+        Ok(res.fee_enabled)
+    }
+
+    /// Gets the unpaid fees of a packet, returns an error if the packet has no fees
+    pub async fn get_incentivized_packet(
+        &self,
+        packet_id: PacketId,
+    ) -> Result<IdentifiedPacketFees, CosmosGrpcError> {
+        let res: QueryIncentivizedPacketResponse = self
+            .grpc_unary(
+                "/ibc.applications.fee.v1.Query/IncentivizedPacket",
+                QueryIncentivizedPacketRequest {
+                    packet_id: Some(packet_id),
+                    query_height: 0,
+                },
+            )
+            .await?;
+        match res.incentivized_packet {
+            Some(packet) => Ok(packet),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No incentivized packet?".to_string(),
+            )),
+        }
+    }
+
+    /// Streams every packet with unpaid fees on the channel, pages are fetched as the
+    /// stream is consumed
+    pub fn get_incentivized_packets_stream(
+        &self,
+        port: String,
+        channel: String,
+    ) -> impl Stream<Item = Result<IdentifiedPacketFees, CosmosGrpcError>> + '_ {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryIncentivizedPacketsForChannelRequest {
+                pagination: Some(pagination),
+                port_id: port.clone(),
+                channel_id: channel.clone(),
+                query_height: 0,
+            };
+            async move {
+                let res: QueryIncentivizedPacketsForChannelResponse = self
+                    .grpc_unary(
+                        "/ibc.applications.fee.v1.Query/IncentivizedPacketsForChannel",
+                        request,
+                    )
+                    .await?;
+                Ok((res.incentivized_packets, res.pagination))
+            }
+        })
+    }
+
+    /// Gets the address ack and timeout fees earned by the relayer on the channel are paid to
+    pub async fn get_payee(
+        &self,
+        channel: String,
+        relayer: Address,
+    ) -> Result<String, CosmosGrpcError> {
+        let res: QueryPayeeResponse = self
+            .grpc_unary(
+                "/ibc.applications.fee.v1.Query/Payee",
+                QueryPayeeRequest {
+                    channel_id: channel,
+                    relayer: relayer.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        Ok(res.payee_address)
+    }
+
+    /// Gets the counterparty chain address receive fees earned by the relayer on the channel
+    /// are paid to
+    pub async fn get_counterparty_payee(
+        &self,
+        channel: String,
+        relayer: Address,
+    ) -> Result<String, CosmosGrpcError> {
+        let res: QueryCounterpartyPayeeResponse = self
+            .grpc_unary(
+                "/ibc.applications.fee.v1.Query/CounterpartyPayee",
+                QueryCounterpartyPayeeRequest {
+                    channel_id: channel,
+                    relayer: relayer.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        Ok(res.counterparty_payee)
+    }
+
+    /// Adds relayer fees to a packet that has already been sent, paid by the provided
+    /// private key
+    pub async fn pay_packet_fee_async(
+        &self,
+        packet_id: PacketId,
+        fees: PacketFees,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = pay_packet_fee_async_msg(our_address, packet_id, fees, Vec::new());
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ibc::TRANSFER_PORT;
+    use prost::Message;
+
+    #[test]
+    fn test_pay_packet_fee_msg() {
+        let signer = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let fees = PacketFees {
+            recv_fee: vec!["100uatom".parse().unwrap()],
+            ack_fee: vec!["50uatom".parse().unwrap()],
+            timeout_fee: Vec::new(),
+        };
+        let msg = pay_packet_fee_msg(signer, TRANSFER_PORT, "channel-0", fees, Vec::new());
+        assert_eq!(msg.0.type_url, MSG_PAY_PACKET_FEE_TYPE_URL);
+        let pay = MsgPayPacketFee::decode(msg.0.value.as_slice()).unwrap();
+        assert_eq!(pay.signer, signer.to_string());
+        assert_eq!(pay.source_channel_id, "channel-0");
+        let fee = pay.fee.unwrap();
+        assert_eq!(fee.recv_fee[0].amount, "100");
+        assert_eq!(fee.ack_fee[0].denom, "uatom");
+        assert!(fee.timeout_fee.is_empty());
+    }
+}
@@ -0,0 +1,151 @@
+//! A builder for packet-forward-middleware memos, PFM allows an ICS-20 transfer to be
+//! forwarded through one or more intermediate chains by placing forwarding instructions
+//! in the memo of the MsgTransfer.
+
+use crate::client::ibc::TRANSFER_PORT;
+use crate::error::CosmosGrpcError;
+use std::time::Duration;
+
+/// A single forwarding hop, the receiver is the address on the chain that the
+/// packet is forwarded to using the provided channel on the intermediate chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardHop {
+    receiver: String,
+    port: String,
+    channel: String,
+    timeout: Option<Duration>,
+    retries: Option<u8>,
+}
+
+impl ForwardHop {
+    pub fn new(receiver: impl Into<String>, channel: impl Into<String>) -> Self {
+        ForwardHop {
+            receiver: receiver.into(),
+            port: TRANSFER_PORT.to_string(),
+            channel: channel.into(),
+            timeout: None,
+            retries: None,
+        }
+    }
+
+    /// Sets the port to forward over, defaults to transfer
+    pub fn port(mut self, port: impl Into<String>) -> Self {
+        self.port = port.into();
+        self
+    }
+
+    /// Sets the timeout of the forwarded packet, the middleware default is used if unset
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of times the forward is retried on timeout, the middleware
+    /// default is used if unset
+    pub fn retries(mut self, retries: u8) -> Self {
+        self.retries = Some(retries);
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct ForwardMetadata {
+    forward: ForwardJson,
+}
+
+#[derive(Serialize)]
+struct ForwardJson {
+    receiver: String,
+    port: String,
+    channel: String,
+    /// go time.Duration is serialized as nanoseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retries: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<Box<ForwardMetadata>>,
+}
+
+/// Builds the memo for a multi-hop transfer, hops are added in the order the packet
+/// travels. The MsgTransfer receiver for the first chain in the path should be set to
+/// an address on that chain, PFM chains accept any placeholder such as "pfm".
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForwardMemo {
+    hops: Vec<ForwardHop>,
+}
+
+impl ForwardMemo {
+    pub fn new() -> Self {
+        ForwardMemo::default()
+    }
+
+    /// Adds the next hop in the path
+    pub fn hop(mut self, hop: ForwardHop) -> Self {
+        self.hops.push(hop);
+        self
+    }
+
+    /// Builds the memo JSON string, nesting each hop in the next field of the previous
+    pub fn build(self) -> Result<String, CosmosGrpcError> {
+        let mut next: Option<Box<ForwardMetadata>> = None;
+        for hop in self.hops.into_iter().rev() {
+            if hop.receiver.is_empty() || hop.channel.is_empty() {
+                return Err(CosmosGrpcError::BadInput(
+                    "Forward hops require a receiver and channel".to_string(),
+                ));
+            }
+            next = Some(Box::new(ForwardMetadata {
+                forward: ForwardJson {
+                    receiver: hop.receiver,
+                    port: hop.port,
+                    channel: hop.channel,
+                    timeout: hop.timeout.map(|t| t.as_nanos() as u64),
+                    retries: hop.retries,
+                    next,
+                },
+            }));
+        }
+        match next {
+            Some(memo) => {
+                serde_json::to_string(&memo).map_err(|e| CosmosGrpcError::BadInput(e.to_string()))
+            }
+            None => Err(CosmosGrpcError::BadInput(
+                "Forward memo requires at least one hop".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forward_memo() {
+        assert!(ForwardMemo::new().build().is_err());
+
+        let single = ForwardMemo::new()
+            .hop(ForwardHop::new("osmo1receiver", "channel-1"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            single,
+            r#"{"forward":{"receiver":"osmo1receiver","port":"transfer","channel":"channel-1"}}"#
+        );
+
+        let multi = ForwardMemo::new()
+            .hop(
+                ForwardHop::new("pfm", "channel-1")
+                    .timeout(Duration::from_secs(600))
+                    .retries(2),
+            )
+            .hop(ForwardHop::new("juno1receiver", "channel-2"))
+            .build()
+            .unwrap();
+        assert_eq!(
+            multi,
+            r#"{"forward":{"receiver":"pfm","port":"transfer","channel":"channel-1","timeout":600000000000,"retries":2,"next":{"forward":{"receiver":"juno1receiver","port":"transfer","channel":"channel-2"}}}}"#
+        );
+    }
+}
@@ -0,0 +1,391 @@
+//! Interchain account controller messages, these allow an account on this chain to
+//! register and control an account on a counterparty chain over an IBC connection.
+//!
+//! ```ignore
+//! let ica = contact.get_interchain_account(owner, connection.clone()).await?;
+//! let report = contact
+//!     .execute_ica_tx(&host, connection, vec![send_from(ica)], timeout, fee, key, wait)
+//!     .await?;
+//! if let IcaTxOutcome::Failed { error } = report.outcome {
+//!     warn!("{} failed on the host chain: {}", report.send_tx.txhash, error);
+//! }
+//! ```
+
+use crate::client::events::decode_events;
+use crate::client::events::PacketEvent;
+use crate::client::events::TypedEvent;
+use crate::client::ibc::PacketOutcome;
+use crate::client::responses::decode_tx_msg_data;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::ibc::applications::interchain_accounts::controller::v1::MsgRegisterInterchainAccount;
+use crate::proto::ibc::applications::interchain_accounts::controller::v1::MsgSendTx;
+use crate::proto::ibc::applications::interchain_accounts::controller::v1::QueryInterchainAccountRequest;
+use crate::proto::ibc::applications::interchain_accounts::controller::v1::QueryInterchainAccountResponse;
+use crate::proto::ibc::applications::interchain_accounts::v1::CosmosTx;
+use crate::proto::ibc::applications::interchain_accounts::v1::InterchainAccountPacketData;
+use crate::proto::ibc::applications::interchain_accounts::v1::Type as IcaPacketType;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use prost::Message;
+use prost_types::Any;
+use serde_json::Value;
+use std::convert::TryFrom;
+use std::time::Duration;
+use std::time::Instant;
+
+pub const MSG_REGISTER_INTERCHAIN_ACCOUNT_TYPE_URL: &str =
+    "/ibc.applications.interchain_accounts.controller.v1.MsgRegisterInterchainAccount";
+pub const MSG_SEND_TX_TYPE_URL: &str =
+    "/ibc.applications.interchain_accounts.controller.v1.MsgSendTx";
+/// The prefix of the port of every interchain account controller channel
+pub const ICA_CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+/// What the host chain did with the messages of an interchain account transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum IcaTxOutcome {
+    /// every message was executed, responses holds the Msg responses in message order
+    Executed { responses: Vec<Any> },
+    /// the host chain rejected the transaction and none of the messages were executed
+    Failed { error: String },
+    /// the packet was not received in time and none of the messages were executed. This
+    /// closes an ordered channel, the account must be registered again to reopen it.
+    TimedOut,
+}
+
+/// The full round trip of an interchain account transaction, see execute_ica_tx
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcaTxReport {
+    /// the address of the interchain account on the host chain
+    pub ica_address: String,
+    /// the transaction on this chain that sent the packet
+    pub send_tx: TxResponse,
+    pub packet: PacketEvent,
+    pub packet_outcome: PacketOutcome,
+    pub outcome: IcaTxOutcome,
+}
+
+/// Reads the result of an interchain account packet. The host acknowledges a packet with
+/// the encoded TxMsgData of the executed messages, or with an error.
+pub fn ica_tx_outcome(outcome: &PacketOutcome) -> Result<IcaTxOutcome, CosmosGrpcError> {
+    let acknowledgement = match outcome {
+        PacketOutcome::TimedOut { .. } => return Ok(IcaTxOutcome::TimedOut),
+        PacketOutcome::Acknowledged {
+            acknowledgement, ..
+        } => acknowledgement,
+    };
+    if let Some(error) = outcome.ack_error() {
+        return Ok(IcaTxOutcome::Failed { error });
+    }
+    let result = acknowledgement
+        .as_ref()
+        .and_then(|ack| serde_json::from_str::<Value>(ack).ok())
+        .and_then(|ack| ack.get("result")?.as_str().map(|r| r.to_string()));
+    let responses = match result {
+        Some(result) => {
+            let bytes = base64::decode(&result)
+                .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid ICA ack {}", e)))?;
+            decode_tx_msg_data(&bytes)?
+        }
+        // the host node did not return the receiving transaction
+        None => Vec::new(),
+    };
+    Ok(IcaTxOutcome::Executed { responses })
+}
+
+/// Builds a MsgRegisterInterchainAccount, which opens a new ICA channel over the provided
+/// connection. If no version is provided the host chain's default metadata is negotiated.
+pub fn register_interchain_account_msg(
+    owner: Address,
+    connection_id: impl Into<String>,
+    version: Option<String>,
+) -> Msg {
+    let register = MsgRegisterInterchainAccount {
+        owner: owner.to_string(),
+        connection_id: connection_id.into(),
+        version: version.unwrap_or_default(),
+    };
+    Msg::new(MSG_REGISTER_INTERCHAIN_ACCOUNT_TYPE_URL, register)
+}
+
+/// Encodes messages for execution by an interchain account, the messages must be
+/// addressed using the interchain account address on the host chain as the signer
+pub fn ica_packet_data(messages: Vec<Msg>, memo: impl Into<String>) -> InterchainAccountPacketData {
+    let tx = CosmosTx {
+        messages: messages.into_iter().map(|m| m.into()).collect(),
+    };
+    let mut data = Vec::new();
+    // encoding into a vec can not fail
+    tx.encode(&mut data).unwrap();
+    InterchainAccountPacketData {
+        r#type: IcaPacketType::ExecuteTx.into(),
+        data,
+        memo: memo.into(),
+    }
+}
+
+/// Builds a MsgSendTx executing the provided messages on the host chain using the interchain
+/// account of the owner on the provided connection. The packet times out relative_timeout
+/// after the block time of the block it is included in.
+pub fn send_ica_tx_msg(
+    owner: Address,
+    connection_id: impl Into<String>,
+    messages: Vec<Msg>,
+    memo: impl Into<String>,
+    relative_timeout: Duration,
+) -> Result<Msg, CosmosGrpcError> {
+    if messages.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Interchain account txs require at least one message".to_string(),
+        ));
+    }
+    let relative_timeout = match u64::try_from(relative_timeout.as_nanos()) {
+        Ok(0) => {
+            return Err(CosmosGrpcError::BadInput(
+                "Interchain account txs require a non zero timeout".to_string(),
+            ))
+        }
+        Ok(nanos) => nanos,
+        Err(_) => {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Interchain account timeout {:?} does not fit in nanoseconds",
+                relative_timeout
+            )))
+        }
+    };
+    let send = MsgSendTx {
+        owner: owner.to_string(),
+        connection_id: connection_id.into(),
+        packet_data: Some(ica_packet_data(messages, memo)),
+        relative_timeout,
+    };
+    Ok(Msg::new(MSG_SEND_TX_TYPE_URL, send))
+}
+
+impl Contact {
+    /// Gets the address of the interchain account owned by the provided address on the host
+    /// chain of the provided connection, returns an error if no account is registered
+    pub async fn get_interchain_account(
+        &self,
+        owner: Address,
+        connection_id: String,
+    ) -> Result<String, CosmosGrpcError> {
+        let res: QueryInterchainAccountResponse = self
+            .grpc_unary(
+                "/ibc.applications.interchain_accounts.controller.v1.Query/InterchainAccount",
+                QueryInterchainAccountRequest {
+                    owner: owner.to_bech32(&self.inner.chain_prefix).unwrap(),
+                    connection_id,
+                },
+            )
+            .await?;
+        Ok(res.address)
+    }
+
+    /// Registers an interchain account owned by the provided private key on the host chain
+    /// of the provided connection, the account is available once a relayer completes the
+    /// channel handshake
+    pub async fn register_interchain_account(
+        &self,
+        connection_id: String,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = register_interchain_account_msg(our_address, connection_id, None);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Executes messages on the host chain of the provided connection using the interchain
+    /// account owned by the provided private key
+    pub async fn send_ica_tx(
+        &self,
+        connection_id: String,
+        messages: Vec<Msg>,
+        relative_timeout: Duration,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = send_ica_tx_msg(our_address, connection_id, messages, "", relative_timeout)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Executes messages with the interchain account owned by the provided private key and
+    /// waits for the host chain to execute them, the full round trip of send_ica_tx. The
+    /// messages must use the interchain account address as their signer. The host Contact is
+    /// connected to the host chain and is searched for the transaction that received the
+    /// packet, which holds the Msg responses. Returns a BadResponse error if the packet is
+    /// neither acknowledged nor timed out within ack_timeout, which is expected when no
+    /// relayer serves the channel.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn execute_ica_tx(
+        &self,
+        host: &Contact,
+        connection_id: String,
+        messages: Vec<Msg>,
+        relative_timeout: Duration,
+        fee: Coin,
+        private_key: PrivateKey,
+        ack_timeout: Duration,
+    ) -> Result<IcaTxReport, CosmosGrpcError> {
+        let start = Instant::now();
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        // fails before anything is sent if the account is not registered
+        let ica_address = self
+            .get_interchain_account(our_address, connection_id.clone())
+            .await?;
+        // the tx must be in a block to read the packet from its events
+        let send_tx = self
+            .send_ica_tx(
+                connection_id,
+                messages,
+                relative_timeout,
+                fee,
+                private_key,
+                Some(ack_timeout),
+            )
+            .await?;
+        let packet = decode_events(&send_tx)?
+            .into_iter()
+            .find_map(|event| match event {
+                TypedEvent::SendPacket(packet)
+                    if packet.src_port.starts_with(ICA_CONTROLLER_PORT_PREFIX) =>
+                {
+                    Some(packet)
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                CosmosGrpcError::BadResponse("No send_packet event in ICA tx".to_string())
+            })?;
+        let remaining = ack_timeout.checked_sub(start.elapsed()).unwrap_or_default();
+        let packet_outcome = self.track_packet(host, &packet, remaining).await?;
+        let outcome = ica_tx_outcome(&packet_outcome)?;
+        Ok(IcaTxReport {
+            ica_address,
+            send_tx,
+            packet,
+            packet_outcome,
+            outcome,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::proto::cosmos::base::abci::v1beta1::TxMsgData;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    #[test]
+    fn test_ica_packet_data() {
+        let send = Msg::new(
+            MSG_SEND_TYPE_URL,
+            MsgSend {
+                from_address: "osmo1ica".to_string(),
+                to_address: "osmo1dest".to_string(),
+                amount: Vec::new(),
+            },
+        );
+        let data = ica_packet_data(vec![send.clone()], "");
+        assert_eq!(data.r#type, IcaPacketType::ExecuteTx as i32);
+        let tx = CosmosTx::decode(data.data.as_slice()).unwrap();
+        assert_eq!(tx.messages, vec![send.into()]);
+    }
+
+    #[test]
+    fn test_send_ica_tx_msg() {
+        let owner = Address::from_slice(&[1; 20], "cosmos").unwrap();
+        let send = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        let msg = send_ica_tx_msg(
+            owner,
+            "connection-0",
+            vec![send.clone()],
+            "memo",
+            Duration::from_secs(600),
+        )
+        .unwrap();
+        let any: Any = msg.into();
+        assert_eq!(any.type_url, MSG_SEND_TX_TYPE_URL);
+        let decoded = MsgSendTx::decode(any.value.as_slice()).unwrap();
+        assert_eq!(decoded.owner, owner.to_string());
+        assert_eq!(decoded.connection_id, "connection-0");
+        assert_eq!(decoded.relative_timeout, 600_000_000_000);
+        assert_eq!(
+            decoded.packet_data,
+            Some(ica_packet_data(vec![send.clone()], "memo"))
+        );
+
+        let no_timeout = send_ica_tx_msg(
+            owner,
+            "connection-0",
+            vec![send.clone()],
+            "",
+            Duration::ZERO,
+        );
+        assert!(matches!(no_timeout, Err(CosmosGrpcError::BadInput(_))));
+        let overflow = send_ica_tx_msg(owner, "connection-0", vec![send], "", Duration::MAX);
+        assert!(matches!(overflow, Err(CosmosGrpcError::BadInput(_))));
+        let empty = send_ica_tx_msg(
+            owner,
+            "connection-0",
+            Vec::new(),
+            "",
+            Duration::from_secs(1),
+        );
+        assert!(matches!(empty, Err(CosmosGrpcError::BadInput(_))));
+
+        let any: Any = register_interchain_account_msg(owner, "connection-0", None).into();
+        assert_eq!(any.type_url, MSG_REGISTER_INTERCHAIN_ACCOUNT_TYPE_URL);
+        let decoded = MsgRegisterInterchainAccount::decode(any.value.as_slice()).unwrap();
+        assert_eq!(decoded.owner, owner.to_string());
+        assert_eq!(decoded.version, "");
+    }
+
+    #[test]
+    fn test_ica_tx_outcome() {
+        let response = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSendResponse".to_string(),
+            value: Vec::new(),
+        };
+        let mut data = Vec::new();
+        TxMsgData {
+            data: Vec::new(),
+            msg_responses: vec![response.clone()],
+        }
+        .encode(&mut data)
+        .unwrap();
+        let acknowledged = |ack: &str| PacketOutcome::Acknowledged {
+            recv_tx: None,
+            ack_tx: TxResponse::default(),
+            acknowledgement: Some(ack.to_string()),
+        };
+        let ack = format!(r#"{{"result":"{}"}}"#, base64::encode(&data));
+        assert_eq!(
+            ica_tx_outcome(&acknowledged(&ack)).unwrap(),
+            IcaTxOutcome::Executed {
+                responses: vec![response]
+            }
+        );
+        let ack = r#"{"error":"ABCI code: 5: error handling packet: see events for details"}"#;
+        match ica_tx_outcome(&acknowledged(ack)).unwrap() {
+            IcaTxOutcome::Failed { error } => assert!(error.starts_with("ABCI code: 5")),
+            other => panic!("Expected Failed, got {:?}", other),
+        }
+        let timed_out = PacketOutcome::TimedOut {
+            timeout_tx: TxResponse::default(),
+        };
+        assert_eq!(ica_tx_outcome(&timed_out).unwrap(), IcaTxOutcome::TimedOut);
+    }
+}
@@ -0,0 +1,153 @@
+//! Interchain queries (ICQ), these let a controller on this chain, usually a contract or
+//! module, query the state of a host chain running the async-icq middleware over an IBC
+//! channel. The packet carries ABCI queries and the acknowledgement carries their responses.
+
+use crate::error::CosmosGrpcError;
+use crate::proto::icq::v1::CosmosQuery;
+use crate::proto::icq::v1::CosmosResponse;
+use crate::proto::icq::v1::InterchainQueryPacketData;
+use prost::Message;
+use serde_json::json;
+use serde_json::Value;
+use tendermint_proto::abci::RequestQuery;
+use tendermint_proto::abci::ResponseQuery;
+
+/// The port the async-icq module binds on host chains
+pub const ICQ_HOST_PORT: &str = "icqhost";
+/// The version ICQ channels are opened with
+pub const ICQ_VERSION: &str = "icq-1";
+
+/// Builds an ABCI query for a gRPC query method, path is the full method path such as
+/// /cosmos.bank.v1beta1.Query/AllBalances. The host chain must allow the path in its
+/// ICQ parameters.
+pub fn icq_query<M: Message>(path: impl Into<String>, request: &M) -> RequestQuery {
+    let mut data = Vec::new();
+    // encoding into a vec can not fail
+    request.encode(&mut data).unwrap();
+    RequestQuery {
+        data,
+        path: path.into(),
+        height: 0,
+        prove: false,
+    }
+}
+
+/// Encodes queries into the data of an ICQ packet, the host chain answers them in order
+pub fn icq_packet_data(
+    queries: Vec<RequestQuery>,
+    memo: impl Into<String>,
+) -> InterchainQueryPacketData {
+    let query = CosmosQuery { requests: queries };
+    let mut data = Vec::new();
+    query.encode(&mut data).unwrap();
+    InterchainQueryPacketData {
+        data,
+        memo: memo.into(),
+    }
+}
+
+/// The bytes sent as the IBC packet data, async-icq uses the JSON encoding of the packet
+pub fn icq_packet_bytes(packet: &InterchainQueryPacketData) -> Vec<u8> {
+    json!({
+        "data": base64::encode(&packet.data),
+        "memo": packet.memo,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+fn bad_ack(reason: impl std::fmt::Display) -> CosmosGrpcError {
+    CosmosGrpcError::BadResponse(format!("Invalid ICQ acknowledgement: {}", reason))
+}
+
+/// Reads a base64 field out of the JSON encoding of an acknowledgement
+fn json_bytes(json: &[u8], field: &str) -> Result<Vec<u8>, CosmosGrpcError> {
+    let value: Value = serde_json::from_slice(json).map_err(bad_ack)?;
+    if let Some(error) = value.get("error").and_then(|e| e.as_str()) {
+        return Err(CosmosGrpcError::BadResponse(format!(
+            "ICQ packet failed on the host chain: {}",
+            error
+        )));
+    }
+    match value.get(field).and_then(|v| v.as_str()) {
+        Some(encoded) => base64::decode(encoded).map_err(bad_ack),
+        None => Err(bad_ack(format!("no {} field", field))),
+    }
+}
+
+/// Decodes the acknowledgement of an ICQ packet, as found in the packet_ack attribute of
+/// the acknowledge_packet event, into the responses to the queries in the order they were
+/// sent. Returns a BadResponse error if the host chain acknowledged with an error.
+pub fn decode_icq_ack(ack: &[u8]) -> Result<Vec<ResponseQuery>, CosmosGrpcError> {
+    // the channel acknowledgement wraps the JSON encoded InterchainQueryPacketAck
+    let packet_ack = json_bytes(ack, "result")?;
+    let data = json_bytes(&packet_ack, "data")?;
+    let response = CosmosResponse::decode(data.as_slice())?;
+    Ok(response.responses)
+}
+
+/// Decodes a single ICQ response into the response type of the query method, returning
+/// a BadResponse error if the host chain failed the query
+pub fn decode_icq_response<M: Message + Default>(
+    response: &ResponseQuery,
+) -> Result<M, CosmosGrpcError> {
+    if response.code != 0 {
+        return Err(CosmosGrpcError::BadResponse(format!(
+            "ICQ query failed with code {} {}",
+            response.code, response.log
+        )));
+    }
+    Ok(M::decode(response.value.as_slice())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceResponse;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+
+    #[test]
+    fn test_icq_round_trip() {
+        let request = QueryBalanceRequest {
+            address: "cosmos1test".to_string(),
+            denom: "uatom".to_string(),
+        };
+        let query = icq_query("/cosmos.bank.v1beta1.Query/Balance", &request);
+        let packet = icq_packet_data(vec![query.clone()], "");
+        let sent: Value = serde_json::from_slice(&icq_packet_bytes(&packet)).unwrap();
+        let data = base64::decode(sent["data"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            CosmosQuery::decode(data.as_slice()).unwrap().requests,
+            vec![query]
+        );
+
+        // the host chain answers with the responses wrapped in two layers of JSON
+        let balance = QueryBalanceResponse {
+            balance: Some(ProtoCoin {
+                denom: "uatom".to_string(),
+                amount: "100".to_string(),
+            }),
+        };
+        let mut value = Vec::new();
+        balance.encode(&mut value).unwrap();
+        let mut responses = Vec::new();
+        CosmosResponse {
+            responses: vec![ResponseQuery {
+                value,
+                ..ResponseQuery::default()
+            }],
+        }
+        .encode(&mut responses)
+        .unwrap();
+        let packet_ack = json!({ "data": base64::encode(&responses) }).to_string();
+        let ack = json!({ "result": base64::encode(packet_ack) }).to_string();
+        let decoded = decode_icq_ack(ack.as_bytes()).unwrap();
+        assert_eq!(decoded.len(), 1);
+        let decoded: QueryBalanceResponse = decode_icq_response(&decoded[0]).unwrap();
+        assert_eq!(decoded, balance);
+
+        let error = json!({ "error": "ABCI code: 5" }).to_string();
+        assert!(decode_icq_ack(error.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! IBC light client maintenance messages, for watchtowers and relayers that keep clients
+//! from expiring and report conflicting headers so the client is frozen.
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::ibc::core::client::v1::MsgSubmitMisbehaviour;
+use crate::proto::ibc::core::client::v1::MsgUpdateClient;
+use crate::proto::ibc::core::client::v1::MsgUpgradeClient;
+use crate::proto::ibc::lightclients::tendermint::v1::Misbehaviour;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use prost_types::Any;
+use std::time::Duration;
+
+pub use crate::proto::ibc::lightclients::tendermint::v1::Header as TendermintClientHeader;
+
+pub const MSG_UPDATE_CLIENT_TYPE_URL: &str = "/ibc.core.client.v1.MsgUpdateClient";
+pub const MSG_UPGRADE_CLIENT_TYPE_URL: &str = "/ibc.core.client.v1.MsgUpgradeClient";
+pub const MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL: &str = "/ibc.core.client.v1.MsgSubmitMisbehaviour";
+pub const TENDERMINT_HEADER_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.Header";
+pub const TENDERMINT_MISBEHAVIOUR_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.Misbehaviour";
+
+/// Packs a Tendermint light client header for use as a client message
+pub fn tendermint_header_any(header: TendermintClientHeader) -> Any {
+    encode_any(header, TENDERMINT_HEADER_TYPE_URL.to_string())
+}
+
+/// Packs two conflicting Tendermint headers for the same height as misbehaviour evidence
+pub fn tendermint_misbehaviour_any(
+    client_id: impl Into<String>,
+    header_1: TendermintClientHeader,
+    header_2: TendermintClientHeader,
+) -> Any {
+    let misbehaviour = Misbehaviour {
+        client_id: client_id.into(),
+        header_1: Some(header_1),
+        header_2: Some(header_2),
+    };
+    encode_any(misbehaviour, TENDERMINT_MISBEHAVIOUR_TYPE_URL.to_string())
+}
+
+/// Builds a MsgUpdateClient, the client message is a packed header, see
+/// tendermint_header_any(). Since ibc-go v7 the client message may also be misbehaviour.
+pub fn update_client_msg(
+    signer: Address,
+    client_id: impl Into<String>,
+    client_message: Any,
+) -> Msg {
+    let update = MsgUpdateClient {
+        client_id: client_id.into(),
+        client_message: Some(client_message),
+        signer: signer.to_string(),
+    };
+    Msg::new(MSG_UPDATE_CLIENT_TYPE_URL, update)
+}
+
+/// Builds a MsgUpgradeClient moving the client to the client and consensus state the
+/// counterparty committed to in its upgrade plan, along with the proofs of that commitment
+pub fn upgrade_client_msg(
+    signer: Address,
+    client_id: impl Into<String>,
+    client_state: Any,
+    consensus_state: Any,
+    proof_upgrade_client: Vec<u8>,
+    proof_upgrade_consensus_state: Vec<u8>,
+) -> Msg {
+    let upgrade = MsgUpgradeClient {
+        client_id: client_id.into(),
+        client_state: Some(client_state),
+        consensus_state: Some(consensus_state),
+        proof_upgrade_client,
+        proof_upgrade_consensus_state,
+        signer: signer.to_string(),
+    };
+    Msg::new(MSG_UPGRADE_CLIENT_TYPE_URL, upgrade)
+}
+
+/// Builds a MsgSubmitMisbehaviour, the misbehaviour is packed evidence, see
+/// tendermint_misbehaviour_any()
+pub fn submit_misbehaviour_msg(
+    signer: Address,
+    client_id: impl Into<String>,
+    misbehaviour: Any,
+) -> Msg {
+    let submit = MsgSubmitMisbehaviour {
+        client_id: client_id.into(),
+        misbehaviour: Some(misbehaviour),
+        signer: signer.to_string(),
+    };
+    Msg::new(MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL, submit)
+}
+
+impl Contact {
+    /// Updates a Tendermint light client with a new header, signed by the provided private key
+    pub async fn update_ibc_client(
+        &self,
+        client_id: String,
+        header: TendermintClientHeader,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = update_client_msg(our_address, client_id, tendermint_header_any(header));
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Reports two conflicting headers to a Tendermint light client, freezing it if the
+    /// evidence is valid
+    pub async fn submit_ibc_misbehaviour(
+        &self,
+        client_id: String,
+        header_1: TendermintClientHeader,
+        header_2: TendermintClientHeader,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let misbehaviour = tendermint_misbehaviour_any(client_id.clone(), header_1, header_2);
+        let msg = submit_misbehaviour_msg(our_address, client_id, misbehaviour);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::ibc::core::client::v1::Height;
+    use prost::Message;
+
+    #[test]
+    fn test_misbehaviour_packing() {
+        let signer = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let header = |height| TendermintClientHeader {
+            trusted_height: Some(Height {
+                revision_number: 4,
+                revision_height: height,
+            }),
+            ..TendermintClientHeader::default()
+        };
+        let evidence = tendermint_misbehaviour_any("07-tendermint-0", header(1), header(2));
+        assert_eq!(evidence.type_url, TENDERMINT_MISBEHAVIOUR_TYPE_URL);
+        let msg = submit_misbehaviour_msg(signer, "07-tendermint-0", evidence);
+        assert_eq!(msg.0.type_url, MSG_SUBMIT_MISBEHAVIOUR_TYPE_URL);
+
+        let submit = MsgSubmitMisbehaviour::decode(msg.0.value.as_slice()).unwrap();
+        assert_eq!(submit.client_id, "07-tendermint-0");
+        let misbehaviour =
+            Misbehaviour::decode(submit.misbehaviour.unwrap().value.as_slice()).unwrap();
+        assert_eq!(misbehaviour.header_1, Some(header(1)));
+        assert_eq!(misbehaviour.header_2, Some(header(2)));
+    }
+}
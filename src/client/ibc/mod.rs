@@ -0,0 +1,425 @@
+//! Contains utility functions for sending ICS-20 fungible token transfers over IBC and
+//! tracking them until they are acknowledged by the counterparty chain.
+
+use crate::any::AnyExt;
+use crate::client::events::tx_events;
+use crate::client::runtime::sleep;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::ibc::applications::transfer::v1::MsgTransfer;
+use crate::proto::ibc::core::channel::v1::QueryChannelClientStateRequest;
+use crate::proto::ibc::core::channel::v1::QueryChannelClientStateResponse;
+use crate::proto::ibc::lightclients::tendermint::v1::ClientState as TendermintClientState;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+mod fee;
+mod forward;
+mod ica;
+#[cfg(feature = "icq")]
+mod icq;
+mod light_client;
+mod tracking;
+
+pub use crate::proto::ibc::core::client::v1::Height;
+pub use fee::*;
+pub use forward::*;
+pub use ica::*;
+#[cfg(feature = "icq")]
+pub use icq::*;
+pub use light_client::*;
+pub use tracking::*;
+
+pub const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+pub const TRANSFER_PORT: &str = "transfer";
+pub const TENDERMINT_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.tendermint.v1.ClientState";
+
+/// The number of counterparty blocks a transfer using IbcTimeout::Default has to be received in
+pub const DEFAULT_TIMEOUT_HEIGHT_OFFSET: u64 = 1000;
+/// The amount of time a transfer using IbcTimeout::Default has to be received in
+pub const DEFAULT_TIMEOUT_DURATION: Duration = Duration::from_secs(600);
+
+/// Determines when an IBC transfer times out, if a packet is not received on the counterparty
+/// chain before the timeout the funds are refunded to the sender once a relayer submits
+/// the timeout proof.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IbcTimeout {
+    /// Times out DEFAULT_TIMEOUT_HEIGHT_OFFSET blocks after the latest counterparty height known
+    /// to the channel's light client or DEFAULT_TIMEOUT_DURATION from now, whichever is first
+    Default,
+    /// Times out after the provided duration from now
+    Relative(Duration),
+    /// Times out at the provided counterparty height
+    Height(Height),
+    /// Times out at the provided time
+    Timestamp(SystemTime),
+}
+
+/// Builds an ICS-20 MsgTransfer over the transfer port, at least one of timeout_height and
+/// timeout_timestamp must be provided. The receiver is an address on the counterparty chain
+/// and so is provided as a string as it may not use the same format as this chain.
+pub fn transfer_msg(
+    sender: Address,
+    channel: impl Into<String>,
+    receiver: impl Into<String>,
+    token: Coin,
+    timeout_height: Option<Height>,
+    timeout_timestamp: Option<SystemTime>,
+    memo: Option<String>,
+) -> Result<Msg, CosmosGrpcError> {
+    if timeout_height.is_none() && timeout_timestamp.is_none() {
+        return Err(CosmosGrpcError::BadInput(
+            "IBC transfers require a timeout height or timestamp".to_string(),
+        ));
+    }
+    let timeout_timestamp = match timeout_timestamp {
+        Some(time) => match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => since_epoch.as_nanos() as u64,
+            Err(_) => {
+                return Err(CosmosGrpcError::BadInput(
+                    "IBC timeout timestamp is before the unix epoch".to_string(),
+                ))
+            }
+        },
+        None => 0,
+    };
+    let transfer = MsgTransfer {
+        source_port: TRANSFER_PORT.to_string(),
+        source_channel: channel.into(),
+        token: Some(token.into()),
+        sender: sender.to_string(),
+        receiver: receiver.into(),
+        timeout_height,
+        timeout_timestamp,
+        memo: memo.unwrap_or_default(),
+    };
+    Ok(Msg::new(MSG_TRANSFER_TYPE_URL, transfer))
+}
+
+/// Gets the packet sequence from the send_packet event of a transaction containing a
+/// single MsgTransfer, returns None if the transaction has no send_packet event
+pub fn packet_sequence(response: &TxResponse) -> Option<u64> {
+    for event in tx_events(response).iter() {
+        if event.r#type != "send_packet" {
+            continue;
+        }
+        for attribute in event.attributes.iter() {
+            if attribute.key == "packet_sequence" {
+                return attribute.value.parse().ok();
+            }
+        }
+    }
+    None
+}
+
+impl Contact {
+    /// Gets the latest counterparty height known to the light client backing the provided
+    /// transfer channel, this is the height the counterparty chain was at as of the last
+    /// time a relayer updated the client.
+    pub async fn get_counterparty_height(
+        &self,
+        channel: String,
+    ) -> Result<Height, CosmosGrpcError> {
+        let res: QueryChannelClientStateResponse = self
+            .grpc_unary(
+                "/ibc.core.channel.v1.Query/ChannelClientState",
+                QueryChannelClientStateRequest {
+                    port_id: TRANSFER_PORT.to_string(),
+                    channel_id: channel,
+                },
+            )
+            .await?;
+        let client_state = match res.identified_client_state.and_then(|c| c.client_state) {
+            Some(client_state) => client_state,
+            None => {
+                return Err(CosmosGrpcError::BadResponse(
+                    "No client state for channel?".to_string(),
+                ))
+            }
+        };
+        if !client_state.is::<TendermintClientState>() {
+            return Err(CosmosGrpcError::BadResponse(format!(
+                "Unsupported light client {}, provide an explicit timeout",
+                client_state.type_url
+            )));
+        }
+        let client_state: TendermintClientState = client_state.unpack()?;
+        match client_state.latest_height {
+            Some(height) => Ok(height),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No latest height in client state?".to_string(),
+            )),
+        }
+    }
+
+    /// Sends an ICS-20 transfer of the provided coin from the provided private key to the receiver
+    /// over the transfer port of the provided channel. The memo is placed in the packet and is used
+    /// by middleware on the counterparty chain, it is not the transaction memo. If an ack_timeout is
+    /// provided this will additionally wait for the packet acknowledgement to be relayed back to
+    /// this chain and return the transaction that contains it, otherwise the transfer transaction
+    /// is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn ibc_transfer(
+        &self,
+        channel: String,
+        receiver: String,
+        coin: Coin,
+        timeout: IbcTimeout,
+        memo: Option<String>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+        ack_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+
+        let (timeout_height, timeout_timestamp) = match timeout {
+            IbcTimeout::Default => {
+                let mut height = self.get_counterparty_height(channel.clone()).await?;
+                height.revision_height += DEFAULT_TIMEOUT_HEIGHT_OFFSET;
+                (
+                    Some(height),
+                    Some(SystemTime::now() + DEFAULT_TIMEOUT_DURATION),
+                )
+            }
+            IbcTimeout::Relative(duration) => (None, Some(SystemTime::now() + duration)),
+            IbcTimeout::Height(height) => (Some(height), None),
+            IbcTimeout::Timestamp(time) => (None, Some(time)),
+        };
+        let msg = transfer_msg(
+            our_address,
+            channel.clone(),
+            receiver,
+            coin,
+            timeout_height,
+            timeout_timestamp,
+            memo,
+        )?;
+
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        // we need the tx to be in a block to read the packet sequence
+        let wait_timeout = match (wait_timeout, ack_timeout) {
+            (None, Some(ack_timeout)) => Some(ack_timeout),
+            (wait_timeout, _) => wait_timeout,
+        };
+        let response = self
+            .send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await?;
+
+        match ack_timeout {
+            Some(ack_timeout) => match packet_sequence(&response) {
+                Some(sequence) => self.wait_for_ibc_ack(channel, sequence, ack_timeout).await,
+                None => Err(CosmosGrpcError::BadResponse(
+                    "No send_packet event in transfer tx".to_string(),
+                )),
+            },
+            None => Ok(response),
+        }
+    }
+
+    /// Waits for the acknowledgement of the packet with the provided sequence sent over the
+    /// transfer port of the provided channel, returning the transaction that relayed the
+    /// acknowledgement. Note that a successful acknowledgement only means the packet was
+    /// processed, the counterparty may have acknowledged it with an error in which case the
+    /// funds are refunded, this is returned as a BadResponse error. If the timeout of the
+    /// packet is relayed instead an IbcPacketTimedOut error is returned, the funds are
+    /// refunded in that case as well.
+    pub async fn wait_for_ibc_ack(
+        &self,
+        channel: String,
+        sequence: u64,
+        timeout: Duration,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let start = Instant::now();
+        while Instant::now() - start < timeout {
+            if let Some(tx) = self
+                .find_transfer_packet_tx("acknowledge_packet", &channel, sequence)
+                .await?
+            {
+                if let Some(error) = ack_error(&tx) {
+                    return Err(CosmosGrpcError::BadResponse(format!(
+                        "IBC transfer acknowledged with error {}",
+                        error
+                    )));
+                }
+                return Ok(tx);
+            }
+            if let Some(tx) = self
+                .find_transfer_packet_tx("timeout_packet", &channel, sequence)
+                .await?
+            {
+                return Err(CosmosGrpcError::IbcPacketTimedOut {
+                    channel,
+                    sequence,
+                    tx_hash: tx.txhash,
+                });
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        Err(CosmosGrpcError::BadResponse(format!(
+            "IBC packet {} on {} not acknowledged in {}ms",
+            sequence,
+            channel,
+            timeout.as_millis()
+        )))
+    }
+}
+
+impl Contact {
+    /// Finds the transaction containing an event of a packet sent over the transfer port of
+    /// the channel
+    async fn find_transfer_packet_tx(
+        &self,
+        event_type: &str,
+        channel: &str,
+        sequence: u64,
+    ) -> Result<Option<TxResponse>, CosmosGrpcError> {
+        let events = vec![
+            format!("{}.packet_src_port='{}'", event_type, TRANSFER_PORT),
+            format!("{}.packet_src_channel='{}'", event_type, channel),
+            format!("{}.packet_sequence='{}'", event_type, sequence),
+        ];
+        let res = self.get_txs_event(events, None).await?;
+        Ok(res.tx_responses.into_iter().next())
+    }
+}
+
+/// Returns the error from the fungible_token_packet event emitted when a transfer
+/// acknowledgement is processed, if the counterparty reported one
+fn ack_error(response: &TxResponse) -> Option<String> {
+    for event in tx_events(response).iter() {
+        if event.r#type != "fungible_token_packet" {
+            continue;
+        }
+        for attribute in event.attributes.iter() {
+            if attribute.key == "error" && !attribute.value.is_empty() {
+                return Some(attribute.value.clone());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::get::txs_event_request;
+    use crate::proto::cosmos::tx::v1beta1::GetTxsEventResponse;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::StringEvent;
+
+    #[test]
+    fn test_packet_sequence() {
+        let response = TxResponse {
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "send_packet".to_string(),
+                    attributes: vec![
+                        Attribute {
+                            key: "packet_src_channel".to_string(),
+                            value: "channel-0".to_string(),
+                        },
+                        Attribute {
+                            key: "packet_sequence".to_string(),
+                            value: "42".to_string(),
+                        },
+                    ],
+                }],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(packet_sequence(&response), Some(42));
+        assert_eq!(packet_sequence(&TxResponse::default()), None);
+    }
+
+    #[test]
+    fn test_transfer_requires_timeout() {
+        let sender = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let coin = Coin {
+            amount: 1u64.into(),
+            denom: "uatom".to_string(),
+        };
+        assert!(
+            transfer_msg(sender, "channel-0", "osmo1", coin.clone(), None, None, None).is_err()
+        );
+        assert!(transfer_msg(
+            sender,
+            "channel-0",
+            "osmo1",
+            coin,
+            None,
+            Some(SystemTime::now()),
+            None
+        )
+        .is_ok());
+    }
+
+    fn encode(msg: impl prost::Message) -> Vec<u8> {
+        let mut buf = Vec::new();
+        msg.encode(&mut buf).unwrap();
+        buf
+    }
+
+    #[actix_rt::test]
+    async fn test_wait_for_ibc_ack_timed_out() {
+        let request = |event_type: &str| {
+            let events = vec![
+                format!("{}.packet_src_port='transfer'", event_type),
+                format!("{}.packet_src_channel='channel-0'", event_type),
+                format!("{}.packet_sequence='42'", event_type),
+            ];
+            encode(txs_event_request(events, None))
+        };
+        let not_acknowledged = GetTxsEventResponse::default();
+        let timed_out = GetTxsEventResponse {
+            tx_responses: vec![TxResponse {
+                txhash: "ABCD".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let file = std::env::temp_dir().join(format!(
+            "deep_space_ibc_timeout_{}.json",
+            rand::random::<u64>()
+        ));
+        let recorder = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_recording(&file);
+        let tape = recorder.inner.tape.as_ref().unwrap();
+        let path = "/cosmos.tx.v1beta1.Service/GetTxsEvent";
+        let response = encode(not_acknowledged);
+        tape.save(path, &request("acknowledge_packet"), Ok(&response))
+            .unwrap();
+        let response = encode(timed_out);
+        tape.save(path, &request("timeout_packet"), Ok(&response))
+            .unwrap();
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        match contact
+            .wait_for_ibc_ack("channel-0".to_string(), 42, Duration::from_secs(5))
+            .await
+        {
+            Err(CosmosGrpcError::IbcPacketTimedOut {
+                channel,
+                sequence,
+                tx_hash,
+            }) => {
+                assert_eq!((channel.as_str(), sequence), ("channel-0", 42));
+                assert_eq!(tx_hash, "ABCD");
+            }
+            other => panic!("Expected IbcPacketTimedOut, got {:?}", other),
+        }
+    }
+}
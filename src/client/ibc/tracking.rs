@@ -0,0 +1,255 @@
+//! Tracking of a packet across both ends of a channel. A sent packet is searched for on
+//! the counterparty chain until it is received, then on the sending chain until the
+//! acknowledgement or a timeout is relayed back, which is when the outcome is final.
+//!
+//! ```ignore
+//! let response = contact.ibc_transfer(channel, receiver, coin, timeout, None, fee, key,
+//!     Some(wait), None).await?;
+//! for event in decode_events(&response)? {
+//!     if let TypedEvent::SendPacket(packet) = event {
+//!         let outcome = contact.track_packet(&osmosis, &packet, ack_timeout).await?;
+//!     }
+//! }
+//! ```
+
+use super::ack_error;
+use crate::client::events::split_event;
+use crate::client::events::tx_events;
+use crate::client::events::PacketEvent;
+use crate::client::runtime::sleep;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use serde_json::Value;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The interval between two searches, each searches both chains
+const TRACK_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The final state of a packet
+#[derive(Debug, Clone, PartialEq)]
+pub enum PacketOutcome {
+    /// the packet was received and its acknowledgement relayed back to the sending chain,
+    /// the acknowledgement may still report an error, see ack_error
+    Acknowledged {
+        /// the counterparty transaction that received the packet, None if the counterparty
+        /// node did not return it
+        recv_tx: Option<TxResponse>,
+        /// the transaction that relayed the acknowledgement to the sending chain
+        ack_tx: TxResponse,
+        /// the acknowledgement written by the counterparty, as in the packet_ack attribute
+        acknowledgement: Option<String>,
+    },
+    /// the packet was not received in time and the timeout was relayed to the sending
+    /// chain, ICS-20 transfers are refunded at this point
+    TimedOut { timeout_tx: TxResponse },
+}
+
+impl PacketOutcome {
+    /// The error the counterparty acknowledged the packet with, None if the packet was
+    /// processed successfully or timed out
+    pub fn ack_error(&self) -> Option<String> {
+        match self {
+            PacketOutcome::Acknowledged {
+                ack_tx,
+                acknowledgement,
+                ..
+            } => {
+                let written = acknowledgement
+                    .as_ref()
+                    .and_then(|ack| serde_json::from_str::<Value>(ack).ok())
+                    .and_then(|ack| ack.get("error")?.as_str().map(|e| e.to_string()));
+                written.or_else(|| ack_error(ack_tx))
+            }
+            PacketOutcome::TimedOut { .. } => None,
+        }
+    }
+}
+
+/// Finds the acknowledgement written for a packet in the transaction that received it
+pub fn written_acknowledgement(recv_tx: &TxResponse, packet: &PacketEvent) -> Option<String> {
+    let sequence = packet.sequence.to_string();
+    for event in tx_events(recv_tx).iter() {
+        if event.r#type != "write_acknowledgement" {
+            continue;
+        }
+        for attrs in split_event(event) {
+            let find = |key: &str| {
+                attrs
+                    .iter()
+                    .find(|(k, _)| k == key)
+                    .map(|(_, v)| v.as_str())
+            };
+            if find("packet_sequence") == Some(sequence.as_str())
+                && find("packet_dst_channel") == Some(packet.dst_channel.as_str())
+            {
+                return find("packet_ack").map(|ack| ack.to_string());
+            }
+        }
+    }
+    None
+}
+
+impl Contact {
+    /// Tracks a packet sent from this chain, as decoded from a send_packet event, until its
+    /// acknowledgement or timeout is relayed back. The counterparty Contact is connected to
+    /// the receiving chain. Returns a BadResponse error if neither happens within timeout,
+    /// which is expected when no relayer serves the channel.
+    pub async fn track_packet(
+        &self,
+        counterparty: &Contact,
+        packet: &PacketEvent,
+        timeout: Duration,
+    ) -> Result<PacketOutcome, CosmosGrpcError> {
+        let start = Instant::now();
+        let mut recv_tx = None;
+        while Instant::now() - start < timeout {
+            if recv_tx.is_none() {
+                recv_tx = counterparty
+                    .find_packet_tx("recv_packet", "dst", packet)
+                    .await?;
+            }
+            if let Some(ack_tx) = self
+                .find_packet_tx("acknowledge_packet", "src", packet)
+                .await?
+            {
+                let acknowledgement = recv_tx
+                    .as_ref()
+                    .and_then(|tx| written_acknowledgement(tx, packet));
+                return Ok(PacketOutcome::Acknowledged {
+                    recv_tx,
+                    ack_tx,
+                    acknowledgement,
+                });
+            }
+            // a received packet can no longer time out
+            if recv_tx.is_none() {
+                if let Some(timeout_tx) =
+                    self.find_packet_tx("timeout_packet", "src", packet).await?
+                {
+                    return Ok(PacketOutcome::TimedOut { timeout_tx });
+                }
+            }
+            sleep(TRACK_POLL_INTERVAL).await;
+        }
+        Err(CosmosGrpcError::BadResponse(format!(
+            "IBC packet {} on {} {} in {}ms",
+            packet.sequence,
+            packet.src_channel,
+            if recv_tx.is_some() {
+                "received but not acknowledged"
+            } else {
+                "not received or timed out"
+            },
+            timeout.as_millis()
+        )))
+    }
+
+    /// Finds the transaction containing an event of a packet, side is src for events on the
+    /// sending chain and dst for events on the receiving chain
+    async fn find_packet_tx(
+        &self,
+        event_type: &str,
+        side: &str,
+        packet: &PacketEvent,
+    ) -> Result<Option<TxResponse>, CosmosGrpcError> {
+        let (port, channel) = match side {
+            "src" => (&packet.src_port, &packet.src_channel),
+            _ => (&packet.dst_port, &packet.dst_channel),
+        };
+        let events = vec![
+            format!("{}.packet_{}_port='{}'", event_type, side, port),
+            format!("{}.packet_{}_channel='{}'", event_type, side, channel),
+            format!("{}.packet_sequence='{}'", event_type, packet.sequence),
+        ];
+        let res = self.get_txs_event(events, None).await?;
+        Ok(res.tx_responses.into_iter().next())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Attribute;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::StringEvent;
+
+    /// The attributes of a write_acknowledgement event in the order ibc-go emits them
+    fn write_ack(sequence: &str, ack: &str) -> Vec<Attribute> {
+        [
+            ("packet_data", "{}"),
+            ("packet_data_hex", "7b7d"),
+            ("packet_timeout_height", "0-0"),
+            ("packet_timeout_timestamp", "1700000000000000000"),
+            ("packet_sequence", sequence),
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "transfer"),
+            ("packet_dst_channel", "channel-141"),
+            ("packet_ack", ack),
+            ("packet_ack_hex", ""),
+            ("packet_connection", "connection-257"),
+            ("connection_id", "connection-257"),
+        ]
+        .iter()
+        .map(|(k, v)| Attribute {
+            key: k.to_string(),
+            value: v.to_string(),
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_written_acknowledgement() {
+        // a relayer received two packets in one transaction
+        let mut attributes = write_ack("6", r#"{"result":"AQ=="}"#);
+        attributes.extend(write_ack(
+            "7",
+            r#"{"error":"ABCI code: 1: error handling packet"}"#,
+        ));
+        let recv_tx = TxResponse {
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "write_acknowledgement".to_string(),
+                    attributes,
+                }],
+            }],
+            ..TxResponse::default()
+        };
+        let mut packet = PacketEvent {
+            sequence: 7,
+            src_port: "transfer".to_string(),
+            src_channel: "channel-0".to_string(),
+            dst_port: "transfer".to_string(),
+            dst_channel: "channel-141".to_string(),
+            data: None,
+            timeout_height: String::new(),
+            timeout_timestamp: 0,
+        };
+        let acknowledgement = written_acknowledgement(&recv_tx, &packet);
+        let outcome = PacketOutcome::Acknowledged {
+            recv_tx: Some(recv_tx.clone()),
+            ack_tx: TxResponse::default(),
+            acknowledgement,
+        };
+        assert_eq!(
+            outcome.ack_error(),
+            Some("ABCI code: 1: error handling packet".to_string())
+        );
+
+        packet.sequence = 6;
+        assert_eq!(
+            written_acknowledgement(&recv_tx, &packet),
+            Some(r#"{"result":"AQ=="}"#.to_string())
+        );
+        packet.sequence = 8;
+        assert_eq!(written_acknowledgement(&recv_tx, &packet), None);
+        let timed_out = PacketOutcome::TimedOut {
+            timeout_tx: TxResponse::default(),
+        };
+        assert_eq!(timed_out.ack_error(), None);
+    }
+}
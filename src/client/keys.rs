@@ -0,0 +1,178 @@
+//! Derivation of the keys of a mnemonic for the chain a Contact is connected to. Chains
+//! differ in the coin type of their HD path and, for Ethermint based chains such as Cronos,
+//! Evmos or Injective, in how the address is derived from the key, so onboarding code asks
+//! the Contact instead of branching per chain.
+//!
+//! ```ignore
+//! let (contact, _) = Contact::from_chain_registry(dir, "cronos", timeout)?;
+//! let key = contact.derive_key(phrase, "", 0)?;
+//! println!("{} {:?}", key.address, key.eth_address);
+//! ```
+//!
+//! The coin type is the one set with Contact::with_coin_type, which ChainInfo::contact sets
+//! from the chain registry, or otherwise the one CoinTypeRegistry::with_defaults has for the
+//! prefix. Keys derived for coin type 60 have their Ethermint address, but transactions
+//! signed by PrivateKey carry a Cosmos secp256k1 public key and are rejected by those chains.
+
+use crate::client::Contact;
+use crate::coin_type::hd_path;
+use crate::coin_type::CoinTypeRegistry;
+use crate::error::PrivateKeyError;
+use crate::hardware_wallet::HdPath;
+use crate::Address;
+use crate::PrivateKey;
+use std::sync::Arc;
+
+/// The coin type of Ethereum, used by Ethermint chains
+pub const ETH_COIN_TYPE: u32 = 60;
+
+/// How an address is derived from a secp256k1 key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// the Cosmos SDK secp256k1 key, ripemd160 of the sha256 of the compressed public key
+    Secp256k1,
+    /// the Ethermint eth_secp256k1 key, the Ethereum address of the key
+    EthSecp256k1,
+}
+
+impl KeyAlgorithm {
+    /// The algorithm of keys derived with the coin type
+    pub fn for_coin_type(coin_type: u32) -> Self {
+        if coin_type == ETH_COIN_TYPE {
+            KeyAlgorithm::EthSecp256k1
+        } else {
+            KeyAlgorithm::Secp256k1
+        }
+    }
+}
+
+/// A key derived from a mnemonic with the address the chain uses for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivedKey {
+    pub private_key: PrivateKey,
+    pub hd_path: HdPath,
+    pub algorithm: KeyAlgorithm,
+    /// the bech32 address with the chain prefix
+    pub address: Address,
+    /// the EIP-55 checksummed 0x address, only for EthSecp256k1 keys
+    pub eth_address: Option<String>,
+}
+
+/// Derives the key with the given index on m/44'/coin_type'/0'/0/index and its address for
+/// the prefix
+pub fn derive_key(
+    phrase: &str,
+    passphrase: &str,
+    prefix: &str,
+    coin_type: u32,
+    index: u32,
+) -> Result<DerivedKey, PrivateKeyError> {
+    let path = hd_path(coin_type, index);
+    let private_key = PrivateKey::from_hd_wallet_path(&path.to_string(), phrase, passphrase)?;
+    let algorithm = KeyAlgorithm::for_coin_type(coin_type);
+    let (address, eth_address) = match algorithm {
+        KeyAlgorithm::Secp256k1 => (private_key.to_address(prefix)?, None),
+        KeyAlgorithm::EthSecp256k1 => (
+            private_key.to_ethermint_address(prefix)?,
+            Some(private_key.to_eth_address()?),
+        ),
+    };
+    Ok(DerivedKey {
+        private_key,
+        hd_path: path,
+        algorithm,
+        address,
+        eth_address,
+    })
+}
+
+impl Contact {
+    /// Sets the coin type keys are derived with for this chain, replacing the one looked up
+    /// by prefix. The returned Contact does not share its connection with clones of this one.
+    pub fn with_coin_type(self, coin_type: u32) -> Self {
+        let mut inner = self.unshared();
+        inner.coin_type = Some(coin_type);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// The coin type keys are derived with for this chain, see the module documentation
+    pub fn coin_type(&self) -> u32 {
+        match self.inner.coin_type {
+            Some(coin_type) => coin_type,
+            None => CoinTypeRegistry::with_defaults().coin_type(&self.inner.chain_prefix),
+        }
+    }
+
+    /// Derives the key with the given index of a mnemonic for this chain
+    pub fn derive_key(
+        &self,
+        phrase: &str,
+        passphrase: &str,
+        index: u32,
+    ) -> Result<DerivedKey, PrivateKeyError> {
+        derive_key(
+            phrase,
+            passphrase,
+            &self.inner.chain_prefix,
+            self.coin_type(),
+            index,
+        )
+    }
+
+    /// Derives the first count keys of a mnemonic for this chain
+    pub fn derive_keys(
+        &self,
+        phrase: &str,
+        passphrase: &str,
+        count: u32,
+    ) -> Result<Vec<DerivedKey>, PrivateKeyError> {
+        (0..count)
+            .map(|index| self.derive_key(phrase, passphrase, index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const PHRASE: &str = "test test test test test test test test test test test junk";
+
+    #[test]
+    fn test_derive_key() {
+        let timeout = Duration::from_secs(1);
+        let cronos = Contact::new("http://localhost:9090", timeout, "crc").unwrap();
+        assert_eq!(cronos.coin_type(), ETH_COIN_TYPE);
+        let key = cronos.derive_key(PHRASE, "", 0).unwrap();
+        assert_eq!(key.hd_path.to_string(), "m/44'/60'/0'/0/0");
+        assert_eq!(key.algorithm, KeyAlgorithm::EthSecp256k1);
+        assert_eq!(
+            key.eth_address.as_deref(),
+            Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266")
+        );
+        assert_eq!(key.address.get_prefix(), "crc");
+        assert_eq!(
+            key.address.as_bytes(),
+            &key.private_key.to_eth_address_bytes().unwrap()
+        );
+
+        let cosmos = Contact::new("http://localhost:9090", timeout, "cosmos").unwrap();
+        let keys = cosmos.derive_keys(PHRASE, "", 2).unwrap();
+        assert_eq!(keys[1].hd_path, HdPath::new(118, 0, 1));
+        assert_eq!(keys[0].eth_address, None);
+        assert_eq!(
+            keys[0].address,
+            keys[0].private_key.to_address("cosmos").unwrap()
+        );
+
+        // an Ethermint chain using a prefix the defaults do not know
+        let custom = cosmos.with_coin_type(ETH_COIN_TYPE);
+        assert_eq!(
+            custom.derive_key(PHRASE, "", 0).unwrap().eth_address,
+            key.eth_address
+        );
+    }
+}
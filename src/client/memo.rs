@@ -0,0 +1,228 @@
+//! Structured transaction memos. Applications put deposit tags, packet forwarding
+//! instructions and contract hooks in the memo, this builds and parses the common formats
+//! and checks memos against the chain limit before signing instead of truncating them.
+
+use crate::client::ibc::ForwardHop;
+use crate::client::ibc::ForwardMemo;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::error::TxLimitError;
+use serde::Serialize;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value;
+use std::time::Duration;
+
+/// The default max_memo_characters of the auth module, chains may set another limit
+pub const DEFAULT_MAX_MEMO_BYTES: u64 = 256;
+/// The longest deposit tag accepted by DepositTag
+pub const MAX_DEPOSIT_TAG_LENGTH: usize = 64;
+
+/// A memo in one of the formats applications commonly use
+#[derive(Debug, Clone, PartialEq)]
+pub enum Memo {
+    Empty,
+    /// free form text
+    Text(String),
+    /// the tag an exchange uses to credit a deposit to an account, letters, digits, dashes
+    /// and underscores only
+    DepositTag(String),
+    /// packet-forward-middleware instructions for an IBC transfer
+    Forward(ForwardMemo),
+    /// an ibc-hooks call of a contract on the receiving chain of an IBC transfer
+    WasmHook {
+        contract: String,
+        msg: Value,
+    },
+    /// any other JSON object
+    Json(Map<String, Value>),
+}
+
+/// Checks that a memo fits in max_bytes and contains no control characters, which wallets
+/// and explorers display inconsistently
+pub fn validate_memo(memo: &str, max_bytes: u64) -> Result<(), CosmosGrpcError> {
+    let length = memo.len() as u64;
+    if length > max_bytes {
+        return Err(TxLimitError::MemoTooLong {
+            length,
+            max: max_bytes,
+        }
+        .into());
+    }
+    if memo.chars().any(|c| c.is_control()) {
+        return Err(CosmosGrpcError::BadInput(
+            "Memo contains control characters".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Serializes a value into a JSON memo, failing if it is longer than max_bytes
+pub fn json_memo<T: Serialize>(value: &T, max_bytes: u64) -> Result<String, CosmosGrpcError> {
+    let memo =
+        serde_json::to_string(value).map_err(|e| CosmosGrpcError::BadInput(e.to_string()))?;
+    validate_memo(&memo, max_bytes)?;
+    Ok(memo)
+}
+
+fn is_deposit_tag(memo: &str) -> bool {
+    !memo.is_empty()
+        && memo.len() <= MAX_DEPOSIT_TAG_LENGTH
+        && memo
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Parses the forward field of a PFM memo, timeouts can be nanoseconds or a Go duration
+/// string in seconds such as "600s"
+fn parse_forward(mut forward: &Value) -> Option<ForwardMemo> {
+    let mut memo = ForwardMemo::new();
+    loop {
+        let receiver = forward.get("receiver")?.as_str()?;
+        let channel = forward.get("channel")?.as_str()?;
+        let mut hop = ForwardHop::new(receiver, channel);
+        if let Some(port) = forward.get("port").and_then(|p| p.as_str()) {
+            hop = hop.port(port);
+        }
+        match forward.get("timeout") {
+            Some(Value::Number(nanos)) => hop = hop.timeout(Duration::from_nanos(nanos.as_u64()?)),
+            Some(Value::String(seconds)) => {
+                let seconds = seconds.strip_suffix('s')?.parse().ok()?;
+                hop = hop.timeout(Duration::from_secs(seconds))
+            }
+            _ => {}
+        }
+        if let Some(retries) = forward.get("retries").and_then(|r| r.as_u64()) {
+            hop = hop.retries(retries.min(u8::MAX as u64) as u8);
+        }
+        memo = memo.hop(hop);
+        match forward.get("next") {
+            Some(next) => forward = next.get("forward")?,
+            None => return Some(memo),
+        }
+    }
+}
+
+impl Memo {
+    /// Builds a deposit tag memo, failing if the tag has characters exchanges do not use
+    pub fn deposit_tag(tag: impl Into<String>) -> Result<Memo, CosmosGrpcError> {
+        let tag = tag.into();
+        if !is_deposit_tag(&tag) {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Invalid deposit tag {}",
+                tag
+            )));
+        }
+        Ok(Memo::DepositTag(tag))
+    }
+
+    /// Recognizes the format of a memo. Memos of only letters, digits, dashes and
+    /// underscores can not be told apart from text and are parsed as deposit tags, JSON
+    /// that is not a recognized object is parsed as text.
+    pub fn parse(memo: &str) -> Memo {
+        let trimmed = memo.trim();
+        if trimmed.is_empty() {
+            return Memo::Empty;
+        }
+        if is_deposit_tag(trimmed) {
+            return Memo::DepositTag(trimmed.to_string());
+        }
+        let object = match serde_json::from_str(trimmed) {
+            Ok(Value::Object(object)) => object,
+            _ => return Memo::Text(memo.to_string()),
+        };
+        if let Some(forward) = object.get("forward").and_then(parse_forward) {
+            return Memo::Forward(forward);
+        }
+        if let Some(wasm) = object.get("wasm") {
+            if let (Some(contract), Some(msg)) = (
+                wasm.get("contract").and_then(|c| c.as_str()),
+                wasm.get("msg"),
+            ) {
+                return Memo::WasmHook {
+                    contract: contract.to_string(),
+                    msg: msg.clone(),
+                };
+            }
+        }
+        Memo::Json(object)
+    }
+
+    /// Encodes the memo, failing if it is longer than max_bytes
+    pub fn encode(&self, max_bytes: u64) -> Result<String, CosmosGrpcError> {
+        let memo = match self {
+            Memo::Empty => String::new(),
+            Memo::Text(text) => text.clone(),
+            Memo::DepositTag(tag) => tag.clone(),
+            Memo::Forward(forward) => forward.clone().build()?,
+            Memo::WasmHook { contract, msg } => {
+                json!({ "wasm": { "contract": contract, "msg": msg } }).to_string()
+            }
+            Memo::Json(object) => Value::Object(object.clone()).to_string(),
+        };
+        validate_memo(&memo, max_bytes)?;
+        Ok(memo)
+    }
+}
+
+impl Contact {
+    /// Encodes a memo and checks it against the memo limit of the chain
+    pub async fn encode_memo(&self, memo: &Memo) -> Result<String, CosmosGrpcError> {
+        let limits = self.cached_tx_limits().await?;
+        memo.encode(limits.max_memo_characters.unwrap_or(u64::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memo_round_trip() {
+        assert_eq!(Memo::parse("  "), Memo::Empty);
+        assert_eq!(
+            Memo::parse("104532"),
+            Memo::DepositTag("104532".to_string())
+        );
+        assert!(Memo::deposit_tag("10 45").is_err());
+        assert_eq!(
+            Memo::parse("thanks for lunch"),
+            Memo::Text("thanks for lunch".to_string())
+        );
+
+        let forward = ForwardMemo::new()
+            .hop(ForwardHop::new("pfm", "channel-1").timeout(Duration::from_secs(600)))
+            .hop(ForwardHop::new("juno1receiver", "channel-2").retries(2));
+        let encoded = Memo::Forward(forward.clone()).encode(1000).unwrap();
+        assert_eq!(Memo::parse(&encoded), Memo::Forward(forward));
+        let string_timeout = concat!(
+            r#"{"forward":{"receiver":"a","port":"transfer","#,
+            r#""channel":"channel-0","timeout":"10s"}}"#
+        );
+        assert_eq!(
+            Memo::parse(string_timeout),
+            Memo::Forward(
+                ForwardMemo::new()
+                    .hop(ForwardHop::new("a", "channel-0").timeout(Duration::from_secs(10)))
+            )
+        );
+
+        let hook = Memo::WasmHook {
+            contract: "osmo1contract".to_string(),
+            msg: json!({ "swap": {} }),
+        };
+        assert_eq!(Memo::parse(&hook.encode(256).unwrap()), hook);
+        assert!(matches!(Memo::parse(r#"{"note":"x"}"#), Memo::Json(_)));
+
+        assert!(matches!(
+            Memo::Text("x".repeat(300)).encode(DEFAULT_MAX_MEMO_BYTES),
+            Err(CosmosGrpcError::TxLimitExceeded {
+                error: TxLimitError::MemoTooLong {
+                    length: 300,
+                    max: 256
+                }
+            })
+        ));
+        assert!(validate_memo("line\nbreak", 256).is_err());
+    }
+}
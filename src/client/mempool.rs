@@ -0,0 +1,235 @@
+//! Inspection of a node's mempool through the Tendermint unconfirmed_txs RPC, so a sender
+//! can tell whether a transaction is still waiting for a block, or has left the mempool and
+//! must be broadcast again. The Cosmos gRPC services do not expose the mempool and no HTTP
+//! client is bundled, the caller fetches `<rpc>/unconfirmed_txs?limit=100` and passes the
+//! json to [`UnconfirmedTxs::from_json`].
+//!
+//! ```ignore
+//! let json = http_get(format!("{}/unconfirmed_txs?limit=100", rpc_url)).await?;
+//! let mempool = UnconfirmedTxs::from_json(&json)?;
+//! if let MempoolStatus::Missing = contact.mempool_status(&tx_hash, &mempool).await? {
+//!     contact.send_transaction(tx_bytes, BroadcastMode::Sync).await?;
+//! }
+//! ```
+
+use crate::any::AnyExt;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::utils::tx_hash;
+use crate::utils::tx_sequence;
+use crate::Address;
+use crate::PublicKey;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as ProtoSecp256k1Pubkey;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::AuthInfo;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use prost::Message;
+use tonic::Code;
+
+#[derive(Deserialize)]
+struct RawEnvelope {
+    result: RawUnconfirmedTxs,
+}
+
+#[derive(Deserialize)]
+struct RawUnconfirmedTxs {
+    total: String,
+    total_bytes: String,
+    txs: Option<Vec<String>>,
+}
+
+/// The mempool of a node as returned by the unconfirmed_txs or num_unconfirmed_txs RPC
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnconfirmedTxs {
+    /// the number of transactions in the mempool
+    pub total: u64,
+    pub total_bytes: u64,
+    /// the encoded transactions returned, at most the requested limit, in mempool order.
+    /// Empty for num_unconfirmed_txs.
+    pub txs: Vec<Vec<u8>>,
+}
+
+/// A transaction in the mempool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolTx {
+    pub tx_hash: String,
+    /// the addresses of the secp256k1 signers, signers with other key types are left out
+    pub signers: Vec<Address>,
+    /// the sequence of the first signer
+    pub sequence: Option<u64>,
+    pub msg_types: Vec<String>,
+    pub tx_bytes: Vec<u8>,
+}
+
+/// Where a transaction is, see Contact::mempool_status
+#[derive(Debug, Clone, PartialEq)]
+pub enum MempoolStatus {
+    /// the transaction is in a block
+    Included(TxResponse),
+    /// the transaction is waiting in the mempool
+    Pending,
+    /// the transaction is neither in a block nor in the mempool, it was evicted or never
+    /// reached this node and can be broadcast again
+    Missing,
+}
+
+impl UnconfirmedTxs {
+    /// Parses the JSON-RPC response of unconfirmed_txs or num_unconfirmed_txs, or only its
+    /// result field
+    pub fn from_json(json: &str) -> Result<UnconfirmedTxs, CosmosGrpcError> {
+        let bad_input = |e: serde_json::Error| CosmosGrpcError::BadInput(e.to_string());
+        let raw: RawUnconfirmedTxs = match serde_json::from_str::<RawEnvelope>(json) {
+            Ok(envelope) => envelope.result,
+            Err(_) => serde_json::from_str(json).map_err(bad_input)?,
+        };
+        let number = |value: &str| {
+            value
+                .parse::<u64>()
+                .map_err(|e| CosmosGrpcError::BadInput(format!("{}: {}", value, e)))
+        };
+        let mut txs = Vec::new();
+        for tx in raw.txs.unwrap_or_default() {
+            txs.push(base64::decode(&tx).map_err(|e| CosmosGrpcError::BadInput(e.to_string()))?);
+        }
+        Ok(UnconfirmedTxs {
+            total: number(&raw.total)?,
+            total_bytes: number(&raw.total_bytes)?,
+            txs,
+        })
+    }
+
+    /// Returns true if a transaction with the hash was returned
+    pub fn contains(&self, tx_hash_hex: &str) -> bool {
+        self.txs
+            .iter()
+            .any(|tx| tx_hash(tx).eq_ignore_ascii_case(tx_hash_hex))
+    }
+
+    /// Decodes the returned transactions, those that are not Cosmos transactions are skipped
+    pub fn decode(&self) -> Vec<MempoolTx> {
+        self.txs
+            .iter()
+            .filter_map(|tx| decode_mempool_tx(tx))
+            .collect()
+    }
+
+    /// The returned transactions signed by the address, ordered by sequence
+    pub fn sent_by(&self, sender: Address) -> Vec<MempoolTx> {
+        let mut txs: Vec<MempoolTx> = self
+            .decode()
+            .into_iter()
+            .filter(|tx| tx.signers.iter().any(|signer| signer.same_account(&sender)))
+            .collect();
+        txs.sort_by_key(|tx| tx.sequence);
+        txs
+    }
+}
+
+fn decode_mempool_tx(tx_bytes: &[u8]) -> Option<MempoolTx> {
+    let raw = TxRaw::decode(tx_bytes).ok()?;
+    let body = TxBody::decode(raw.body_bytes.as_slice()).ok()?;
+    let auth_info = AuthInfo::decode(raw.auth_info_bytes.as_slice()).ok()?;
+    let signers = auth_info
+        .signer_infos
+        .iter()
+        .filter_map(|signer| {
+            let key: ProtoSecp256k1Pubkey = signer.public_key.as_ref()?.unpack().ok()?;
+            PublicKey::from_slice(&key.key, PublicKey::DEFAULT_PREFIX)
+                .ok()
+                .map(|key| key.to_address())
+        })
+        .collect();
+    Some(MempoolTx {
+        tx_hash: tx_hash(tx_bytes),
+        signers,
+        sequence: tx_sequence(tx_bytes),
+        msg_types: body.messages.into_iter().map(|msg| msg.type_url).collect(),
+        tx_bytes: tx_bytes.to_vec(),
+    })
+}
+
+impl Contact {
+    /// Finds a transaction in a block, or in a mempool fetched from the node it was sent to.
+    /// Fetch the mempool before calling this, otherwise a transaction included in between
+    /// is reported as Missing. Only the returned transactions are searched so the limit of
+    /// the request should be above the mempool total.
+    pub async fn mempool_status(
+        &self,
+        tx_hash: &str,
+        mempool: &UnconfirmedTxs,
+    ) -> Result<MempoolStatus, CosmosGrpcError> {
+        match self.get_tx_by_hash(tx_hash.to_string()).await {
+            Ok(res) => {
+                if let Some(response) = res.tx_response {
+                    return Ok(MempoolStatus::Included(response));
+                }
+            }
+            // older nodes do not return NotFound for unknown hashes
+            Err(e) if matches!(e.grpc_code(), Some(Code::NotFound) | Some(Code::Unknown)) => {}
+            Err(e) => return Err(e),
+        }
+        if mempool.contains(tx_hash) {
+            Ok(MempoolStatus::Pending)
+        } else {
+            Ok(MempoolStatus::Missing)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::private_key::MessageArgs;
+    use crate::Fee;
+    use crate::Msg;
+    use crate::PrivateKey;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    fn signed(key: PrivateKey, sequence: u64) -> Vec<u8> {
+        let args = MessageArgs {
+            sequence,
+            fee: Fee {
+                amount: vec![],
+                gas_limit: 200_000,
+                granter: None,
+                payer: None,
+            },
+            timeout_height: 100,
+            chain_id: "testing".to_string(),
+            account_number: 1,
+        };
+        let msg = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+        key.sign_std_msg(&[msg], args, "").unwrap()
+    }
+
+    #[test]
+    fn test_unconfirmed_txs() {
+        let ours = PrivateKey::from_secret(b"mempool sender");
+        let other = PrivateKey::from_secret(b"mempool other sender");
+        let txs = [signed(ours, 8), signed(other, 0), signed(ours, 7)];
+        let encoded: Vec<String> = txs.iter().map(base64::encode).collect();
+        let json = format!(
+            r#"{{"jsonrpc":"2.0","id":-1,"result":{{"n_txs":"3","total":"120",
+            "total_bytes":"36000","txs":["{}"]}}}}"#,
+            encoded.join(r#"",""#)
+        );
+        let mempool = UnconfirmedTxs::from_json(&json).unwrap();
+        assert_eq!(mempool.total, 120);
+        assert_eq!(mempool.txs.len(), 3);
+        assert!(mempool.contains(&tx_hash(&txs[1]).to_lowercase()));
+        assert!(!mempool.contains(&tx_hash(&signed(other, 1))));
+
+        let ours = mempool.sent_by(ours.to_address("osmo").unwrap());
+        assert_eq!(ours.len(), 2);
+        assert_eq!(ours[0].sequence, Some(7));
+        assert_eq!(ours[1].tx_hash, tx_hash(&txs[0]));
+        assert_eq!(ours[1].msg_types, vec![MSG_SEND_TYPE_URL.to_string()]);
+
+        let count = r#"{"n_txs":"0","total":"120","total_bytes":"36000","txs":null}"#;
+        let count = UnconfirmedTxs::from_json(count).unwrap();
+        assert_eq!(count.total, 120);
+        assert!(count.txs.is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! Formatting of coin amounts for display using the denom metadata registered with the
+//! bank module, for example rendering `12500000uatom` as `12.5 ATOM`
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+pub use crate::proto::cosmos::bank::v1beta1::DenomUnit;
+pub use crate::proto::cosmos::bank::v1beta1::Metadata;
+use crate::proto::cosmos::bank::v1beta1::QueryDenomMetadataRequest;
+use crate::proto::cosmos::bank::v1beta1::QueryDenomMetadataResponse;
+use crate::Coin;
+use std::collections::HashMap;
+use tonic::Code as GrpcCode;
+
+/// Returns the exponent of the display unit of this metadata, zero if the display unit
+/// is not one of the listed denom units
+pub fn display_exponent(metadata: &Metadata) -> u32 {
+    metadata
+        .denom_units
+        .iter()
+        .find(|unit| unit.denom == metadata.display)
+        .map(|unit| unit.exponent)
+        .unwrap_or(0)
+}
+
+/// Formats a coin of the metadata base denom in its display unit, using the symbol if the
+/// chain provides one and the display denom otherwise. Trailing fractional zeros are removed.
+/// Coins of any other denom are formatted as is.
+pub fn format_coin(coin: &Coin, metadata: &Metadata) -> String {
+    if coin.denom != metadata.base {
+        return coin.to_string();
+    }
+    let exponent = display_exponent(metadata) as usize;
+    let label = if metadata.symbol.is_empty() {
+        &metadata.display
+    } else {
+        &metadata.symbol
+    };
+    let digits = format!("{:0>width$}", coin.amount.to_string(), width = exponent + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - exponent);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{} {}", integer, label)
+    } else {
+        format!("{}.{} {}", integer, fraction, label)
+    }
+}
+
+/// Local cache of denom metadata so that repeatedly formatting coins does not require a
+/// query per coin. Denoms with no metadata on chain are cached as well.
+#[derive(Debug, Default, Clone)]
+pub struct DenomMetadataCache {
+    metadata: HashMap<String, Option<Metadata>>,
+}
+
+impl DenomMetadataCache {
+    pub fn new() -> Self {
+        DenomMetadataCache::default()
+    }
+
+    /// Adds metadata to the cache, for example from a chain registry or a config file
+    pub fn insert(&mut self, metadata: Metadata) {
+        self.metadata.insert(metadata.base.clone(), Some(metadata));
+    }
+
+    /// Returns the metadata for the base denom if it is known
+    pub fn get(&self, denom: &str) -> Option<&Metadata> {
+        self.metadata.get(denom).and_then(|m| m.as_ref())
+    }
+
+    /// Returns true if the denom has been looked up, whether or not it has metadata
+    pub fn contains(&self, denom: &str) -> bool {
+        self.metadata.contains_key(denom)
+    }
+
+    /// Formats the coin using the cached metadata, coins without metadata are
+    /// formatted as is
+    pub fn format(&self, coin: &Coin) -> String {
+        match self.get(&coin.denom) {
+            Some(metadata) => format_coin(coin, metadata),
+            None => coin.to_string(),
+        }
+    }
+}
+
+impl Contact {
+    /// Gets the metadata registered with the bank module for a base denom, returns None if
+    /// the chain has no metadata for the denom
+    pub async fn get_denom_metadata(
+        &self,
+        denom: String,
+    ) -> Result<Option<Metadata>, CosmosGrpcError> {
+        let res: Result<QueryDenomMetadataResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.bank.v1beta1.Query/DenomMetadata",
+                QueryDenomMetadataRequest { denom },
+            )
+            .await;
+        match res {
+            Ok(res) => Ok(res.metadata),
+            Err(e) if e.grpc_code() == Some(GrpcCode::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Formats a coin for display, querying and caching the denom metadata if it is not
+    /// already in the cache
+    pub async fn format_coin(
+        &self,
+        coin: &Coin,
+        cache: &mut DenomMetadataCache,
+    ) -> Result<String, CosmosGrpcError> {
+        if !cache.contains(&coin.denom) {
+            let metadata = self.get_denom_metadata(coin.denom.clone()).await?;
+            cache.metadata.insert(coin.denom.clone(), metadata);
+        }
+        Ok(cache.format(coin))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atom() -> Metadata {
+        let unit = |denom: &str, exponent| DenomUnit {
+            denom: denom.to_string(),
+            exponent,
+            aliases: Vec::new(),
+        };
+        Metadata {
+            description: String::new(),
+            denom_units: vec![unit("uatom", 0), unit("matom", 3), unit("atom", 6)],
+            base: "uatom".to_string(),
+            display: "atom".to_string(),
+            name: "Cosmos Atom".to_string(),
+            symbol: "ATOM".to_string(),
+            uri: String::new(),
+            uri_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_coin() {
+        let coin = |amount: u64, denom: &str| Coin::new(amount.into(), denom.to_string());
+        let metadata = atom();
+        assert_eq!(
+            format_coin(&coin(12_500_000, "uatom"), &metadata),
+            "12.5 ATOM"
+        );
+        assert_eq!(format_coin(&coin(3, "uatom"), &metadata), "0.000003 ATOM");
+        assert_eq!(format_coin(&coin(7_000_000, "uatom"), &metadata), "7 ATOM");
+        assert_eq!(format_coin(&coin(5, "ustake"), &metadata), "5ustake");
+
+        let mut cache = DenomMetadataCache::new();
+        cache.insert(Metadata {
+            symbol: String::new(),
+            ..metadata
+        });
+        assert_eq!(cache.format(&coin(1_000_000, "uatom")), "1 atom");
+        assert_eq!(cache.format(&coin(1, "uosmo")), "1uosmo");
+    }
+}
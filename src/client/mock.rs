@@ -0,0 +1,344 @@
+//! A programmable CosmosClient for unit testing transaction logic without a live node.
+//! Query responses are set ahead of time and broadcast or simulation results are queued,
+//! every broadcast transaction is recorded so tests can inspect what would have been sent.
+
+use crate::client::cosmos_client::sign_and_send;
+use crate::client::cosmos_client::CosmosClient;
+use crate::client::send::check_broadcast_response;
+use crate::client::send::check_included_tx;
+use crate::client::types::ChainStatus;
+use crate::client::types::LatestBlock;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+use crate::utils::tx_hash;
+use crate::utils::tx_sequence;
+use crate::utils::GasDetails;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::MessageArgs;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tonic::Status;
+
+fn not_programmed(what: &str) -> CosmosGrpcError {
+    CosmosGrpcError::BadResponse(format!("MockCosmosClient has no {} programmed", what))
+}
+
+#[derive(Default)]
+struct MockState {
+    chain_id: String,
+    chain_status: Option<ChainStatus>,
+    latest_block: Option<LatestBlock>,
+    accounts: HashMap<Address, BaseAccount>,
+    balances: HashMap<Address, Vec<Coin>>,
+    txs: HashMap<String, TxResponse>,
+    broadcast_responses: VecDeque<Result<TxResponse, CosmosGrpcError>>,
+    simulate_responses: VecDeque<Result<GasDetails, CosmosGrpcError>>,
+    broadcasts: Vec<Vec<u8>>,
+}
+
+/// A CosmosClient returning programmed responses. Queries that have not been programmed
+/// return a BadResponse error, except for accounts which return AccountNotFound like a real node.
+/// Broadcasts consume the queued responses in order, once the queue is empty every
+/// broadcast succeeds. Successful broadcasts can be found by get_tx_by_hash and wait_for_tx.
+pub struct MockCosmosClient {
+    prefix: String,
+    state: Mutex<MockState>,
+}
+
+impl MockCosmosClient {
+    pub fn new(prefix: &str, chain_id: &str) -> Self {
+        MockCosmosClient {
+            prefix: prefix.to_string(),
+            state: Mutex::new(MockState {
+                chain_id: chain_id.to_string(),
+                ..MockState::default()
+            }),
+        }
+    }
+
+    /// Sets the chain status, the block height is used for message timeouts
+    pub fn set_chain_status(&self, status: ChainStatus) {
+        self.state.lock().unwrap().chain_status = Some(status);
+    }
+
+    pub fn set_latest_block(&self, block: LatestBlock) {
+        self.state.lock().unwrap().latest_block = Some(block);
+    }
+
+    pub fn set_account(&self, address: Address, account_number: u64, sequence: u64) {
+        let account = BaseAccount {
+            address: address.to_bech32(&self.prefix).unwrap(),
+            pub_key: None,
+            account_number,
+            sequence,
+        };
+        self.state.lock().unwrap().accounts.insert(address, account);
+    }
+
+    pub fn set_balances(&self, address: Address, balances: Vec<Coin>) {
+        self.state
+            .lock()
+            .unwrap()
+            .balances
+            .insert(address, balances);
+    }
+
+    /// Makes a transaction available to get_tx_by_hash and wait_for_tx
+    pub fn add_tx(&self, response: TxResponse) {
+        let mut state = self.state.lock().unwrap();
+        state.txs.insert(response.txhash.clone(), response);
+    }
+
+    /// Queues the result of the next broadcast. Responses are checked for fee and sequence
+    /// errors the same way Contact checks them, so failed transactions can be programmed
+    /// either as a TxResponse or as an error.
+    pub fn push_broadcast_response(&self, response: Result<TxResponse, CosmosGrpcError>) {
+        let mut state = self.state.lock().unwrap();
+        state.broadcast_responses.push_back(response);
+    }
+
+    /// Queues the result of the next simulation
+    pub fn push_simulate_response(&self, response: Result<GasDetails, CosmosGrpcError>) {
+        let mut state = self.state.lock().unwrap();
+        state.simulate_responses.push_back(response);
+    }
+
+    /// The encoded bytes of every transaction broadcast so far, in order
+    pub fn broadcasts(&self) -> Vec<Vec<u8>> {
+        self.state.lock().unwrap().broadcasts.clone()
+    }
+
+    fn account_not_found(&self, address: Address) -> CosmosGrpcError {
+        CosmosGrpcError::AccountNotFound {
+            address: address.to_bech32(&self.prefix).unwrap(),
+        }
+    }
+
+    fn message_args(&self, our_address: Address, fee: Fee) -> Result<MessageArgs, CosmosGrpcError> {
+        let state = self.state.lock().unwrap();
+        let account = match state.accounts.get(&our_address) {
+            Some(account) => account,
+            None => return Err(self.account_not_found(our_address)),
+        };
+        let height = match state.chain_status {
+            Some(ChainStatus::Moving { block_height }) => block_height,
+            Some(ChainStatus::Syncing) => return Err(CosmosGrpcError::NodeNotSynced),
+            Some(ChainStatus::WaitingToStart) => return Err(CosmosGrpcError::ChainNotRunning),
+            None => return Err(not_programmed("chain status")),
+        };
+        Ok(MessageArgs {
+            sequence: account.sequence,
+            account_number: account.account_number,
+            chain_id: state.chain_id.clone(),
+            fee,
+            timeout_height: height + 100,
+        })
+    }
+
+    fn broadcast(&self, tx_bytes: Vec<u8>) -> Result<TxResponse, CosmosGrpcError> {
+        let mut state = self.state.lock().unwrap();
+        let txhash = tx_hash(&tx_bytes);
+        let sequence = tx_sequence(&tx_bytes);
+        state.broadcasts.push(tx_bytes);
+        let response = match state.broadcast_responses.pop_front() {
+            Some(response) => response?,
+            None => TxResponse {
+                txhash,
+                ..TxResponse::default()
+            },
+        };
+        let response = check_broadcast_response(response, sequence)?;
+        state.txs.insert(response.txhash.clone(), response.clone());
+        Ok(response)
+    }
+}
+
+impl CosmosClient for MockCosmosClient {
+    fn get_prefix(&self) -> String {
+        self.prefix.clone()
+    }
+
+    fn get_chain_status(&self) -> BoxFuture<'_, Result<ChainStatus, CosmosGrpcError>> {
+        let status = self.state.lock().unwrap().chain_status.clone();
+        futures::future::ready(status.ok_or_else(|| not_programmed("chain status"))).boxed()
+    }
+
+    fn get_latest_block(&self) -> BoxFuture<'_, Result<LatestBlock, CosmosGrpcError>> {
+        let block = self.state.lock().unwrap().latest_block.clone();
+        futures::future::ready(block.ok_or_else(|| not_programmed("latest block"))).boxed()
+    }
+
+    fn get_account_info(
+        &self,
+        address: Address,
+    ) -> BoxFuture<'_, Result<BaseAccount, CosmosGrpcError>> {
+        let account = self.state.lock().unwrap().accounts.get(&address).cloned();
+        let res = account.ok_or_else(|| self.account_not_found(address));
+        futures::future::ready(res).boxed()
+    }
+
+    fn get_balances(&self, address: Address) -> BoxFuture<'_, Result<Vec<Coin>, CosmosGrpcError>> {
+        let balances = self.state.lock().unwrap().balances.get(&address).cloned();
+        futures::future::ready(Ok(balances.unwrap_or_default())).boxed()
+    }
+
+    fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> BoxFuture<'_, Result<Option<Coin>, CosmosGrpcError>> {
+        let state = self.state.lock().unwrap();
+        let balance = state
+            .balances
+            .get(&address)
+            .and_then(|b| {
+                b.iter()
+                    .find(|c| c.denom == denom && c.amount > 0u64.into())
+            })
+            .cloned();
+        futures::future::ready(Ok(balance)).boxed()
+    }
+
+    fn get_message_args(
+        &self,
+        our_address: Address,
+        fee: Fee,
+    ) -> BoxFuture<'_, Result<MessageArgs, CosmosGrpcError>> {
+        futures::future::ready(self.message_args(our_address, fee)).boxed()
+    }
+
+    fn get_tx_by_hash(
+        &self,
+        txhash: String,
+    ) -> BoxFuture<'_, Result<GetTxResponse, CosmosGrpcError>> {
+        let res = match self.state.lock().unwrap().txs.get(&txhash) {
+            Some(response) => Ok(GetTxResponse {
+                tx: None,
+                tx_response: Some(response.clone()),
+            }),
+            None => Err(CosmosGrpcError::RequestError {
+                endpoint: "mock".to_string(),
+                method: "/cosmos.tx.v1beta1.Service/GetTx".to_string(),
+                error: Status::not_found(format!("tx {} not found", txhash)),
+            }),
+        };
+        futures::future::ready(res).boxed()
+    }
+
+    fn send_transaction(
+        &self,
+        msg: Vec<u8>,
+        _mode: BroadcastMode,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        futures::future::ready(self.broadcast(msg)).boxed()
+    }
+
+    fn send_message<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<TxResponse, CosmosGrpcError>> {
+        sign_and_send(self, messages, memo, fee, private_key, wait_timeout).boxed()
+    }
+
+    /// Returns immediately, transactions are either already known or will never be found
+    fn wait_for_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        let res = match self.state.lock().unwrap().txs.get(&response.txhash) {
+            Some(found) => check_included_tx(found.clone()),
+            None => Err(CosmosGrpcError::ConfirmationTimeout {
+                tx_hash: response.txhash,
+                time: timeout,
+            }),
+        };
+        futures::future::ready(res).boxed()
+    }
+
+    fn simulate_tx<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        _memo: Option<String>,
+        _private_key: PrivateKey,
+        _attribute_msgs: bool,
+    ) -> BoxFuture<'a, Result<GasDetails, CosmosGrpcError>> {
+        let res = if messages.is_empty() {
+            Err(CosmosGrpcError::BadInput(
+                "Can not simulate a transaction with no messages".to_string(),
+            ))
+        } else {
+            match self.state.lock().unwrap().simulate_responses.pop_front() {
+                Some(response) => response,
+                None => Err(not_programmed("simulation")),
+            }
+        };
+        futures::future::ready(res).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    #[actix_rt::test]
+    async fn test_mock_send_message() {
+        let key = PrivateKey::from_secret(b"mock client test key");
+        let address = key.to_address("cosmos").unwrap();
+        let client = MockCosmosClient::new("cosmos", "testing");
+        let client: &dyn CosmosClient = &client;
+        let msg = Msg::new(MSG_SEND_TYPE_URL, MsgSend::default());
+
+        // no account or chain status programmed yet
+        assert!(matches!(
+            client
+                .send_message(&[msg.clone()], None, Fee::default(), key, None)
+                .await,
+            Err(CosmosGrpcError::AccountNotFound { .. })
+        ));
+
+        let mock = MockCosmosClient::new("cosmos", "testing");
+        mock.set_account(address, 1, 0);
+        mock.set_chain_status(ChainStatus::Moving { block_height: 10 });
+        mock.push_broadcast_response(Ok(TxResponse {
+            code: 32,
+            raw_log: "account sequence mismatch, expected 1, got 0".to_string(),
+            ..TxResponse::default()
+        }));
+        assert!(matches!(
+            mock.send_message(&[msg.clone()], None, Fee::default(), key, None)
+                .await,
+            Err(CosmosGrpcError::TransactionFailed { .. })
+        ));
+        let res = mock
+            .send_message(
+                &[msg],
+                None,
+                Fee::default(),
+                key,
+                Some(Duration::from_secs(1)),
+            )
+            .await
+            .unwrap();
+        let broadcasts = mock.broadcasts();
+        assert_eq!(broadcasts.len(), 2);
+        assert_eq!(res.txhash, tx_hash(&broadcasts[1]));
+    }
+}
@@ -1,29 +1,166 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
+use std::time::Instant;
 
+pub mod accounts;
+pub mod authz;
+pub mod bank;
+pub mod block_time;
+pub mod block_watcher;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod broadcast_log;
+pub mod cache;
+#[cfg(feature = "chainregistry")]
+pub mod chain_registry;
+mod codec;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod confirmations;
+#[cfg(feature = "cosmwasm")]
+pub mod contract_events;
+pub mod cosmos_client;
+pub mod crisis;
+pub mod deposits;
+pub mod distribution;
+pub mod dry_run;
+#[cfg(feature = "ethermint")]
+pub mod ethermint;
+pub mod events;
+pub mod evidence;
+pub mod fee_budget;
+pub mod feegrant;
+pub mod gas_price;
+pub mod gas_table;
 pub mod get;
 pub mod gov;
+#[cfg(feature = "sdk-0_46")]
+pub mod group;
+pub mod ibc;
+pub mod keys;
+pub mod memo;
+pub mod mempool;
+pub mod metadata;
+pub mod mock;
+pub mod paginate;
+pub mod portfolio;
+pub mod prefix;
+mod recording;
+pub mod reflection;
+pub mod responses;
+pub(crate) mod runtime;
 pub mod send;
+pub mod simulate;
+pub mod simulated_chain;
 pub mod staking;
+#[cfg(feature = "tokenfactory")]
+pub mod tokenfactory;
+pub mod tx_limits;
+pub mod tx_policy;
 pub mod types;
+pub mod vesting;
+pub mod wallet;
+#[cfg(feature = "cosmwasm")]
+pub mod wasm;
 
+pub use cosmos_client::CosmosClient;
+pub use mock::MockCosmosClient;
+pub use simulated_chain::SimulatedChain;
 pub use types::ChainStatus;
+pub use types::ConnectionOptions;
+pub use types::ReconnectPolicy;
+pub use types::TlsOptions;
+pub use wallet::Signer;
+pub use wallet::Wallet;
 
+use crate::address::Address;
+use crate::client::block_watcher::BlockWatcher;
+use crate::client::broadcast_log::BroadcastSink;
+use crate::client::cache::QueryCache;
+use crate::client::codec::LimitedProstCodec;
+use crate::client::codec::MessageLimits;
+use crate::client::fee_budget::FeeBudget;
+use crate::client::gas_price::AutoFee;
+use crate::client::gas_table::GasTable;
+use crate::client::recording::Cassette;
+use crate::client::recording::GrpcTape;
+use crate::client::runtime::sleep;
+use crate::client::tx_limits::TxLimits;
+use crate::client::tx_policy::TxPolicy;
 use crate::{error::CosmosGrpcError, utils::ArrayString};
+use tonic::client::Grpc;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::Channel;
+use tonic::transport::Endpoint;
+use tonic::Request;
 
 pub const MEMO: &str = "Sent with Deep Space";
 
-/// An instance of Contact Cosmos RPC Client.
+/// The shared connection of a Contact and its reconnect state
+#[derive(Default)]
+struct ConnectionState {
+    /// the open connection and when it was opened
+    channel: Option<(Channel, Instant)>,
+    /// the number of connection attempts that failed in a row
+    failures: u32,
+    /// when the last connection attempt failed
+    failed_at: Option<Instant>,
+}
+
+/// An instance of Contact Cosmos RPC Client. Contact is cheap to clone, clones share the
+/// configuration and the connection to the node so a single Contact can be handed to many
+/// tasks without opening a connection per task.
 #[derive(Clone)]
 pub struct Contact {
+    inner: Arc<ContactInner>,
+}
+
+struct ContactInner {
     /// The GRPC server url, we connect to this address
-    /// with a new instance for each call to ensure
-    /// proper failover
     url: String,
     /// The maximum amount of wall time any action taken
-    /// will wait for.
+    /// will wait for, applied to connecting and to each request
     timeout: Duration,
     /// The prefix being used by this node / chain for Addresses
     chain_prefix: String,
+    /// The maximum sizes of requests and responses
+    limits: MessageLimits,
+    /// Keepalive and HTTP/2 settings applied when connecting
+    connection_options: ConnectionOptions,
+    /// When to replace the connection and how long to wait after failing to connect
+    reconnect_policy: ReconnectPolicy,
+    /// TLS settings, only used for https endpoints
+    #[cfg(feature = "tls")]
+    tls: Option<TlsOptions>,
+    /// Records or replays every gRPC call, see with_recording and from_recording
+    tape: Option<Arc<GrpcTape>>,
+    /// Cached query responses, see with_query_cache
+    cache: Option<Arc<QueryCache>>,
+    /// Receives a record of every broadcast, see with_broadcast_sink
+    broadcast_sink: Option<Arc<dyn BroadcastSink>>,
+    /// Limits the fees broadcast, see with_fee_budget
+    fee_budget: Option<Arc<FeeBudget>>,
+    /// Checked before signing, see with_tx_policy
+    tx_policies: Vec<Arc<dyn TxPolicy>>,
+    /// Simulate instead of broadcasting, see with_dry_run
+    dry_run: bool,
+    /// Gas limits of the transaction helpers, see with_gas_table
+    gas_table: Arc<GasTable>,
+    /// The coin type keys are derived with, see with_coin_type
+    coin_type: Option<u32>,
+    /// Pays the fees of the transaction helpers, see with_fee_granter
+    fee_granter: Option<Address>,
+    /// Prices fees left without an amount, see with_auto_fee
+    auto_fee: Option<AutoFee>,
+    /// The transaction limits checked before broadcasting and when they were fetched
+    tx_limits: Mutex<Option<(TxLimits, Instant)>>,
+    /// The connection shared by every clone, opened on first use and dropped after a
+    /// network error so the next call reconnects
+    connection: Mutex<ConnectionState>,
+    /// The background block polling task, see start_block_watcher
+    watcher: Mutex<Option<BlockWatcher>>,
 }
 
 impl Contact {
@@ -33,23 +170,404 @@ impl Contact {
             url = url.trim_end_matches('/');
         }
         ArrayString::new(chain_prefix)?;
-        Ok(Self {
-            url: url.to_string(),
-            timeout,
-            chain_prefix: chain_prefix.to_string(),
+        Ok(Contact {
+            inner: Arc::new(ContactInner {
+                url: url.to_string(),
+                timeout,
+                chain_prefix: chain_prefix.to_string(),
+                limits: MessageLimits::default(),
+                connection_options: ConnectionOptions::default(),
+                reconnect_policy: ReconnectPolicy::default(),
+                #[cfg(feature = "tls")]
+                tls: None,
+                tape: None,
+                cache: None,
+                broadcast_sink: None,
+                fee_budget: None,
+                tx_policies: Vec::new(),
+                dry_run: false,
+                gas_table: Arc::new(GasTable::default()),
+                coin_type: None,
+                fee_granter: None,
+                auto_fee: None,
+                tx_limits: Mutex::new(None),
+                connection: Mutex::new(ConnectionState::default()),
+                watcher: Mutex::new(None),
+            }),
         })
     }
 
+    /// Copies the configuration of this Contact, the copy has its own connection and no
+    /// block watcher
+    fn unshared(&self) -> ContactInner {
+        ContactInner {
+            url: self.inner.url.clone(),
+            timeout: self.inner.timeout,
+            chain_prefix: self.inner.chain_prefix.clone(),
+            limits: self.inner.limits,
+            connection_options: self.inner.connection_options,
+            reconnect_policy: self.inner.reconnect_policy,
+            #[cfg(feature = "tls")]
+            tls: self.inner.tls.clone(),
+            tape: self.inner.tape.clone(),
+            cache: self.inner.cache.clone(),
+            broadcast_sink: self.inner.broadcast_sink.clone(),
+            fee_budget: self.inner.fee_budget.clone(),
+            tx_policies: self.inner.tx_policies.clone(),
+            dry_run: self.inner.dry_run,
+            gas_table: self.inner.gas_table.clone(),
+            coin_type: self.inner.coin_type,
+            fee_granter: self.inner.fee_granter,
+            auto_fee: self.inner.auto_fee.clone(),
+            tx_limits: Mutex::new(None),
+            connection: Mutex::new(ConnectionState::default()),
+            watcher: Mutex::new(None),
+        }
+    }
+
+    /// Records every gRPC request and response made through this Contact to the provided
+    /// file, which is rewritten after each call. The file can be replayed with from_recording.
+    /// The returned Contact does not share its connection with clones of this one.
+    pub fn with_recording(self, file: &Path) -> Self {
+        let cassette = Cassette::new(self.inner.url.clone(), self.inner.chain_prefix.clone());
+        let mut inner = self.unshared();
+        inner.tape = Some(Arc::new(GrpcTape::record(file.to_path_buf(), cassette)));
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Creates a Contact that answers every gRPC call from a file created by with_recording
+    /// instead of a node. Calls that were not recorded return an error, identical calls are
+    /// answered in the order they were recorded.
+    pub fn from_recording(file: &Path, timeout: Duration) -> Result<Self, CosmosGrpcError> {
+        let cassette = Cassette::load(file)?;
+        let contact = Contact::new(&cassette.url, timeout, &cassette.chain_prefix)?;
+        let mut inner = contact.unshared();
+        inner.tape = Some(Arc::new(GrpcTape::replay(cassette)));
+        Ok(Contact {
+            inner: Arc::new(inner),
+        })
+    }
+
+    /// Sets the largest response in bytes this Contact will decode, larger responses fail
+    /// with a RequestError with the OutOfRange code. There is no limit by default, setting one
+    /// protects against a misbehaving node exhausting memory. The returned Contact does not
+    /// share its connection with clones of this one.
+    pub fn with_max_decoding_message_size(self, limit: usize) -> Self {
+        let mut inner = self.unshared();
+        inner.limits.max_decoding_message_size = Some(limit);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Sets the largest request in bytes this Contact will send, larger requests fail with a
+    /// RequestError with the OutOfRange code without being sent. There is no limit by default.
+    /// The returned Contact does not share its connection with clones of this one.
+    pub fn with_max_encoding_message_size(self, limit: usize) -> Self {
+        let mut inner = self.unshared();
+        inner.limits.max_encoding_message_size = Some(limit);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Sets the keepalive and HTTP/2 settings of the connection to the node. The returned
+    /// Contact does not share its connection with clones of this one.
+    pub fn with_connection_options(self, options: ConnectionOptions) -> Self {
+        let mut inner = self.unshared();
+        inner.connection_options = options;
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Sets how often the connection to the node is replaced and how long to wait between
+    /// failed connection attempts. The returned Contact does not share its connection with
+    /// clones of this one.
+    pub fn with_reconnect_policy(self, policy: ReconnectPolicy) -> Self {
+        let mut inner = self.unshared();
+        inner.reconnect_policy = policy;
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Sets the TLS settings used to connect to https endpoints, without them the system
+    /// root certificates and the endpoint host name are used. The returned Contact does not
+    /// share its connection with clones of this one.
+    #[cfg(feature = "tls")]
+    pub fn with_tls(self, tls: TlsOptions) -> Self {
+        let mut inner = self.unshared();
+        inner.tls = Some(tls);
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
     pub fn get_prefix(&self) -> String {
-        self.chain_prefix.clone()
+        self.inner.chain_prefix.clone()
     }
 
     pub fn get_url(&self) -> String {
-        self.url.clone()
+        self.inner.url.clone()
     }
 
     pub fn get_timeout(&self) -> Duration {
-        self.timeout
+        self.inner.timeout
+    }
+
+    /// Performs a unary gRPC request against an arbitrary service method, for example
+    /// `/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator`. This is used internally
+    /// for modules that do not have a generated client available and is provided so that
+    /// downstream users can query their own chain specific modules using prost types.
+    pub async fn grpc_unary<Req, Resp>(
+        &self,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, CosmosGrpcError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let cached = match &self.inner.cache {
+            Some(cache) => cache.ttl(path).map(|ttl| (cache, ttl)),
+            None => None,
+        };
+        let (cache, ttl) = match cached {
+            Some(cached) => cached,
+            None => return self.grpc_unary_uncached(path, request).await,
+        };
+        let mut request_bytes = Vec::new();
+        request.encode(&mut request_bytes).unwrap();
+        if let Some(response) = cache.get(path, &request_bytes) {
+            return Ok(Resp::decode(response.as_slice())?);
+        }
+        let response: Resp = self.grpc_unary_uncached(path, request).await?;
+        let mut response_bytes = Vec::new();
+        response.encode(&mut response_bytes).unwrap();
+        cache.insert(path, request_bytes, response_bytes, ttl);
+        Ok(response)
+    }
+
+    /// Performs a unary request against the node or the recording
+    async fn grpc_unary_uncached<Req, Resp>(
+        &self,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, CosmosGrpcError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let tape = match &self.inner.tape {
+            Some(tape) => tape,
+            None => return self.grpc_call(path, request).await,
+        };
+        let mut request_bytes = Vec::new();
+        request.encode(&mut request_bytes).unwrap();
+        if let Some(recorded) = tape.find(path, &request_bytes) {
+            return Ok(Resp::decode(recorded?.as_slice())?);
+        }
+        let response = self.grpc_call(path, request).await;
+        match &response {
+            Ok(response) => {
+                let mut response_bytes = Vec::new();
+                response.encode(&mut response_bytes).unwrap();
+                tape.save(path, &request_bytes, Ok(&response_bytes))?;
+            }
+            Err(CosmosGrpcError::RequestError { error, .. }) => {
+                tape.save(path, &request_bytes, Err(error))?
+            }
+            // connection failures are not part of the recording
+            Err(_) => {}
+        }
+        response
+    }
+
+    async fn grpc_call<Req, Resp>(
+        &self,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, CosmosGrpcError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let grpc = self.connect().await?;
+        self.grpc_call_on(grpc, path, request).await
+    }
+
+    /// Performs a unary request on an existing connection, clones of a connection can be used
+    /// concurrently to share it between requests
+    pub(crate) async fn grpc_call_on<Req, Resp>(
+        &self,
+        grpc: Grpc<Channel>,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, CosmosGrpcError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let response = self.unary_call(grpc, path, request).await;
+        if let Err(e) = &response {
+            if e.is_transient_network() {
+                self.reset_channel();
+            }
+        }
+        response
+    }
+
+    async fn unary_call<Req, Resp>(
+        &self,
+        mut grpc: Grpc<Channel>,
+        path: &'static str,
+        request: Req,
+    ) -> Result<Resp, CosmosGrpcError>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        grpc.ready()
+            .await
+            .map_err(|error| CosmosGrpcError::ConnectionError {
+                endpoint: self.inner.url.clone(),
+                error,
+            })?;
+        let codec: LimitedProstCodec<Req, Resp> = LimitedProstCodec::new(self.inner.limits);
+        let call = grpc.unary(
+            Request::new(request),
+            PathAndQuery::from_static(path),
+            codec,
+        );
+        let response = match runtime::timeout(self.inner.timeout, call).await {
+            Ok(response) => response.map_err(|error| CosmosGrpcError::RequestError {
+                endpoint: self.inner.url.clone(),
+                method: path.to_string(),
+                error,
+            })?,
+            Err(_) => {
+                return Err(CosmosGrpcError::RequestTimeout {
+                    endpoint: self.inner.url.clone(),
+                    method: path.to_string(),
+                    timeout: self.inner.timeout,
+                })
+            }
+        };
+        Ok(response.into_inner())
+    }
+
+    /// Returns the connection shared by all clones of this Contact, opening it if there is
+    /// none yet, the previous one was dropped after a network error or it is older than the
+    /// max_connection_age of the reconnect policy. After failed attempts this first waits
+    /// out the backoff of the policy, which does not count against the timeout, or returns
+    /// ConnectBackoff without attempting to connect if the backoff is longer than the
+    /// timeout. Returns ConnectTimeout if the node does not accept the connection within the
+    /// timeout.
+    pub(crate) async fn connect(&self) -> Result<Grpc<Channel>, CosmosGrpcError> {
+        let connection_error = |error| CosmosGrpcError::ConnectionError {
+            endpoint: self.inner.url.clone(),
+            error,
+        };
+        let policy = self.inner.reconnect_policy;
+        let (shared, backoff) = {
+            let mut state = self.inner.connection.lock().unwrap();
+            let expired = match (&state.channel, policy.max_connection_age) {
+                (Some((_, opened)), Some(max_age)) => opened.elapsed() >= max_age,
+                _ => false,
+            };
+            if expired {
+                // requests in flight keep their clone of the old connection
+                state.channel = None;
+            }
+            let backoff = match (&state.channel, state.failed_at) {
+                (None, Some(failed_at)) => policy
+                    .backoff(state.failures)
+                    .checked_sub(failed_at.elapsed()),
+                _ => None,
+            };
+            (state.channel.as_ref().map(|(c, _)| c.clone()), backoff)
+        };
+        if let Some(backoff) = backoff {
+            // nothing was attempted so this is not another failure
+            if backoff > self.inner.timeout {
+                return Err(CosmosGrpcError::ConnectBackoff {
+                    endpoint: self.inner.url.clone(),
+                    retry_in: backoff,
+                });
+            }
+            sleep(backoff).await;
+        }
+        let connect = async {
+            let channel = match shared {
+                Some(channel) => channel,
+                None => {
+                    let endpoint = self.endpoint().map_err(connection_error)?;
+                    let channel = endpoint.connect().await.map_err(connection_error)?;
+                    // concurrent first calls may each connect, the last one to finish is kept
+                    self.inner.connection.lock().unwrap().channel =
+                        Some((channel.clone(), Instant::now()));
+                    channel
+                }
+            };
+            let mut grpc = Grpc::new(channel);
+            grpc.ready().await.map_err(connection_error)?;
+            Ok(grpc)
+        };
+        let res = match runtime::timeout(self.inner.timeout, connect).await {
+            Ok(grpc) => grpc,
+            Err(_) => Err(CosmosGrpcError::ConnectTimeout {
+                endpoint: self.inner.url.clone(),
+                timeout: self.inner.timeout,
+            }),
+        };
+        let mut state = self.inner.connection.lock().unwrap();
+        if res.is_err() {
+            state.channel = None;
+            state.failures = state.failures.saturating_add(1);
+            state.failed_at = Some(Instant::now());
+        } else {
+            state.failures = 0;
+            state.failed_at = None;
+        }
+        res
+    }
+
+    /// The endpoint of the node with the connection options applied
+    fn endpoint(&self) -> Result<Endpoint, tonic::transport::Error> {
+        let options = &self.inner.connection_options;
+        let mut endpoint = Endpoint::new(self.inner.url.clone())?
+            .tcp_keepalive(options.tcp_keepalive)
+            .tcp_nodelay(options.tcp_nodelay)
+            .keep_alive_while_idle(options.keep_alive_while_idle)
+            .initial_stream_window_size(options.initial_stream_window_size)
+            .initial_connection_window_size(options.initial_connection_window_size);
+        if let Some(interval) = options.http2_keep_alive_interval {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = options.keep_alive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+        #[cfg(feature = "tls")]
+        if self.inner.url.starts_with("https://") {
+            let tls = self.inner.tls.clone().unwrap_or_default();
+            let mut config = tonic::transport::ClientTlsConfig::new();
+            if let Some(domain_name) = tls.domain_name {
+                config = config.domain_name(domain_name);
+            }
+            if let Some(pem) = tls.ca_certificate_pem {
+                config = config.ca_certificate(tonic::transport::Certificate::from_pem(pem));
+            }
+            endpoint = endpoint.tls_config(config)?;
+        }
+        Ok(endpoint)
+    }
+
+    /// Drops the shared connection so that the next call reconnects to the node
+    fn reset_channel(&self) {
+        self.inner.connection.lock().unwrap().channel = None;
     }
 }
 
@@ -61,6 +579,90 @@ mod tests {
 
     const TIMEOUT: Duration = Duration::from_secs(60);
 
+    #[test]
+    fn test_clone_shares_connection() {
+        let contact = Contact::new("http://localhost:9090", TIMEOUT, "cosmos").unwrap();
+        let clone = contact.clone();
+        assert!(Arc::ptr_eq(&contact.inner, &clone.inner));
+        assert_eq!(clone.get_url(), "http://localhost:9090");
+        // a recording Contact has its own connection and tape
+        let recording = clone.with_recording(Path::new("recording.json"));
+        assert!(!Arc::ptr_eq(&contact.inner, &recording.inner));
+        assert!(contact.inner.tape.is_none());
+    }
+
+    #[actix_rt::test]
+    async fn test_connect_backoff() {
+        let policy = ReconnectPolicy {
+            initial_backoff: Duration::from_secs(60),
+            max_backoff: Duration::from_secs(60),
+            ..ReconnectPolicy::default()
+        };
+        // nothing listens on port 1
+        let contact = Contact::new("http://127.0.0.1:1", Duration::from_secs(5), "cosmos")
+            .unwrap()
+            .with_reconnect_policy(policy);
+        assert!(matches!(
+            contact.connect().await,
+            Err(CosmosGrpcError::ConnectionError { .. })
+        ));
+        assert_eq!(contact.inner.connection.lock().unwrap().failures, 1);
+        let start = Instant::now();
+        match contact.connect().await {
+            Err(CosmosGrpcError::ConnectBackoff { retry_in, .. }) => {
+                assert!(retry_in > Duration::from_secs(55))
+            }
+            other => panic!("Expected ConnectBackoff, got {:?}", other.map(|_| ())),
+        }
+        assert!(start.elapsed() < Duration::from_secs(1));
+        // no attempt was made so the backoff does not grow
+        assert_eq!(contact.inner.connection.lock().unwrap().failures, 1);
+
+        // a backoff shorter than the timeout is waited out before the full timeout starts
+        let policy = ReconnectPolicy {
+            initial_backoff: Duration::from_millis(300),
+            max_backoff: Duration::from_millis(300),
+            ..ReconnectPolicy::default()
+        };
+        let contact = Contact::new("http://127.0.0.1:1", Duration::from_millis(400), "cosmos")
+            .unwrap()
+            .with_reconnect_policy(policy);
+        assert!(contact.connect().await.is_err());
+        assert!(matches!(
+            contact.connect().await,
+            Err(CosmosGrpcError::ConnectionError { .. })
+        ));
+        assert_eq!(contact.inner.connection.lock().unwrap().failures, 2);
+    }
+
+    #[test]
+    fn test_contact_options() {
+        let contact = Contact::new("http://localhost:9090", TIMEOUT, "cosmos")
+            .unwrap()
+            .with_recording(Path::new("recording.json"))
+            .with_max_decoding_message_size(64 * 1024 * 1024)
+            .with_max_encoding_message_size(1024);
+        assert_eq!(
+            contact.inner.limits,
+            MessageLimits {
+                max_decoding_message_size: Some(64 * 1024 * 1024),
+                max_encoding_message_size: Some(1024),
+            }
+        );
+        // the configuration is carried over
+        let options = ConnectionOptions {
+            http2_keep_alive_interval: Some(Duration::from_secs(30)),
+            keep_alive_while_idle: true,
+            ..ConnectionOptions::default()
+        };
+        let contact = contact.with_connection_options(options);
+        assert_eq!(contact.inner.connection_options, options);
+        assert_eq!(contact.inner.limits.max_encoding_message_size, Some(1024));
+        assert!(contact.endpoint().is_ok());
+        assert!(contact.inner.tape.is_some());
+        assert_eq!(contact.get_prefix(), "cosmos");
+    }
+
     /// If you run the start-chains.sh script in the Gravity repo it will pass
     /// port 9090 on localhost and allow you to debug things quickly
     /// then be used to run this test and debug things quickly. You will need
@@ -0,0 +1,158 @@
+//! Lazy pagination of Cosmos SDK list queries. Paginated queries are exposed as streams that
+//! request the next page only once the items of the previous page have been consumed, so
+//! iterating over hundreds of thousands of records keeps at most one page in memory.
+
+use crate::error::CosmosGrpcError;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse;
+use futures::stream;
+use futures::Future;
+use futures::Stream;
+use futures::StreamExt;
+
+/// The number of items requested per page by the Contact stream methods
+pub const DEFAULT_PAGE_SIZE: u64 = 100;
+
+/// Turns a paginated query into a stream of its items. The query is called with the page
+/// request of each page as the stream is polled and returns the items of the page and the
+/// pagination of the response. The stream ends after the last page or after the first error.
+///
+/// ```ignore
+/// let grants = paginate(DEFAULT_PAGE_SIZE, |pagination| async move {
+///     let res: QueryGrantsResponse = contact.grpc_unary(PATH, QueryGrantsRequest {
+///         pagination: Some(pagination),
+///         ..request.clone()
+///     }).await?;
+///     Ok((res.grants, res.pagination))
+/// });
+/// ```
+pub fn paginate<'a, T, F, Fut>(
+    page_size: u64,
+    query: F,
+) -> impl Stream<Item = Result<T, CosmosGrpcError>> + 'a
+where
+    T: 'a,
+    F: Fn(PageRequest) -> Fut + 'a,
+    Fut: Future<Output = Result<(Vec<T>, Option<PageResponse>), CosmosGrpcError>> + 'a,
+{
+    // the key of the next page, None once the last page has been fetched
+    let first_key = Some(Vec::new());
+    stream::unfold((query, first_key), move |(query, key)| async move {
+        let page = PageRequest {
+            key: key?,
+            limit: page_size,
+            ..PageRequest::default()
+        };
+        let (items, next_key) = match query(page).await {
+            Ok((items, pagination)) => (
+                items.into_iter().map(Ok).collect::<Vec<_>>(),
+                pagination
+                    .map(|p| p.next_key)
+                    .filter(|next_key| !next_key.is_empty()),
+            ),
+            Err(e) => (vec![Err(e)], None),
+        };
+        Some((stream::iter(items), (query, next_key)))
+    })
+    .flatten()
+}
+
+/// Turns a query paged by offset, such as the transaction search, into a stream of its
+/// items. These queries never return a next key, instead the query is called with the
+/// offset of each page and returns the items of the page and the total number of items.
+/// The stream ends once total items were returned, at an empty page or after the first
+/// error.
+pub fn paginate_by_offset<'a, T, F, Fut>(
+    page_size: u64,
+    query: F,
+) -> impl Stream<Item = Result<T, CosmosGrpcError>> + 'a
+where
+    T: 'a,
+    F: Fn(PageRequest) -> Fut + 'a,
+    Fut: Future<Output = Result<(Vec<T>, u64), CosmosGrpcError>> + 'a,
+{
+    // the offset of the next page, None once the last page has been fetched
+    stream::unfold((query, Some(0)), move |(query, offset)| async move {
+        let offset = offset?;
+        let page = PageRequest {
+            offset,
+            limit: page_size,
+            count_total: true,
+            ..PageRequest::default()
+        };
+        let (items, next_offset) = match query(page).await {
+            Ok((items, total)) => {
+                let next_offset = offset + items.len() as u64;
+                let more = !items.is_empty() && next_offset < total;
+                (
+                    items.into_iter().map(Ok).collect::<Vec<_>>(),
+                    Some(next_offset).filter(|_| more),
+                )
+            }
+            Err(e) => (vec![Err(e)], None),
+        };
+        Some((stream::iter(items), (query, next_offset)))
+    })
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[actix_rt::test]
+    async fn test_paginate() {
+        let items: Vec<u64> = (0..25).collect();
+        let requests = Mutex::new(Vec::new());
+        let stream = paginate(10, |page: PageRequest| {
+            requests.lock().unwrap().push(page.key.clone());
+            let start = if page.key.is_empty() {
+                0
+            } else {
+                page.key[0] as usize
+            };
+            let end = (start + page.limit as usize).min(items.len());
+            let next_key = if end < items.len() {
+                vec![end as u8]
+            } else {
+                Vec::new()
+            };
+            let page = items[start..end].to_vec();
+            async move { Ok((page, Some(PageResponse { next_key, total: 0 }))) }
+        });
+        futures::pin_mut!(stream);
+        // pages are only requested as they are needed
+        assert_eq!(stream.next().await.unwrap().unwrap(), 0);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        let rest: Vec<u64> = stream.map(|i| i.unwrap()).collect().await;
+        assert_eq!(rest, (1..25).collect::<Vec<u64>>());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![Vec::new(), vec![10], vec![20]]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_paginate_by_offset() {
+        let items: Vec<u64> = (0..25).collect();
+        let offsets = Mutex::new(Vec::new());
+        // like the transaction search, no next key is returned
+        let stream = paginate_by_offset(10, |page: PageRequest| {
+            offsets.lock().unwrap().push(page.offset);
+            assert!(page.key.is_empty());
+            let start = (page.offset as usize).min(items.len());
+            let end = (start + page.limit as usize).min(items.len());
+            let page = items[start..end].to_vec();
+            let total = items.len() as u64;
+            async move { Ok((page, total)) }
+        });
+        let all: Vec<u64> = stream.map(|i| i.unwrap()).collect().await;
+        assert_eq!(all, items);
+        assert_eq!(*offsets.lock().unwrap(), vec![0, 10, 20]);
+
+        // a total the node can not serve does not loop forever
+        let stream = paginate_by_offset(10, |_| async { Ok((Vec::<u64>::new(), 100)) });
+        assert_eq!(stream.collect::<Vec<_>>().await.len(), 0);
+    }
+}
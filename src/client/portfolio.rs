@@ -0,0 +1,213 @@
+//! A single call view of everything an address holds or is involved in, for wallet
+//! dashboards. The queries run concurrently over the shared connection of the Contact.
+
+use crate::client::distribution::truncate_dec_coin;
+use crate::error::CosmosGrpcError;
+#[cfg(feature = "sdk-0_46")]
+use crate::proto::cosmos::authz::v1beta1::GrantAuthorization;
+use crate::proto::cosmos::feegrant::v1beta1::Grant as FeeGrant;
+use crate::Address;
+use crate::Coin;
+use crate::Coins;
+use crate::Contact;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegationTotalRewardsRequest;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryDelegationTotalRewardsResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::DelegationResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::UnbondingDelegation;
+use futures::TryStreamExt;
+use num256::Uint256;
+use num_traits::CheckedAdd;
+
+/// The holdings of an address at the time of the snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct PortfolioSnapshot {
+    pub address: Address,
+    /// the bank balances, including coins that are still vesting
+    pub balances: Vec<Coin>,
+    pub delegations: Vec<DelegationResponse>,
+    pub unbonding: Vec<UnbondingDelegation>,
+    /// the pending staking rewards of each validator, truncated to whole units
+    pub rewards: Vec<(Address, Vec<Coin>)>,
+    /// fee allowances other accounts have given this address
+    pub fee_allowances_received: Vec<FeeGrant>,
+    /// fee allowances this address has given other accounts
+    #[cfg(feature = "sdk-0_46")]
+    pub fee_allowances_given: Vec<FeeGrant>,
+    /// authz grants this address has given other accounts
+    #[cfg(feature = "sdk-0_46")]
+    pub grants_given: Vec<GrantAuthorization>,
+    /// authz grants other accounts have given this address
+    #[cfg(feature = "sdk-0_46")]
+    pub grants_received: Vec<GrantAuthorization>,
+}
+
+impl PortfolioSnapshot {
+    /// The tokens currently delegated
+    pub fn total_delegated(&self) -> Result<Coins, CosmosGrpcError> {
+        let mut coins = Vec::new();
+        for delegation in self.delegations.iter() {
+            if let Some(balance) = &delegation.balance {
+                let coin =
+                    Coin::from_proto(balance.clone()).map_err(CosmosGrpcError::BadResponse)?;
+                coins.push(coin);
+            }
+        }
+        Coins::new(coins).map_err(CosmosGrpcError::BadResponse)
+    }
+
+    /// The amount of the bond denom still unbonding across every validator
+    pub fn total_unbonding(&self) -> Result<Uint256, CosmosGrpcError> {
+        let mut total: Uint256 = 0u64.into();
+        for entry in self.unbonding.iter().flat_map(|u| u.entries.iter()) {
+            let balance: Uint256 = entry.balance.parse().map_err(|_| {
+                CosmosGrpcError::BadResponse(format!("Invalid unbonding balance {}", entry.balance))
+            })?;
+            total = total.checked_add(&balance).ok_or_else(|| {
+                CosmosGrpcError::BadResponse("Unbonding balance overflow".to_string())
+            })?;
+        }
+        Ok(total)
+    }
+
+    /// The pending rewards summed over every validator
+    pub fn total_rewards(&self) -> Result<Coins, CosmosGrpcError> {
+        let coins = self
+            .rewards
+            .iter()
+            .flat_map(|(_, coins)| coins.iter().cloned())
+            .collect();
+        Coins::new(coins).map_err(CosmosGrpcError::BadResponse)
+    }
+}
+
+impl Contact {
+    /// Gets the pending rewards of every validator the delegator has delegated to, in
+    /// every denom, truncated to whole units
+    pub async fn get_all_pending_rewards(
+        &self,
+        delegator: Address,
+    ) -> Result<Vec<(Address, Vec<Coin>)>, CosmosGrpcError> {
+        let res: QueryDelegationTotalRewardsResponse = self
+            .grpc_unary(
+                "/cosmos.distribution.v1beta1.Query/DelegationTotalRewards",
+                QueryDelegationTotalRewardsRequest {
+                    delegator_address: delegator.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        let mut rewards = Vec::new();
+        for reward in res.rewards {
+            let validator = match reward.validator_address.parse() {
+                Ok(v) => v,
+                Err(e) => return Err(CosmosGrpcError::BadResponse(format!("{}", e))),
+            };
+            let mut coins = Vec::new();
+            for coin in reward.reward.iter() {
+                coins.push(truncate_dec_coin(coin)?);
+            }
+            rewards.push((validator, coins));
+        }
+        Ok(rewards)
+    }
+
+    /// Gets the balances, delegations, unbonding delegations, pending rewards, fee
+    /// allowances and authz grants of an address in one call. Fails if any query fails.
+    pub async fn get_portfolio_snapshot(
+        &self,
+        address: Address,
+    ) -> Result<PortfolioSnapshot, CosmosGrpcError> {
+        let delegations = self.get_delegations_stream(address).try_collect::<Vec<_>>();
+        let unbonding = self
+            .get_unbonding_delegations_stream(address)
+            .try_collect::<Vec<_>>();
+        let fee_allowances = self
+            .get_fee_allowances_stream(address)
+            .try_collect::<Vec<_>>();
+        let (balances, delegations, unbonding, rewards, allowances) = futures::try_join!(
+            self.get_balances(address),
+            delegations,
+            unbonding,
+            self.get_all_pending_rewards(address),
+            fee_allowances,
+        )?;
+        #[cfg(feature = "sdk-0_46")]
+        let (fee_allowances_given, grants_given, grants_received) = futures::try_join!(
+            self.get_fee_allowances_by_granter_stream(address)
+                .try_collect::<Vec<_>>(),
+            self.get_granter_grants_stream(address)
+                .try_collect::<Vec<_>>(),
+            self.get_grantee_grants_stream(address)
+                .try_collect::<Vec<_>>(),
+        )?;
+        Ok(PortfolioSnapshot {
+            address,
+            balances,
+            delegations,
+            unbonding,
+            rewards,
+            fee_allowances_received: allowances,
+            #[cfg(feature = "sdk-0_46")]
+            fee_allowances_given,
+            #[cfg(feature = "sdk-0_46")]
+            grants_given,
+            #[cfg(feature = "sdk-0_46")]
+            grants_received,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+    use cosmos_sdk_proto::cosmos::staking::v1beta1::UnbondingDelegationEntry;
+
+    #[test]
+    fn test_portfolio_totals() {
+        let address = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let validator = Address::from_bytes([2; 20], "cosmosvaloper").unwrap();
+        let delegation = |amount: &str| DelegationResponse {
+            delegation: None,
+            balance: Some(ProtoCoin {
+                denom: "uatom".to_string(),
+                amount: amount.to_string(),
+            }),
+        };
+        let entry = |balance: &str| UnbondingDelegationEntry {
+            balance: balance.to_string(),
+            ..UnbondingDelegationEntry::default()
+        };
+        let snapshot = PortfolioSnapshot {
+            address,
+            balances: Vec::new(),
+            delegations: vec![delegation("100"), delegation("50")],
+            unbonding: vec![UnbondingDelegation {
+                entries: vec![entry("10"), entry("5")],
+                ..UnbondingDelegation::default()
+            }],
+            rewards: vec![
+                (validator, vec!["3uatom".parse().unwrap()]),
+                (
+                    validator,
+                    vec!["4uatom".parse().unwrap(), "1uosmo".parse().unwrap()],
+                ),
+            ],
+            fee_allowances_received: Vec::new(),
+            #[cfg(feature = "sdk-0_46")]
+            fee_allowances_given: Vec::new(),
+            #[cfg(feature = "sdk-0_46")]
+            grants_given: Vec::new(),
+            #[cfg(feature = "sdk-0_46")]
+            grants_received: Vec::new(),
+        };
+        assert_eq!(
+            snapshot.total_delegated().unwrap(),
+            "150uatom".parse().unwrap()
+        );
+        assert_eq!(snapshot.total_unbonding().unwrap(), 15u64.into());
+        assert_eq!(
+            snapshot.total_rewards().unwrap(),
+            "7uatom,1uosmo".parse().unwrap()
+        );
+    }
+}
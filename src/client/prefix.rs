@@ -0,0 +1,90 @@
+//! Detection of the account address prefix used by a chain, so that a Contact can be
+//! configured from just a gRPC url without risking an InvalidPrefix misconfiguration
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::auth::v1beta1::Bech32PrefixRequest;
+use crate::proto::cosmos::auth::v1beta1::Bech32PrefixResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorsRequest;
+use std::time::Duration;
+
+/// The suffix the SDK appends to the account prefix for validator operator addresses
+const VALOPER_SUFFIX: &str = "valoper";
+
+/// Returns the account prefix from a validator operator address such as cosmosvaloper1...
+pub fn prefix_from_operator_address(operator_address: &str) -> Option<String> {
+    let hrp = &operator_address[..operator_address.rfind('1')?];
+    let prefix = hrp.strip_suffix(VALOPER_SUFFIX)?;
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix.to_string())
+    }
+}
+
+impl Contact {
+    /// Detects the account address prefix of the connected chain. Chains running Cosmos SDK
+    /// v0.46+ are asked directly, older chains are detected from a validator operator address.
+    pub async fn get_bech32_prefix(&self) -> Result<String, CosmosGrpcError> {
+        let res: Result<Bech32PrefixResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.auth.v1beta1.Query/Bech32Prefix",
+                Bech32PrefixRequest {},
+            )
+            .await;
+        match res {
+            Ok(res) if !res.bech32_prefix.is_empty() => return Ok(res.bech32_prefix),
+            Ok(_) | Err(CosmosGrpcError::RequestError { .. }) => {}
+            Err(e) => return Err(e),
+        }
+
+        let validators = self
+            .get_validators_list(QueryValidatorsRequest {
+                status: String::new(),
+                pagination: None,
+            })
+            .await?;
+        match validators
+            .validators
+            .iter()
+            .find_map(|v| prefix_from_operator_address(&v.operator_address))
+        {
+            Some(prefix) => Ok(prefix),
+            None => Err(CosmosGrpcError::BadResponse(
+                "Could not detect the chain address prefix".to_string(),
+            )),
+        }
+    }
+
+    /// Creates a Contact using the address prefix of the chain at the provided url, if
+    /// prefix_override is set it is used instead and no detection is performed
+    pub async fn new_with_detected_prefix(
+        url: &str,
+        timeout: Duration,
+        prefix_override: Option<&str>,
+    ) -> Result<Self, CosmosGrpcError> {
+        if let Some(prefix) = prefix_override {
+            return Contact::new(url, timeout, prefix);
+        }
+        // the prefix is only used for addresses, the placeholder is never used by detection
+        let contact = Contact::new(url, timeout, "cosmos")?;
+        let prefix = contact.get_bech32_prefix().await?;
+        Contact::new(url, timeout, &prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefix_from_operator_address() {
+        assert_eq!(
+            prefix_from_operator_address("cosmosvaloper1sjllsnramtg3ewxqwwrwjxfgc4n4ef9u2lcnj0"),
+            Some("cosmos".to_string())
+        );
+        // chains with custom validator prefixes can not be detected this way
+        assert_eq!(prefix_from_operator_address("crocncl1abc"), None);
+        assert_eq!(prefix_from_operator_address("valoper1abc"), None);
+    }
+}
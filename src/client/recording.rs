@@ -0,0 +1,260 @@
+//! Record and replay of the gRPC traffic of a Contact. A recording Contact saves every
+//! request and response it makes to a cassette file, a Contact created from that file later
+//! replays the responses without a node. This makes it possible to capture a session against
+//! a live chain once and run it deterministically in CI.
+//!
+//! Requests are matched by method path and encoded request bytes, identical requests are
+//! replayed in the order they were recorded so polling loops see the same sequence of
+//! responses.
+
+use crate::error::CosmosGrpcError;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tonic::Code;
+use tonic::Status;
+
+/// The recorded outcome of a single gRPC call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedResponse {
+    /// the base64 encoded response message
+    Ok(String),
+    /// the gRPC status returned by the node
+    Err { code: i32, message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Interaction {
+    path: String,
+    /// the base64 encoded request message
+    request: String,
+    response: RecordedResponse,
+}
+
+/// The contents of a recording file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Cassette {
+    pub url: String,
+    pub chain_prefix: String,
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn new(url: String, chain_prefix: String) -> Self {
+        Cassette {
+            url,
+            chain_prefix,
+            interactions: Vec::new(),
+        }
+    }
+
+    pub fn load(file: &Path) -> Result<Self, CosmosGrpcError> {
+        let contents = fs::read_to_string(file).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Failed to read {}: {}", file.display(), e))
+        })?;
+        serde_json::from_str(&contents).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Invalid recording {}: {}", file.display(), e))
+        })
+    }
+}
+
+/// Where the gRPC calls of a Contact go besides the node
+#[derive(Debug)]
+pub(crate) enum GrpcTape {
+    /// calls go to the node and are appended to the file
+    Record {
+        file: PathBuf,
+        cassette: Mutex<Cassette>,
+    },
+    /// calls are answered from the cassette, used marks interactions already replayed
+    Replay {
+        cassette: Cassette,
+        used: Mutex<Vec<bool>>,
+    },
+}
+
+impl GrpcTape {
+    pub fn record(file: PathBuf, cassette: Cassette) -> Self {
+        GrpcTape::Record {
+            file,
+            cassette: Mutex::new(cassette),
+        }
+    }
+
+    pub fn replay(cassette: Cassette) -> Self {
+        let used = vec![false; cassette.interactions.len()];
+        GrpcTape::Replay {
+            cassette,
+            used: Mutex::new(used),
+        }
+    }
+
+    /// Appends a call to the recording and rewrites the file so that nothing is lost if
+    /// the process exits early. Does nothing when replaying.
+    pub fn save(
+        &self,
+        path: &str,
+        request: &[u8],
+        response: Result<&[u8], &Status>,
+    ) -> Result<(), CosmosGrpcError> {
+        if let GrpcTape::Record { file, cassette } = self {
+            let response = match response {
+                Ok(bytes) => RecordedResponse::Ok(base64::encode(bytes)),
+                Err(status) => RecordedResponse::Err {
+                    code: status.code() as i32,
+                    message: status.message().to_string(),
+                },
+            };
+            let mut cassette = cassette.lock().unwrap();
+            cassette.interactions.push(Interaction {
+                path: path.to_string(),
+                request: base64::encode(request),
+                response,
+            });
+            let contents = serde_json::to_string_pretty(&*cassette).unwrap();
+            fs::write(file, contents).map_err(|e| {
+                CosmosGrpcError::BadInput(format!("Failed to write {}: {}", file.display(), e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Returns the encoded response to a call when replaying, None when recording. A recorded
+    /// error status is returned as a RequestError, a call that was never recorded is an error.
+    pub fn find(&self, path: &str, request: &[u8]) -> Option<Result<Vec<u8>, CosmosGrpcError>> {
+        let (cassette, used) = match self {
+            GrpcTape::Record { .. } => return None,
+            GrpcTape::Replay { cassette, used } => (cassette, used),
+        };
+        let request = base64::encode(request);
+        let mut used = used.lock().unwrap();
+        let found = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .find(|(i, interaction)| {
+                !used[*i] && interaction.path == path && interaction.request == request
+            });
+        let res = match found {
+            Some((i, interaction)) => {
+                used[i] = true;
+                match &interaction.response {
+                    RecordedResponse::Ok(bytes) => base64::decode(bytes).map_err(|e| {
+                        CosmosGrpcError::BadResponse(format!("Invalid recorded response {}", e))
+                    }),
+                    RecordedResponse::Err { code, message } => Err(CosmosGrpcError::RequestError {
+                        endpoint: cassette.url.clone(),
+                        method: path.to_string(),
+                        error: Status::new(Code::from_i32(*code), message.clone()),
+                    }),
+                }
+            }
+            None => Err(CosmosGrpcError::BadResponse(format!(
+                "No recorded response for {} with request {}",
+                path, request
+            ))),
+        };
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+    use crate::Coin;
+    use crate::Contact;
+    use crate::PrivateKey;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceRequest;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::QueryBalanceResponse;
+    use prost::Message;
+    use std::time::Duration;
+
+    fn temp_file() -> PathBuf {
+        std::env::temp_dir().join(format!("deep_space_replay_{}.json", rand::random::<u64>()))
+    }
+
+    #[test]
+    fn test_replay_order() {
+        let file = temp_file();
+        let recorder = GrpcTape::record(
+            file.clone(),
+            Cassette::new("http://localhost:9090".to_string(), "cosmos".to_string()),
+        );
+        assert!(recorder.find("/a.Query/B", &[1]).is_none());
+        recorder.save("/a.Query/B", &[1], Ok(&[2])).unwrap();
+        recorder.save("/a.Query/B", &[1], Ok(&[3])).unwrap();
+        recorder
+            .save("/a.Query/B", &[4], Err(&Status::not_found("missing")))
+            .unwrap();
+
+        let cassette = Cassette::load(&file).unwrap();
+        fs::remove_file(&file).unwrap();
+        assert_eq!(cassette.chain_prefix, "cosmos");
+        let replay = GrpcTape::replay(cassette);
+        assert_eq!(replay.find("/a.Query/B", &[1]).unwrap().unwrap(), vec![2]);
+        assert_eq!(replay.find("/a.Query/B", &[1]).unwrap().unwrap(), vec![3]);
+        // every recorded response has been used
+        assert!(replay.find("/a.Query/B", &[1]).unwrap().is_err());
+        // the path is part of the match
+        assert!(replay.find("/a.Query/C", &[4]).unwrap().is_err());
+        match replay.find("/a.Query/B", &[4]).unwrap() {
+            Err(e) => assert_eq!(e.grpc_code(), Some(Code::NotFound)),
+            Ok(_) => panic!("Unexpected replay response"),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_replay_balances_many() {
+        let file = temp_file();
+        let recorder = GrpcTape::record(
+            file.clone(),
+            Cassette::new("http://localhost:9090".to_string(), "cosmos".to_string()),
+        );
+        let addresses: Vec<Address> = (1..=3u8)
+            .map(|i| PrivateKey::from_secret(&[i]).to_address("cosmos").unwrap())
+            .collect();
+        for (i, address) in addresses.iter().enumerate() {
+            let mut request = Vec::new();
+            QueryBalanceRequest {
+                address: address.to_string(),
+                denom: "uatom".to_string(),
+            }
+            .encode(&mut request)
+            .unwrap();
+            let mut response = Vec::new();
+            QueryBalanceResponse {
+                balance: Some(Coin::new((i as u64 * 10).into(), "uatom".to_string()).into()),
+            }
+            .encode(&mut response)
+            .unwrap();
+            recorder
+                .save(
+                    "/cosmos.bank.v1beta1.Query/Balance",
+                    &request,
+                    Ok(&response),
+                )
+                .unwrap();
+        }
+
+        let contact = Contact::from_recording(&file, Duration::from_secs(1)).unwrap();
+        fs::remove_file(&file).unwrap();
+        let balances = contact
+            .get_balances_many(&addresses, "uatom", 2)
+            .await
+            .unwrap();
+        assert_eq!(balances.len(), 3);
+        assert_eq!(balances[0].as_ref().unwrap(), &None);
+        assert_eq!(
+            balances[2].as_ref().unwrap(),
+            &Some("20uatom".parse().unwrap())
+        );
+        // every recorded response has been replayed
+        assert!(contact
+            .get_balance(addresses[1], "uatom".to_string())
+            .await
+            .is_err());
+    }
+}
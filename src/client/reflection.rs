@@ -0,0 +1,73 @@
+//! Contains utility functions for discovering which gRPC services a node exposes using
+//! server reflection, allowing callers to check for module support before making requests
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::grpc::reflection::v1alpha::server_reflection_request::MessageRequest;
+use crate::proto::grpc::reflection::v1alpha::server_reflection_response::MessageResponse;
+use crate::proto::grpc::reflection::v1alpha::ServerReflectionRequest;
+use crate::proto::grpc::reflection::v1alpha::ServerReflectionResponse;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::Request;
+
+const SERVER_REFLECTION_INFO_PATH: &str =
+    "/grpc.reflection.v1alpha.ServerReflection/ServerReflectionInfo";
+
+impl Contact {
+    /// Lists the fully qualified names of all gRPC services registered on the node, for example
+    /// `cosmos.bank.v1beta1.Query`. This requires the node to have gRPC server reflection enabled
+    /// which is the default for Cosmos SDK nodes, if it is not a RequestError is returned.
+    pub async fn list_services(&self) -> Result<Vec<String>, CosmosGrpcError> {
+        let mut grpc = self.connect().await?;
+        let request_error = |error| CosmosGrpcError::RequestError {
+            endpoint: self.inner.url.clone(),
+            method: SERVER_REFLECTION_INFO_PATH.to_string(),
+            error,
+        };
+
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            // the content of this field is not checked by the server
+            message_request: Some(MessageRequest::ListServices(String::new())),
+        };
+        let codec: ProstCodec<ServerReflectionRequest, ServerReflectionResponse> =
+            ProstCodec::default();
+        let mut stream = grpc
+            .streaming(
+                Request::new(futures::stream::iter(vec![request])),
+                PathAndQuery::from_static(SERVER_REFLECTION_INFO_PATH),
+                codec,
+            )
+            .await
+            .map_err(request_error)?
+            .into_inner();
+
+        match stream.message().await.map_err(request_error)? {
+            Some(ServerReflectionResponse {
+                message_response: Some(MessageResponse::ListServicesResponse(list)),
+                ..
+            }) => Ok(list.service.into_iter().map(|s| s.name).collect()),
+            Some(ServerReflectionResponse {
+                message_response: Some(MessageResponse::ErrorResponse(e)),
+                ..
+            }) => Err(CosmosGrpcError::BadResponse(format!(
+                "Reflection error {} {}",
+                e.error_code, e.error_message
+            ))),
+            _ => Err(CosmosGrpcError::BadResponse(
+                "Unexpected reflection response".to_string(),
+            )),
+        }
+    }
+
+    /// Checks if the node exposes the given fully qualified gRPC service, for example
+    /// `contact.supports("cosmos.gov.v1.Query")`. This allows callers to gracefully degrade
+    /// when working with chains running different sets of modules or SDK versions. Note that
+    /// each call performs a reflection request, if you need to check many services use
+    /// list_services() once instead.
+    pub async fn supports(&self, service: &str) -> Result<bool, CosmosGrpcError> {
+        let services = self.list_services().await?;
+        Ok(services.iter().any(|s| s == service))
+    }
+}
@@ -0,0 +1,179 @@
+//! Decoding of the Msg handler responses returned in the data field of a TxResponse. Each
+//! message in a transaction produces a response, for example MsgInstantiateContractResponse
+//! contains the address of the new contract, so decoding these avoids searching events.
+
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxMsgData;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::registry::DecodedMsg;
+use crate::utils::hex_str_to_bytes;
+use crate::MsgRegistry;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse;
+use prost::Message;
+use prost_types::Any;
+
+/// Returns the Msg responses of a confirmed transaction in message order. Chains running
+/// Cosmos SDK v0.46 or later return responses packed into Anys, older chains return them
+/// keyed by the message type in which case the type url of the returned Any is the message
+/// type url with a Response suffix.
+pub fn msg_responses(response: &TxResponse) -> Result<Vec<Any>, CosmosGrpcError> {
+    if response.data.is_empty() {
+        return Err(CosmosGrpcError::BadResponse(
+            "Tx has no data, has it been included in a block?".to_string(),
+        ));
+    }
+    let bytes = hex_str_to_bytes(&response.data)
+        .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid tx data {:?}", e)))?;
+    decode_tx_msg_data(&bytes)
+}
+
+/// Decodes the Msg responses from an encoded TxMsgData, as found in the data of a
+/// TxResponse or the acknowledgement of an interchain account packet
+pub(crate) fn decode_tx_msg_data(bytes: &[u8]) -> Result<Vec<Any>, CosmosGrpcError> {
+    let data = TxMsgData::decode(bytes).map_err(|error| CosmosGrpcError::DecodeError { error })?;
+    if !data.msg_responses.is_empty() {
+        return Ok(data.msg_responses);
+    }
+    Ok(data
+        .data
+        .into_iter()
+        .map(|d| Any {
+            type_url: if d.msg_type.starts_with('/') {
+                format!("{}Response", d.msg_type)
+            } else {
+                d.msg_type
+            },
+            value: d.data,
+        })
+        .collect())
+}
+
+/// Decodes the response of the message at the provided index as T
+pub fn decode_msg_response<T: Message + Default>(
+    response: &TxResponse,
+    index: usize,
+) -> Result<T, CosmosGrpcError> {
+    match msg_responses(response)?.get(index) {
+        Some(any) => {
+            T::decode(any.value.as_slice()).map_err(|error| CosmosGrpcError::DecodeError { error })
+        }
+        None => Err(CosmosGrpcError::BadInput(format!(
+            "Tx has no response for message {}",
+            index
+        ))),
+    }
+}
+
+impl MsgRegistry {
+    /// Decodes every Msg response of a confirmed transaction using the registered response
+    /// types, response types are registered in the same way as messages
+    pub fn decode_msg_responses(
+        &self,
+        response: &TxResponse,
+    ) -> Result<Vec<DecodedMsg>, CosmosGrpcError> {
+        msg_responses(response)?
+            .iter()
+            .map(|any| self.decode(any))
+            .collect()
+    }
+
+    /// Decodes every Msg response of a simulation using the registered response types, the
+    /// data of the simulation result is a TxMsgData like the data of a confirmed transaction
+    pub fn decode_simulated_responses(
+        &self,
+        response: &SimulateResponse,
+    ) -> Result<Vec<DecodedMsg>, CosmosGrpcError> {
+        let data = match &response.result {
+            Some(result) => &result.data,
+            None => {
+                return Err(CosmosGrpcError::BadResponse(
+                    "No result in simulation?".to_string(),
+                ))
+            }
+        };
+        decode_tx_msg_data(data)?
+            .iter()
+            .map(|any| self.decode(any))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::cosmos::base::abci::v1beta1::MsgData;
+    use crate::utils::bytes_to_hex_str;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::Result as AbciResult;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct MsgCustomResponse {
+        #[prost(uint64, tag = "1")]
+        pub id: u64,
+    }
+
+    fn encode(msg: impl Message) -> Vec<u8> {
+        let mut buf = Vec::new();
+        msg.encode(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_decode_msg_responses() {
+        let inner = encode(MsgCustomResponse { id: 7 });
+        let new_style = TxMsgData {
+            data: Vec::new(),
+            msg_responses: vec![Any {
+                type_url: "/custom.v1.MsgCustomResponse".to_string(),
+                value: inner.clone(),
+            }],
+        };
+        let old_style = TxMsgData {
+            data: vec![MsgData {
+                msg_type: "/custom.v1.MsgCustom".to_string(),
+                data: inner,
+            }],
+            msg_responses: Vec::new(),
+        };
+        for data in [new_style, old_style].iter() {
+            let response = TxResponse {
+                data: bytes_to_hex_str(&encode(data.clone())).to_uppercase(),
+                ..Default::default()
+            };
+            let responses = msg_responses(&response).unwrap();
+            assert_eq!(responses[0].type_url, "/custom.v1.MsgCustomResponse");
+            let decoded: MsgCustomResponse = decode_msg_response(&response, 0).unwrap();
+            assert_eq!(decoded.id, 7);
+            assert!(decode_msg_response::<MsgCustomResponse>(&response, 1).is_err());
+        }
+        assert!(msg_responses(&TxResponse::default()).is_err());
+    }
+
+    #[test]
+    fn test_decode_simulated_responses() {
+        let data = TxMsgData {
+            data: Vec::new(),
+            msg_responses: vec![Any {
+                type_url: "/custom.v1.MsgCustomResponse".to_string(),
+                value: encode(MsgCustomResponse { id: 7 }),
+            }],
+        };
+        let response = SimulateResponse {
+            gas_info: None,
+            result: Some(AbciResult {
+                data: encode(data),
+                ..Default::default()
+            }),
+        };
+        let mut registry = MsgRegistry::new();
+        assert!(registry.decode_simulated_responses(&response).is_err());
+        registry.register::<MsgCustomResponse>("/custom.v1.MsgCustomResponse", None);
+        let decoded = registry.decode_simulated_responses(&response).unwrap();
+        assert_eq!(
+            decoded[0].downcast_ref::<MsgCustomResponse>().unwrap().id,
+            7
+        );
+        assert!(registry
+            .decode_simulated_responses(&SimulateResponse::default())
+            .is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! The async runtime services the client depends on, timers and background tasks. The rest of
+//! the client only uses these functions so supporting another executor means providing
+//! another implementation of this module.
+//!
+//! Tokio is used by default. With the async-std feature timers and background tasks run on
+//! async-std instead, which does not need a Tokio context. The gRPC transport (tonic and
+//! hyper) always requires Tokio, so calls that reach a node must still run inside a Tokio
+//! context, for example through the async-compat crate. Keys, signing and message building
+//! do not use a runtime at all.
+
+#[cfg(feature = "async-std")]
+use futures::future::AbortHandle;
+#[cfg(feature = "async-std")]
+use futures::future::Abortable;
+use futures::Future;
+use std::time::Duration;
+#[cfg(not(feature = "async-std"))]
+use tokio::task::JoinHandle;
+
+/// Waits until the duration has elapsed
+#[cfg(not(feature = "async-std"))]
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await
+}
+
+/// Waits until the duration has elapsed
+#[cfg(feature = "async-std")]
+pub(crate) async fn sleep(duration: Duration) {
+    async_std::task::sleep(duration).await
+}
+
+/// Returned by timeout when the future did not complete in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Elapsed;
+
+/// Runs the future, giving up after the duration
+#[cfg(not(feature = "async-std"))]
+pub(crate) async fn timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| Elapsed)
+}
+
+/// Runs the future, giving up after the duration
+#[cfg(feature = "async-std")]
+pub(crate) async fn timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, Elapsed> {
+    async_std::future::timeout(duration, future)
+        .await
+        .map_err(|_| Elapsed)
+}
+
+/// A background task started with spawn, the task keeps running if the handle is dropped
+#[cfg(not(feature = "async-std"))]
+#[derive(Debug)]
+pub(crate) struct TaskHandle(JoinHandle<()>);
+
+/// A background task started with spawn, the task keeps running if the handle is dropped
+#[cfg(feature = "async-std")]
+#[derive(Debug)]
+pub(crate) struct TaskHandle(AbortHandle);
+
+impl TaskHandle {
+    /// Stops the task at its next await point
+    pub fn abort(&self) {
+        self.0.abort()
+    }
+}
+
+/// Runs the future in the background, this must be called from within the runtime
+#[cfg(not(feature = "async-std"))]
+pub(crate) fn spawn<F>(future: F) -> TaskHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    TaskHandle(tokio::spawn(future))
+}
+
+/// Runs the future in the background on the async-std executor
+#[cfg(feature = "async-std")]
+pub(crate) fn spawn<F>(future: F) -> TaskHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let (handle, registration) = AbortHandle::new_pair();
+    async_std::task::spawn(async move {
+        let _ = Abortable::new(future, registration).await;
+    });
+    TaskHandle(handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_timeout() {
+        assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Ok(1));
+        let slow = sleep(Duration::from_secs(10));
+        assert_eq!(timeout(Duration::from_millis(10), slow).await, Err(Elapsed));
+    }
+
+    #[actix_rt::test]
+    async fn test_spawn_abort() {
+        let (sender, mut receiver) = futures::channel::mpsc::unbounded();
+        let task = spawn(async move {
+            loop {
+                let _ = sender.unbounded_send(());
+                sleep(Duration::from_millis(5)).await;
+            }
+        });
+        sleep(Duration::from_millis(20)).await;
+        task.abort();
+        sleep(Duration::from_millis(20)).await;
+        // the sender is dropped with the aborted task, ending the channel
+        let mut sent = 0;
+        while let Some(()) = futures::StreamExt::next(&mut receiver).await {
+            sent += 1;
+        }
+        assert!(sent > 0);
+    }
+}
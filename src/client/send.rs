@@ -1,4 +1,8 @@
 use crate::address::Address;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::broadcast_log::BroadcastRecord;
+use crate::client::fee_budget::reserve_tx_fee;
+use crate::client::runtime::sleep;
 use crate::client::Contact;
 use crate::client::MEMO;
 use crate::coin::Coin;
@@ -6,55 +10,100 @@ use crate::coin::Fee;
 use crate::error::CosmosGrpcError;
 use crate::msg::Msg;
 use crate::private_key::PrivateKey;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::BroadcastTxResponse;
 use crate::utils::check_tx_response;
+use crate::utils::determine_insufficient_funds;
 use crate::utils::determine_min_fees_and_gas;
+use crate::utils::tx_sequence;
 use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxRequest;
-use cosmos_sdk_proto::cosmos::{
-    base::abci::v1beta1::TxResponse, tx::v1beta1::service_client::ServiceClient as TxServiceClient,
-};
 use std::time::Instant;
 use std::{clone::Clone, time::Duration};
-use tokio::time::sleep;
 use tonic::Code as TonicCode;
 
+/// Converts a broadcast response that was rejected by the chain into the matching error,
+/// any nonzero CheckTx code that is not a fee or funds problem is a TransactionFailed
+pub(crate) fn check_broadcast_response(
+    response: TxResponse,
+    sequence: Option<u64>,
+) -> Result<TxResponse, CosmosGrpcError> {
+    if let Some(v) = determine_min_fees_and_gas(&response) {
+        return Err(CosmosGrpcError::InsufficientFees { fee_info: v });
+    } else if let Some(funds_info) = determine_insufficient_funds(&response) {
+        return Err(CosmosGrpcError::InsufficientFunds { funds_info });
+    } else if response.code != 0 || !check_tx_response(&response) {
+        return Err(CosmosGrpcError::TransactionFailed {
+            tx: response,
+            time: Duration::from_secs(0),
+            sequence,
+        });
+    }
+    Ok(response)
+}
+
+/// Converts a transaction that was included in a block but failed for lack of funds into
+/// an InsufficientFunds error, other included transactions are returned as is
+pub(crate) fn check_included_tx(response: TxResponse) -> Result<TxResponse, CosmosGrpcError> {
+    match determine_insufficient_funds(&response) {
+        Some(funds_info) => Err(CosmosGrpcError::InsufficientFunds { funds_info }),
+        None => Ok(response),
+    }
+}
+
 impl Contact {
     /// The advanced version of create_and_send transaction that expects you to
     /// perform your own signing and prep first. This is used by all message sending
     /// functions in deep_space and I suggest you use it that way as well. It provides
     /// validation for various failure conditions that is non-trivial to perform correctly.
+    /// In dry run mode the transaction is only simulated, see with_dry_run.
     pub async fn send_transaction(
         &self,
         // proto serialized message for us to turn into an 'any' object
         msg: Vec<u8>,
         mode: BroadcastMode,
     ) -> Result<TxResponse, CosmosGrpcError> {
-        let mut txrpc = TxServiceClient::connect(self.get_url()).await?;
-        let response = txrpc
-            .broadcast_tx(BroadcastTxRequest {
-                tx_bytes: msg,
-                mode: mode.into(),
-            })
-            .await?
-            .into_inner()
-            .tx_response
-            .unwrap();
-        if let Some(v) = determine_min_fees_and_gas(&response) {
-            return Err(CosmosGrpcError::InsufficientFees { fee_info: v });
-        } else if !check_tx_response(&response) {
-            return Err(CosmosGrpcError::TransactionFailed {
-                tx: response,
-                time: Duration::from_secs(0),
-            });
+        if self.inner.dry_run {
+            return self.dry_run_transaction(msg).await;
         }
-        Ok(response)
+        let sequence = tx_sequence(&msg);
+        let spend = match &self.inner.fee_budget {
+            Some(budget) => Some(reserve_tx_fee(budget, &msg)?),
+            None => None,
+        };
+        let start = Instant::now();
+        let sink = self.inner.broadcast_sink.clone();
+        // the bytes are only kept if a sink needs to describe them
+        let tx_bytes = sink.as_ref().map(|_| msg.clone());
+        let response: Result<BroadcastTxResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.tx.v1beta1.Service/BroadcastTx",
+                BroadcastTxRequest {
+                    tx_bytes: msg,
+                    mode: mode.into(),
+                },
+            )
+            .await;
+        let result = response
+            .and_then(|response| check_broadcast_response(response.tx_response.unwrap(), sequence));
+        if let (Some(budget), Some(spend), Err(e)) = (&self.inner.fee_budget, spend, &result) {
+            // the node may have received a transaction it did not answer for
+            if !e.is_transient_network() {
+                budget.release(spend);
+            }
+        }
+        if let (Some(sink), Some(tx_bytes)) = (sink, tx_bytes) {
+            let record = BroadcastRecord::new(&tx_bytes, &self.inner.url, &result, start.elapsed());
+            sink.record(&record);
+        }
+        result
     }
 
     /// A utility function that creates a one to one simple transaction
     /// and sends it from the provided private key, waiting the configured
-    /// amount of time for the tx to enter the chain, if you do not specify
-    /// a fee the smallest working amount will be selected.
+    /// amount of time for the tx to enter the chain. If you do not specify
+    /// a fee it is priced by the auto fee of the Contact, see with_auto_fee.
     pub async fn send_tokens(
         &self,
         coin: Coin,
@@ -64,35 +113,69 @@ impl Contact {
         wait_timeout: Option<Duration>,
     ) -> Result<TxResponse, CosmosGrpcError> {
         trace!("Creating transaction");
-        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
 
         let send = MsgSend {
-            amount: vec![coin.into()],
-            from_address: our_address.to_bech32(&self.chain_prefix).unwrap(),
-            to_address: destination.to_bech32(&self.chain_prefix).unwrap(),
-        };
-        let msg = Msg::new("/cosmos.bank.v1beta1.MsgSend", send);
-
-        let fee_obj = if let Some(fee) = fee {
-            Fee {
-                amount: vec![fee],
-                gas_limit: 500_000,
-                granter: None,
-                payer: None,
-            }
-        } else {
-            Fee {
-                amount: vec![],
-                gas_limit: 500_000,
-                granter: None,
-                payer: None,
-            }
+            amount: vec![coin.clone().into()],
+            from_address: our_address.to_bech32(&self.inner.chain_prefix).unwrap(),
+            to_address: destination.to_bech32(&self.inner.chain_prefix).unwrap(),
         };
+        let msg = Msg::new(MSG_SEND_TYPE_URL, send);
 
+        let messages = [msg];
+        let fee_obj = self.default_fee(fee, &messages);
+        let fee_obj = self.fill_fee(our_address, fee_obj, &[coin]).await?;
+        self.check_tx_policy(our_address, &messages, &fee_obj, MEMO)?;
+        self.check_fee_grant(our_address, &messages, &fee_obj)
+            .await?;
         let args = self.get_message_args(our_address, fee_obj).await?;
 
-        let msg_bytes = private_key.sign_std_msg(&[msg], args, MEMO)?;
+        let msg_bytes = private_key.sign_std_msg(&messages, args, MEMO)?;
         trace!("{}", msg_bytes.len());
+        self.check_tx_limits(&msg_bytes).await?;
+
+        let response = self
+            .send_transaction(msg_bytes, BroadcastMode::Sync)
+            .await?;
+
+        trace!("broadcasted! with response {:?}", response);
+        if let Some(time) = wait_timeout {
+            self.wait_for_tx(response, time).await
+        } else {
+            Ok(response)
+        }
+    }
+
+    /// Sends one or more messages in a single transaction signed by the provided private key.
+    /// This is the generic form of the module specific helpers in this crate and can be used
+    /// to send any Msg, including those for modules deep_space does not know about. If a
+    /// wait_timeout is provided this will wait for the tx to enter the chain. The signed
+    /// tx is checked against the chain memo, signature and size limits before broadcasting.
+    /// A fee without an amount is priced by the auto fee of the Contact, see with_auto_fee.
+    pub async fn send_message(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        if messages.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "Can not send a transaction with no messages".to_string(),
+            ));
+        }
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        let fee = self.fill_fee(our_address, fee, &[]).await?;
+        self.check_tx_policy(our_address, messages, &fee, &memo)?;
+        self.check_fee_grant(our_address, messages, &fee).await?;
+
+        let args = self.get_message_args(our_address, fee).await?;
+        trace!("got optional tx info");
+
+        let msg_bytes = private_key.sign_std_msg(messages, args, memo)?;
+        self.check_tx_limits(&msg_bytes).await?;
 
         let response = self
             .send_transaction(msg_bytes, BroadcastMode::Sync)
@@ -108,12 +191,16 @@ impl Contact {
 
     /// Utility function that waits for a tx to enter the chain by querying
     /// it's txid, will not exit for timeout time unless the error is known
-    /// and unrecoverable
+    /// and unrecoverable. Returns ConfirmationTimeout if the tx is not found in time.
     pub async fn wait_for_tx(
         &self,
         response: TxResponse,
         timeout: Duration,
     ) -> Result<TxResponse, CosmosGrpcError> {
+        // dry run responses are never included
+        if self.inner.dry_run {
+            return Ok(response);
+        }
         let start = Instant::now();
         while Instant::now() - start < timeout {
             // TODO what actually determines when the tx is in the chain?
@@ -121,15 +208,16 @@ impl Contact {
             match status {
                 Ok(status) => {
                     if let Some(res) = status.tx_response {
-                        return Ok(res);
+                        return check_included_tx(res);
                     }
                 }
-                Err(CosmosGrpcError::RequestError { error }) => match error.code() {
+                Err(CosmosGrpcError::RequestError { error, .. }) => match error.code() {
                     TonicCode::NotFound | TonicCode::Unknown | TonicCode::InvalidArgument => {}
                     _ => {
                         return Err(CosmosGrpcError::TransactionFailed {
                             tx: response,
                             time: Instant::now() - start,
+                            sequence: None,
                         });
                     }
                 },
@@ -137,8 +225,8 @@ impl Contact {
             }
             sleep(Duration::from_secs(1)).await;
         }
-        Err(CosmosGrpcError::TransactionFailed {
-            tx: response,
+        Err(CosmosGrpcError::ConfirmationTimeout {
+            tx_hash: response.txhash,
             time: timeout,
         })
     }
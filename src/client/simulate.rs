@@ -0,0 +1,187 @@
+//! Transaction simulation, used to find the gas and fee a transaction requires before
+//! it is broadcast
+
+use crate::client::Contact;
+use crate::client::MEMO;
+use crate::error::CosmosGrpcError;
+use crate::error::SimulationError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::SimulateRequest;
+use crate::registry::DecodedMsg;
+use crate::utils::GasDetails;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use crate::MsgRegistry;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SimulateResponse;
+use std::time::Duration;
+use tonic::Code;
+
+/// Converts the error of a simulation the node refused into a SimulationFailed error,
+/// errors reaching the node are returned as is
+pub(crate) fn simulation_failure(e: CosmosGrpcError) -> CosmosGrpcError {
+    match e {
+        CosmosGrpcError::RequestError { error, .. }
+            if matches!(
+                error.code(),
+                Code::Unknown
+                    | Code::InvalidArgument
+                    | Code::FailedPrecondition
+                    | Code::PermissionDenied
+                    | Code::Unauthenticated
+            ) =>
+        {
+            SimulationError::from_log(error.message()).into()
+        }
+        e => e,
+    }
+}
+
+impl Contact {
+    /// Simulates a single transaction
+    async fn simulate(
+        &self,
+        messages: &[Msg],
+        memo: &str,
+        private_key: PrivateKey,
+    ) -> Result<SimulateResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        // fees are not checked during simulation
+        let fee = Fee {
+            amount: Vec::new(),
+            gas_limit: 0,
+            granter: None,
+            payer: None,
+        };
+        let args = self.get_message_args(our_address, fee).await?;
+        let tx = private_key.get_signed_tx(messages, args.clone(), memo)?;
+        let tx_bytes = private_key.sign_std_msg(messages, args, memo)?;
+        self.grpc_unary(
+            "/cosmos.tx.v1beta1.Service/Simulate",
+            SimulateRequest {
+                tx: Some(tx),
+                tx_bytes,
+            },
+        )
+        .await
+    }
+
+    /// Simulates a single transaction returning the gas it used
+    async fn simulate_gas_used(
+        &self,
+        messages: &[Msg],
+        memo: &str,
+        private_key: PrivateKey,
+    ) -> Result<(u64, u64), CosmosGrpcError> {
+        let res = self.simulate(messages, memo, private_key).await?;
+        match res.gas_info {
+            Some(info) => Ok((info.gas_wanted, info.gas_used)),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No gas info in simulation?".to_string(),
+            )),
+        }
+    }
+
+    /// Simulates a transaction containing the provided messages, signed by the provided
+    /// private key, and returns the gas used along with the fee required at the connected
+    /// node's minimum gas prices. If attribute_msgs is set each message is also simulated
+    /// on its own to attribute gas per message, this costs an extra simulation per message.
+    pub async fn simulate_tx(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+        attribute_msgs: bool,
+    ) -> Result<GasDetails, CosmosGrpcError> {
+        if messages.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "Can not simulate a transaction with no messages".to_string(),
+            ));
+        }
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        let (gas_wanted, gas_used) = self.simulate_gas_used(messages, &memo, private_key).await?;
+
+        let mut msg_gas = Vec::new();
+        if attribute_msgs {
+            for msg in messages {
+                let (_, used) = self
+                    .simulate_gas_used(&[msg.clone()], &memo, private_key)
+                    .await?;
+                msg_gas.push(used);
+            }
+        }
+
+        // older nodes do not expose their minimum gas prices, in which case the required
+        // fee is left empty rather than failing the simulation
+        let required_fees: Vec<Coin> = match self.get_minimum_gas_prices().await {
+            Ok(prices) => prices
+                .iter()
+                .filter_map(|price| price.fee_for_gas(gas_used))
+                .filter(|fee| fee.amount > 0u64.into())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        Ok(GasDetails {
+            gas_wanted,
+            gas_used,
+            msg_gas,
+            required_fees,
+        })
+    }
+
+    /// Simulates a transaction containing the provided messages and decodes the Msg
+    /// response of each with the provided registry, for example to find the address a
+    /// contract instantiation would create before broadcasting it
+    pub async fn simulate_msg_responses(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+        registry: &MsgRegistry,
+    ) -> Result<Vec<DecodedMsg>, CosmosGrpcError> {
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        let res = self
+            .simulate(messages, &memo, private_key)
+            .await
+            .map_err(simulation_failure)?;
+        registry.decode_simulated_responses(&res)
+    }
+
+    /// Simulates the transaction send_message would broadcast and converts a failed
+    /// simulation into a SimulationFailed error, so that messages the chain would reject
+    /// are caught before any fee is spent. Errors reaching the node are returned as is.
+    pub async fn preflight(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        private_key: PrivateKey,
+    ) -> Result<(), CosmosGrpcError> {
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        self.simulate_gas_used(messages, &memo, private_key)
+            .await
+            .map(|_| ())
+            .map_err(simulation_failure)
+    }
+
+    /// Sends one or more messages like send_message() after checking them with preflight(),
+    /// use this instead of send_message() for the calls that should be simulated first
+    pub async fn send_message_with_preflight(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        if messages.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "Can not send a transaction with no messages".to_string(),
+            ));
+        }
+        self.preflight(messages, memo.clone(), private_key).await?;
+        self.send_message(messages, memo, fee, private_key, wait_timeout)
+            .await
+    }
+}
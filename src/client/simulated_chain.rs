@@ -0,0 +1,792 @@
+//! An in-memory chain implementing CosmosClient, for testing sequencing, retry and batching
+//! logic deterministically without running a node. Account numbers, sequences, fees and
+//! bank sends are executed against simulated state, signatures are not verified and
+//! messages of other modules are included without changing any state.
+//!
+//! By default every accepted transaction is included in its own block as soon as it is
+//! broadcast. With [`SimulatedChain::with_manual_blocks`] transactions wait in the mempool
+//! until [`SimulatedChain::produce_block`] is called, which includes them in broadcast order.
+
+use crate::any::AnyExt;
+use crate::client::cosmos_client::sign_and_send;
+use crate::client::cosmos_client::CosmosClient;
+use crate::client::send::check_broadcast_response;
+use crate::client::send::check_included_tx;
+use crate::client::types::ChainStatus;
+use crate::client::types::LatestBlock;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::tx::v1beta1::GetTxResponse;
+use crate::utils::tx_hash;
+use crate::utils::tx_sequence;
+use crate::utils::GasDetails;
+use crate::Address;
+use crate::Coin;
+use crate::Coins;
+use crate::DecCoin;
+use crate::Fee;
+use crate::MessageArgs;
+use crate::Msg;
+use crate::PrivateKey;
+use crate::PublicKey;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as ProtoSecp256k1Pubkey;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::AuthInfo;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use prost::Message;
+use prost_types::Any;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tendermint_proto::types::Block;
+use tendermint_proto::types::Header;
+use tonic::Status;
+
+/// The gas used by every transaction on a SimulatedChain
+pub const SIMULATED_TX_GAS: u64 = 50_000;
+/// The gas used by every message on a SimulatedChain
+pub const SIMULATED_MSG_GAS: u64 = 25_000;
+
+/// The gas used by a transaction with the provided number of messages on a SimulatedChain
+pub fn simulated_gas(messages: usize) -> u64 {
+    SIMULATED_TX_GAS + SIMULATED_MSG_GAS * messages as u64
+}
+
+/// A rejected transaction, the abci code and raw log
+type TxFailure = (u32, String);
+
+#[derive(Debug, Clone, Default)]
+struct SimAccount {
+    account_number: u64,
+    sequence: u64,
+    /// the sequence including transactions waiting in the mempool
+    check_sequence: u64,
+    balances: Coins,
+}
+
+struct PendingTx {
+    txhash: String,
+    signer: String,
+    tx: Tx,
+}
+
+#[derive(Default)]
+struct ChainState {
+    height: u64,
+    next_account_number: u64,
+    manual_blocks: bool,
+    min_gas_prices: Vec<DecCoin>,
+    accounts: HashMap<String, SimAccount>,
+    mempool: Vec<PendingTx>,
+    txs: HashMap<String, GetTxResponse>,
+    broadcast_errors: VecDeque<CosmosGrpcError>,
+}
+
+fn decode_tx(tx_bytes: &[u8]) -> Result<Tx, prost::DecodeError> {
+    let raw = TxRaw::decode(tx_bytes)?;
+    Ok(Tx {
+        body: Some(TxBody::decode(raw.body_bytes.as_slice())?),
+        auth_info: Some(AuthInfo::decode(raw.auth_info_bytes.as_slice())?),
+        signatures: raw.signatures,
+    })
+}
+
+fn to_coins(coins: &[ProtoCoin]) -> Result<Coins, TxFailure> {
+    let mut out = Vec::new();
+    for coin in coins {
+        out.push(
+            Coin::from_proto(coin.clone()).map_err(|e| (10, format!("{}: invalid coins", e)))?,
+        );
+    }
+    Coins::new(out).map_err(|e| (10, format!("{}: invalid coins", e)))
+}
+
+/// Returns the address and sequence of the single signer of a transaction
+fn signer_of(tx: &Tx, prefix: &str) -> Result<(String, u64), TxFailure> {
+    let signer_infos = match &tx.auth_info {
+        Some(auth_info) => &auth_info.signer_infos,
+        None => return Err((2, "no auth info: tx parse error".to_string())),
+    };
+    if signer_infos.len() != 1 {
+        return Err((
+            4,
+            "only single signer transactions are simulated: unauthorized".to_string(),
+        ));
+    }
+    let key = match &signer_infos[0].public_key {
+        Some(any) if any.is::<ProtoSecp256k1Pubkey>() => any
+            .unpack::<ProtoSecp256k1Pubkey>()
+            .map_err(|e| (2, format!("{}: tx parse error", e)))?,
+        _ => return Err((4, "unsupported public key: invalid pubkey".to_string())),
+    };
+    let address = PublicKey::from_slice(&key.key, PublicKey::DEFAULT_PREFIX)
+        .map_err(|e| (4, format!("{}: invalid pubkey", e)))?
+        .to_address_with_prefix(prefix)
+        .and_then(|address| address.to_bech32(prefix))
+        .map_err(|e| (4, format!("{}: invalid pubkey", e)))?;
+    Ok((address, signer_infos[0].sequence))
+}
+
+fn fee_of(tx: &Tx) -> Result<(Coins, u64), TxFailure> {
+    match tx.auth_info.as_ref().and_then(|a| a.fee.as_ref()) {
+        Some(fee) => Ok((to_coins(&fee.amount)?, fee.gas_limit)),
+        None => Ok((Coins::default(), 0)),
+    }
+}
+
+impl ChainState {
+    fn account_mut(&mut self, address: &str) -> &mut SimAccount {
+        let next_account_number = &mut self.next_account_number;
+        self.accounts.entry(address.to_string()).or_insert_with(|| {
+            let account = SimAccount {
+                account_number: *next_account_number,
+                ..SimAccount::default()
+            };
+            *next_account_number += 1;
+            account
+        })
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, amount: &Coins) -> Result<(), TxFailure> {
+        let sender = self.account_mut(from);
+        sender.balances = match sender.balances.checked_sub(amount) {
+            Some(balances) => balances,
+            None => {
+                return Err((
+                    5,
+                    format!(
+                        "{} is smaller than {}: insufficient funds",
+                        sender.balances, amount
+                    ),
+                ))
+            }
+        };
+        let receiver = self.account_mut(to);
+        receiver.balances = match receiver.balances.checked_add(amount) {
+            Some(balances) => balances,
+            None => return Err((10, "balance overflow: invalid coins".to_string())),
+        };
+        Ok(())
+    }
+
+    /// Executes a message sent by signer, only bank messages change state
+    fn apply_msg(&mut self, signer: &str, msg: &Any) -> Result<(), TxFailure> {
+        let decode_error = |e: CosmosGrpcError| (2, format!("{}: tx parse error", e));
+        let unauthorized = |address: &str| {
+            (
+                4,
+                format!("{} is not the tx signer {}: unauthorized", address, signer),
+            )
+        };
+        if msg.is::<MsgSend>() {
+            let send: MsgSend = msg.unpack().map_err(decode_error)?;
+            if send.from_address != signer {
+                return Err(unauthorized(&send.from_address));
+            }
+            self.transfer(
+                &send.from_address,
+                &send.to_address,
+                &to_coins(&send.amount)?,
+            )
+        } else if msg.is::<MsgMultiSend>() {
+            let send: MsgMultiSend = msg.unpack().map_err(decode_error)?;
+            let mut inputs = Coins::default();
+            let mut outputs = Coins::default();
+            for input in send.inputs.iter() {
+                if input.address != signer {
+                    return Err(unauthorized(&input.address));
+                }
+                inputs = inputs
+                    .checked_add(&to_coins(&input.coins)?)
+                    .ok_or_else(|| (10, "input overflow: invalid coins".to_string()))?;
+            }
+            for output in send.outputs.iter() {
+                outputs = outputs
+                    .checked_add(&to_coins(&output.coins)?)
+                    .ok_or_else(|| (10, "output overflow: invalid coins".to_string()))?;
+            }
+            if inputs != outputs {
+                return Err((
+                    4,
+                    "sum inputs != sum outputs: input/output mismatch".to_string(),
+                ));
+            }
+            for output in send.outputs.iter() {
+                self.transfer(signer, &output.address, &to_coins(&output.coins)?)?;
+            }
+            Ok(())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validates a transaction for the mempool, returning the signer address
+    fn check_tx(&self, prefix: &str, txhash: &str, tx: &Tx) -> Result<String, TxFailure> {
+        if self.txs.contains_key(txhash) || self.mempool.iter().any(|p| p.txhash == txhash) {
+            return Err((19, "tx already exists in cache".to_string()));
+        }
+        let (signer, sequence) = signer_of(tx, prefix)?;
+        let account = match self.accounts.get(&signer) {
+            Some(account) => account,
+            None => return Err((9, format!("account {} not found: unknown address", signer))),
+        };
+        if sequence != account.check_sequence {
+            return Err((
+                32,
+                format!(
+                    "account sequence mismatch, expected {}, got {}: incorrect account sequence",
+                    account.check_sequence, sequence
+                ),
+            ));
+        }
+        let body = tx.body.as_ref().unwrap();
+        if body.messages.is_empty() {
+            return Err((
+                18,
+                "must contain at least one message: invalid request".to_string(),
+            ));
+        }
+        if body.timeout_height != 0 && self.height + 1 > body.timeout_height {
+            return Err((
+                30,
+                format!(
+                    "block height {} is greater than timeout height {}: tx timeout height",
+                    self.height + 1,
+                    body.timeout_height
+                ),
+            ));
+        }
+        let (fee, gas_limit) = fee_of(tx)?;
+        let required: Vec<Coin> = self
+            .min_gas_prices
+            .iter()
+            .filter_map(|price| price.fee_for_gas(gas_limit))
+            .filter(|coin| coin.amount > 0u64.into())
+            .collect();
+        // like the SDK a fee is sufficient if any one of the minimum gas prices is met
+        if !required.is_empty() && !required.iter().any(|r| fee.amount_of(&r.denom) >= r.amount) {
+            return Err((
+                13,
+                format!(
+                    "insufficient fees; got: {} required: {}: insufficient fee",
+                    fee,
+                    Coin::display_list(&required)
+                ),
+            ));
+        }
+        if !account.balances.is_all_gte(&fee) {
+            return Err((
+                5,
+                format!(
+                    "{} is smaller than {}: insufficient funds",
+                    account.balances, fee
+                ),
+            ));
+        }
+        Ok(signer)
+    }
+
+    /// Executes a transaction included in the current block, the fee is charged and the
+    /// sequence incremented even if the messages fail
+    fn deliver_tx(&mut self, pending: &PendingTx) -> TxResponse {
+        let body = pending.tx.body.as_ref().unwrap();
+        let gas_used = simulated_gas(body.messages.len());
+        let mut response = TxResponse {
+            height: self.height as i64,
+            txhash: pending.txhash.clone(),
+            ..TxResponse::default()
+        };
+        let result = fee_of(&pending.tx).and_then(|(fee, gas_limit)| {
+            response.gas_wanted = gas_limit as i64;
+            response.gas_used = gas_used as i64;
+            let account = self.account_mut(&pending.signer);
+            account.balances = match account.balances.checked_sub(&fee) {
+                Some(balances) => balances,
+                None => {
+                    return Err((
+                        5,
+                        format!(
+                            "{} is smaller than {}: insufficient funds",
+                            account.balances, fee
+                        ),
+                    ))
+                }
+            };
+            account.sequence += 1;
+            if gas_used > gas_limit {
+                return Err((
+                    11,
+                    format!(
+                        "out of gas in location: simulated; gasWanted: {}, gasUsed: {}: out of gas",
+                        gas_limit, gas_used
+                    ),
+                ));
+            }
+            // messages are executed atomically, a failure reverts all of them
+            let accounts = self.accounts.clone();
+            let next_account_number = self.next_account_number;
+            for msg in body.messages.iter() {
+                if let Err(e) = self.apply_msg(&pending.signer, msg) {
+                    self.accounts = accounts;
+                    self.next_account_number = next_account_number;
+                    return Err(e);
+                }
+            }
+            Ok(())
+        });
+        if let Err((code, raw_log)) = result {
+            response.codespace = "sdk".to_string();
+            response.code = code;
+            response.raw_log = raw_log;
+        }
+        response
+    }
+
+    fn produce_block(&mut self) -> Vec<TxResponse> {
+        self.height += 1;
+        let mut responses = Vec::new();
+        for pending in std::mem::take(&mut self.mempool) {
+            let response = self.deliver_tx(&pending);
+            self.txs.insert(
+                pending.txhash.clone(),
+                GetTxResponse {
+                    tx: Some(pending.tx),
+                    tx_response: Some(response.clone()),
+                },
+            );
+            responses.push(response);
+        }
+        // the mempool is empty again, so the check state matches the committed state
+        for account in self.accounts.values_mut() {
+            account.check_sequence = account.sequence;
+        }
+        responses
+    }
+}
+
+/// An in-memory chain implementing CosmosClient, see the module documentation
+pub struct SimulatedChain {
+    prefix: String,
+    chain_id: String,
+    state: Mutex<ChainState>,
+}
+
+impl SimulatedChain {
+    /// Creates a chain at height 1 with no accounts
+    pub fn new(prefix: &str, chain_id: &str) -> Self {
+        SimulatedChain {
+            prefix: prefix.to_string(),
+            chain_id: chain_id.to_string(),
+            state: Mutex::new(ChainState {
+                height: 1,
+                ..ChainState::default()
+            }),
+        }
+    }
+
+    /// Keeps broadcast transactions in the mempool until produce_block is called
+    pub fn with_manual_blocks(mut self) -> Self {
+        self.state.get_mut().unwrap().manual_blocks = true;
+        self
+    }
+
+    /// Rejects transactions that do not pay these minimum gas prices
+    pub fn with_min_gas_prices(mut self, prices: Vec<DecCoin>) -> Self {
+        self.state.get_mut().unwrap().min_gas_prices = prices;
+        self
+    }
+
+    /// Adds coins to an address, creating the account if it does not exist
+    pub fn fund(&self, address: Address, coins: Vec<Coin>) -> Result<(), CosmosGrpcError> {
+        let coins = Coins::new(coins).map_err(CosmosGrpcError::BadInput)?;
+        let address = address.to_bech32(&self.prefix).unwrap();
+        let mut state = self.state.lock().unwrap();
+        let account = state.account_mut(&address);
+        account.balances = match account.balances.checked_add(&coins) {
+            Some(balances) => balances,
+            None => return Err(CosmosGrpcError::BadInput("Balance overflow".to_string())),
+        };
+        Ok(())
+    }
+
+    /// The height of the latest block
+    pub fn height(&self) -> u64 {
+        self.state.lock().unwrap().height
+    }
+
+    /// The number of transactions waiting to be included in a block
+    pub fn mempool_size(&self) -> usize {
+        self.state.lock().unwrap().mempool.len()
+    }
+
+    /// Produces a block including every transaction in the mempool, in the order they were
+    /// broadcast, and returns their results
+    pub fn produce_block(&self) -> Vec<TxResponse> {
+        self.state.lock().unwrap().produce_block()
+    }
+
+    /// Fails the next broadcast with the provided error before it reaches the mempool, for
+    /// testing how connection errors are retried
+    pub fn push_broadcast_error(&self, error: CosmosGrpcError) {
+        self.state.lock().unwrap().broadcast_errors.push_back(error);
+    }
+
+    fn bech32(&self, address: Address) -> String {
+        address.to_bech32(&self.prefix).unwrap()
+    }
+
+    fn message_args(&self, our_address: Address, fee: Fee) -> Result<MessageArgs, CosmosGrpcError> {
+        let state = self.state.lock().unwrap();
+        let address = self.bech32(our_address);
+        match state.accounts.get(&address) {
+            Some(account) => Ok(MessageArgs {
+                sequence: account.sequence,
+                account_number: account.account_number,
+                chain_id: self.chain_id.clone(),
+                fee,
+                timeout_height: state.height + 100,
+            }),
+            None => Err(CosmosGrpcError::AccountNotFound { address }),
+        }
+    }
+
+    fn broadcast(&self, tx_bytes: Vec<u8>) -> Result<TxResponse, CosmosGrpcError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(error) = state.broadcast_errors.pop_front() {
+            return Err(error);
+        }
+        let txhash = tx_hash(&tx_bytes);
+        let checked = match decode_tx(&tx_bytes) {
+            Ok(tx) => state
+                .check_tx(&self.prefix, &txhash, &tx)
+                .map(|signer| (signer, tx)),
+            Err(e) => Err((2, format!("{}: tx parse error", e))),
+        };
+        let response = match checked {
+            Ok((signer, tx)) => {
+                state.account_mut(&signer).check_sequence += 1;
+                state.mempool.push(PendingTx {
+                    txhash: txhash.clone(),
+                    signer,
+                    tx,
+                });
+                if !state.manual_blocks {
+                    state.produce_block();
+                }
+                TxResponse {
+                    txhash,
+                    ..TxResponse::default()
+                }
+            }
+            Err((code, raw_log)) => TxResponse {
+                txhash,
+                codespace: "sdk".to_string(),
+                code,
+                raw_log,
+                ..TxResponse::default()
+            },
+        };
+        check_broadcast_response(response, tx_sequence(&tx_bytes))
+    }
+}
+
+impl CosmosClient for SimulatedChain {
+    fn get_prefix(&self) -> String {
+        self.prefix.clone()
+    }
+
+    fn get_chain_status(&self) -> BoxFuture<'_, Result<ChainStatus, CosmosGrpcError>> {
+        let block_height = self.height();
+        futures::future::ready(Ok(ChainStatus::Moving { block_height })).boxed()
+    }
+
+    fn get_latest_block(&self) -> BoxFuture<'_, Result<LatestBlock, CosmosGrpcError>> {
+        let block = Block {
+            header: Some(Header {
+                chain_id: self.chain_id.clone(),
+                height: self.height() as i64,
+                ..Header::default()
+            }),
+            ..Block::default()
+        };
+        futures::future::ready(Ok(LatestBlock::Latest { block })).boxed()
+    }
+
+    fn get_account_info(
+        &self,
+        address: Address,
+    ) -> BoxFuture<'_, Result<BaseAccount, CosmosGrpcError>> {
+        let address = self.bech32(address);
+        let res = match self.state.lock().unwrap().accounts.get(&address) {
+            Some(account) => Ok(BaseAccount {
+                address,
+                pub_key: None,
+                account_number: account.account_number,
+                sequence: account.sequence,
+            }),
+            None => Err(CosmosGrpcError::AccountNotFound { address }),
+        };
+        futures::future::ready(res).boxed()
+    }
+
+    fn get_balances(&self, address: Address) -> BoxFuture<'_, Result<Vec<Coin>, CosmosGrpcError>> {
+        let balances = match self
+            .state
+            .lock()
+            .unwrap()
+            .accounts
+            .get(&self.bech32(address))
+        {
+            Some(account) => account.balances.clone().into(),
+            None => Vec::new(),
+        };
+        futures::future::ready(Ok(balances)).boxed()
+    }
+
+    fn get_balance(
+        &self,
+        address: Address,
+        denom: String,
+    ) -> BoxFuture<'_, Result<Option<Coin>, CosmosGrpcError>> {
+        let balance = match self
+            .state
+            .lock()
+            .unwrap()
+            .accounts
+            .get(&self.bech32(address))
+        {
+            Some(account) => account.balances.iter().find(|c| c.denom == denom).cloned(),
+            None => None,
+        };
+        futures::future::ready(Ok(balance)).boxed()
+    }
+
+    fn get_message_args(
+        &self,
+        our_address: Address,
+        fee: Fee,
+    ) -> BoxFuture<'_, Result<MessageArgs, CosmosGrpcError>> {
+        futures::future::ready(self.message_args(our_address, fee)).boxed()
+    }
+
+    fn get_tx_by_hash(
+        &self,
+        txhash: String,
+    ) -> BoxFuture<'_, Result<GetTxResponse, CosmosGrpcError>> {
+        let res = match self.state.lock().unwrap().txs.get(&txhash) {
+            Some(tx) => Ok(tx.clone()),
+            None => Err(CosmosGrpcError::RequestError {
+                endpoint: "simulated".to_string(),
+                method: "/cosmos.tx.v1beta1.Service/GetTx".to_string(),
+                error: Status::not_found(format!("tx {} not found", txhash)),
+            }),
+        };
+        futures::future::ready(res).boxed()
+    }
+
+    fn send_transaction(
+        &self,
+        msg: Vec<u8>,
+        _mode: BroadcastMode,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        futures::future::ready(self.broadcast(msg)).boxed()
+    }
+
+    fn send_message<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        memo: Option<String>,
+        fee: Fee,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> BoxFuture<'a, Result<TxResponse, CosmosGrpcError>> {
+        sign_and_send(self, messages, memo, fee, private_key, wait_timeout).boxed()
+    }
+
+    /// Returns immediately, blocks are only produced by broadcasts or produce_block so a
+    /// transaction that is not included yet can not be included while waiting
+    fn wait_for_tx(
+        &self,
+        response: TxResponse,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<TxResponse, CosmosGrpcError>> {
+        let found = self
+            .state
+            .lock()
+            .unwrap()
+            .txs
+            .get(&response.txhash)
+            .and_then(|tx| tx.tx_response.clone());
+        let res = match found {
+            Some(found) => check_included_tx(found),
+            None => Err(CosmosGrpcError::ConfirmationTimeout {
+                tx_hash: response.txhash,
+                time: timeout,
+            }),
+        };
+        futures::future::ready(res).boxed()
+    }
+
+    fn simulate_tx<'a>(
+        &'a self,
+        messages: &'a [Msg],
+        _memo: Option<String>,
+        private_key: PrivateKey,
+        attribute_msgs: bool,
+    ) -> BoxFuture<'a, Result<GasDetails, CosmosGrpcError>> {
+        let res = if messages.is_empty() {
+            Err(CosmosGrpcError::BadInput(
+                "Can not simulate a transaction with no messages".to_string(),
+            ))
+        } else {
+            let address = private_key.to_address(&self.prefix).unwrap();
+            let address = self.bech32(address);
+            let state = self.state.lock().unwrap();
+            if state.accounts.contains_key(&address) {
+                let gas_used = simulated_gas(messages.len());
+                Ok(GasDetails {
+                    gas_wanted: 0,
+                    gas_used,
+                    msg_gas: if attribute_msgs {
+                        vec![simulated_gas(1); messages.len()]
+                    } else {
+                        Vec::new()
+                    },
+                    required_fees: state
+                        .min_gas_prices
+                        .iter()
+                        .filter_map(|price| price.fee_for_gas(gas_used))
+                        .filter(|fee| fee.amount > 0u64.into())
+                        .collect(),
+                })
+            } else {
+                Err(CosmosGrpcError::AccountNotFound { address })
+            }
+        };
+        futures::future::ready(res).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+
+    fn send(from: Address, to: Address, amount: &str) -> Msg {
+        let coin: Coin = amount.parse().unwrap();
+        Msg::new(
+            MSG_SEND_TYPE_URL,
+            MsgSend {
+                from_address: from.to_bech32("cosmos").unwrap(),
+                to_address: to.to_bech32("cosmos").unwrap(),
+                amount: vec![coin.into()],
+            },
+        )
+    }
+
+    fn fee(amount: &str) -> Fee {
+        Fee {
+            amount: vec![amount.parse().unwrap()],
+            gas_limit: 200_000,
+            granter: None,
+            payer: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_simulated_chain_send() {
+        let key = PrivateKey::from_secret(b"simulated chain sender");
+        let sender = key.to_address("cosmos").unwrap();
+        let receiver = PrivateKey::from_secret(b"simulated chain receiver")
+            .to_address("cosmos")
+            .unwrap();
+        let chain = SimulatedChain::new("cosmos", "simulated")
+            .with_min_gas_prices(vec!["0.01uatom".parse().unwrap()]);
+        chain
+            .fund(sender, vec!["10000uatom".parse().unwrap()])
+            .unwrap();
+
+        let msg = send(sender, receiver, "1000uatom");
+        // 200_000 gas at 0.01uatom requires a 2000uatom fee
+        assert!(matches!(
+            chain
+                .send_message(&[msg.clone()], None, fee("1000uatom"), key, None)
+                .await,
+            Err(CosmosGrpcError::InsufficientFees { .. })
+        ));
+        let res = chain
+            .send_message(
+                &[msg],
+                None,
+                fee("2000uatom"),
+                key,
+                Some(Duration::from_secs(1)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.code, 0);
+        assert_eq!(res.height, 2);
+        assert_eq!(res.gas_used as u64, simulated_gas(1));
+        assert_eq!(
+            chain.get_balances(sender).await.unwrap(),
+            vec!["7000uatom".parse().unwrap()]
+        );
+        assert_eq!(
+            chain
+                .get_balance(receiver, "uatom".to_string())
+                .await
+                .unwrap(),
+            Some("1000uatom".parse().unwrap())
+        );
+        assert_eq!(chain.get_account_info(sender).await.unwrap().sequence, 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_simulated_chain_sequencing() {
+        let key = PrivateKey::from_secret(b"simulated chain sender");
+        let sender = key.to_address("cosmos").unwrap();
+        let chain = SimulatedChain::new("cosmos", "simulated").with_manual_blocks();
+        chain
+            .fund(sender, vec!["10000uatom".parse().unwrap()])
+            .unwrap();
+        let msg = send(sender, sender, "1uatom");
+
+        chain
+            .send_message(&[msg.clone()], None, fee("1uatom"), key, None)
+            .await
+            .unwrap();
+        // the committed sequence has not changed, so the second tx reuses sequence 0
+        assert!(matches!(
+            chain
+                .send_message(&[msg.clone()], None, fee("1uatom"), key, None)
+                .await,
+            Err(CosmosGrpcError::TransactionFailed { .. })
+        ));
+        let mut args = chain.get_message_args(sender, fee("1uatom")).await.unwrap();
+        args.sequence += 1;
+        let tx = key.sign_std_msg(&[msg], args, "").unwrap();
+        chain
+            .send_transaction(tx, BroadcastMode::Sync)
+            .await
+            .unwrap();
+        assert_eq!(chain.mempool_size(), 2);
+
+        let included = chain.produce_block();
+        assert_eq!(included.len(), 2);
+        assert!(included.iter().all(|r| r.code == 0 && r.height == 2));
+        assert_eq!(chain.get_account_info(sender).await.unwrap().sequence, 2);
+        assert_eq!(
+            chain.get_balances(sender).await.unwrap(),
+            vec!["9998uatom".parse().unwrap()]
+        );
+    }
+}
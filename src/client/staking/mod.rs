@@ -1,30 +1,144 @@
 //! Contains utility functions for interacting with and submitting Cosmos governance proposals
 
-use crate::client::MEMO;
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
 use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::staking::v1beta1::MsgCancelUnbondingDelegation;
 use crate::Address;
 use crate::Coin;
 use crate::Contact;
-use crate::Fee;
+use crate::Dec;
 use crate::Msg;
 use crate::PrivateKey;
-use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
-use cosmos_sdk_proto::cosmos::staking::v1beta1::query_client::QueryClient as StakingQueryClient;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::DelegationResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegatorDelegationsRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegatorDelegationsResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegatorUnbondingDelegationsRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegatorUnbondingDelegationsResponse;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorsRequest;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorsResponse;
-use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::UnbondingDelegation;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Validator;
+use futures::Stream;
 use std::time::Duration;
 
+mod monitor;
+mod safety;
+mod validator;
+
+pub use monitor::*;
+pub use safety::*;
+pub use validator::*;
+
+pub const MSG_DELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgDelegate";
+pub const MSG_UNDELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgUndelegate";
+pub const MSG_BEGIN_REDELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgBeginRedelegate";
+pub const MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL: &str =
+    "/cosmos.staking.v1beta1.MsgCancelUnbondingDelegation";
+pub const MSG_CREATE_VALIDATOR_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgCreateValidator";
+pub const MSG_EDIT_VALIDATOR_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgEditValidator";
+pub const MSG_UNJAIL_TYPE_URL: &str = "/cosmos.slashing.v1beta1.MsgUnjail";
+
+/// Converts delegation shares of a validator into the tokens they are worth, rounding
+/// down as the SDK does when undelegating. Shares are the sdk.Dec returned by delegation
+/// queries and are not equal to tokens once a validator has been slashed.
+pub fn shares_to_tokens(validator: &Validator, shares: &Dec) -> Result<Dec, CosmosGrpcError> {
+    let bad_response = |value: &str| CosmosGrpcError::BadResponse(format!("Invalid {}", value));
+    let tokens: Dec = match validator.tokens.parse() {
+        Ok(tokens) => tokens,
+        Err(_) => return Err(bad_response(&validator.tokens)),
+    };
+    let delegator_shares = match Dec::from_proto_string(&validator.delegator_shares) {
+        Ok(delegator_shares) => delegator_shares,
+        Err(_) => return Err(bad_response(&validator.delegator_shares)),
+    };
+    match shares.mul(&tokens).quo_truncate(&delegator_shares) {
+        Some(amount) => Ok(amount),
+        None => Ok(Dec::zero()),
+    }
+}
+
 impl Contact {
     /// Gets a list of validators
     pub async fn get_validators_list(
         &self,
         filters: QueryValidatorsRequest,
     ) -> Result<QueryValidatorsResponse, CosmosGrpcError> {
-        let mut grpc = StakingQueryClient::connect(self.url.clone()).await?;
-        let res = grpc.validators(filters).await?.into_inner();
-        Ok(res)
+        self.grpc_unary("/cosmos.staking.v1beta1.Query/Validators", filters)
+            .await
+    }
+
+    /// Streams every validator with the provided status, for example "BOND_STATUS_BONDED", or
+    /// all validators if the status is empty. Pages are fetched as the stream is consumed.
+    pub fn get_validators_stream(
+        &self,
+        status: String,
+    ) -> impl Stream<Item = Result<Validator, CosmosGrpcError>> + '_ {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryValidatorsRequest {
+                status: status.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryValidatorsResponse = self
+                    .grpc_unary("/cosmos.staking.v1beta1.Query/Validators", request)
+                    .await?;
+                Ok((res.validators, res.pagination))
+            }
+        })
+    }
+
+    /// Streams every delegation of the provided delegator, pages are fetched as the
+    /// stream is consumed
+    pub fn get_delegations_stream(
+        &self,
+        delegator: Address,
+    ) -> impl Stream<Item = Result<DelegationResponse, CosmosGrpcError>> + '_ {
+        let delegator = delegator.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryDelegatorDelegationsRequest {
+                delegator_addr: delegator.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryDelegatorDelegationsResponse = self
+                    .grpc_unary(
+                        "/cosmos.staking.v1beta1.Query/DelegatorDelegations",
+                        request,
+                    )
+                    .await?;
+                Ok((res.delegation_responses, res.pagination))
+            }
+        })
+    }
+
+    /// Streams every unbonding delegation of the provided delegator, each holds the
+    /// entries still unbonding from one validator. Pages are fetched as the stream is
+    /// consumed.
+    pub fn get_unbonding_delegations_stream(
+        &self,
+        delegator: Address,
+    ) -> impl Stream<Item = Result<UnbondingDelegation, CosmosGrpcError>> + '_ {
+        let delegator = delegator.to_bech32(&self.inner.chain_prefix).unwrap();
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryDelegatorUnbondingDelegationsRequest {
+                delegator_addr: delegator.clone(),
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryDelegatorUnbondingDelegationsResponse = self
+                    .grpc_unary(
+                        "/cosmos.staking.v1beta1.Query/DelegatorUnbondingDelegations",
+                        request,
+                    )
+                    .await?;
+                Ok((res.unbonding_responses, res.pagination))
+            }
+        })
     }
 
     /// Gets a list of bonded validators
@@ -45,36 +159,121 @@ impl Contact {
         private_key: PrivateKey,
         wait_timeout: Option<Duration>,
     ) -> Result<TxResponse, CosmosGrpcError> {
-        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
-        let vote = MsgDelegate {
-            amount: Some(amount_to_delegate.into()),
-            delegator_address: our_address.to_string(),
-            validator_address: validator_address.to_string(),
-        };
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = delegate_msg(our_address, validator_address, amount_to_delegate);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
 
-        let fee = Fee {
-            amount: vec![fee],
-            gas_limit: 500_000u64,
-            granter: None,
-            payer: None,
-        };
+    /// Begins unbonding tokens delegated to the specified validator, the tokens
+    /// will be returned once the chain's unbonding period has elapsed
+    pub async fn undelegate(
+        &self,
+        validator_address: Address,
+        amount_to_undelegate: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = undelegate_msg(our_address, validator_address, amount_to_undelegate);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
 
-        let msg = Msg::new("/cosmos.staking.v1beta1.MsgDelegate", vote);
+    /// Moves a delegation from one validator to another without waiting for the unbonding
+    /// period, note that the chain limits the number of redelegations in flight
+    pub async fn redelegate(
+        &self,
+        source_validator: Address,
+        destination_validator: Address,
+        amount_to_redelegate: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = redelegate_msg(
+            our_address,
+            source_validator,
+            destination_validator,
+            amount_to_redelegate,
+        );
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Cancels an in progress unbonding, returning the tokens to the validator. The
+    /// creation_height must match the height at which the unbonding entry was created.
+    /// Requires Cosmos SDK v0.46 or later.
+    pub async fn cancel_unbonding(
+        &self,
+        validator_address: Address,
+        amount: Coin,
+        creation_height: u64,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = cancel_unbonding_msg(our_address, validator_address, amount, creation_height);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
 
-        let args = self.get_message_args(our_address, fee).await?;
-        trace!("got optional tx info");
+/// Builds a MsgDelegate, delegating amount from the delegator to the validator
+pub fn delegate_msg(delegator: Address, validator: Address, amount: Coin) -> Msg {
+    let delegate = MsgDelegate {
+        amount: Some(amount.into()),
+        delegator_address: delegator.to_string(),
+        validator_address: validator.to_string(),
+    };
+    Msg::new(MSG_DELEGATE_TYPE_URL, delegate)
+}
 
-        let msg_bytes = private_key.sign_std_msg(&[msg], args, MEMO)?;
+/// Builds a MsgUndelegate, unbonding amount from the validator back to the delegator
+pub fn undelegate_msg(delegator: Address, validator: Address, amount: Coin) -> Msg {
+    let undelegate = MsgUndelegate {
+        amount: Some(amount.into()),
+        delegator_address: delegator.to_string(),
+        validator_address: validator.to_string(),
+    };
+    Msg::new(MSG_UNDELEGATE_TYPE_URL, undelegate)
+}
 
-        let response = self
-            .send_transaction(msg_bytes, BroadcastMode::Sync)
-            .await?;
+/// Builds a MsgBeginRedelegate, moving amount between the source and destination validators
+pub fn redelegate_msg(
+    delegator: Address,
+    source_validator: Address,
+    destination_validator: Address,
+    amount: Coin,
+) -> Msg {
+    let redelegate = MsgBeginRedelegate {
+        amount: Some(amount.into()),
+        delegator_address: delegator.to_string(),
+        validator_src_address: source_validator.to_string(),
+        validator_dst_address: destination_validator.to_string(),
+    };
+    Msg::new(MSG_BEGIN_REDELEGATE_TYPE_URL, redelegate)
+}
 
-        trace!("broadcasted! with response {:?}", response);
-        if let Some(time) = wait_timeout {
-            self.wait_for_tx(response, time).await
-        } else {
-            Ok(response)
-        }
-    }
+/// Builds a MsgCancelUnbondingDelegation for the unbonding entry created at creation_height
+pub fn cancel_unbonding_msg(
+    delegator: Address,
+    validator: Address,
+    amount: Coin,
+    creation_height: u64,
+) -> Msg {
+    let cancel = MsgCancelUnbondingDelegation {
+        amount: Some(amount.into()),
+        delegator_address: delegator.to_string(),
+        validator_address: validator.to_string(),
+        creation_height: creation_height as i64,
+    };
+    Msg::new(MSG_CANCEL_UNBONDING_DELEGATION_TYPE_URL, cancel)
 }
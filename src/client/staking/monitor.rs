@@ -0,0 +1,336 @@
+//! Validator performance monitoring. Combines the slashing module signing infos, the current
+//! validator set and the commit signatures of recent blocks into per validator uptime over
+//! a window, so operators can alert on missed blocks before the chain jails their validator.
+//!
+//! ```ignore
+//! let report = contact.get_validator_performance(100).await?;
+//! for validator in report.validators.iter().filter(|v| v.uptime() < 0.95) {
+//!     warn!("{} missed {} blocks", validator.consensus_address, validator.missed);
+//! }
+//! ```
+
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::error::CosmosGrpcError;
+use crate::Address;
+use crate::Contact;
+use crate::Dec;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestValidatorSetRequest;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::GetLatestValidatorSetResponse;
+use cosmos_sdk_proto::cosmos::base::tendermint::v1beta1::Validator as ConsensusValidator;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::Params as SlashingParams;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QueryParamsRequest;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QueryParamsResponse;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfoRequest;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfoResponse;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfosRequest;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::QuerySigningInfosResponse;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::ValidatorSigningInfo;
+use futures::stream;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tendermint_proto::types::Block;
+use tendermint_proto::types::BlockIdFlag;
+
+/// The number of blocks fetched at once by get_validator_performance
+const BLOCK_FETCH_CONCURRENCY: usize = 10;
+
+/// The signing record of a validator in the current set over a window of blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatorUptime {
+    /// the bech32 consensus address, with the valcons prefix
+    pub consensus_address: String,
+    pub voting_power: i64,
+    /// blocks in the window the validator signed
+    pub signed: u64,
+    /// blocks in the window without a signature from the validator
+    pub missed: u64,
+    /// blocks missed in the slashing window as counted by the chain, None if the chain
+    /// has no signing info for the validator
+    pub missed_blocks_counter: Option<i64>,
+    /// the validator can not unjail before this time, None if never jailed
+    pub jailed_until: Option<SystemTime>,
+    pub tombstoned: bool,
+}
+
+impl ValidatorUptime {
+    /// The fraction of blocks in the window the validator signed
+    pub fn uptime(&self) -> f64 {
+        let total = self.signed + self.missed;
+        if total == 0 {
+            0.0
+        } else {
+            self.signed as f64 / total as f64
+        }
+    }
+}
+
+/// Validator uptime over the commits of a range of blocks, along with the slashing
+/// parameters the chain jails validators by
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatorPerformanceReport {
+    /// the first and last height whose commit was counted
+    pub start_height: u64,
+    pub end_height: u64,
+    /// the number of blocks the chain counts missed blocks over
+    pub signed_blocks_window: i64,
+    /// the fraction of the signed blocks window a validator must sign to stay unjailed
+    pub min_signed_per_window: Option<Dec>,
+    /// the validators of the latest set, ordered by voting power
+    pub validators: Vec<ValidatorUptime>,
+}
+
+/// Counts the commit signatures of each validator in the provided blocks, keyed by the
+/// consensus address bytes. Returns the lowest and highest committed height and the counts.
+/// Each block contains the commit of the previous height.
+pub fn count_commit_signatures(blocks: &[Block]) -> (u64, u64, HashMap<Vec<u8>, u64>) {
+    let mut signed = HashMap::new();
+    let mut start = u64::MAX;
+    let mut end = 0;
+    for commit in blocks.iter().filter_map(|block| block.last_commit.as_ref()) {
+        start = start.min(commit.height as u64);
+        end = end.max(commit.height as u64);
+        for signature in commit.signatures.iter() {
+            if signature.block_id_flag != BlockIdFlag::Absent as i32
+                && signature.block_id_flag != BlockIdFlag::Unknown as i32
+            {
+                *signed
+                    .entry(signature.validator_address.clone())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+    (start.min(end), end, signed)
+}
+
+pub(crate) fn timestamp_to_time(timestamp: &prost_types::Timestamp) -> Option<SystemTime> {
+    if timestamp.seconds <= 0 || timestamp.nanos < 0 {
+        return None;
+    }
+    Some(UNIX_EPOCH + Duration::new(timestamp.seconds as u64, timestamp.nanos as u32))
+}
+
+fn build_report(
+    blocks: &[Block],
+    validators: &[ConsensusValidator],
+    signing_infos: &[ValidatorSigningInfo],
+    params: Option<SlashingParams>,
+) -> Result<ValidatorPerformanceReport, CosmosGrpcError> {
+    let commits = blocks.iter().filter(|b| b.last_commit.is_some()).count() as u64;
+    let (start_height, end_height, signed) = count_commit_signatures(blocks);
+    let infos: HashMap<&str, &ValidatorSigningInfo> = signing_infos
+        .iter()
+        .map(|info| (info.address.as_str(), info))
+        .collect();
+    let mut uptimes = Vec::new();
+    for validator in validators {
+        let address = Address::from_bech32(validator.address.clone()).map_err(|_| {
+            CosmosGrpcError::BadResponse(format!("Invalid consensus address {}", validator.address))
+        })?;
+        let signed = signed.get(address.as_bytes()).copied().unwrap_or(0);
+        let info = infos.get(validator.address.as_str());
+        uptimes.push(ValidatorUptime {
+            consensus_address: validator.address.clone(),
+            voting_power: validator.voting_power,
+            signed,
+            missed: commits.saturating_sub(signed),
+            missed_blocks_counter: info.map(|i| i.missed_blocks_counter),
+            jailed_until: info
+                .and_then(|i| i.jailed_until.as_ref())
+                .and_then(timestamp_to_time),
+            tombstoned: info.map(|i| i.tombstoned).unwrap_or(false),
+        });
+    }
+    uptimes.sort_by(|a, b| b.voting_power.cmp(&a.voting_power));
+    let params = params.unwrap_or_default();
+    let min_signed_per_window = String::from_utf8(params.min_signed_per_window)
+        .ok()
+        .and_then(|value| Dec::from_proto_string(&value).ok());
+    Ok(ValidatorPerformanceReport {
+        start_height,
+        end_height,
+        signed_blocks_window: params.signed_blocks_window,
+        min_signed_per_window,
+        validators: uptimes,
+    })
+}
+
+impl Contact {
+    /// Gets the slashing signing info of a validator by bech32 consensus address
+    pub async fn get_signing_info(
+        &self,
+        consensus_address: String,
+    ) -> Result<ValidatorSigningInfo, CosmosGrpcError> {
+        let res: QuerySigningInfoResponse = self
+            .grpc_unary(
+                "/cosmos.slashing.v1beta1.Query/SigningInfo",
+                QuerySigningInfoRequest {
+                    cons_address: consensus_address,
+                },
+            )
+            .await?;
+        match res.val_signing_info {
+            Some(info) => Ok(info),
+            None => Err(CosmosGrpcError::BadResponse("No signing info?".to_string())),
+        }
+    }
+
+    /// Streams the slashing signing info of every validator, pages are fetched as the
+    /// stream is consumed
+    pub fn get_signing_infos_stream(
+        &self,
+    ) -> impl Stream<Item = Result<ValidatorSigningInfo, CosmosGrpcError>> + '_ {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QuerySigningInfosRequest {
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QuerySigningInfosResponse = self
+                    .grpc_unary("/cosmos.slashing.v1beta1.Query/SigningInfos", request)
+                    .await?;
+                Ok((res.info, res.pagination))
+            }
+        })
+    }
+
+    /// Gets the slashing module parameters
+    pub async fn get_slashing_params(&self) -> Result<SlashingParams, CosmosGrpcError> {
+        let res: QueryParamsResponse = self
+            .grpc_unary(
+                "/cosmos.slashing.v1beta1.Query/Params",
+                QueryParamsRequest {},
+            )
+            .await?;
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No slashing params?".to_string(),
+            )),
+        }
+    }
+
+    /// Gets every validator in the latest consensus validator set
+    pub async fn get_latest_validator_set(
+        &self,
+    ) -> Result<Vec<ConsensusValidator>, CosmosGrpcError> {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = GetLatestValidatorSetRequest {
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: GetLatestValidatorSetResponse = self
+                    .grpc_unary(
+                        "/cosmos.base.tendermint.v1beta1.Service/GetLatestValidatorSet",
+                        request,
+                    )
+                    .await?;
+                Ok((res.validators, res.pagination))
+            }
+        })
+        .try_collect()
+        .await
+    }
+
+    /// Computes the uptime of every validator in the latest set over the commits of the last
+    /// window blocks. Validators that joined the set during the window are counted as
+    /// missing the blocks before they joined.
+    pub async fn get_validator_performance(
+        &self,
+        window: u64,
+    ) -> Result<ValidatorPerformanceReport, CosmosGrpcError> {
+        let latest = self.latest_height().await?;
+        let first = latest.saturating_sub(window).max(1) + 1;
+        let blocks: Vec<Block> = stream::iter(first..=latest)
+            .map(|height| self.get_block_by_height(height))
+            .buffered(BLOCK_FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+        let validators = self.get_latest_validator_set().await?;
+        let signing_infos: Vec<ValidatorSigningInfo> =
+            self.get_signing_infos_stream().try_collect().await?;
+        let params = self.get_slashing_params().await.ok();
+        build_report(&blocks, &validators, &signing_infos, params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tendermint_proto::types::Commit;
+    use tendermint_proto::types::CommitSig;
+
+    fn block(height: i64, signers: &[(u8, BlockIdFlag)]) -> Block {
+        let signatures = signers
+            .iter()
+            .map(|(signer, flag)| CommitSig {
+                block_id_flag: *flag as i32,
+                validator_address: vec![*signer; 20],
+                timestamp: None,
+                signature: Vec::new(),
+            })
+            .collect();
+        Block {
+            last_commit: Some(Commit {
+                height,
+                signatures,
+                ..Commit::default()
+            }),
+            ..Block::default()
+        }
+    }
+
+    #[test]
+    fn test_validator_performance_report() {
+        let one = Address::from_bytes([1; 20], "cosmosvalcons").unwrap();
+        let two = Address::from_bytes([2; 20], "cosmosvalcons").unwrap();
+        let blocks = vec![
+            block(10, &[(1, BlockIdFlag::Commit), (2, BlockIdFlag::Commit)]),
+            block(11, &[(1, BlockIdFlag::Nil), (2, BlockIdFlag::Absent)]),
+            block(12, &[(1, BlockIdFlag::Commit), (2, BlockIdFlag::Absent)]),
+            block(13, &[(1, BlockIdFlag::Commit)]),
+        ];
+        let validators = vec![
+            ConsensusValidator {
+                address: two.to_string(),
+                pub_key: None,
+                voting_power: 10,
+                proposer_priority: 0,
+            },
+            ConsensusValidator {
+                address: one.to_string(),
+                pub_key: None,
+                voting_power: 20,
+                proposer_priority: 0,
+            },
+        ];
+        let infos = vec![ValidatorSigningInfo {
+            address: two.to_string(),
+            missed_blocks_counter: 7,
+            tombstoned: false,
+            ..ValidatorSigningInfo::default()
+        }];
+        let params = SlashingParams {
+            signed_blocks_window: 100,
+            min_signed_per_window: b"500000000000000000".to_vec(),
+            ..SlashingParams::default()
+        };
+
+        let report = build_report(&blocks, &validators, &infos, Some(params)).unwrap();
+        assert_eq!((report.start_height, report.end_height), (10, 13));
+        assert_eq!(report.signed_blocks_window, 100);
+        assert_eq!(report.min_signed_per_window, Some("0.5".parse().unwrap()));
+        // ordered by voting power, nil votes count as signed
+        assert_eq!(report.validators[0].consensus_address, one.to_string());
+        assert_eq!(report.validators[0].signed, 4);
+        assert_eq!(report.validators[0].missed_blocks_counter, None);
+        let two = &report.validators[1];
+        assert_eq!((two.signed, two.missed), (1, 3));
+        assert_eq!(two.uptime(), 0.25);
+        assert_eq!(two.missed_blocks_counter, Some(7));
+    }
+}
@@ -0,0 +1,365 @@
+//! Checks a validator before delegating to it. A delegation to a jailed or tombstoned
+//! validator is accepted by the chain but earns nothing, so automated delegators such as
+//! auto-compounders should check the validator first and move on to another one.
+//!
+//! ```ignore
+//! let policy = DelegationPolicy::default().max_commission("0.1".parse()?);
+//! match contact.delegate_checked(validator, amount, fee, key, &policy, None).await {
+//!     Ok((response, warnings)) => warnings.iter().for_each(|w| warn!("{}", w)),
+//!     Err(CosmosGrpcError::UnsafeValidator { risk, .. }) => pick_another_validator(risk),
+//!     Err(e) => return Err(e),
+//! }
+//! ```
+//!
+//! The staking module applies commission changes immediately, there is no schedule to
+//! query, so a commission changed within the policy window is reported instead.
+
+use crate::any::AnyExt;
+use crate::client::staking::delegate_msg;
+use crate::client::staking::monitor::timestamp_to_time;
+use crate::error::CosmosGrpcError;
+use crate::error::ValidatorRisk;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Dec;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::crypto::ed25519::PubKey as Ed25519PubKey;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::Params as SlashingParams;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::ValidatorSigningInfo;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::BondStatus;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Validator;
+use sha2::Digest;
+use sha2::Sha256;
+use std::time::Duration;
+use std::time::SystemTime;
+use tonic::Code;
+
+/// How long after a commission change it is reported, by default one day, the period in
+/// which the staking module allows a single change
+pub const DEFAULT_COMMISSION_CHANGE_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What ValidatorCheck reports, see the module documentation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DelegationPolicy {
+    /// commission rates above this are blocking, no limit if None
+    pub max_commission: Option<Dec>,
+    pub commission_change_window: Duration,
+    /// if set validators outside the active set are not reported
+    pub allow_inactive: bool,
+}
+
+impl Default for DelegationPolicy {
+    fn default() -> Self {
+        DelegationPolicy {
+            max_commission: None,
+            commission_change_window: DEFAULT_COMMISSION_CHANGE_WINDOW,
+            allow_inactive: false,
+        }
+    }
+}
+
+impl DelegationPolicy {
+    pub fn max_commission(mut self, rate: Dec) -> Self {
+        self.max_commission = Some(rate);
+        self
+    }
+
+    pub fn commission_change_window(mut self, window: Duration) -> Self {
+        self.commission_change_window = window;
+        self
+    }
+
+    pub fn allow_inactive(mut self) -> Self {
+        self.allow_inactive = true;
+        self
+    }
+}
+
+/// The result of Contact::check_validator
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatorCheck {
+    pub validator: Validator,
+    pub risks: Vec<ValidatorRisk>,
+}
+
+impl ValidatorCheck {
+    /// The first risk that should stop a delegation, if any
+    pub fn blocking(&self) -> Option<&ValidatorRisk> {
+        self.risks.iter().find(|risk| risk.is_blocking())
+    }
+
+    /// The risks that do not stop a delegation
+    pub fn warnings(&self) -> Vec<ValidatorRisk> {
+        self.risks
+            .iter()
+            .filter(|risk| !risk.is_blocking())
+            .cloned()
+            .collect()
+    }
+}
+
+/// The consensus address of a validator with an ed25519 consensus key, the first 20 bytes
+/// of the sha256 hash of the key, with the valcons suffix added to the prefix
+pub fn consensus_address(validator: &Validator, prefix: &str) -> Result<Address, CosmosGrpcError> {
+    let key: Ed25519PubKey = match validator.consensus_pubkey.as_ref() {
+        Some(key) => key.unpack()?,
+        None => {
+            return Err(CosmosGrpcError::BadResponse(format!(
+                "Validator {} has no consensus key",
+                validator.operator_address
+            )))
+        }
+    };
+    let hash = Sha256::digest(&key.key);
+    match Address::from_slice(&hash[..20], format!("{}valcons", prefix)) {
+        Ok(address) => Ok(address),
+        Err(e) => Err(CosmosGrpcError::BadInput(e.to_string())),
+    }
+}
+
+/// Lists the risks of delegating to a validator at the time now. signing_info is None if
+/// the chain has none for the validator, which is the case until it first signs a block.
+pub fn validator_risks(
+    validator: &Validator,
+    signing_info: Option<&ValidatorSigningInfo>,
+    params: &SlashingParams,
+    policy: &DelegationPolicy,
+    now: SystemTime,
+) -> Vec<ValidatorRisk> {
+    let mut risks = Vec::new();
+    if signing_info.map(|info| info.tombstoned).unwrap_or(false) {
+        risks.push(ValidatorRisk::Tombstoned);
+    }
+    if validator.jailed {
+        risks.push(ValidatorRisk::Jailed {
+            until: signing_info
+                .and_then(|info| info.jailed_until.as_ref())
+                .and_then(timestamp_to_time),
+        });
+    }
+    if !policy.allow_inactive && validator.status != BondStatus::Bonded as i32 {
+        let status = match BondStatus::from_i32(validator.status) {
+            Some(status) => format!("{:?}", status),
+            None => validator.status.to_string(),
+        };
+        risks.push(ValidatorRisk::NotBonded { status });
+    }
+    if let Some(info) = signing_info {
+        let window = params.signed_blocks_window;
+        let min_signed = String::from_utf8(params.min_signed_per_window.clone())
+            .ok()
+            .and_then(|value| Dec::from_proto_string(&value).ok());
+        if let Some(min_signed) = min_signed.filter(|_| window > 0) {
+            let window_dec = Dec::from(window as u64);
+            let max_missed = window_dec.clone() - window_dec.mul(&min_signed);
+            let missed = Dec::from(info.missed_blocks_counter.max(0) as u64);
+            if missed.clone() + missed > max_missed {
+                risks.push(ValidatorRisk::MissingBlocks {
+                    missed: info.missed_blocks_counter,
+                    window,
+                });
+            }
+        }
+    }
+    if let Some(commission) = validator.commission.as_ref() {
+        let rate = commission
+            .commission_rates
+            .as_ref()
+            .and_then(|rates| Dec::from_proto_string(&rates.rate).ok());
+        if let Some(rate) = rate {
+            let changed = commission
+                .update_time
+                .as_ref()
+                .and_then(timestamp_to_time)
+                .and_then(|time| now.duration_since(time).ok());
+            if let Some(age) = changed {
+                if age < policy.commission_change_window {
+                    risks.push(ValidatorRisk::CommissionChanged {
+                        rate: rate.clone(),
+                        age,
+                    });
+                }
+            }
+            if let Some(limit) = policy.max_commission.as_ref() {
+                if &rate > limit {
+                    risks.push(ValidatorRisk::CommissionAboveLimit {
+                        rate,
+                        limit: limit.clone(),
+                    });
+                }
+            }
+        }
+    }
+    risks
+}
+
+impl Contact {
+    /// Gets a validator by operator address
+    pub async fn get_validator(&self, validator: Address) -> Result<Validator, CosmosGrpcError> {
+        let res: QueryValidatorResponse = self
+            .grpc_unary(
+                "/cosmos.staking.v1beta1.Query/Validator",
+                QueryValidatorRequest {
+                    validator_addr: validator.to_string(),
+                },
+            )
+            .await?;
+        match res.validator {
+            Some(validator) => Ok(validator),
+            None => Err(CosmosGrpcError::BadResponse("No validator?".to_string())),
+        }
+    }
+
+    /// Checks the status, signing info and commission of a validator against the policy
+    pub async fn check_validator(
+        &self,
+        validator: Address,
+        policy: &DelegationPolicy,
+    ) -> Result<ValidatorCheck, CosmosGrpcError> {
+        let info = self.get_validator(validator).await?;
+        let consensus = consensus_address(&info, &self.inner.chain_prefix)?;
+        let signing_info = match self.get_signing_info(consensus.to_string()).await {
+            Ok(signing_info) => Some(signing_info),
+            Err(e) if e.grpc_code() == Some(Code::NotFound) => None,
+            Err(e) => return Err(e),
+        };
+        let params = self.get_slashing_params().await?;
+        let risks = validator_risks(
+            &info,
+            signing_info.as_ref(),
+            &params,
+            policy,
+            SystemTime::now(),
+        );
+        Ok(ValidatorCheck {
+            validator: info,
+            risks,
+        })
+    }
+
+    /// Same as delegate_to_validator but checks the validator first. Returns UnsafeValidator
+    /// without broadcasting if a blocking risk is found, otherwise the response and the
+    /// warnings.
+    pub async fn delegate_checked(
+        &self,
+        validator_address: Address,
+        amount_to_delegate: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        policy: &DelegationPolicy,
+        wait_timeout: Option<Duration>,
+    ) -> Result<(TxResponse, Vec<ValidatorRisk>), CosmosGrpcError> {
+        let check = self.check_validator(validator_address, policy).await?;
+        if let Some(risk) = check.blocking() {
+            return Err(CosmosGrpcError::UnsafeValidator {
+                validator: validator_address.to_string(),
+                risk: risk.clone(),
+            });
+        }
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = delegate_msg(our_address, validator_address, amount_to_delegate);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        let response = self
+            .send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await?;
+        Ok((response, check.warnings()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::staking::ed25519_consensus_pubkey;
+    use cosmos_sdk_proto::cosmos::staking::v1beta1::Commission;
+    use cosmos_sdk_proto::cosmos::staking::v1beta1::CommissionRates;
+    use prost_types::Timestamp;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_validator_risks() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let validator = Validator {
+            operator_address: "cosmosvaloper1test".to_string(),
+            consensus_pubkey: Some(ed25519_consensus_pubkey(&[7; 32]).unwrap()),
+            status: BondStatus::Bonded as i32,
+            commission: Some(Commission {
+                commission_rates: Some(CommissionRates {
+                    rate: "100000000000000000".to_string(),
+                    max_rate: "200000000000000000".to_string(),
+                    max_change_rate: "10000000000000000".to_string(),
+                }),
+                update_time: Some(Timestamp {
+                    seconds: 1_700_000_000 - 3600,
+                    nanos: 0,
+                }),
+            }),
+            ..Default::default()
+        };
+        let params = SlashingParams {
+            signed_blocks_window: 100,
+            min_signed_per_window: b"500000000000000000".to_vec(),
+            ..Default::default()
+        };
+        let mut info = ValidatorSigningInfo {
+            missed_blocks_counter: 20,
+            ..Default::default()
+        };
+        let policy = DelegationPolicy::default();
+        assert_eq!(
+            validator_risks(&validator, Some(&info), &params, &policy, now),
+            vec![ValidatorRisk::CommissionChanged {
+                rate: "0.1".parse().unwrap(),
+                age: Duration::from_secs(3600),
+            }]
+        );
+        let policy = policy
+            .commission_change_window(Duration::from_secs(60))
+            .max_commission("0.05".parse().unwrap());
+        let check = ValidatorCheck {
+            validator: validator.clone(),
+            risks: validator_risks(&validator, Some(&info), &params, &policy, now),
+        };
+        assert!(matches!(
+            check.blocking(),
+            Some(ValidatorRisk::CommissionAboveLimit { .. })
+        ));
+
+        let jailed = Validator {
+            jailed: true,
+            status: BondStatus::Unbonding as i32,
+            commission: None,
+            ..validator.clone()
+        };
+        info.tombstoned = true;
+        info.missed_blocks_counter = 30;
+        let risks = validator_risks(&jailed, Some(&info), &params, &Default::default(), now);
+        assert_eq!(risks[0], ValidatorRisk::Tombstoned);
+        assert_eq!(risks[1], ValidatorRisk::Jailed { until: None });
+        assert_eq!(
+            risks[2],
+            ValidatorRisk::NotBonded {
+                status: "Unbonding".to_string()
+            }
+        );
+        assert_eq!(
+            risks[3],
+            ValidatorRisk::MissingBlocks {
+                missed: 30,
+                window: 100
+            }
+        );
+        let check = ValidatorCheck {
+            validator: jailed,
+            risks,
+        };
+        assert_eq!(check.warnings().len(), 1);
+
+        let address = consensus_address(&validator, "cosmos").unwrap();
+        assert_eq!(address.get_prefix(), "cosmosvalcons");
+        assert_eq!(address.as_bytes(), &Sha256::digest(&[7; 32])[..20]);
+    }
+}
@@ -0,0 +1,336 @@
+//! Contains utility functions for creating, editing, and unjailing validators
+
+use crate::client::staking::MSG_CREATE_VALIDATOR_TYPE_URL;
+use crate::client::staking::MSG_EDIT_VALIDATOR_TYPE_URL;
+use crate::client::staking::MSG_UNJAIL_TYPE_URL;
+use crate::decimal::Decimal;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::utils::encode_any;
+use crate::Address;
+use crate::Coin;
+use crate::Contact;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::crypto::ed25519::PubKey as Ed25519PubKey;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::MsgUnjail;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::CommissionRates as ProtoCommissionRates;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::Description;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgCreateValidator;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgEditValidator;
+use num256::Uint256;
+use prost_types::Any;
+use std::time::Duration;
+
+pub const ED25519_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.ed25519.PubKey";
+
+/// The value the staking module uses to indicate a description field should not be changed
+pub const DO_NOT_MODIFY_DESCRIPTION: &str = "[do-not-modify]";
+
+/// Maximum lengths of the validator description fields as enforced by the staking module
+const MAX_MONIKER_LENGTH: usize = 70;
+const MAX_IDENTITY_LENGTH: usize = 3000;
+const MAX_WEBSITE_LENGTH: usize = 140;
+const MAX_SECURITY_CONTACT_LENGTH: usize = 140;
+const MAX_DETAILS_LENGTH: usize = 280;
+
+/// The commission parameters of a new validator, only the rate may be changed
+/// after the validator is created and then only by max_change_rate per day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommissionRates {
+    pub rate: Decimal,
+    pub max_rate: Decimal,
+    pub max_change_rate: Decimal,
+}
+
+impl CommissionRates {
+    /// Performs the same validation as the staking module, all rates must be between
+    /// zero and one, the rate can not exceed max_rate and max_change_rate can not
+    /// exceed max_rate
+    pub fn validate(&self) -> Result<(), CosmosGrpcError> {
+        let zero = Decimal::from(0u8);
+        let one = Decimal::from(1u8);
+        if self.max_rate < zero || self.max_rate > one {
+            Err(CosmosGrpcError::BadInput(
+                "Commission max rate must be between 0 and 1".to_string(),
+            ))
+        } else if self.rate < zero || self.rate > self.max_rate {
+            Err(CosmosGrpcError::BadInput(
+                "Commission rate must be between 0 and max rate".to_string(),
+            ))
+        } else if self.max_change_rate < zero || self.max_change_rate > self.max_rate {
+            Err(CosmosGrpcError::BadInput(
+                "Commission max change rate must be between 0 and max rate".to_string(),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl From<CommissionRates> for ProtoCommissionRates {
+    fn from(value: CommissionRates) -> Self {
+        ProtoCommissionRates {
+            rate: value.rate.to_proto_string(),
+            max_rate: value.max_rate.to_proto_string(),
+            max_change_rate: value.max_change_rate.to_proto_string(),
+        }
+    }
+}
+
+/// Packs a 32 byte ed25519 Tendermint consensus public key into the Any type used by
+/// MsgCreateValidator. The key can be found base64 encoded in the output of
+/// `<chain binary> tendermint show-validator`
+pub fn ed25519_consensus_pubkey(key: &[u8]) -> Result<Any, CosmosGrpcError> {
+    if key.len() != 32 {
+        return Err(CosmosGrpcError::BadInput(format!(
+            "Ed25519 consensus key must be 32 bytes, got {}",
+            key.len()
+        )));
+    }
+    Ok(encode_any(
+        Ed25519PubKey { key: key.to_vec() },
+        ED25519_PUBKEY_TYPE_URL.to_string(),
+    ))
+}
+
+/// Same as ed25519_consensus_pubkey() but takes the base64 encoded key
+pub fn ed25519_consensus_pubkey_from_base64(key: &str) -> Result<Any, CosmosGrpcError> {
+    match base64::decode(key) {
+        Ok(bytes) => ed25519_consensus_pubkey(&bytes),
+        Err(e) => Err(CosmosGrpcError::BadInput(format!(
+            "Invalid base64 consensus key {}",
+            e
+        ))),
+    }
+}
+
+/// Returns the validator operator address for an account, this is the same bytes as the
+/// account address with the `valoper` suffix added to the prefix
+pub fn operator_address(account: Address) -> Result<Address, CosmosGrpcError> {
+    let prefix = format!("{}valoper", account.get_prefix());
+    match Address::from_slice(account.as_bytes(), prefix) {
+        Ok(address) => Ok(address),
+        Err(e) => Err(CosmosGrpcError::BadInput(e.to_string())),
+    }
+}
+
+fn validate_description(description: &Description) -> Result<(), CosmosGrpcError> {
+    let fields = [
+        ("moniker", &description.moniker, MAX_MONIKER_LENGTH),
+        ("identity", &description.identity, MAX_IDENTITY_LENGTH),
+        ("website", &description.website, MAX_WEBSITE_LENGTH),
+        (
+            "security contact",
+            &description.security_contact,
+            MAX_SECURITY_CONTACT_LENGTH,
+        ),
+        ("details", &description.details, MAX_DETAILS_LENGTH),
+    ];
+    for (name, value, max) in fields.iter() {
+        if value.len() > *max {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Validator {} is longer than {} characters",
+                name, max
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Builds a MsgCreateValidator, the delegator is the account creating the validator and
+/// self_delegation is the initial amount it bonds. Validates the description and commission
+/// rates locally since the chain will reject the transaction if they are invalid.
+pub fn create_validator_msg(
+    delegator: Address,
+    description: Description,
+    commission: CommissionRates,
+    min_self_delegation: Uint256,
+    consensus_pubkey: Any,
+    self_delegation: Coin,
+) -> Result<Msg, CosmosGrpcError> {
+    if description.moniker.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Validator moniker can not be empty".to_string(),
+        ));
+    }
+    validate_description(&description)?;
+    commission.validate()?;
+    if self_delegation.amount < min_self_delegation {
+        return Err(CosmosGrpcError::BadInput(
+            "Self delegation is less than the min self delegation".to_string(),
+        ));
+    }
+    let create = MsgCreateValidator {
+        description: Some(description),
+        commission: Some(commission.into()),
+        min_self_delegation: min_self_delegation.to_string(),
+        delegator_address: delegator.to_string(),
+        validator_address: operator_address(delegator)?.to_string(),
+        pubkey: Some(consensus_pubkey),
+        value: Some(self_delegation.into()),
+    };
+    Ok(Msg::new(MSG_CREATE_VALIDATOR_TYPE_URL, create))
+}
+
+/// Builds a MsgEditValidator, any field provided as None is left unchanged. Individual
+/// description fields can be left unchanged by setting them to DO_NOT_MODIFY_DESCRIPTION
+pub fn edit_validator_msg(
+    validator: Address,
+    description: Option<Description>,
+    commission_rate: Option<Decimal>,
+    min_self_delegation: Option<Uint256>,
+) -> Result<Msg, CosmosGrpcError> {
+    let description = match description {
+        Some(d) => {
+            validate_description(&d)?;
+            d
+        }
+        None => Description {
+            moniker: DO_NOT_MODIFY_DESCRIPTION.to_string(),
+            identity: DO_NOT_MODIFY_DESCRIPTION.to_string(),
+            website: DO_NOT_MODIFY_DESCRIPTION.to_string(),
+            security_contact: DO_NOT_MODIFY_DESCRIPTION.to_string(),
+            details: DO_NOT_MODIFY_DESCRIPTION.to_string(),
+        },
+    };
+    let edit = MsgEditValidator {
+        description: Some(description),
+        validator_address: validator.to_string(),
+        // Go default values indicate that these fields are not being changed
+        commission_rate: commission_rate
+            .map(|r| r.to_proto_string())
+            .unwrap_or_default(),
+        min_self_delegation: min_self_delegation
+            .map(|m| m.to_string())
+            .unwrap_or_default(),
+    };
+    Ok(Msg::new(MSG_EDIT_VALIDATOR_TYPE_URL, edit))
+}
+
+/// Builds a MsgUnjail for the provided validator operator address
+pub fn unjail_msg(validator: Address) -> Msg {
+    let unjail = MsgUnjail {
+        validator_addr: validator.to_string(),
+    };
+    Msg::new(MSG_UNJAIL_TYPE_URL, unjail)
+}
+
+impl Contact {
+    /// Creates a new validator operated by the provided private key, self delegating the
+    /// provided amount. See ed25519_consensus_pubkey() for help packing the consensus key.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_validator(
+        &self,
+        description: Description,
+        commission: CommissionRates,
+        min_self_delegation: Uint256,
+        consensus_pubkey: Any,
+        self_delegation: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = create_validator_msg(
+            our_address,
+            description,
+            commission,
+            min_self_delegation,
+            consensus_pubkey,
+            self_delegation,
+        )?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Edits the validator operated by the provided private key, any field provided
+    /// as None is left unchanged
+    pub async fn edit_validator(
+        &self,
+        description: Option<Description>,
+        commission_rate: Option<Decimal>,
+        min_self_delegation: Option<Uint256>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = edit_validator_msg(
+            operator_address(our_address)?,
+            description,
+            commission_rate,
+            min_self_delegation,
+        )?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Unjails the validator operated by the provided private key, this will fail if
+    /// the validator is tombstoned or the jail period has not yet elapsed
+    pub async fn unjail(
+        &self,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = unjail_msg(operator_address(our_address)?);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_commission_validation() {
+        let valid = CommissionRates {
+            rate: dec("0.100000000000000000"),
+            max_rate: dec("0.200000000000000000"),
+            max_change_rate: dec("0.010000000000000000"),
+        };
+        assert!(valid.validate().is_ok());
+
+        let rate_too_high = CommissionRates {
+            rate: dec("0.300000000000000000"),
+            ..valid
+        };
+        assert!(rate_too_high.validate().is_err());
+
+        let max_too_high = CommissionRates {
+            max_rate: dec("2"),
+            ..valid
+        };
+        assert!(max_too_high.validate().is_err());
+
+        let change_too_high = CommissionRates {
+            max_change_rate: dec("0.500000000000000000"),
+            ..valid
+        };
+        assert!(change_too_high.validate().is_err());
+    }
+
+    #[test]
+    fn test_operator_address() {
+        let account = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let operator = operator_address(account).unwrap();
+        assert_eq!(operator.get_prefix(), "cosmosvaloper");
+        assert_eq!(operator.as_bytes(), account.as_bytes());
+    }
+
+    #[test]
+    fn test_consensus_pubkey() {
+        assert!(ed25519_consensus_pubkey(&[0; 32]).is_ok());
+        assert!(ed25519_consensus_pubkey(&[0; 33]).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! Contains utility functions for interacting with the Osmosis derived tokenfactory module,
+//! which allows any account to create and administer its own denoms. Only compiled if the
+//! tokenfactory feature is enabled.
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::osmosis::tokenfactory::v1beta1::MsgBurn;
+use crate::proto::osmosis::tokenfactory::v1beta1::MsgChangeAdmin;
+use crate::proto::osmosis::tokenfactory::v1beta1::MsgCreateDenom;
+use crate::proto::osmosis::tokenfactory::v1beta1::MsgMint;
+use crate::proto::osmosis::tokenfactory::v1beta1::QueryDenomAuthorityMetadataRequest;
+use crate::proto::osmosis::tokenfactory::v1beta1::QueryDenomAuthorityMetadataResponse;
+use crate::proto::osmosis::tokenfactory::v1beta1::QueryDenomsFromCreatorRequest;
+use crate::proto::osmosis::tokenfactory::v1beta1::QueryDenomsFromCreatorResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use std::time::Duration;
+
+pub const MSG_CREATE_DENOM_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgCreateDenom";
+pub const MSG_MINT_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgMint";
+pub const MSG_BURN_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+pub const MSG_CHANGE_ADMIN_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgChangeAdmin";
+
+/// Returns the full denom that will be created by a MsgCreateDenom from this creator
+pub fn factory_denom(creator: Address, subdenom: &str) -> String {
+    format!("factory/{}/{}", creator, subdenom)
+}
+
+/// Builds a MsgCreateDenom, the resulting denom will be `factory/{sender}/{subdenom}`
+/// and will be administered by the sender
+pub fn create_denom_msg(sender: Address, subdenom: impl Into<String>) -> Msg {
+    let create = MsgCreateDenom {
+        sender: sender.to_string(),
+        subdenom: subdenom.into(),
+    };
+    Msg::new(MSG_CREATE_DENOM_TYPE_URL, create)
+}
+
+/// Builds a MsgMint, the sender must be the admin of the denom. Chains running older
+/// versions of the module ignore mint_to and always mint to the sender.
+pub fn mint_msg(sender: Address, amount: Coin, mint_to: Address) -> Msg {
+    let mint = MsgMint {
+        sender: sender.to_string(),
+        amount: Some(amount.into()),
+        mint_to_address: mint_to.to_string(),
+    };
+    Msg::new(MSG_MINT_TYPE_URL, mint)
+}
+
+/// Builds a MsgBurn, the sender must be the admin of the denom. Chains running older
+/// versions of the module ignore burn_from and always burn from the sender.
+pub fn burn_msg(sender: Address, amount: Coin, burn_from: Address) -> Msg {
+    let burn = MsgBurn {
+        sender: sender.to_string(),
+        amount: Some(amount.into()),
+        burn_from_address: burn_from.to_string(),
+    };
+    Msg::new(MSG_BURN_TYPE_URL, burn)
+}
+
+/// Builds a MsgChangeAdmin transferring control of the denom to new_admin
+pub fn change_admin_msg(sender: Address, denom: impl Into<String>, new_admin: Address) -> Msg {
+    let change = MsgChangeAdmin {
+        sender: sender.to_string(),
+        denom: denom.into(),
+        new_admin: new_admin.to_string(),
+    };
+    Msg::new(MSG_CHANGE_ADMIN_TYPE_URL, change)
+}
+
+impl Contact {
+    /// Gets the admin of a tokenfactory denom, returns None if the denom has no admin
+    pub async fn get_denom_admin(&self, denom: String) -> Result<Option<Address>, CosmosGrpcError> {
+        let res: QueryDenomAuthorityMetadataResponse = self
+            .grpc_unary(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata",
+                QueryDenomAuthorityMetadataRequest { denom },
+            )
+            .await?;
+        match res.authority_metadata {
+            Some(metadata) => {
+                if metadata.admin.is_empty() {
+                    Ok(None)
+                } else {
+                    match metadata.admin.parse() {
+                        Ok(address) => Ok(Some(address)),
+                        Err(e) => Err(CosmosGrpcError::BadResponse(e.to_string())),
+                    }
+                }
+            }
+            None => Err(CosmosGrpcError::BadResponse(
+                "No authority metadata?".to_string(),
+            )),
+        }
+    }
+
+    /// Gets a list of all the tokenfactory denoms created by the provided address
+    pub async fn get_denoms_from_creator(
+        &self,
+        creator: Address,
+    ) -> Result<Vec<String>, CosmosGrpcError> {
+        let res: QueryDenomsFromCreatorResponse = self
+            .grpc_unary(
+                "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator",
+                QueryDenomsFromCreatorRequest {
+                    creator: creator.to_bech32(&self.inner.chain_prefix).unwrap(),
+                },
+            )
+            .await?;
+        Ok(res.denoms)
+    }
+
+    /// Creates a new tokenfactory denom administered by the provided private key
+    pub async fn create_denom(
+        &self,
+        subdenom: String,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = create_denom_msg(our_address, subdenom);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Mints tokens of a denom administered by the provided private key, sending them
+    /// to the admin account
+    pub async fn mint_tokens(
+        &self,
+        amount: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = mint_msg(our_address, amount, our_address);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Burns tokens of a denom administered by the provided private key from the
+    /// admin account
+    pub async fn burn_tokens(
+        &self,
+        amount: Coin,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = burn_msg(our_address, amount, our_address);
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factory_denom() {
+        let creator: Address = "cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            factory_denom(creator, "foo"),
+            "factory/cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a/foo"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Local validation of signed transactions against the memo, signature and size limits set
+//! by the chain parameters, so oversized transactions fail with a precise error before they
+//! are broadcast
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::error::TxLimitError;
+#[cfg(feature = "sdk-0_47")]
+use crate::proto::cosmos::consensus::v1::QueryParamsRequest as ConsensusParamsRequest;
+#[cfg(feature = "sdk-0_47")]
+use crate::proto::cosmos::consensus::v1::QueryParamsResponse as ConsensusParamsResponse;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryParamsRequest as AuthParamsRequest;
+use cosmos_sdk_proto::cosmos::auth::v1beta1::QueryParamsResponse as AuthParamsResponse;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use prost::Message;
+use std::time::Duration;
+use std::time::Instant;
+
+/// How long the limits checked before every broadcast are reused before they are fetched
+/// again, the params only change through governance
+const TX_LIMITS_TTL: Duration = Duration::from_secs(600);
+
+/// The transaction limits of a chain, None if the limit is unknown or unlimited
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TxLimits {
+    pub max_memo_characters: Option<u64>,
+    pub tx_sig_limit: Option<u64>,
+    /// the maximum block size, a transaction larger than this can never be included
+    pub max_tx_bytes: Option<u64>,
+}
+
+impl TxLimits {
+    /// Checks the encoded TxRaw bytes of a signed transaction against these limits
+    pub fn check(&self, tx_bytes: &[u8]) -> Result<(), TxLimitError> {
+        if let Some(max) = self.max_tx_bytes {
+            let size = tx_bytes.len() as u64;
+            if size > max {
+                return Err(TxLimitError::TxTooLarge { size, max });
+            }
+        }
+        let raw = TxRaw::decode(tx_bytes).map_err(|e| TxLimitError::InvalidTx(e.to_string()))?;
+        if let Some(max) = self.tx_sig_limit {
+            let count = raw.signatures.len() as u64;
+            if count > max {
+                return Err(TxLimitError::TooManySignatures { count, max });
+            }
+        }
+        if let Some(max) = self.max_memo_characters {
+            let body = TxBody::decode(raw.body_bytes.as_slice())
+                .map_err(|e| TxLimitError::InvalidTx(e.to_string()))?;
+            // the SDK limits the memo by its length in bytes
+            let length = body.memo.len() as u64;
+            if length > max {
+                return Err(TxLimitError::MemoTooLong { length, max });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Contact {
+    /// Gets the transaction limits of the chain from the auth params and, on chains running
+    /// Cosmos SDK v0.47+, the consensus params
+    pub async fn get_tx_limits(&self) -> Result<TxLimits, CosmosGrpcError> {
+        let auth: AuthParamsResponse = self
+            .grpc_unary("/cosmos.auth.v1beta1.Query/Params", AuthParamsRequest {})
+            .await?;
+        let auth = auth.params;
+        Ok(TxLimits {
+            max_memo_characters: auth
+                .as_ref()
+                .map(|p| p.max_memo_characters)
+                .filter(|m| *m > 0),
+            tx_sig_limit: auth.as_ref().map(|p| p.tx_sig_limit).filter(|m| *m > 0),
+            max_tx_bytes: self.get_max_tx_bytes().await?,
+        })
+    }
+
+    /// The block size limit from the consensus module, older chains keep it in the
+    /// Tendermint genesis where it can not be queried over gRPC
+    #[cfg(feature = "sdk-0_47")]
+    async fn get_max_tx_bytes(&self) -> Result<Option<u64>, CosmosGrpcError> {
+        let consensus: Result<ConsensusParamsResponse, CosmosGrpcError> = self
+            .grpc_unary(
+                "/cosmos.consensus.v1.Query/Params",
+                ConsensusParamsRequest {},
+            )
+            .await;
+        match consensus {
+            Ok(res) => Ok(res
+                .params
+                .and_then(|p| p.block)
+                .filter(|b| b.max_bytes > 0)
+                .map(|b| b.max_bytes as u64)),
+            Err(CosmosGrpcError::RequestError { .. }) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    #[cfg(not(feature = "sdk-0_47"))]
+    async fn get_max_tx_bytes(&self) -> Result<Option<u64>, CosmosGrpcError> {
+        Ok(None)
+    }
+
+    /// The transaction limits fetched by the last call to this Contact or its clones,
+    /// fetched again once they are older than TX_LIMITS_TTL
+    pub(crate) async fn cached_tx_limits(&self) -> Result<TxLimits, CosmosGrpcError> {
+        if let Some((limits, fetched)) = &*self.inner.tx_limits.lock().unwrap() {
+            if fetched.elapsed() < TX_LIMITS_TTL {
+                return Ok(limits.clone());
+            }
+        }
+        let limits = self.get_tx_limits().await?;
+        *self.inner.tx_limits.lock().unwrap() = Some((limits.clone(), Instant::now()));
+        Ok(limits)
+    }
+
+    /// Checks signed transaction bytes against the chain limits, returning a
+    /// TxLimitExceeded error if the transaction would be rejected. The limits are cached
+    /// for ten minutes so sending does not query the params every time.
+    pub async fn check_tx_limits(&self, tx_bytes: &[u8]) -> Result<(), CosmosGrpcError> {
+        let limits = self.cached_tx_limits().await?;
+        limits.check(tx_bytes)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx_bytes(memo: &str, signatures: usize) -> Vec<u8> {
+        let body = TxBody {
+            messages: Vec::new(),
+            memo: memo.to_string(),
+            timeout_height: 0,
+            extension_options: Vec::new(),
+            non_critical_extension_options: Vec::new(),
+        };
+        let mut body_bytes = Vec::new();
+        body.encode(&mut body_bytes).unwrap();
+        let raw = TxRaw {
+            body_bytes,
+            auth_info_bytes: Vec::new(),
+            signatures: vec![vec![1; 64]; signatures],
+        };
+        let mut bytes = Vec::new();
+        raw.encode(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_tx_limits() {
+        let limits = TxLimits {
+            max_memo_characters: Some(10),
+            tx_sig_limit: Some(2),
+            max_tx_bytes: Some(200),
+        };
+        assert!(limits.check(&tx_bytes("hello", 1)).is_ok());
+        assert_eq!(
+            limits.check(&tx_bytes("hello world", 1)),
+            Err(TxLimitError::MemoTooLong {
+                length: 11,
+                max: 10
+            })
+        );
+        assert_eq!(
+            limits.check(&tx_bytes("", 3)),
+            Err(TxLimitError::TooManySignatures { count: 3, max: 2 })
+        );
+        let large = tx_bytes("", 2);
+        assert!(matches!(
+            TxLimits {
+                max_tx_bytes: Some(100),
+                ..TxLimits::default()
+            }
+            .check(&large),
+            Err(TxLimitError::TxTooLarge { max: 100, .. })
+        ));
+        assert!(TxLimits::default().check(&large).is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_cached_tx_limits() {
+        // nothing listens here, the limits can only come from the cache
+        let contact = Contact::new("http://127.0.0.1:1", Duration::from_secs(1), "cosmos").unwrap();
+        let limits = TxLimits {
+            max_memo_characters: Some(5),
+            ..TxLimits::default()
+        };
+        *contact.inner.tx_limits.lock().unwrap() = Some((limits, Instant::now()));
+        let clone = contact.clone();
+        assert!(clone.check_tx_limits(&tx_bytes("hello", 1)).await.is_ok());
+        assert!(matches!(
+            clone.check_tx_limits(&tx_bytes("hello world", 1)).await,
+            Err(CosmosGrpcError::TxLimitExceeded {
+                error: TxLimitError::MemoTooLong { length: 11, max: 5 }
+            })
+        ));
+    }
+}
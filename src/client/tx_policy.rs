@@ -0,0 +1,344 @@
+//! Policies checked before a Contact signs a transaction. Every transaction signed through
+//! send_message, send_tokens, the governance helpers or a Wallet is described to each policy
+//! of the Contact before the key is used, and any policy can veto it, so limits such as
+//! "never send more than X" or "only pay these addresses" are enforced in one place rather
+//! than at every call site.
+//!
+//! ```ignore
+//! let contact = contact
+//!     .with_tx_policy(MaxTransferPolicy::new().limit("1000000000uatom".parse()?))
+//!     .with_tx_policy(DestinationAllowlist::new(vec![treasury, exchange_deposit]));
+//! ```
+
+use crate::any::AnyExt;
+use crate::client::authz::MSG_EXEC_TYPE_URL;
+use crate::client::bank::MSG_MULTI_SEND_TYPE_URL;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::ibc::MSG_TRANSFER_TYPE_URL;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::authz::v1beta1::MsgExec;
+use crate::proto::ibc::applications::transfer::v1::MsgTransfer;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use num256::Uint256;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A transaction about to be signed
+#[derive(Debug, Clone, Copy)]
+pub struct TxProposal<'a> {
+    pub signer: Address,
+    pub messages: &'a [Msg],
+    pub fee: &'a Fee,
+    pub memo: &'a str,
+}
+
+impl TxProposal<'_> {
+    /// The value every message of the transaction moves, see transfers
+    pub fn transfers(&self) -> Result<Vec<Transfer>, CosmosGrpcError> {
+        let mut transfers = Vec::new();
+        for msg in self.messages {
+            transfers.extend(transfers_of(msg)?);
+        }
+        Ok(transfers)
+    }
+}
+
+/// Value moved to a destination by a message, the destination is an address on another
+/// chain for IBC transfers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transfer {
+    pub destination: String,
+    pub amount: Vec<Coin>,
+}
+
+/// The transfers made by a MsgSend, MsgMultiSend, IBC MsgTransfer or the messages executed
+/// by a MsgExec. Other messages, including those that move value in module specific ways
+/// such as delegations, return no transfers.
+pub fn transfers_of(msg: &Msg) -> Result<Vec<Transfer>, CosmosGrpcError> {
+    let transfer =
+        |destination: String, amount: Vec<ProtoCoin>| -> Result<Transfer, CosmosGrpcError> {
+            let amount = amount
+                .into_iter()
+                .map(Coin::from_proto)
+                .collect::<Result<Vec<Coin>, String>>()
+                .map_err(CosmosGrpcError::BadInput)?;
+            Ok(Transfer {
+                destination,
+                amount,
+            })
+        };
+    match msg.type_url() {
+        MSG_SEND_TYPE_URL => {
+            let send: MsgSend = msg.unpack()?;
+            Ok(vec![transfer(send.to_address, send.amount)?])
+        }
+        MSG_MULTI_SEND_TYPE_URL => msg
+            .unpack::<MsgMultiSend>()?
+            .outputs
+            .into_iter()
+            .map(|output| transfer(output.address, output.coins))
+            .collect(),
+        MSG_TRANSFER_TYPE_URL => {
+            let send: MsgTransfer = msg.unpack()?;
+            Ok(vec![transfer(
+                send.receiver,
+                send.token.into_iter().collect(),
+            )?])
+        }
+        MSG_EXEC_TYPE_URL => {
+            let mut transfers = Vec::new();
+            for msg in msg.unpack::<MsgExec>()?.msgs {
+                transfers.extend(transfers_of(&msg.into())?);
+            }
+            Ok(transfers)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Decides whether a transaction may be signed, implement this for checks the built in
+/// policies do not cover. Returning an error vetoes the transaction with the provided reason.
+pub trait TxPolicy: Send + Sync {
+    fn check(&self, tx: &TxProposal) -> Result<(), String>;
+}
+
+impl<F> TxPolicy for F
+where
+    F: Fn(&TxProposal) -> Result<(), String> + Send + Sync,
+{
+    fn check(&self, tx: &TxProposal) -> Result<(), String> {
+        self(tx)
+    }
+}
+
+/// Limits the total amount per denom a single transaction transfers, denoms without a limit
+/// are not restricted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaxTransferPolicy {
+    limits: BTreeMap<String, Uint256>,
+}
+
+impl MaxTransferPolicy {
+    pub fn new() -> Self {
+        MaxTransferPolicy::default()
+    }
+
+    /// Limits the transfers in the denom of the coin to its amount per transaction
+    pub fn limit(mut self, limit: Coin) -> Self {
+        self.limits.insert(limit.denom, limit.amount);
+        self
+    }
+}
+
+impl TxPolicy for MaxTransferPolicy {
+    fn check(&self, tx: &TxProposal) -> Result<(), String> {
+        let mut totals: BTreeMap<String, Uint256> = BTreeMap::new();
+        for transfer in tx.transfers().map_err(|e| e.to_string())? {
+            for coin in transfer.amount {
+                let total = totals.entry(coin.denom).or_default();
+                *total = total.clone() + coin.amount;
+            }
+        }
+        for (denom, total) in totals {
+            if let Some(limit) = self.limits.get(&denom) {
+                if total > *limit {
+                    return Err(format!(
+                        "transfers {}{} but at most {}{} is allowed",
+                        total, denom, limit, denom
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Only allows transfers to the listed destinations, transactions without transfers are
+/// not restricted
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DestinationAllowlist {
+    destinations: HashSet<String>,
+}
+
+impl DestinationAllowlist {
+    pub fn new<I, S>(destinations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        DestinationAllowlist {
+            destinations: destinations.into_iter().map(|d| d.to_string()).collect(),
+        }
+    }
+}
+
+impl TxPolicy for DestinationAllowlist {
+    fn check(&self, tx: &TxProposal) -> Result<(), String> {
+        for transfer in tx.transfers().map_err(|e| e.to_string())? {
+            if !self.destinations.contains(&transfer.destination) {
+                return Err(format!(
+                    "{} is not an allowed destination",
+                    transfer.destination
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Restricts the message types a transaction may contain, messages executed through a
+/// MsgExec are not inspected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MsgTypePolicy {
+    /// only these type urls may be signed
+    Allow(HashSet<String>),
+    /// these type urls may not be signed
+    Deny(HashSet<String>),
+}
+
+impl MsgTypePolicy {
+    pub fn allow<I, S>(type_urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        MsgTypePolicy::Allow(type_urls.into_iter().map(|t| t.to_string()).collect())
+    }
+
+    pub fn deny<I, S>(type_urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: ToString,
+    {
+        MsgTypePolicy::Deny(type_urls.into_iter().map(|t| t.to_string()).collect())
+    }
+}
+
+impl TxPolicy for MsgTypePolicy {
+    fn check(&self, tx: &TxProposal) -> Result<(), String> {
+        for msg in tx.messages {
+            let allowed = match self {
+                MsgTypePolicy::Allow(types) => types.contains(msg.type_url()),
+                MsgTypePolicy::Deny(types) => !types.contains(msg.type_url()),
+            };
+            if !allowed {
+                return Err(format!("{} messages are not allowed", msg.type_url()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Contact {
+    /// Adds a policy every transaction signed through this Contact and its clones must pass,
+    /// see the module documentation. Policies are checked in the order they were added. The
+    /// returned Contact does not share its connection with clones of this one.
+    pub fn with_tx_policy(self, policy: impl TxPolicy + 'static) -> Self {
+        let mut inner = self.unshared();
+        inner.tx_policies.push(Arc::new(policy));
+        Contact {
+            inner: Arc::new(inner),
+        }
+    }
+
+    /// Returns PolicyViolation if any policy vetoes the transaction, called before signing
+    pub(crate) fn check_tx_policy(
+        &self,
+        signer: Address,
+        messages: &[Msg],
+        fee: &Fee,
+        memo: &str,
+    ) -> Result<(), CosmosGrpcError> {
+        let tx = TxProposal {
+            signer,
+            messages,
+            fee,
+            memo,
+        };
+        for policy in self.inner.tx_policies.iter() {
+            policy
+                .check(&tx)
+                .map_err(|reason| CosmosGrpcError::PolicyViolation { reason })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::authz::exec_msg;
+    use crate::PrivateKey;
+    use std::time::Duration;
+
+    fn send(to: &str, amount: &str) -> Msg {
+        let coin: Coin = amount.parse().unwrap();
+        Msg::new(
+            MSG_SEND_TYPE_URL,
+            MsgSend {
+                from_address: "cosmos1sender".to_string(),
+                to_address: to.to_string(),
+                amount: vec![coin.into()],
+            },
+        )
+    }
+
+    #[actix_rt::test]
+    async fn test_tx_policy() {
+        let contact = Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos")
+            .unwrap()
+            .with_tx_policy(MaxTransferPolicy::new().limit("1000uatom".parse().unwrap()))
+            .with_tx_policy(DestinationAllowlist::new(vec!["cosmos1treasury"]));
+        let key = PrivateKey::from_secret(b"tx policy test key");
+        let signer = key.to_address("cosmos").unwrap();
+        let fee = Fee {
+            amount: vec![],
+            gas_limit: 200_000,
+            granter: None,
+            payer: None,
+        };
+        let check = |messages: &[Msg]| contact.check_tx_policy(signer, messages, &fee, "");
+
+        check(&[send("cosmos1treasury", "600uatom")]).unwrap();
+        // the limit applies to the total of the transaction
+        let split = [
+            send("cosmos1treasury", "600uatom"),
+            send("cosmos1treasury", "600uatom"),
+        ];
+        match check(&split) {
+            Err(CosmosGrpcError::PolicyViolation { reason }) => {
+                assert!(reason.contains("1200uatom"))
+            }
+            other => panic!("Expected PolicyViolation, got {:?}", other),
+        }
+        // transfers hidden in a MsgExec are found
+        let exec = exec_msg(signer, vec![send("cosmos1thief", "1uatom")]);
+        assert!(check(&[exec]).is_err());
+        assert!(check(&[send("cosmos1treasury", "5000ufoo")]).is_ok());
+
+        let denied = contact
+            .clone()
+            .with_tx_policy(MsgTypePolicy::deny(vec![MSG_SEND_TYPE_URL]));
+        assert!(denied
+            .check_tx_policy(signer, &[send("cosmos1treasury", "1uatom")], &fee, "")
+            .is_err());
+        let memo_required = contact.with_tx_policy(|tx: &TxProposal| {
+            if tx.memo.is_empty() {
+                Err("a memo is required".to_string())
+            } else {
+                Ok(())
+            }
+        });
+        assert!(memo_required
+            .check_tx_policy(signer, &[], &fee, "")
+            .is_err());
+    }
+}
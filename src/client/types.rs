@@ -1,12 +1,14 @@
 use crate::address::Address;
 use cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount as ProtoBaseAccount;
 use serde::Deserialize;
+use std::time::Duration;
 use tendermint_proto::types::Block;
 
 /// This struct represents the status of a Cosmos chain, instead of just getting the
 /// latest block height we mandate that chain status is used, this allows callers to
 /// handle the possibility of a halted chain explicitly since essentially all requests
 /// about block height come with assumptions about the chains status
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ChainStatus {
     /// The chain is operating correctly and blocks are being produced
     Moving { block_height: u64 },
@@ -23,6 +25,7 @@ pub enum ChainStatus {
 /// we can either be syncing, waiting for the chain to start, or have the the
 /// actual latest block to the best of the nodes knowledge, which isn't at all
 /// a guarantee
+#[derive(Debug, Clone)]
 pub enum LatestBlock {
     /// The chain is operating correctly and blocks are being produced, this is
     /// the latest one this node has access to
@@ -36,6 +39,77 @@ pub enum LatestBlock {
     WaitingToStart,
 }
 
+/// Transport settings for the connection of a Contact, see Contact::with_connection_options.
+/// The defaults are those of tonic, which sends no keepalives. Load balancers in front of
+/// public endpoints often drop idle connections without closing them, enabling HTTP/2
+/// keepalives detects this so the Contact reconnects instead of a request hanging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConnectionOptions {
+    /// how often to send HTTP/2 keepalive pings
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// how long to wait for a keepalive ping to be acknowledged before closing the connection
+    pub keep_alive_timeout: Option<Duration>,
+    /// whether to send keepalive pings when there are no requests in flight
+    pub keep_alive_while_idle: bool,
+    /// the TCP keepalive interval set on the socket
+    pub tcp_keepalive: Option<Duration>,
+    /// disables Nagle's algorithm, lowering latency for small requests
+    pub tcp_nodelay: bool,
+    /// the HTTP/2 flow control windows in bytes, larger windows help large responses on high
+    /// latency links
+    pub initial_stream_window_size: Option<u32>,
+    pub initial_connection_window_size: Option<u32>,
+}
+
+/// TLS settings for https endpoints, see Contact::with_tls. The system root certificates
+/// are trusted in addition to the provided certificate authority.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TlsOptions {
+    /// the name the server certificate is checked against, defaults to the endpoint host
+    pub domain_name: Option<String>,
+    /// a PEM encoded certificate authority, for nodes using a private CA
+    pub ca_certificate_pem: Option<Vec<u8>>,
+}
+
+/// How a Contact replaces its connection, see Contact::with_reconnect_policy. The hostname
+/// of the node is resolved every time a connection is opened, limiting the age of connections
+/// lets a long running Contact follow a managed endpoint whose addresses rotate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// connections older than this are replaced before the next request, None keeps a
+    /// connection until it fails
+    pub max_connection_age: Option<Duration>,
+    /// the wait before reconnecting after a failed connection attempt, doubled after every
+    /// further failure up to max_backoff
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    /// Keeps connections until they fail and reconnects without waiting
+    fn default() -> Self {
+        ReconnectPolicy {
+            max_connection_age: None,
+            initial_backoff: Duration::from_secs(0),
+            max_backoff: Duration::from_secs(0),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// The wait before the next connection attempt after failures attempts in a row failed
+    pub fn backoff(&self, failures: u32) -> Duration {
+        if failures == 0 {
+            return Duration::from_secs(0);
+        }
+        let factor = 1u32 << (failures - 1).min(16);
+        self.initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
 /// This is a parsed and validated version of the Cosmos base account proto
 /// struct
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -59,4 +133,24 @@ impl From<ProtoBaseAccount> for BaseAccount {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let policy = ReconnectPolicy {
+            max_connection_age: None,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        };
+        assert_eq!(policy.backoff(0), Duration::from_secs(0));
+        assert_eq!(policy.backoff(1), Duration::from_millis(500));
+        assert_eq!(policy.backoff(3), Duration::from_secs(2));
+        assert_eq!(policy.backoff(7), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+        assert_eq!(
+            ReconnectPolicy::default().backoff(5),
+            Duration::from_secs(0)
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Contains utility functions for creating vesting accounts, the funds for a vesting
+//! account are sent from the creator and the destination account must not already exist.
+
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmos::vesting::v1beta1::MsgCreatePeriodicVestingAccount;
+use crate::proto::cosmos::vesting::v1beta1::MsgCreatePermanentLockedAccount;
+use crate::proto::cosmos::vesting::v1beta1::Period;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::MsgCreateVestingAccount;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+pub const MSG_CREATE_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreateVestingAccount";
+pub const MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreatePermanentLockedAccount";
+pub const MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount";
+
+/// Gas used by each vesting account creation message in a transaction
+
+/// A single vesting period, the amount vests once length has elapsed since the
+/// end of the previous period (or the start time for the first period)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VestingPeriod {
+    pub length: Duration,
+    pub amount: Vec<Coin>,
+}
+
+/// Checks that a vesting amount is not empty and contains no zero or repeated coins
+fn validate_amount(amount: &[Coin]) -> Result<(), CosmosGrpcError> {
+    if amount.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Vesting amount can not be empty".to_string(),
+        ));
+    }
+    let mut seen = Vec::new();
+    for coin in amount {
+        if coin.amount == 0u64.into() {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Vesting amount has a zero amount of {}",
+                coin.denom
+            )));
+        }
+        if seen.contains(&&coin.denom) {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Vesting amount repeats denom {}",
+                coin.denom
+            )));
+        }
+        seen.push(&coin.denom);
+    }
+    Ok(())
+}
+
+fn unix_seconds(time: SystemTime) -> Result<i64, CosmosGrpcError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => Ok(since_epoch.as_secs() as i64),
+        Err(_) => Err(CosmosGrpcError::BadInput(
+            "Vesting time is before the unix epoch".to_string(),
+        )),
+    }
+}
+
+fn to_proto_coins(coins: Vec<Coin>) -> Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> {
+    coins.into_iter().map(|c| c.into()).collect()
+}
+
+/// Builds a MsgCreateVestingAccount, the amount vests linearly until end_time or, if
+/// delayed is set, all at once at end_time
+pub fn create_vesting_account_msg(
+    from: Address,
+    to: Address,
+    amount: Vec<Coin>,
+    end_time: SystemTime,
+    delayed: bool,
+) -> Result<Msg, CosmosGrpcError> {
+    validate_amount(&amount)?;
+    if end_time <= SystemTime::now() {
+        return Err(CosmosGrpcError::BadInput(
+            "Vesting end time must be in the future".to_string(),
+        ));
+    }
+    let create = MsgCreateVestingAccount {
+        from_address: from.to_string(),
+        to_address: to.to_string(),
+        amount: to_proto_coins(amount),
+        end_time: unix_seconds(end_time)?,
+        delayed,
+    };
+    Ok(Msg::new(MSG_CREATE_VESTING_ACCOUNT_TYPE_URL, create))
+}
+
+/// Builds a MsgCreatePermanentLockedAccount, the amount can be delegated but never
+/// transferred. Added in Cosmos SDK v0.46.
+pub fn create_permanent_locked_account_msg(
+    from: Address,
+    to: Address,
+    amount: Vec<Coin>,
+) -> Result<Msg, CosmosGrpcError> {
+    validate_amount(&amount)?;
+    let create = MsgCreatePermanentLockedAccount {
+        from_address: from.to_string(),
+        to_address: to.to_string(),
+        amount: to_proto_coins(amount),
+    };
+    Ok(Msg::new(
+        MSG_CREATE_PERMANENT_LOCKED_ACCOUNT_TYPE_URL,
+        create,
+    ))
+}
+
+/// Builds a MsgCreatePeriodicVestingAccount, each period vests in order starting from
+/// start_time. Added in Cosmos SDK v0.46.
+pub fn create_periodic_vesting_account_msg(
+    from: Address,
+    to: Address,
+    start_time: SystemTime,
+    periods: Vec<VestingPeriod>,
+) -> Result<Msg, CosmosGrpcError> {
+    if periods.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "Periodic vesting requires at least one period".to_string(),
+        ));
+    }
+    let mut vesting_periods = Vec::new();
+    for period in periods {
+        if period.length.as_secs() == 0 {
+            return Err(CosmosGrpcError::BadInput(
+                "Vesting periods must be at least one second long".to_string(),
+            ));
+        }
+        validate_amount(&period.amount)?;
+        vesting_periods.push(Period {
+            length: period.length.as_secs() as i64,
+            amount: to_proto_coins(period.amount),
+        });
+    }
+    let create = MsgCreatePeriodicVestingAccount {
+        from_address: from.to_string(),
+        to_address: to.to_string(),
+        start_time: unix_seconds(start_time)?,
+        vesting_periods,
+    };
+    Ok(Msg::new(
+        MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL,
+        create,
+    ))
+}
+
+impl Contact {
+    /// Creates many continuous or delayed vesting accounts funded by the provided private key in
+    /// a single transaction, each entry is the destination, amount and vesting end time.
+    pub async fn create_vesting_accounts(
+        &self,
+        accounts: Vec<(Address, Vec<Coin>, SystemTime)>,
+        delayed: bool,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let mut msgs = Vec::new();
+        for (to, amount, end_time) in accounts {
+            msgs.push(create_vesting_account_msg(
+                our_address,
+                to,
+                amount,
+                end_time,
+                delayed,
+            )?);
+        }
+        let fee = self.default_fee(Some(fee), &msgs);
+        self.send_message(&msgs, None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(amount: u64) -> Coin {
+        Coin {
+            amount: amount.into(),
+            denom: "uatom".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_vesting_validation() {
+        let a = Address::from_bytes([0; 20], "cosmos").unwrap();
+        let b = Address::from_bytes([1; 20], "cosmos").unwrap();
+        let future = SystemTime::now() + Duration::from_secs(3600);
+
+        assert!(create_vesting_account_msg(a, b, vec![coin(1)], future, false).is_ok());
+        assert!(create_vesting_account_msg(a, b, vec![coin(0)], future, false).is_err());
+        assert!(create_vesting_account_msg(a, b, vec![], future, false).is_err());
+        assert!(create_vesting_account_msg(a, b, vec![coin(1)], UNIX_EPOCH, false).is_err());
+        assert!(create_vesting_account_msg(a, b, vec![coin(1), coin(2)], future, false).is_err());
+
+        let period = VestingPeriod {
+            length: Duration::from_secs(60),
+            amount: vec![coin(1)],
+        };
+        let empty_period = VestingPeriod {
+            length: Duration::from_millis(10),
+            amount: vec![coin(1)],
+        };
+        assert!(create_periodic_vesting_account_msg(a, b, future, vec![period.clone()]).is_ok());
+        assert!(create_periodic_vesting_account_msg(a, b, future, vec![]).is_err());
+        assert!(
+            create_periodic_vesting_account_msg(a, b, future, vec![period, empty_period]).is_err()
+        );
+    }
+}
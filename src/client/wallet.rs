@@ -0,0 +1,711 @@
+//! A set of labeled keys for services that split operational roles across accounts, for
+//! example one key paying out rewards while another votes on proposals. Each key has its
+//! own sequencer so transactions from different keys, or several tasks sharing one key,
+//! can be sent concurrently without sequence mismatches. A Signer remembers the transactions
+//! it broadcast until they are in a block, so if one is dropped from the mempool and the
+//! ones after it are stuck behind the gap they can be broadcast again.
+//!
+//! ```ignore
+//! let mut wallet = Wallet::new(contact);
+//! wallet.add_key("payouts", payouts_key)?;
+//! wallet.add_key("votes", votes_key)?;
+//! let payouts = wallet.signer("payouts")?;
+//! payouts.send_message(&[msg], None, fee, Some(timeout)).await?;
+//! ```
+
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::cosmos_client::CosmosClient;
+use crate::client::Contact;
+use crate::client::MEMO;
+use crate::error::CosmosGrpcError;
+use crate::private_key::MessageArgs;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::utils::tx_hash;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
+use futures::lock::Mutex;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The SDK error code of a transaction that is already in the mempool of the node
+const SDK_TX_IN_MEMPOOL_CACHE: u32 = 19;
+
+/// A transaction a Signer broadcast that is not yet known to be in a block
+#[derive(Clone)]
+struct PendingTx {
+    messages: Vec<Msg>,
+    memo: String,
+    fee: Fee,
+    tx_bytes: Vec<u8>,
+    broadcast_at: Instant,
+}
+
+#[derive(Default)]
+struct Sequencer {
+    /// the sequence of the next transaction if it is known locally
+    next: Option<u64>,
+    /// the transactions accepted into the mempool by sequence, dropped once the chain
+    /// sequence passes them
+    pending: BTreeMap<u64, PendingTx>,
+}
+
+impl Sequencer {
+    /// Forgets the pending transactions the chain has included
+    fn included(&mut self, chain_sequence: u64) {
+        self.pending = self.pending.split_off(&chain_sequence);
+    }
+}
+
+/// A sequence the chain is waiting for that the mempool may not hold a transaction for, so
+/// every pending transaction after it is stuck
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceGap {
+    /// the sequence of the account on chain, the first sequence not in a block
+    pub chain_sequence: u64,
+    /// the sequence the next transaction of the Signer would use
+    pub next_sequence: u64,
+    /// how long ago the transaction with the chain sequence was broadcast, None if the
+    /// Signer did not broadcast one
+    pub stalled_for: Option<Duration>,
+}
+
+/// The transactions broadcast to fill a sequence gap, by hash. Pending transactions are
+/// never signed again, so none of their messages can be executed twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GapRepair {
+    /// broadcast again with their original signature, the node ignores these if it still
+    /// holds them
+    pub rebroadcast: Vec<String>,
+    /// no-op sends of the Signer to itself, filling sequences the Signer had no pending
+    /// transaction for or whose transaction was dropped
+    pub filled: Vec<String>,
+    /// pending transactions the node rejected when broadcast again, for example because
+    /// their timeout height passed. Their messages were not executed and their sequence
+    /// was filled, they must be sent again.
+    pub dropped: Vec<String>,
+}
+
+/// A key of a Wallet, cheap to clone, clones share the sequencer of the key
+#[derive(Clone)]
+pub struct Signer {
+    label: String,
+    private_key: PrivateKey,
+    address: Address,
+    contact: Contact,
+    /// broadcasts hold the lock from signing until the node accepts the transaction into
+    /// its mempool
+    sequencer: Arc<Mutex<Sequencer>>,
+}
+
+impl Signer {
+    fn new(
+        label: String,
+        private_key: PrivateKey,
+        contact: Contact,
+    ) -> Result<Self, CosmosGrpcError> {
+        let address = private_key.to_address(&contact.inner.chain_prefix)?;
+        Ok(Signer {
+            label,
+            private_key,
+            address,
+            contact,
+            sequencer: Arc::new(Mutex::new(Sequencer::default())),
+        })
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    pub fn private_key(&self) -> PrivateKey {
+        self.private_key
+    }
+
+    /// The sequence the next transaction will use if it is known locally, None if it will
+    /// be queried from the chain
+    pub async fn next_sequence(&self) -> Option<u64> {
+        self.sequencer.lock().await.next
+    }
+
+    /// The sequences of the transactions broadcast by this Signer that were not in a block
+    /// when the chain was last queried
+    pub async fn pending_sequences(&self) -> Vec<u64> {
+        self.sequencer
+            .lock()
+            .await
+            .pending
+            .keys()
+            .copied()
+            .collect()
+    }
+
+    /// Forgets the locally tracked sequence and pending transactions so the next
+    /// transaction queries the sequence from the chain, use this after sending transactions
+    /// with the same key outside of the Signer
+    pub async fn reset_sequence(&self) {
+        *self.sequencer.lock().await = Sequencer::default();
+    }
+
+    /// Sends one or more messages like Contact::send_message(), transactions sent through
+    /// the same Signer, or its clones, are given consecutive sequences so they do not need
+    /// to wait for each other to enter a block. If the node rejects the transaction because
+    /// an earlier one left the mempool the gap is repaired, see repair_sequence_gap, and the
+    /// transaction is sent again after the repaired ones.
+    pub async fn send_message(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        if messages.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "Can not send a transaction with no messages".to_string(),
+            ));
+        }
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        self.contact
+            .check_tx_policy(self.address, messages, &fee, &memo)?;
+        self.contact
+            .check_fee_grant(self.address, messages, &fee)
+            .await?;
+        let response = {
+            let mut sequencer = self.sequencer.lock().await;
+            let mut args = self
+                .contact
+                .get_message_args(self.address, fee.clone())
+                .await?;
+            sequencer.included(args.sequence);
+            // the chain only knows about transactions that are in a block, ours may
+            // still be in the mempool
+            let after_pending = sequencer.pending.keys().next_back().map(|s| s + 1);
+            for next in sequencer.next.iter().chain(after_pending.iter()) {
+                args.sequence = std::cmp::max(args.sequence, *next);
+            }
+            let mut result = self.broadcast(messages, args.clone(), &memo).await;
+            // an earlier transaction left the mempool and the node wants its sequence again
+            let gap = match &result {
+                Err(e) => e
+                    .expected_sequence()
+                    .filter(|expected| *expected < args.sequence),
+                Ok(_) => None,
+            };
+            if let Some(expected) = gap {
+                warn!(
+                    "Sequence gap for {} at {}, repairing before sending {}",
+                    self.label, expected, args.sequence
+                );
+                self.repair(&mut sequencer, expected).await?;
+                args.sequence = sequencer.next.unwrap_or(expected);
+                result = self.broadcast(messages, args.clone(), &memo).await;
+            }
+            match &result {
+                // a dry run does not use the sequence
+                Ok(_) if self.contact.is_dry_run() => {}
+                Ok((tx_bytes, _)) => {
+                    sequencer.next = Some(args.sequence + 1);
+                    sequencer.pending.insert(
+                        args.sequence,
+                        PendingTx {
+                            messages: messages.to_vec(),
+                            memo: memo.clone(),
+                            fee,
+                            tx_bytes: tx_bytes.clone(),
+                            broadcast_at: Instant::now(),
+                        },
+                    );
+                }
+                // the mempool may or may not hold the transaction, query the chain again
+                Err(_) => sequencer.next = None,
+            }
+            result?.1
+        };
+        trace!("broadcasted! with response {:?}", response);
+        match wait_timeout {
+            Some(time) => self.contact.wait_for_tx(response, time).await,
+            None => Ok(response),
+        }
+    }
+
+    /// Checks for a sequence gap, a transaction this Signer broadcast with the current chain
+    /// sequence that has been pending for longer than stall, or no such transaction while
+    /// later ones are pending. Either means the pending transactions may never be included.
+    pub async fn detect_sequence_gap(
+        &self,
+        stall: Duration,
+    ) -> Result<Option<SequenceGap>, CosmosGrpcError> {
+        let mut sequencer = self.sequencer.lock().await;
+        detect_gap(&self.contact, self.address, &mut sequencer, stall).await
+    }
+
+    /// Fills a sequence gap by broadcasting every pending transaction from the chain sequence
+    /// on again, in order, with its original signature. Sequences this Signer has no pending
+    /// transaction for, and those of transactions the node rejects, are filled with a no-op
+    /// send to the Signer itself paying the fee of the next pending transaction. A pending
+    /// transaction is never signed again, a copy with another sequence could be included
+    /// next to the original.
+    pub async fn repair_sequence_gap(&self) -> Result<GapRepair, CosmosGrpcError> {
+        let mut sequencer = self.sequencer.lock().await;
+        let chain_sequence = self.contact.get_account_info(self.address).await?.sequence;
+        sequencer.included(chain_sequence);
+        self.repair(&mut sequencer, chain_sequence).await
+    }
+
+    /// Broadcasts the pending transactions from sequence on again, see repair_sequence_gap
+    async fn repair(
+        &self,
+        sequencer: &mut Sequencer,
+        sequence: u64,
+    ) -> Result<GapRepair, CosmosGrpcError> {
+        repair_gap(
+            &self.contact,
+            &self.private_key,
+            self.address,
+            sequencer,
+            sequence,
+        )
+        .await
+    }
+
+    /// Signs and broadcasts a transaction, returning the signed bytes with the response
+    async fn broadcast(
+        &self,
+        messages: &[Msg],
+        args: MessageArgs,
+        memo: &str,
+    ) -> Result<(Vec<u8>, TxResponse), CosmosGrpcError> {
+        let msg_bytes = self.private_key.sign_std_msg(messages, args, memo)?;
+        self.contact.check_tx_limits(&msg_bytes).await?;
+        let response = self
+            .contact
+            .send_transaction(msg_bytes.clone(), BroadcastMode::Sync)
+            .await?;
+        Ok((msg_bytes, response))
+    }
+}
+
+/// Queries the chain sequence and checks the pending transactions for a gap, see
+/// Signer::detect_sequence_gap
+async fn detect_gap<C: CosmosClient + ?Sized>(
+    client: &C,
+    address: Address,
+    sequencer: &mut Sequencer,
+    stall: Duration,
+) -> Result<Option<SequenceGap>, CosmosGrpcError> {
+    let chain_sequence = client.get_account_info(address).await?.sequence;
+    sequencer.included(chain_sequence);
+    let next_sequence = match sequencer.pending.keys().next_back() {
+        Some(last) => last + 1,
+        None => return Ok(None),
+    };
+    let stalled_for = match sequencer.pending.get(&chain_sequence) {
+        Some(tx) if tx.broadcast_at.elapsed() < stall => return Ok(None),
+        Some(tx) => Some(tx.broadcast_at.elapsed()),
+        None => None,
+    };
+    Ok(Some(SequenceGap {
+        chain_sequence,
+        next_sequence,
+        stalled_for,
+    }))
+}
+
+/// A no-op transaction of the provided sequence, sending the smallest amount of the fee
+/// denom from the signer to itself
+async fn fill_sequence<C: CosmosClient + ?Sized>(
+    client: &C,
+    private_key: &PrivateKey,
+    address: Address,
+    fee: &Fee,
+    sequence: u64,
+) -> Result<PendingTx, CosmosGrpcError> {
+    let denom = match fee.amount.first() {
+        Some(coin) => coin.denom.clone(),
+        None => {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Can not fill sequence {} without a fee denom",
+                sequence
+            )))
+        }
+    };
+    let own_address = address.to_bech32(client.get_prefix()).unwrap();
+    let amount = Coin {
+        amount: 1u64.into(),
+        denom,
+    };
+    let send = MsgSend {
+        amount: vec![amount.into()],
+        from_address: own_address.clone(),
+        to_address: own_address,
+    };
+    let messages = vec![Msg::new(MSG_SEND_TYPE_URL, send)];
+    let mut args = client.get_message_args(address, fee.clone()).await?;
+    args.sequence = sequence;
+    let memo = MEMO.to_string();
+    let tx_bytes = private_key.sign_std_msg(&messages, args, &memo)?;
+    client
+        .send_transaction(tx_bytes.clone(), BroadcastMode::Sync)
+        .await?;
+    Ok(PendingTx {
+        messages,
+        memo,
+        fee: fee.clone(),
+        tx_bytes,
+        broadcast_at: Instant::now(),
+    })
+}
+
+/// Broadcasts the pending transactions from sequence on again, see
+/// Signer::repair_sequence_gap
+async fn repair_gap<C: CosmosClient + ?Sized>(
+    client: &C,
+    private_key: &PrivateKey,
+    address: Address,
+    sequencer: &mut Sequencer,
+    sequence: u64,
+) -> Result<GapRepair, CosmosGrpcError> {
+    let stuck = sequencer.pending.split_off(&sequence);
+    // the sequence is unknown if the repair stops early
+    sequencer.next = None;
+    let mut repair = GapRepair::default();
+    let mut next = sequence;
+    for (signed_sequence, mut tx) in stuck {
+        // sequences before this transaction that the Signer has nothing pending for
+        while next < signed_sequence {
+            let filler = fill_sequence(client, private_key, address, &tx.fee, next).await?;
+            repair.filled.push(tx_hash(&filler.tx_bytes));
+            sequencer.pending.insert(next, filler);
+            next += 1;
+        }
+        let response = client
+            .send_transaction(tx.tx_bytes.clone(), BroadcastMode::Sync)
+            .await;
+        // a transaction that is already in the mempool cache is still pending
+        let response = match response {
+            Err(CosmosGrpcError::TransactionFailed { tx, .. })
+                if tx.code == SDK_TX_IN_MEMPOOL_CACHE =>
+            {
+                Ok(tx)
+            }
+            response => response,
+        };
+        match response {
+            Ok(_) => {
+                repair.rebroadcast.push(tx_hash(&tx.tx_bytes));
+                tx.broadcast_at = Instant::now();
+                sequencer.pending.insert(next, tx);
+            }
+            // the node holds another transaction for this sequence or expects a different
+            // one, filling it could replace a transaction that is still valid
+            Err(e) if e.expected_sequence().is_some() => return Err(e),
+            Err(CosmosGrpcError::TransactionFailed { .. }) => {
+                warn!(
+                    "Pending tx {} at {} was rejected, filling its sequence",
+                    tx_hash(&tx.tx_bytes),
+                    next
+                );
+                let filler = fill_sequence(client, private_key, address, &tx.fee, next).await?;
+                repair.dropped.push(tx_hash(&tx.tx_bytes));
+                repair.filled.push(tx_hash(&filler.tx_bytes));
+                sequencer.pending.insert(next, filler);
+            }
+            Err(e) => return Err(e),
+        }
+        next += 1;
+    }
+    sequencer.next = Some(next);
+    Ok(repair)
+}
+
+/// Labeled keys sharing one Contact, see the module documentation
+#[derive(Clone)]
+pub struct Wallet {
+    contact: Contact,
+    signers: BTreeMap<String, Signer>,
+}
+
+impl Wallet {
+    pub fn new(contact: Contact) -> Self {
+        Wallet {
+            contact,
+            signers: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a key under a label, fails if the label is already in use
+    pub fn add_key(&mut self, label: &str, private_key: PrivateKey) -> Result<(), CosmosGrpcError> {
+        if self.signers.contains_key(label) {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Wallet already has a key labeled {}",
+                label
+            )));
+        }
+        let signer = Signer::new(label.to_string(), private_key, self.contact.clone())?;
+        self.signers.insert(label.to_string(), signer);
+        Ok(())
+    }
+
+    /// Replaces the key under a label with a new one that starts with a fresh sequencer,
+    /// returning the previous key. Clones of the old Signer keep using the old key.
+    pub fn rotate_key(
+        &mut self,
+        label: &str,
+        private_key: PrivateKey,
+    ) -> Result<PrivateKey, CosmosGrpcError> {
+        let old = self.signer(label)?.private_key;
+        let signer = Signer::new(label.to_string(), private_key, self.contact.clone())?;
+        self.signers.insert(label.to_string(), signer);
+        Ok(old)
+    }
+
+    /// Removes the key under a label, returning it
+    pub fn remove_key(&mut self, label: &str) -> Option<PrivateKey> {
+        self.signers.remove(label).map(|signer| signer.private_key)
+    }
+
+    /// The Signer of the key under a label
+    pub fn signer(&self, label: &str) -> Result<&Signer, CosmosGrpcError> {
+        self.signers.get(label).ok_or_else(|| no_key(label))
+    }
+
+    /// The labels of every key in alphabetical order
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.signers.keys().map(|label| label.as_str())
+    }
+
+    /// Finds the Signer of the key with the provided address
+    pub fn signer_for(&self, address: Address) -> Option<&Signer> {
+        self.signers
+            .values()
+            .find(|signer| signer.address == address)
+    }
+}
+
+fn no_key(label: &str) -> CosmosGrpcError {
+    CosmosGrpcError::BadInput(format!("Wallet has no key labeled {}", label))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::simulated_chain::SimulatedChain;
+
+    fn fee() -> Fee {
+        Fee {
+            amount: vec!["100uatom".parse().unwrap()],
+            gas_limit: 200_000,
+            granter: None,
+            payer: None,
+        }
+    }
+
+    /// A send of 10uatom signed with the provided sequence and broadcast nowhere
+    async fn signed_send(
+        chain: &SimulatedChain,
+        key: &PrivateKey,
+        receiver: Address,
+        sequence: u64,
+        timeout_height: Option<u64>,
+    ) -> PendingTx {
+        let address = key.to_address("cosmos").unwrap();
+        let send = MsgSend {
+            amount: vec!["10uatom".parse::<Coin>().unwrap().into()],
+            from_address: address.to_bech32("cosmos").unwrap(),
+            to_address: receiver.to_bech32("cosmos").unwrap(),
+        };
+        let messages = vec![Msg::new(MSG_SEND_TYPE_URL, send)];
+        let mut args = chain.get_message_args(address, fee()).await.unwrap();
+        args.sequence = sequence;
+        if let Some(timeout_height) = timeout_height {
+            args.timeout_height = timeout_height;
+        }
+        let tx_bytes = key.sign_std_msg(&messages, args, "").unwrap();
+        PendingTx {
+            messages,
+            memo: String::new(),
+            fee: fee(),
+            tx_bytes,
+            broadcast_at: Instant::now(),
+        }
+    }
+
+    fn setup() -> (SimulatedChain, PrivateKey, Address, Address) {
+        let key = PrivateKey::from_secret(b"wallet gap sender");
+        let address = key.to_address("cosmos").unwrap();
+        let receiver = PrivateKey::from_secret(b"wallet gap receiver")
+            .to_address("cosmos")
+            .unwrap();
+        let chain = SimulatedChain::new("cosmos", "simulated").with_manual_blocks();
+        chain
+            .fund(address, vec!["10000uatom".parse().unwrap()])
+            .unwrap();
+        (chain, key, address, receiver)
+    }
+
+    #[actix_rt::test]
+    async fn test_repair_foreign_gap() {
+        let (chain, key, address, receiver) = setup();
+        // sequence 0 was used outside of the Signer and never reached the mempool
+        let tx = signed_send(&chain, &key, receiver, 1, None).await;
+        let original = tx_hash(&tx.tx_bytes);
+        let mut sequencer = Sequencer::default();
+        sequencer.pending.insert(1, tx);
+
+        let gap = detect_gap(&chain, address, &mut sequencer, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(
+            gap,
+            Some(SequenceGap {
+                chain_sequence: 0,
+                next_sequence: 2,
+                stalled_for: None,
+            })
+        );
+
+        let repair = repair_gap(&chain, &key, address, &mut sequencer, 0)
+            .await
+            .unwrap();
+        assert_eq!(repair.rebroadcast, vec![original.clone()]);
+        assert_eq!(repair.filled.len(), 1);
+        assert!(repair.dropped.is_empty());
+        assert_eq!(sequencer.next, Some(2));
+
+        let block = chain.produce_block();
+        assert_eq!(
+            block.iter().map(|tx| tx.txhash.clone()).collect::<Vec<_>>(),
+            vec![repair.filled[0].clone(), original]
+        );
+        assert!(block.iter().all(|tx| tx.code == 0));
+        // two fees and the 10uatom send, the no-op returns its coin to the sender
+        assert_eq!(
+            chain.get_balances(address).await.unwrap(),
+            vec!["9790uatom".parse().unwrap()]
+        );
+        let gap = detect_gap(&chain, address, &mut sequencer, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(gap, None);
+    }
+
+    #[actix_rt::test]
+    async fn test_repair_never_resigns() {
+        let (chain, key, address, receiver) = setup();
+        // the first transaction can no longer be included, its timeout height has passed
+        let expired = signed_send(&chain, &key, receiver, 0, Some(chain.height())).await;
+        let stuck = signed_send(&chain, &key, receiver, 1, None).await;
+        let (expired_hash, stuck_hash) = (tx_hash(&expired.tx_bytes), tx_hash(&stuck.tx_bytes));
+        let mut sequencer = Sequencer::default();
+        sequencer.pending.insert(0, expired);
+        sequencer.pending.insert(1, stuck);
+
+        let gap = detect_gap(&chain, address, &mut sequencer, Duration::from_secs(0))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(gap.chain_sequence, 0);
+        assert!(gap.stalled_for.is_some());
+
+        let repair = repair_gap(&chain, &key, address, &mut sequencer, 0)
+            .await
+            .unwrap();
+        assert_eq!(repair.dropped, vec![expired_hash]);
+        assert_eq!(repair.rebroadcast, vec![stuck_hash.clone()]);
+        assert_eq!(repair.filled.len(), 1);
+
+        let block = chain.produce_block();
+        assert_eq!(block.len(), 2);
+        assert_eq!(block[1].txhash, stuck_hash);
+        // only the transaction that was not dropped sent coins
+        assert_eq!(
+            chain
+                .get_balance(receiver, "uatom".to_string())
+                .await
+                .unwrap(),
+            Some("10uatom".parse().unwrap())
+        );
+
+        // a foreign transaction in the mempool holds the sequence, nothing is filled
+        let foreign = signed_send(&chain, &key, receiver, 2, None).await;
+        chain
+            .send_transaction(foreign.tx_bytes, BroadcastMode::Sync)
+            .await
+            .unwrap();
+        let mut sequencer = Sequencer::default();
+        let pending = signed_send(&chain, &key, address, 2, None).await;
+        sequencer.pending.insert(2, pending);
+        assert!(repair_gap(&chain, &key, address, &mut sequencer, 2)
+            .await
+            .is_err());
+        assert_eq!(chain.mempool_size(), 1);
+    }
+
+    #[test]
+    fn test_sequencer_included() {
+        let tx = PendingTx {
+            messages: Vec::new(),
+            memo: String::new(),
+            fee: Fee::default(),
+            tx_bytes: Vec::new(),
+            broadcast_at: Instant::now(),
+        };
+        let mut sequencer = Sequencer::default();
+        for sequence in 4..8 {
+            sequencer.pending.insert(sequence, tx.clone());
+        }
+        sequencer.included(6);
+        assert_eq!(
+            sequencer.pending.keys().copied().collect::<Vec<_>>(),
+            vec![6, 7]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_wallet_labels() {
+        let contact =
+            Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos").unwrap();
+        let payouts = PrivateKey::from_secret(b"payouts");
+        let votes = PrivateKey::from_secret(b"votes");
+        let mut wallet = Wallet::new(contact);
+        wallet.add_key("payouts", payouts).unwrap();
+        wallet.add_key("votes", votes).unwrap();
+        assert!(wallet.add_key("votes", payouts).is_err());
+        assert_eq!(
+            wallet.labels().collect::<Vec<_>>(),
+            vec!["payouts", "votes"]
+        );
+
+        let signer = wallet.signer("payouts").unwrap();
+        assert_eq!(signer.label(), "payouts");
+        assert_eq!(signer.address(), payouts.to_address("cosmos").unwrap());
+        assert_eq!(signer.next_sequence().await, None);
+        assert!(signer.pending_sequences().await.is_empty());
+        assert!(wallet.signer("missing").is_err());
+        assert_eq!(
+            wallet
+                .signer_for(votes.to_address("cosmos").unwrap())
+                .unwrap()
+                .label(),
+            "votes"
+        );
+
+        let rotated = PrivateKey::from_secret(b"payouts2");
+        assert_eq!(wallet.rotate_key("payouts", rotated).unwrap(), payouts);
+        assert_eq!(wallet.signer("payouts").unwrap().private_key(), rotated);
+        assert!(wallet.rotate_key("missing", rotated).is_err());
+        assert!(wallet.signer("missing").is_err());
+        assert_eq!(wallet.remove_key("votes"), Some(votes));
+        assert_eq!(wallet.labels().count(), 1);
+    }
+}
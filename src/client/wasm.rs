@@ -0,0 +1,351 @@
+//! Contains utility functions for deploying and interacting with CosmWasm smart contracts.
+//! Only compiled if the cosmwasm feature is enabled. Contract addresses are 32 bytes and so
+//! can not be represented by Address, they are passed as bech32 strings instead.
+
+use crate::client::events::tx_events;
+use crate::client::paginate::paginate;
+use crate::client::paginate::DEFAULT_PAGE_SIZE;
+use crate::client::responses::decode_msg_response;
+use crate::client::Contact;
+use crate::error::CosmosGrpcError;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::proto::cosmwasm::wasm::v1::MsgClearAdmin;
+use crate::proto::cosmwasm::wasm::v1::MsgExecuteContract;
+use crate::proto::cosmwasm::wasm::v1::MsgExecuteContractResponse;
+use crate::proto::cosmwasm::wasm::v1::MsgInstantiateContract;
+use crate::proto::cosmwasm::wasm::v1::MsgInstantiateContract2;
+use crate::proto::cosmwasm::wasm::v1::MsgInstantiateContractResponse;
+use crate::proto::cosmwasm::wasm::v1::MsgMigrateContract;
+use crate::proto::cosmwasm::wasm::v1::MsgStoreCode;
+use crate::proto::cosmwasm::wasm::v1::MsgStoreCodeResponse;
+use crate::proto::cosmwasm::wasm::v1::MsgUpdateAdmin;
+use crate::proto::cosmwasm::wasm::v1::QueryContractsByCodeRequest;
+use crate::proto::cosmwasm::wasm::v1::QueryContractsByCodeResponse;
+use crate::Address;
+use crate::Coin;
+use crate::Msg;
+use crate::PrivateKey;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::Stream;
+use serde::Serialize;
+use std::io::Write;
+use std::time::Duration;
+
+pub const MSG_STORE_CODE_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgStoreCode";
+pub const MSG_INSTANTIATE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgInstantiateContract";
+pub const MSG_INSTANTIATE_CONTRACT2_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgInstantiateContract2";
+pub const MSG_EXECUTE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgExecuteContract";
+pub const MSG_MIGRATE_CONTRACT_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgMigrateContract";
+pub const MSG_UPDATE_ADMIN_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgUpdateAdmin";
+pub const MSG_CLEAR_ADMIN_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgClearAdmin";
+
+const WASM_MAGIC: [u8; 4] = [0x00, 0x61, 0x73, 0x6d];
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Gas used by a MsgStoreCode in addition to the per byte costs, wasmd charges for both
+/// compiling the uncompressed code and storing the compressed code
+const STORE_CODE_BASE_GAS: u64 = 500_000;
+const STORE_CODE_GAS_PER_BYTE: u64 = 40;
+
+/// Compresses raw wasm bytecode with gzip, code that is already compressed is returned
+/// unmodified and anything that is neither wasm nor gzip is rejected
+pub fn gzip_wasm(code: Vec<u8>) -> Result<Vec<u8>, CosmosGrpcError> {
+    if code.starts_with(&GZIP_MAGIC) {
+        Ok(code)
+    } else if code.starts_with(&WASM_MAGIC) {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder
+            .write_all(&code)
+            .and_then(|_| encoder.finish())
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Failed to compress wasm {}", e)))
+    } else {
+        Err(CosmosGrpcError::BadInput(
+            "Code is not wasm or gzip compressed wasm".to_string(),
+        ))
+    }
+}
+
+fn json_msg(msg: &impl Serialize) -> Result<Vec<u8>, CosmosGrpcError> {
+    serde_json::to_vec(msg).map_err(|e| CosmosGrpcError::BadInput(e.to_string()))
+}
+
+/// Funds must be sorted by denom to pass validation
+fn sorted_funds(mut funds: Vec<Coin>) -> Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin> {
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+    funds.into_iter().map(|c| c.into()).collect()
+}
+
+/// Builds a MsgStoreCode, raw wasm is gzip compressed before being placed in the message
+pub fn store_code_msg(sender: Address, code: Vec<u8>) -> Result<Msg, CosmosGrpcError> {
+    let store = MsgStoreCode {
+        sender: sender.to_string(),
+        wasm_byte_code: gzip_wasm(code)?,
+        instantiate_permission: None,
+    };
+    Ok(Msg::new(MSG_STORE_CODE_TYPE_URL, store))
+}
+
+/// Builds a MsgInstantiateContract, msg is serialized to JSON and passed to the contract
+pub fn instantiate_contract_msg(
+    sender: Address,
+    admin: Option<Address>,
+    code_id: u64,
+    label: impl Into<String>,
+    msg: &impl Serialize,
+    funds: Vec<Coin>,
+) -> Result<Msg, CosmosGrpcError> {
+    let instantiate = MsgInstantiateContract {
+        sender: sender.to_string(),
+        admin: admin.map(|a| a.to_string()).unwrap_or_default(),
+        code_id,
+        label: label.into(),
+        msg: json_msg(msg)?,
+        funds: sorted_funds(funds),
+    };
+    Ok(Msg::new(MSG_INSTANTIATE_CONTRACT_TYPE_URL, instantiate))
+}
+
+/// Builds a MsgInstantiateContract2, which instantiates the contract at an address
+/// determined by the code checksum, sender and salt rather than the instance count
+#[allow(clippy::too_many_arguments)]
+pub fn instantiate_contract2_msg(
+    sender: Address,
+    admin: Option<Address>,
+    code_id: u64,
+    label: impl Into<String>,
+    msg: &impl Serialize,
+    funds: Vec<Coin>,
+    salt: Vec<u8>,
+    fix_msg: bool,
+) -> Result<Msg, CosmosGrpcError> {
+    if salt.is_empty() || salt.len() > 64 {
+        return Err(CosmosGrpcError::BadInput(
+            "Instantiate2 salt must be between 1 and 64 bytes".to_string(),
+        ));
+    }
+    let instantiate = MsgInstantiateContract2 {
+        sender: sender.to_string(),
+        admin: admin.map(|a| a.to_string()).unwrap_or_default(),
+        code_id,
+        label: label.into(),
+        msg: json_msg(msg)?,
+        funds: sorted_funds(funds),
+        salt,
+        fix_msg,
+    };
+    Ok(Msg::new(MSG_INSTANTIATE_CONTRACT2_TYPE_URL, instantiate))
+}
+
+/// Builds a MsgExecuteContract, msg is serialized to JSON and passed to the contract
+pub fn execute_contract_msg(
+    sender: Address,
+    contract: impl Into<String>,
+    msg: &impl Serialize,
+    funds: Vec<Coin>,
+) -> Result<Msg, CosmosGrpcError> {
+    let execute = MsgExecuteContract {
+        sender: sender.to_string(),
+        contract: contract.into(),
+        msg: json_msg(msg)?,
+        funds: sorted_funds(funds),
+    };
+    Ok(Msg::new(MSG_EXECUTE_CONTRACT_TYPE_URL, execute))
+}
+
+/// Builds a MsgMigrateContract, the sender must be the contract admin
+pub fn migrate_contract_msg(
+    sender: Address,
+    contract: impl Into<String>,
+    code_id: u64,
+    msg: &impl Serialize,
+) -> Result<Msg, CosmosGrpcError> {
+    let migrate = MsgMigrateContract {
+        sender: sender.to_string(),
+        contract: contract.into(),
+        code_id,
+        msg: json_msg(msg)?,
+    };
+    Ok(Msg::new(MSG_MIGRATE_CONTRACT_TYPE_URL, migrate))
+}
+
+/// Builds a MsgUpdateAdmin, the sender must be the current contract admin
+pub fn update_admin_msg(sender: Address, contract: impl Into<String>, new_admin: Address) -> Msg {
+    let update = MsgUpdateAdmin {
+        sender: sender.to_string(),
+        new_admin: new_admin.to_string(),
+        contract: contract.into(),
+    };
+    Msg::new(MSG_UPDATE_ADMIN_TYPE_URL, update)
+}
+
+/// Builds a MsgClearAdmin, after which the contract can no longer be migrated
+pub fn clear_admin_msg(sender: Address, contract: impl Into<String>) -> Msg {
+    let clear = MsgClearAdmin {
+        sender: sender.to_string(),
+        contract: contract.into(),
+    };
+    Msg::new(MSG_CLEAR_ADMIN_TYPE_URL, clear)
+}
+
+/// Finds the value of the provided event attribute in a transaction
+fn event_attribute(response: &TxResponse, event_type: &str, key: &str) -> Option<String> {
+    for event in tx_events(response).iter() {
+        if event.r#type != event_type {
+            continue;
+        }
+        for attribute in event.attributes.iter() {
+            if attribute.key == key {
+                return Some(attribute.value.clone());
+            }
+        }
+    }
+    None
+}
+
+/// Gets the code id from a transaction containing a single MsgStoreCode, using the
+/// Msg response if available and falling back to the events otherwise
+pub fn stored_code_id(response: &TxResponse) -> Option<u64> {
+    match decode_msg_response::<MsgStoreCodeResponse>(response, 0) {
+        Ok(res) if res.code_id != 0 => Some(res.code_id),
+        _ => event_attribute(response, "store_code", "code_id").and_then(|v| v.parse().ok()),
+    }
+}
+
+/// Gets the contract address from a transaction containing a single MsgInstantiateContract,
+/// using the Msg response if available and falling back to the events otherwise
+pub fn instantiated_contract_address(response: &TxResponse) -> Option<String> {
+    match decode_msg_response::<MsgInstantiateContractResponse>(response, 0) {
+        Ok(res) if !res.address.is_empty() => Some(res.address),
+        _ => event_attribute(response, "instantiate", "_contract_address"),
+    }
+}
+
+/// Gets the data returned by the contract for the MsgExecuteContract at the provided
+/// index of a confirmed transaction
+pub fn executed_contract_data(
+    response: &TxResponse,
+    index: usize,
+) -> Result<Vec<u8>, CosmosGrpcError> {
+    let res: MsgExecuteContractResponse = decode_msg_response(response, index)?;
+    Ok(res.data)
+}
+
+impl Contact {
+    /// Streams the address of every contract instantiated from the provided code id, pages
+    /// are fetched as the stream is consumed
+    pub fn get_contracts_by_code_stream(
+        &self,
+        code_id: u64,
+    ) -> impl Stream<Item = Result<Address, CosmosGrpcError>> + '_ {
+        paginate(DEFAULT_PAGE_SIZE, move |pagination| {
+            let request = QueryContractsByCodeRequest {
+                code_id,
+                pagination: Some(pagination),
+            };
+            async move {
+                let res: QueryContractsByCodeResponse = self
+                    .grpc_unary("/cosmwasm.wasm.v1.Query/ContractsByCode", request)
+                    .await?;
+                let mut contracts = Vec::new();
+                for contract in res.contracts {
+                    match contract.parse() {
+                        Ok(address) => contracts.push(address),
+                        Err(e) => return Err(CosmosGrpcError::BadResponse(format!("{}", e))),
+                    }
+                }
+                Ok((contracts, res.pagination))
+            }
+        })
+    }
+
+    /// Uploads wasm code from the provided private key, the code id can be read from
+    /// the returned transaction using stored_code_id. The gas limit is estimated from
+    /// the size of the code.
+    pub async fn store_code(
+        &self,
+        code: Vec<u8>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let gas_limit = STORE_CODE_BASE_GAS + STORE_CODE_GAS_PER_BYTE * code.len() as u64;
+        let msg = store_code_msg(our_address, code)?;
+        // the gas of storing code grows with its size
+        let fee = self.fee_with_gas(Some(fee), gas_limit);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Instantiates a contract from the provided private key, the contract address can be
+    /// read from the returned transaction using instantiated_contract_address
+    #[allow(clippy::too_many_arguments)]
+    pub async fn instantiate_contract(
+        &self,
+        code_id: u64,
+        admin: Option<Address>,
+        label: String,
+        msg: &impl Serialize,
+        funds: Vec<Coin>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = instantiate_contract_msg(our_address, admin, code_id, label, msg, funds)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Executes a contract from the provided private key, attaching the provided funds
+    pub async fn execute_contract(
+        &self,
+        contract: String,
+        msg: &impl Serialize,
+        funds: Vec<Coin>,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = execute_contract_msg(our_address, contract, msg, funds)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+
+    /// Migrates a contract to new code, the provided private key must be the contract admin
+    pub async fn migrate_contract(
+        &self,
+        contract: String,
+        code_id: u64,
+        msg: &impl Serialize,
+        fee: Coin,
+        private_key: PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TxResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.inner.chain_prefix).unwrap();
+        let msg = migrate_contract_msg(our_address, contract, code_id, msg)?;
+        let fee = self.default_fee(Some(fee), &[msg.clone()]);
+        self.send_message(&[msg], None, fee, private_key, wait_timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzip_wasm() {
+        let mut code = WASM_MAGIC.to_vec();
+        code.extend_from_slice(&[0x01, 0x00, 0x00, 0x00]);
+        let compressed = gzip_wasm(code).unwrap();
+        assert!(compressed.starts_with(&GZIP_MAGIC));
+        // already compressed code is passed through
+        assert_eq!(gzip_wasm(compressed.clone()).unwrap(), compressed);
+        assert!(gzip_wasm(vec![1, 2, 3, 4]).is_err());
+    }
+}
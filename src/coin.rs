@@ -2,6 +2,10 @@ use crate::address::Address;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee as ProtoFee;
 use num256::Uint256;
+use num_traits::CheckedAdd;
+use num_traits::CheckedDiv;
+use num_traits::CheckedMul;
+use num_traits::CheckedSub;
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
@@ -31,16 +35,19 @@ impl TryFrom<&str> for Coin {
 impl FromStr for Coin {
     type Err = String;
 
+    /// Parses a coin in the Cosmos SDK format, an integer amount followed by a denom
+    /// for example `1000uatom` or `5ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2`
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let value = value.trim();
-        let mut split_idx = 0;
-        for (idx, char) in value.char_indices() {
-            if char.is_alphabetic() {
-                split_idx = idx;
-                break;
-            }
-        }
+        let split_idx = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
         let (amount, denom) = value.split_at(split_idx);
+        if amount.is_empty() {
+            return Err(format!("Invalid coin {}, no amount", value));
+        }
+        let denom = denom.trim_start();
+        validate_denom(denom)?;
         match amount.parse() {
             Ok(v) => Ok(Coin {
                 amount: v,
@@ -51,27 +58,224 @@ impl FromStr for Coin {
     }
 }
 
+/// Checks a denom against the Cosmos SDK denom rules, a letter followed by 2 to 127
+/// letters, digits or one of `/:._-`
+pub fn validate_denom(denom: &str) -> Result<(), String> {
+    let mut chars = denom.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => {}
+        _ => return Err(format!("Invalid denom {}, must start with a letter", denom)),
+    }
+    if denom.len() < 3 || denom.len() > 128 {
+        return Err(format!(
+            "Invalid denom {}, must be between 3 and 128 characters",
+            denom
+        ));
+    }
+    for c in chars {
+        if !(c.is_ascii_alphanumeric() || "/:._-".contains(c)) {
+            return Err(format!("Invalid denom {}, invalid character {}", denom, c));
+        }
+    }
+    Ok(())
+}
+
 impl Coin {
     pub fn new(amount: Uint256, denom: String) -> Coin {
         Coin { amount, denom }
     }
 
-    /// utility function to display a list of coins
+    /// utility function to display a list of coins in the Cosmos SDK format
+    /// for example `1000uatom,5ustake`
     pub fn display_list(input: &[Coin]) -> String {
-        let mut out = String::new();
-        for i in input {
-            out += &i.to_string()
+        let coins: Vec<String> = input.iter().map(|c| c.to_string()).collect();
+        coins.join(",")
+    }
+
+    /// Parses a comma separated list of coins in the Cosmos SDK format, the inverse
+    /// of display_list. An empty string is an empty list, duplicate denoms are rejected
+    pub fn parse_list(input: &str) -> Result<Vec<Coin>, String> {
+        let input = input.trim();
+        let mut out: Vec<Coin> = Vec::new();
+        if input.is_empty() {
+            return Ok(out);
+        }
+        for part in input.split(',') {
+            let coin: Coin = part.parse()?;
+            if out.iter().any(|c| c.denom == coin.denom) {
+                return Err(format!("Duplicate denom {} in {}", coin.denom, input));
+            }
+            out.push(coin);
+        }
+        Ok(out)
+    }
+
+    /// Adds two coins of the same denom, returns None if the denoms differ or on overflow
+    pub fn checked_add(&self, other: &Coin) -> Option<Coin> {
+        if self.denom != other.denom {
+            return None;
+        }
+        Some(Coin {
+            amount: self.amount.checked_add(&other.amount)?,
+            denom: self.denom.clone(),
+        })
+    }
+
+    /// Subtracts a coin of the same denom, returns None if the denoms differ or the
+    /// result would be negative
+    pub fn checked_sub(&self, other: &Coin) -> Option<Coin> {
+        if self.denom != other.denom {
+            return None;
+        }
+        Some(Coin {
+            amount: self.amount.checked_sub(&other.amount)?,
+            denom: self.denom.clone(),
+        })
+    }
+
+    /// Multiplies the amount by numerator / denominator rounding down, as is done when
+    /// splitting fees or payouts by share. Returns None if the denominator is zero or
+    /// on overflow
+    pub fn checked_mul_ratio(&self, numerator: Uint256, denominator: Uint256) -> Option<Coin> {
+        Some(Coin {
+            amount: self
+                .amount
+                .checked_mul(&numerator)?
+                .checked_div(&denominator)?,
+            denom: self.denom.clone(),
+        })
+    }
+}
+
+/// Coins is a normalized set of coins, sorted by denom with at most one entry per denom
+/// and no zero amounts, matching the Cosmos SDK sdk.Coins semantics
+#[derive(Serialize, Debug, Default, Clone, Eq, PartialEq, Hash)]
+pub struct Coins(Vec<Coin>);
+
+impl Coins {
+    /// Builds a normalized set of coins, amounts of duplicate denoms are summed and zero
+    /// amounts are removed. Errors if a denom is invalid or a sum overflows
+    pub fn new(coins: Vec<Coin>) -> Result<Coins, String> {
+        let mut out: Vec<Coin> = Vec::new();
+        for coin in coins {
+            validate_denom(&coin.denom)?;
+            match out.iter_mut().find(|c| c.denom == coin.denom) {
+                Some(existing) => {
+                    *existing = match existing.checked_add(&coin) {
+                        Some(sum) => sum,
+                        None => return Err(format!("Overflow adding {}", coin.denom)),
+                    }
+                }
+                None => out.push(coin),
+            }
+        }
+        out.retain(|c| c.amount != 0u64.into());
+        out.sort_by(|a, b| a.denom.cmp(&b.denom));
+        Ok(Coins(out))
+    }
+
+    /// The amount of the provided denom in this set, zero if not present
+    pub fn amount_of(&self, denom: &str) -> Uint256 {
+        match self.0.iter().find(|c| c.denom == denom) {
+            Some(c) => c.amount.clone(),
+            None => 0u64.into(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Coin> {
+        self.0.iter()
+    }
+
+    /// Adds two sets of coins, returns None on overflow
+    pub fn checked_add(&self, other: &Coins) -> Option<Coins> {
+        let mut out = self.0.clone();
+        for coin in other.iter() {
+            match out.iter_mut().find(|c| c.denom == coin.denom) {
+                Some(existing) => *existing = existing.checked_add(coin)?,
+                None => out.push(coin.clone()),
+            }
+        }
+        out.sort_by(|a, b| a.denom.cmp(&b.denom));
+        Some(Coins(out))
+    }
+
+    /// Subtracts a set of coins from this one, returns None if any denom would
+    /// go negative
+    pub fn checked_sub(&self, other: &Coins) -> Option<Coins> {
+        let mut out = self.0.clone();
+        for coin in other.iter() {
+            let existing = out.iter_mut().find(|c| c.denom == coin.denom)?;
+            *existing = existing.checked_sub(coin)?;
+        }
+        out.retain(|c| c.amount != 0u64.into());
+        Some(Coins(out))
+    }
+
+    /// Returns true if this set has at least as much of every denom in the other set
+    pub fn is_all_gte(&self, other: &Coins) -> bool {
+        other
+            .iter()
+            .all(|coin| self.amount_of(&coin.denom) >= coin.amount)
+    }
+}
+
+impl fmt::Display for Coins {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Coin::display_list(&self.0))
+    }
+}
+
+impl FromStr for Coins {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Coins::new(Coin::parse_list(value)?)
+    }
+}
+
+impl From<Coins> for Vec<Coin> {
+    fn from(value: Coins) -> Self {
+        value.0
+    }
+}
+
+impl From<Coin> for Coins {
+    fn from(value: Coin) -> Self {
+        if value.amount == 0u64.into() {
+            Coins(Vec::new())
+        } else {
+            Coins(vec![value])
+        }
+    }
+}
+
+impl Coin {
+    /// Converts a ProtoCoin returning an error if the amount is not a valid integer or
+    /// does not fit in 256 bits, use this rather than the From impl for untrusted input
+    pub fn from_proto(value: ProtoCoin) -> Result<Coin, String> {
+        // Uint256 parsing does not reject values above 2^256 - 1
+        match value.amount.parse::<Uint256>() {
+            Ok(amount) if amount.bits() <= 256 => Ok(Coin {
+                denom: value.denom,
+                amount,
+            }),
+            _ => Err(format!("Invalid amount {}{}", value.amount, value.denom)),
         }
-        out
     }
 }
 
 impl From<ProtoCoin> for Coin {
+    /// Panics if the amount is invalid, see Coin::from_proto
     fn from(value: ProtoCoin) -> Self {
-        Coin {
-            denom: value.denom,
-            amount: value.amount.parse().unwrap(),
-        }
+        Coin::from_proto(value).unwrap()
     }
 }
 
@@ -155,4 +359,83 @@ mod tests {
             .parse()
             .unwrap();
     }
+
+    #[test]
+    fn test_coin_sdk_format() {
+        let coin: Coin = "1000uatom".parse().unwrap();
+        assert_eq!(coin, Coin::new(1000u64.into(), "uatom".to_string()));
+        assert_eq!(coin.to_string(), "1000uatom");
+        let ibc: Coin = " 5 ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            ibc.denom,
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+
+        assert!("uatom".parse::<Coin>().is_err());
+        assert!("100".parse::<Coin>().is_err());
+        assert!("-5uatom".parse::<Coin>().is_err());
+        assert!("1.5uatom".parse::<Coin>().is_err());
+        assert!("10ua".parse::<Coin>().is_err());
+        assert!("10u atom".parse::<Coin>().is_err());
+
+        let list = Coin::parse_list("1000uatom,5ibc/ABC,7ustake").unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(Coin::display_list(&list), "1000uatom,5ibc/ABC,7ustake");
+        assert!(Coin::parse_list("").unwrap().is_empty());
+        assert!(Coin::parse_list("1uatom,2uatom").is_err());
+        assert!(Coin::parse_list("1uatom,").is_err());
+    }
+
+    #[test]
+    fn test_coin_from_proto() {
+        // 18 decimal denoms routinely exceed u128 in aggregate
+        let large = format!("1{}", "0".repeat(76));
+        let coin = Coin::from_proto(ProtoCoin {
+            denom: "aevmos".to_string(),
+            amount: large.clone(),
+        })
+        .unwrap();
+        assert_eq!(coin.to_string(), format!("{}aevmos", large));
+        assert!(Coin::from_proto(ProtoCoin {
+            denom: "aevmos".to_string(),
+            amount: format!("1{}", "0".repeat(78)),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_coin_arithmetic() {
+        let atom = |amount: u64| Coin::new(amount.into(), "uatom".to_string());
+        let stake = |amount: u64| Coin::new(amount.into(), "ustake".to_string());
+        assert_eq!(atom(5).checked_add(&atom(7)), Some(atom(12)));
+        assert_eq!(atom(5).checked_add(&stake(7)), None);
+        assert_eq!(atom(7).checked_sub(&atom(5)), Some(atom(2)));
+        assert_eq!(atom(5).checked_sub(&atom(7)), None);
+        assert_eq!(
+            atom(1000).checked_mul_ratio(1u64.into(), 3u64.into()),
+            Some(atom(333))
+        );
+        assert_eq!(atom(1000).checked_mul_ratio(1u64.into(), 0u64.into()), None);
+
+        let coins = Coins::new(vec![stake(1), atom(2), atom(3), stake(0)]).unwrap();
+        assert_eq!(coins.to_string(), "5uatom,1ustake");
+        let other: Coins = "2uatom".parse().unwrap();
+        assert_eq!(
+            coins.checked_sub(&other).unwrap().to_string(),
+            "3uatom,1ustake"
+        );
+        assert_eq!(other.checked_sub(&coins), None);
+        assert_eq!(
+            other
+                .checked_add(&"4uosmo".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "2uatom,4uosmo"
+        );
+        assert!(coins.is_all_gte(&other));
+        assert!(!other.is_all_gte(&coins));
+        assert!(coins.checked_sub(&coins).unwrap().is_empty());
+    }
 }
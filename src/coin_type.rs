@@ -0,0 +1,110 @@
+//! The BIP44 coin types chains use in their HD derivation path. Deriving a key with the coin
+//! type of another chain gives a valid but different address, which then shows no balance,
+//! so key derivation for a chain should look its coin type up here rather than assume 118.
+//!
+//! ```ignore
+//! let key = PrivateKey::from_phrase_for_prefix(phrase, "", "cro", &CoinTypeRegistry::with_defaults())?;
+//! ```
+//!
+//! Chains with coin type 60 derive Ethermint eth_secp256k1 keys, the path is correct but the
+//! address of such a key is not the one PrivateKey::to_address returns, use
+//! PrivateKey::to_ethermint_address or the helpers of the client::keys module.
+
+use crate::hardware_wallet::HdPath;
+use std::collections::HashMap;
+
+/// The coin type used by the Cosmos Hub and most other Cosmos chains
+pub const DEFAULT_COIN_TYPE: u32 = 118;
+
+/// The coin types of well known chains by bech32 address prefix
+const DEFAULT_COIN_TYPES: &[(&str, u32)] = &[
+    ("akash", 118),
+    ("band", 494),
+    ("bitsong", 639),
+    ("canto", 60),
+    ("celestia", 118),
+    ("cosmos", 118),
+    ("crc", 60),
+    ("cro", 394),
+    ("desmos", 852),
+    ("evmos", 60),
+    ("inj", 60),
+    ("juno", 118),
+    ("kava", 459),
+    ("osmo", 118),
+    ("persistence", 750),
+    ("secret", 529),
+    ("stars", 118),
+    ("terra", 330),
+];
+
+/// Returns the HD path of the key with the given index for a coin type,
+/// m/44'/coin_type'/0'/0/index
+pub fn hd_path(coin_type: u32, index: u32) -> HdPath {
+    HdPath::new(coin_type, 0, index)
+}
+
+/// Maps address prefixes to coin types, prefixes that are not registered use
+/// DEFAULT_COIN_TYPE
+#[derive(Debug, Clone, Default)]
+pub struct CoinTypeRegistry {
+    coin_types: HashMap<String, u32>,
+}
+
+impl CoinTypeRegistry {
+    /// Creates an empty registry, every prefix uses DEFAULT_COIN_TYPE
+    pub fn new() -> Self {
+        CoinTypeRegistry::default()
+    }
+
+    /// Creates a registry containing the coin types of well known chains
+    pub fn with_defaults() -> Self {
+        let mut registry = CoinTypeRegistry::new();
+        for (prefix, coin_type) in DEFAULT_COIN_TYPES {
+            registry.register(*prefix, *coin_type);
+        }
+        registry
+    }
+
+    /// Sets the coin type for a prefix, replacing any existing entry
+    pub fn register(&mut self, prefix: impl Into<String>, coin_type: u32) {
+        self.coin_types.insert(prefix.into(), coin_type);
+    }
+
+    /// The coin type registered for the prefix, if any
+    pub fn get(&self, prefix: &str) -> Option<u32> {
+        self.coin_types.get(prefix).copied()
+    }
+
+    /// The coin type to derive keys for the prefix with
+    pub fn coin_type(&self, prefix: &str) -> u32 {
+        self.get(prefix).unwrap_or(DEFAULT_COIN_TYPE)
+    }
+
+    /// The HD path of the key with the given index for the prefix
+    pub fn hd_path(&self, prefix: &str, index: u32) -> HdPath {
+        hd_path(self.coin_type(prefix), index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coin_types() {
+        let mut registry = CoinTypeRegistry::with_defaults();
+        assert_eq!(registry.coin_type("cosmos"), 118);
+        assert_eq!(registry.coin_type("cro"), 394);
+        assert_eq!(
+            registry.hd_path("terra", 0).to_string(),
+            "m/44'/330'/0'/0/0"
+        );
+        // unknown prefixes use the default
+        assert_eq!(registry.get("unknown"), None);
+        assert_eq!(registry.hd_path("unknown", 2), HdPath::new(118, 0, 2));
+
+        registry.register("cro", 118);
+        assert_eq!(registry.coin_type("cro"), 118);
+    }
+}
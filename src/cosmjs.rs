@@ -0,0 +1,205 @@
+//! JSON in the shapes CosmJS uses for sign docs, public keys and signatures. This allows
+//! signatures produced by this crate to be handed to JS frontends, for example to collect
+//! multisig signatures or to have a browser relay the broadcast.
+//!
+//! ```ignore
+//! let std_sign_doc = std_sign_doc_json(&messages, &args, "", &MsgRegistry::with_defaults())?;
+//! let signature = private_key.sign_bytes(&serialize_sign_doc(&std_sign_doc))?;
+//! let response = amino_sign_response_json(&std_sign_doc, &public_key, &signature);
+//! ```
+
+use crate::amino::coins_json;
+use crate::amino::secp256k1_pubkey_json;
+use crate::canonical_json::canonical_json;
+use crate::error::CosmosGrpcError;
+use crate::registry::MsgRegistry;
+use crate::Fee;
+use crate::MessageArgs;
+use crate::Msg;
+use crate::PublicKey;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::SignDoc;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use prost::Message;
+use serde_json::json;
+use serde_json::Value;
+
+/// A public key as a CosmJS Pubkey object, `{"type": ..., "value": base64}`
+pub fn pubkey_json(key: &PublicKey) -> Value {
+    secp256k1_pubkey_json(key.as_bytes())
+}
+
+/// A signature as a CosmJS StdSignature, the public key and the base64 signature
+pub fn signature_json(key: &PublicKey, signature: &[u8]) -> Value {
+    json!({
+        "pub_key": pubkey_json(key),
+        "signature": base64::encode(signature),
+    })
+}
+
+/// Rebuilds the SIGN_MODE_DIRECT sign doc of a transaction built by this crate
+pub fn sign_doc_for_tx(
+    tx: &Tx,
+    chain_id: impl Into<String>,
+    account_number: u64,
+) -> Result<SignDoc, CosmosGrpcError> {
+    let (body, auth_info) = match (&tx.body, &tx.auth_info) {
+        (Some(body), Some(auth_info)) => (body, auth_info),
+        _ => {
+            return Err(CosmosGrpcError::BadInput(
+                "Tx has no body or auth info".to_string(),
+            ))
+        }
+    };
+    let mut body_bytes = Vec::new();
+    body.encode(&mut body_bytes).unwrap();
+    let mut auth_info_bytes = Vec::new();
+    auth_info.encode(&mut auth_info_bytes).unwrap();
+    Ok(SignDoc {
+        body_bytes,
+        auth_info_bytes,
+        chain_id: chain_id.into(),
+        account_number,
+    })
+}
+
+/// A SIGN_MODE_DIRECT sign doc as CosmJS serializes it, camelCase keys, base64 bytes and
+/// the account number as a string
+pub fn sign_doc_json(sign_doc: &SignDoc) -> Value {
+    json!({
+        "bodyBytes": base64::encode(&sign_doc.body_bytes),
+        "authInfoBytes": base64::encode(&sign_doc.auth_info_bytes),
+        "chainId": sign_doc.chain_id,
+        "accountNumber": sign_doc.account_number.to_string(),
+    })
+}
+
+/// The DirectSignResponse an OfflineDirectSigner returns for the sign doc
+pub fn direct_sign_response_json(sign_doc: &SignDoc, key: &PublicKey, signature: &[u8]) -> Value {
+    json!({
+        "signed": sign_doc_json(sign_doc),
+        "signature": signature_json(key, signature),
+    })
+}
+
+/// A fee as a CosmJS StdFee, payer and granter are only present when set
+pub fn fee_json(fee: &Fee) -> Value {
+    let amount: Vec<ProtoCoin> = fee.amount.iter().map(|c| c.clone().into()).collect();
+    let mut value = json!({
+        "amount": coins_json(&amount),
+        "gas": fee.gas_limit.to_string(),
+    });
+    if let Some(payer) = &fee.payer {
+        value["payer"] = Value::String(payer.to_string());
+    }
+    if let Some(granter) = &fee.granter {
+        value["granter"] = Value::String(granter.clone());
+    }
+    value
+}
+
+/// The legacy Amino StdSignDoc for the messages, as CosmJS makeSignDoc builds it. Every
+/// message type must have an Amino JSON encoder registered.
+pub fn std_sign_doc_json(
+    messages: &[Msg],
+    args: &MessageArgs,
+    memo: impl Into<String>,
+    registry: &MsgRegistry,
+) -> Result<Value, CosmosGrpcError> {
+    let msgs = messages
+        .iter()
+        .map(|m| registry.amino_json(&m.0))
+        .collect::<Result<Vec<Value>, CosmosGrpcError>>()?;
+    let mut value = json!({
+        "chain_id": args.chain_id,
+        "account_number": args.account_number.to_string(),
+        "sequence": args.sequence.to_string(),
+        "fee": fee_json(&args.fee),
+        "msgs": msgs,
+        "memo": memo.into(),
+    });
+    if args.timeout_height != 0 {
+        value["timeout_height"] = Value::String(args.timeout_height.to_string());
+    }
+    Ok(value)
+}
+
+/// The bytes signed for a StdSignDoc, the canonical JSON of the sign doc as CosmJS
+/// serializeSignDoc and the Cosmos SDK produce it, see canonical_json.
+pub fn serialize_sign_doc(std_sign_doc: &Value) -> Vec<u8> {
+    canonical_json(std_sign_doc)
+}
+
+/// The AminoSignResponse an OfflineAminoSigner returns for the sign doc
+pub fn amino_sign_response_json(std_sign_doc: &Value, key: &PublicKey, signature: &[u8]) -> Value {
+    json!({
+        "signed": std_sign_doc,
+        "signature": signature_json(key, signature),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sign_doc_vectors;
+
+    #[test]
+    fn test_sign_doc_json() {
+        let vector = &sign_doc_vectors()[0];
+        let key = vector.private_key();
+        let public_key = key.to_public_key("cosmospub").unwrap();
+        let tx = key
+            .get_signed_tx(
+                &vector.messages(),
+                vector.message_args(),
+                vector.memo.clone(),
+            )
+            .unwrap();
+        let sign_doc =
+            sign_doc_for_tx(&tx, vector.chain_id.clone(), vector.account_number).unwrap();
+        let mut sign_doc_bytes = Vec::new();
+        sign_doc.encode(&mut sign_doc_bytes).unwrap();
+        assert_eq!(sign_doc_bytes, vector.sign_doc_bytes());
+
+        let response = direct_sign_response_json(&sign_doc, &public_key, &vector.signature_bytes());
+        assert_eq!(
+            response["signed"]["accountNumber"],
+            vector.account_number.to_string()
+        );
+        assert_eq!(response["signed"]["chainId"], vector.chain_id);
+        assert_eq!(
+            response["signature"]["signature"],
+            base64::encode(vector.signature_bytes())
+        );
+        assert_eq!(
+            response["signature"]["pub_key"]["type"],
+            "tendermint/PubKeySecp256k1"
+        );
+    }
+
+    #[test]
+    fn test_std_sign_doc() {
+        let vector = &sign_doc_vectors()[0];
+        let key = vector.private_key();
+        let public_key = key.to_public_key("cosmospub").unwrap();
+        let std_sign_doc = std_sign_doc_json(
+            &vector.messages(),
+            &vector.message_args(),
+            "a <b> & c",
+            &MsgRegistry::with_defaults(),
+        )
+        .unwrap();
+        let bytes = serialize_sign_doc(&std_sign_doc);
+        let serialized = String::from_utf8(bytes.clone()).unwrap();
+        assert!(serialized.starts_with("{\"account_number\":\""));
+        assert!(serialized.contains("\"memo\":\"a \\u003cb\\u003e \\u0026 c\""));
+        // the escaped form is still the same JSON document
+        let parsed: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed, std_sign_doc);
+
+        let signature = key.sign_bytes(&bytes).unwrap();
+        assert!(public_key.verify(&bytes, &signature));
+        let response = amino_sign_response_json(&std_sign_doc, &public_key, &signature);
+        assert_eq!(response["signed"]["msgs"][0]["type"], "cosmos-sdk/MsgSend");
+    }
+}
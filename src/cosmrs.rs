@@ -0,0 +1,195 @@
+//! Conversions between deep_space types and their cosmrs equivalents, for projects that use
+//! both crates. Only compiled if the cosmrs feature is enabled.
+//!
+//! cosmrs is built on a newer cosmos-sdk-proto and prost than deep_space, so the conversions
+//! go through bytes and strings rather than sharing protobuf types. Transactions are
+//! converted through their protobuf encoding, which is the same for both.
+
+use crate::error::CosmrsConversionError;
+use crate::Address;
+use crate::Coin;
+use crate::Fee;
+use crate::Msg;
+use crate::PublicKey;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use cosmrs::crypto::PublicKey as CosmrsPublicKey;
+use cosmrs::tx::Fee as CosmrsFee;
+use cosmrs::AccountId;
+use cosmrs::Any as CosmrsAny;
+use cosmrs::Coin as CosmrsCoin;
+use prost::Message;
+use std::convert::TryFrom;
+
+fn cosmrs_error(error: impl ToString) -> CosmrsConversionError {
+    CosmrsConversionError::Cosmrs(error.to_string())
+}
+
+impl TryFrom<Address> for AccountId {
+    type Error = CosmrsConversionError;
+
+    fn try_from(value: Address) -> Result<Self, Self::Error> {
+        AccountId::new(&value.get_prefix(), value.as_bytes()).map_err(cosmrs_error)
+    }
+}
+
+impl TryFrom<&AccountId> for Address {
+    type Error = CosmrsConversionError;
+
+    /// Fails for 32 byte module and contract accounts, Address only holds 20 bytes
+    fn try_from(value: &AccountId) -> Result<Self, Self::Error> {
+        Ok(Address::from_slice(&value.to_bytes(), value.prefix())?)
+    }
+}
+
+impl TryFrom<PublicKey> for CosmrsPublicKey {
+    type Error = CosmrsConversionError;
+
+    fn try_from(value: PublicKey) -> Result<Self, Self::Error> {
+        CosmrsPublicKey::from_raw_secp256k1(value.as_bytes())
+            .ok_or_else(|| cosmrs_error("invalid secp256k1 public key"))
+    }
+}
+
+impl TryFrom<&CosmrsPublicKey> for PublicKey {
+    type Error = CosmrsConversionError;
+
+    /// The public key is given the default prefix, see PublicKey::change_prefix
+    fn try_from(value: &CosmrsPublicKey) -> Result<Self, Self::Error> {
+        if value.type_url() != CosmrsPublicKey::SECP256K1_TYPE_URL {
+            return Err(CosmrsConversionError::UnsupportedKey(
+                value.type_url().to_string(),
+            ));
+        }
+        Ok(PublicKey::from_slice(
+            &value.to_bytes(),
+            PublicKey::DEFAULT_PREFIX,
+        )?)
+    }
+}
+
+impl TryFrom<Coin> for CosmrsCoin {
+    type Error = CosmrsConversionError;
+
+    fn try_from(value: Coin) -> Result<Self, Self::Error> {
+        let amount = value.amount.to_string();
+        let amount: u128 = amount
+            .parse()
+            .map_err(|_| CosmrsConversionError::AmountTooLarge(amount))?;
+        CosmrsCoin::new(amount, &value.denom).map_err(cosmrs_error)
+    }
+}
+
+impl From<CosmrsCoin> for Coin {
+    fn from(value: CosmrsCoin) -> Self {
+        Coin {
+            amount: value
+                .amount
+                .to_string()
+                .parse()
+                .expect("A u128 is always a valid Uint256"),
+            denom: value.denom.to_string(),
+        }
+    }
+}
+
+impl TryFrom<Fee> for CosmrsFee {
+    type Error = CosmrsConversionError;
+
+    fn try_from(value: Fee) -> Result<Self, Self::Error> {
+        let mut amount = Vec::new();
+        for coin in value.amount {
+            amount.push(CosmrsCoin::try_from(coin)?);
+        }
+        let payer = match value.payer {
+            Some(payer) => Some(AccountId::try_from(payer)?),
+            None => None,
+        };
+        let granter = match value.granter {
+            Some(granter) => Some(granter.parse::<AccountId>().map_err(cosmrs_error)?),
+            None => None,
+        };
+        Ok(CosmrsFee {
+            amount,
+            gas_limit: value.gas_limit,
+            payer,
+            granter,
+        })
+    }
+}
+
+impl TryFrom<CosmrsFee> for Fee {
+    type Error = CosmrsConversionError;
+
+    fn try_from(value: CosmrsFee) -> Result<Self, Self::Error> {
+        let payer = match &value.payer {
+            Some(payer) => Some(Address::try_from(payer)?),
+            None => None,
+        };
+        Ok(Fee {
+            amount: value.amount.into_iter().map(Coin::from).collect(),
+            gas_limit: value.gas_limit,
+            payer,
+            granter: value.granter.map(|granter| granter.to_string()),
+        })
+    }
+}
+
+impl From<Msg> for CosmrsAny {
+    fn from(value: Msg) -> Self {
+        CosmrsAny {
+            type_url: value.0.type_url,
+            value: value.0.value,
+        }
+    }
+}
+
+impl From<CosmrsAny> for Msg {
+    fn from(value: CosmrsAny) -> Self {
+        Msg(prost_types::Any {
+            type_url: value.type_url,
+            value: value.value,
+        })
+    }
+}
+
+/// Converts a signed transaction, such as the output of PrivateKey::sign_std_msg, to a cosmrs Tx
+pub fn to_cosmrs_tx(tx_bytes: &[u8]) -> Result<cosmrs::Tx, CosmrsConversionError> {
+    cosmrs::Tx::from_bytes(tx_bytes).map_err(cosmrs_error)
+}
+
+/// Converts a cosmrs Tx to the protobuf Tx used by deep_space
+pub fn from_cosmrs_tx(tx: &cosmrs::Tx) -> Result<Tx, CosmrsConversionError> {
+    let bytes = tx.to_bytes().map_err(cosmrs_error)?;
+    Ok(Tx::decode(bytes.as_slice())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sign_doc_vectors;
+
+    #[test]
+    fn test_cosmrs_round_trip() {
+        let vector = &sign_doc_vectors()[0];
+        let key = vector.private_key();
+        let address = key.to_address(&vector.prefix).unwrap();
+        let account = AccountId::try_from(address).unwrap();
+        assert_eq!(account.to_string(), vector.address);
+        assert_eq!(Address::try_from(&account).unwrap(), address);
+
+        let public_key = key.to_public_key("cosmospub").unwrap();
+        let cosmrs_key = CosmrsPublicKey::try_from(public_key).unwrap();
+        assert_eq!(PublicKey::try_from(&cosmrs_key).unwrap(), public_key);
+
+        let fee = vector.message_args().fee;
+        let cosmrs_fee = CosmrsFee::try_from(fee.clone()).unwrap();
+        assert_eq!(cosmrs_fee.gas_limit, fee.gas_limit);
+        assert_eq!(Fee::try_from(cosmrs_fee).unwrap(), fee);
+
+        let tx_bytes = vector.tx_raw_bytes();
+        let tx = to_cosmrs_tx(&tx_bytes).unwrap();
+        assert_eq!(tx.body.memo, vector.memo);
+        let proto = from_cosmrs_tx(&tx).unwrap();
+        assert_eq!(proto.signatures, vec![vector.signature_bytes()]);
+    }
+}
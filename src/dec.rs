@@ -0,0 +1,396 @@
+//! Arbitrary precision fixed point decimal type with the same semantics as the Cosmos SDK
+//! `sdk.Dec`, an integer scaled by 10^18. Unlike [`crate::decimal::Decimal`] this is not
+//! limited to 96 bits, so it can represent rewards, shares and prices of 18 decimal denoms.
+//!
+//! Over gRPC `sdk.Dec` values are encoded as the scaled integer with no decimal point, use
+//! [`Dec::from_proto_string`] and [`Dec::to_proto_string`] for those values and the `FromStr`
+//! and `Display` implementations for human readable values such as `0.025`.
+
+use crate::coin::validate_denom;
+use crate::decimal::DecimalError;
+use crate::decimal::PRECISION;
+use crate::Coin;
+use cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin as ProtoDecCoin;
+use num256::Uint256;
+use num_bigint::BigInt;
+use num_traits::Signed;
+use num_traits::Zero;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Add;
+use std::ops::Sub;
+use std::str::FromStr;
+
+/// 10^18, the scale of an `sdk.Dec`
+const PRECISION_MULTIPLIER: u64 = 1_000_000_000_000_000_000;
+
+fn precision_multiplier() -> BigInt {
+    BigInt::from(PRECISION_MULTIPLIER)
+}
+
+/// Removes the 18 digits of precision from a scaled value rounding half to even,
+/// this is the rounding used by the SDK for multiplication and division
+fn chop_precision_and_round(value: BigInt) -> BigInt {
+    if value.is_negative() {
+        return -chop_precision_and_round(-value);
+    }
+    let multiplier = precision_multiplier();
+    let quotient = &value / &multiplier;
+    let remainder = value - &quotient * &multiplier;
+    let twice = remainder * 2u8;
+    if twice > multiplier || (twice == multiplier && &quotient % 2u8 == BigInt::from(1u8)) {
+        quotient + 1u8
+    } else {
+        quotient
+    }
+}
+
+/// Fixed point decimal with 18 digits of precision, see the module docs
+#[derive(Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Dec(BigInt);
+
+impl Dec {
+    pub fn zero() -> Dec {
+        Dec(BigInt::zero())
+    }
+
+    pub fn one() -> Dec {
+        Dec(precision_multiplier())
+    }
+
+    /// Parses the gRPC representation of an `sdk.Dec`, the value scaled by 10^18 with no
+    /// decimal point. Values containing a decimal point, as returned by legacy endpoints,
+    /// are parsed as human readable decimals.
+    pub fn from_proto_string(value: &str) -> Result<Dec, DecimalError> {
+        if value.contains('.') {
+            return value.parse();
+        }
+        match value.parse() {
+            Ok(v) => Ok(Dec(v)),
+            Err(_) => Err(DecimalError::ParseError(value.to_string())),
+        }
+    }
+
+    /// Returns the representation used when an `sdk.Dec` is a field in a protobuf
+    /// message, for example `0.05` is encoded as `50000000000000000`
+    pub fn to_proto_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.0.is_negative()
+    }
+
+    /// Multiplies two decimals rounding half to even
+    pub fn mul(&self, other: &Dec) -> Dec {
+        Dec(chop_precision_and_round(&self.0 * &other.0))
+    }
+
+    /// Divides two decimals rounding half to even, returns None when dividing by zero
+    pub fn quo(&self, other: &Dec) -> Option<Dec> {
+        if other.is_zero() {
+            return None;
+        }
+        let multiplier = precision_multiplier();
+        let scaled = &self.0 * &multiplier * &multiplier;
+        Some(Dec(chop_precision_and_round(scaled / &other.0)))
+    }
+
+    /// Divides two decimals rounding towards zero, returns None when dividing by zero
+    pub fn quo_truncate(&self, other: &Dec) -> Option<Dec> {
+        if other.is_zero() {
+            return None;
+        }
+        let multiplier = precision_multiplier();
+        let scaled = &self.0 * &multiplier * &multiplier;
+        Some(Dec(scaled / &other.0 / multiplier))
+    }
+
+    /// The integer part of this decimal, rounding towards zero
+    pub fn truncate_int(&self) -> BigInt {
+        &self.0 / precision_multiplier()
+    }
+
+    /// This decimal rounded half to even to an integer
+    pub fn round_int(&self) -> BigInt {
+        chop_precision_and_round(self.0.clone())
+    }
+
+    /// The smallest integer greater than or equal to this decimal
+    pub fn ceil_int(&self) -> BigInt {
+        let truncated = self.truncate_int();
+        if !self.is_negative() && truncated.clone() * precision_multiplier() != self.0 {
+            truncated + 1u8
+        } else {
+            truncated
+        }
+    }
+
+    /// The integer part of this decimal as a Uint256, None if negative or too large
+    pub fn truncate_uint256(&self) -> Option<Uint256> {
+        if self.is_negative() {
+            return None;
+        }
+        // Uint256 parsing does not reject values above 2^256 - 1
+        self.truncate_int()
+            .to_string()
+            .parse::<Uint256>()
+            .ok()
+            .filter(|value| value.bits() <= 256)
+    }
+}
+
+impl Add for Dec {
+    type Output = Dec;
+    fn add(self, other: Dec) -> Dec {
+        Dec(self.0 + other.0)
+    }
+}
+
+impl Sub for Dec {
+    type Output = Dec;
+    fn sub(self, other: Dec) -> Dec {
+        Dec(self.0 - other.0)
+    }
+}
+
+impl From<u64> for Dec {
+    fn from(value: u64) -> Dec {
+        Dec(BigInt::from(value) * precision_multiplier())
+    }
+}
+
+impl From<Uint256> for Dec {
+    fn from(value: Uint256) -> Dec {
+        // a Uint256 is always a valid integer
+        let value: BigInt = value.to_string().parse().unwrap();
+        Dec(value * precision_multiplier())
+    }
+}
+
+impl fmt::Display for Dec {
+    /// Displays all 18 digits of precision like the SDK, for example `1.500000000000000000`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.0.abs().to_string();
+        let digits = format!("{:0>width$}", digits, width = PRECISION as usize + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - PRECISION as usize);
+        let sign = if self.is_negative() { "-" } else { "" };
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl FromStr for Dec {
+    type Err = DecimalError;
+
+    /// Parses a human readable decimal such as `12`, `-0.5` or `0.025`, at most 18 fractional
+    /// digits are allowed
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalError::ParseError(value.to_string());
+        let (negative, unsigned) = match value.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, value),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+            Some(_) => return Err(invalid()),
+            None => (unsigned, ""),
+        };
+        if integer.is_empty()
+            || !(integer.chars().chain(fraction.chars())).all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+        if fraction.len() > PRECISION as usize {
+            return Err(DecimalError::ExcessivePrecision);
+        }
+        let scaled = format!(
+            "{}{}{}",
+            integer,
+            fraction,
+            "0".repeat(PRECISION as usize - fraction.len())
+        );
+        let scaled: BigInt = scaled.parse().map_err(|_| invalid())?;
+        Ok(Dec(if negative { -scaled } else { scaled }))
+    }
+}
+
+/// A decimal amount of a denom, used for rewards and gas prices, with the same
+/// semantics as the SDK sdk.DecCoin
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct DecCoin {
+    pub amount: Dec,
+    pub denom: String,
+}
+
+impl DecCoin {
+    pub fn new(amount: Dec, denom: String) -> DecCoin {
+        DecCoin { amount, denom }
+    }
+
+    /// Converts to a Coin dropping the fractional part, as the SDK does when withdrawing
+    /// rewards. None if the amount is negative.
+    pub fn truncate(&self) -> Option<Coin> {
+        Some(Coin {
+            amount: self.amount.truncate_uint256()?,
+            denom: self.denom.clone(),
+        })
+    }
+
+    /// Treating this DecCoin as a gas price, returns the fee required for the provided gas
+    /// limit, rounded up as the SDK does when checking minimum gas prices. None if the
+    /// price is negative
+    pub fn fee_for_gas(&self, gas_limit: u64) -> Option<Coin> {
+        if self.amount.is_negative() {
+            return None;
+        }
+        let fee = self.amount.mul(&Dec::from(gas_limit));
+        Some(Coin {
+            amount: fee.ceil_int().to_string().parse().ok()?,
+            denom: self.denom.clone(),
+        })
+    }
+}
+
+impl fmt::Display for DecCoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.denom)
+    }
+}
+
+impl FromStr for DecCoin {
+    type Err = String;
+
+    /// Parses a decimal coin such as `0.025uatom`
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let split_idx = value
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(value.len());
+        let (amount, denom) = value.split_at(split_idx);
+        let denom = denom.trim_start();
+        validate_denom(denom)?;
+        match amount.parse() {
+            Ok(amount) => Ok(DecCoin {
+                amount,
+                denom: denom.to_string(),
+            }),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+impl TryFrom<ProtoDecCoin> for DecCoin {
+    type Error = DecimalError;
+
+    fn try_from(value: ProtoDecCoin) -> Result<Self, Self::Error> {
+        Ok(DecCoin {
+            amount: Dec::from_proto_string(&value.amount)?,
+            denom: value.denom,
+        })
+    }
+}
+
+impl From<DecCoin> for ProtoDecCoin {
+    fn from(value: DecCoin) -> Self {
+        ProtoDecCoin {
+            denom: value.denom,
+            amount: value.amount.to_proto_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Dec {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_dec_parse_and_display() {
+        assert_eq!(dec("1.5").to_string(), "1.500000000000000000");
+        assert_eq!(dec("-0.025").to_string(), "-0.025000000000000000");
+        assert_eq!(dec("12").to_proto_string(), "12000000000000000000");
+        assert_eq!(
+            Dec::from_proto_string("50000000000000000").unwrap(),
+            dec("0.05")
+        );
+        assert_eq!(Dec::from_proto_string("12.75").unwrap(), dec("12.75"));
+        // larger than the 96 bit limit of Decimal
+        let big = dec("123456789012345678901234567890.5");
+        assert_eq!(
+            big.to_string(),
+            "123456789012345678901234567890.500000000000000000"
+        );
+        assert!("".parse::<Dec>().is_err());
+        assert!("1.".parse::<Dec>().is_err());
+        assert!(".5".parse::<Dec>().is_err());
+        assert!("1.2.3".parse::<Dec>().is_err());
+        assert!("0.0000000000000000001".parse::<Dec>().is_err());
+    }
+
+    #[test]
+    fn test_dec_arithmetic() {
+        assert_eq!(dec("1.5").mul(&dec("2.5")), dec("3.75"));
+        assert_eq!(dec("10").quo(&dec("4")).unwrap(), dec("2.5"));
+        assert_eq!(
+            dec("1").quo(&dec("3")).unwrap(),
+            dec("0.333333333333333333")
+        );
+        assert_eq!(
+            dec("2").quo(&dec("3")).unwrap(),
+            dec("0.666666666666666667")
+        );
+        assert_eq!(
+            dec("2").quo_truncate(&dec("3")).unwrap(),
+            dec("0.666666666666666666")
+        );
+        assert!(dec("1").quo(&Dec::zero()).is_none());
+        // 0.000000000000000001 * 0.5 rounds half to even
+        assert_eq!(dec("0.000000000000000001").mul(&dec("0.5")), Dec::zero());
+        assert_eq!(
+            dec("0.000000000000000003").mul(&dec("0.5")),
+            dec("0.000000000000000002")
+        );
+
+        assert_eq!(dec("2.5").truncate_int(), 2.into());
+        assert_eq!(dec("2.5").round_int(), 2.into());
+        assert_eq!(dec("3.5").round_int(), 4.into());
+        assert_eq!(dec("2.1").ceil_int(), 3.into());
+        assert_eq!(dec("-2.5").truncate_int(), (-2).into());
+        assert_eq!(dec("-2.5").truncate_uint256(), None);
+        assert_eq!(dec("2.5").truncate_uint256(), Some(2u64.into()));
+        // 2^256 - 1
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(dec(max).truncate_uint256(), Some(max.parse().unwrap()));
+        assert_eq!(
+            dec(&format!("1{}", "0".repeat(78))).truncate_uint256(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_dec_coin() {
+        let price: DecCoin = "0.025uatom".parse().unwrap();
+        assert_eq!(price.to_string(), "0.025000000000000000uatom");
+        assert_eq!(
+            price.fee_for_gas(200_001).unwrap(),
+            Coin::new(5001u64.into(), "uatom".to_string())
+        );
+        let reward = DecCoin::try_from(ProtoDecCoin {
+            denom: "uatom".to_string(),
+            amount: "1500500000000000000000".to_string(),
+        })
+        .unwrap();
+        assert_eq!(
+            reward.truncate().unwrap(),
+            Coin::new(1500u64.into(), "uatom".to_string())
+        );
+        assert!("0.5".parse::<DecCoin>().is_err());
+    }
+}
@@ -9,36 +9,19 @@ use std::{
     fmt::{self, Debug, Display},
     str::FromStr,
 };
+use thiserror::Error;
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum DecimalError {
+    #[error("Decimal exceeds maximum fractional digits")]
     ExcessivePrecision,
+    #[error("Decimal is using an invalid precision must be 0 or 18")]
     InvalidPrecision,
-    DecimalError(DecimalLibraryError),
-}
-
-impl fmt::Display for DecimalError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            DecimalError::ExcessivePrecision => {
-                write!(f, "Decimal exceeds maximum fractional digits")
-            }
-            DecimalError::InvalidPrecision => {
-                write!(f, "Decimal is using an invalid precision must be 0 or 18")
-            }
-            DecimalError::DecimalError(v) => {
-                write!(f, "{:?}", v)
-            }
-        }
-    }
-}
-
-impl std::error::Error for DecimalError {}
-
-impl From<DecimalLibraryError> for DecimalError {
-    fn from(error: DecimalLibraryError) -> Self {
-        DecimalError::DecimalError(error)
-    }
+    #[error("Invalid decimal {0}")]
+    ParseError(String),
+    #[error("{0}")]
+    DecimalError(#[from] DecimalLibraryError),
 }
 
 /// Number of decimal places required by an `sdk.Dec`
@@ -74,6 +57,13 @@ impl Decimal {
         combined_decimal.set_scale(PRECISION)?;
         Ok(Decimal(combined_decimal))
     }
+
+    /// Returns the representation used when an `sdk.Dec` is a field in a protobuf
+    /// message, which is the underlying integer value scaled by 10^18 with no decimal
+    /// point, for example `0.05` is encoded as `50000000000000000`
+    pub fn to_proto_string(&self) -> String {
+        self.0.mantissa().to_string()
+    }
 }
 
 impl Debug for Decimal {
@@ -135,4 +125,12 @@ mod tests {
         let num = Decimal::from(-1i8);
         assert_eq!(num.to_string(), "-1.000000000000000000")
     }
+
+    #[test]
+    fn proto_serialization_test() {
+        let num: Decimal = "0.050000000000000000".parse().unwrap();
+        assert_eq!(num.to_proto_string(), "50000000000000000");
+        let num = Decimal::from(1u8);
+        assert_eq!(num.to_proto_string(), "1000000000000000000");
+    }
 }
@@ -1,101 +1,318 @@
 use crate::mnemonic::Language;
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::utils::parse_expected_sequence;
+use crate::utils::parse_insufficient_funds;
+use crate::utils::parse_lowest_height;
 use crate::utils::FeeInfo;
+use crate::utils::FundsInfo;
+use crate::Coin;
+use crate::Dec;
 use base64::DecodeError as Base64DecodeError;
-use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
-use fmt::Debug;
 use prost::DecodeError;
 use prost::EncodeError;
 use secp256k1::Error as CurveError;
-use std::fmt::Display;
-use std::fmt::Formatter;
-use std::fmt::Result;
-use std::fmt::Result as FmtResult;
-use std::fmt::Result as FormatResult;
+use std::fmt;
+use std::fmt::Debug;
 use std::num::ParseIntError;
-use std::{error::Error, str::Utf8Error};
-use std::{fmt, time::Duration};
+use std::str::Utf8Error;
+use std::time::Duration;
+use std::time::SystemTime;
+use thiserror::Error;
 use tonic::transport::Error as TonicError;
+use tonic::Code;
 use tonic::Status;
 
-#[derive(Debug)]
+/// The errors returned by Contact and the CosmosClient implementations. Variants carry the
+/// context needed to act on them, such as the endpoint that could not be reached or the
+/// gRPC method that failed, and expose the underlying error through `source()`.
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum CosmosGrpcError {
-    NoToken,
+    /// The account has never received tokens, so the chain has no account number or
+    /// sequence for it yet. Accounts are created by their first incoming transfer.
+    #[error("account {address} not found, it has never received tokens")]
+    AccountNotFound { address: String },
+    #[error("bad response from node: {0}")]
     BadResponse(String),
+    #[error("unexpected json returned: {0}")]
     BadStruct(String),
-    SigningError { error: PrivateKeyError },
-    ConnectionError { error: TonicError },
-    RequestError { error: Status },
-    DecodeError { error: DecodeError },
+    #[error("could not sign using private key")]
+    SigningError {
+        #[from]
+        error: PrivateKeyError,
+    },
+    #[error("could not connect to {endpoint} within {}ms", .timeout.as_millis())]
+    ConnectTimeout { endpoint: String, timeout: Duration },
+    #[error("not reconnecting to {endpoint} for {}ms after failures", .retry_in.as_millis())]
+    ConnectBackoff {
+        endpoint: String,
+        retry_in: Duration,
+    },
+    #[error("could not connect to {endpoint}")]
+    ConnectionError {
+        endpoint: String,
+        #[source]
+        error: TonicError,
+    },
+    #[error("request {method} to {endpoint} failed")]
+    RequestError {
+        /// the node the request was sent to
+        endpoint: String,
+        /// the full gRPC method path, for example /cosmos.bank.v1beta1.Query/Balance
+        method: String,
+        #[source]
+        error: Status,
+    },
+    #[error(
+        "request {method} to {endpoint} did not complete within {}ms",
+        .timeout.as_millis()
+    )]
+    RequestTimeout {
+        endpoint: String,
+        method: String,
+        timeout: Duration,
+    },
+    #[error("could not decode response")]
+    DecodeError {
+        #[from]
+        error: DecodeError,
+    },
+    #[error("bad input: {0}")]
     BadInput(String),
+    #[error("the node is waiting for the chain to start")]
     ChainNotRunning,
+    #[error("the node is syncing")]
     NodeNotSynced,
+    #[error("invalid address prefix")]
     InvalidPrefix,
+    #[error("no block produced in {}ms", .time.as_millis())]
     NoBlockProduced { time: Duration },
-    TransactionFailed { tx: TxResponse, time: Duration },
+    #[error(
+        "transaction {}{} failed after {}ms, code {} {}",
+        .tx.txhash,
+        sequence_context(.sequence),
+        .time.as_millis(),
+        .tx.code,
+        .tx.raw_log
+    )]
+    TransactionFailed {
+        tx: TxResponse,
+        time: Duration,
+        /// the sequence the transaction was signed with, if known
+        sequence: Option<u64>,
+    },
+    /// The transaction was accepted into the mempool but not seen in a block in time, it may
+    /// still be included. Broadcasting the same signed bytes again is safe, signing a new
+    /// transaction is not until the sequence is known to be unused.
+    #[error(
+        "transaction {tx_hash} was not included in a block within {}ms",
+        .time.as_millis()
+    )]
+    ConfirmationTimeout { tx_hash: String, time: Duration },
+    /// The transaction was included in a block but the requested number of blocks were not
+    /// produced on top of it in time, it is still included and may yet reach the depth
+    #[error(
+        "transaction {tx_hash} reached {depth} of {required} confirmations within {}ms",
+        .time.as_millis()
+    )]
+    ConfirmationDepthTimeout {
+        tx_hash: String,
+        depth: u64,
+        required: u64,
+        time: Duration,
+    },
+    /// The transaction was seen in a block at height but is no longer found there, the
+    /// block was replaced. The transaction may be included again later.
+    #[error("transaction {tx_hash} included at height {height} is no longer in the chain")]
+    TxReorged { tx_hash: String, height: u64 },
+    /// The node no longer stores the blocks or state of the requested height, queries at
+    /// earliest_available or later can succeed on this node, earlier heights need an
+    /// archive node
+    #[error(
+        "height {height} has been pruned by the node, the earliest available is {}",
+        .earliest_available
+    )]
+    HeightPruned {
+        height: u64,
+        earliest_available: u64,
+    },
+    #[error("insufficient fees or gas for transaction {fee_info:?}")]
     InsufficientFees { fee_info: FeeInfo },
-}
+    #[error(
+        "insufficient funds, {} {} required but only {} available",
+        .funds_info.required,
+        .funds_info.denom,
+        .funds_info.available
+    )]
+    InsufficientFunds { funds_info: FundsInfo },
+    #[error("unknown account type {type_url}")]
+    InvalidAccount { type_url: String },
+    /// Broadcasting the transaction would exceed the fee budget of the Contact, nothing was
+    /// broadcast. The fee fits once retry_after has passed if no other fees are counted.
+    #[error(
+        "fee {fee} exceeds the budget, {spent} of {limit} spent in the last {}s",
+        .window.as_secs()
+    )]
+    FeeBudgetExceeded {
+        fee: Coin,
+        spent: Coin,
+        limit: Coin,
+        window: Duration,
+        retry_after: Duration,
+    },
+    /// A policy of the Contact vetoed the transaction, nothing was signed or broadcast
+    #[error("transaction vetoed by policy: {reason}")]
+    PolicyViolation { reason: String },
+    #[error("transaction rejected locally")]
+    TxLimitExceeded {
+        #[from]
+        error: TxLimitError,
+    },
+    #[error("transaction failed simulation")]
+    SimulationFailed {
+        #[from]
+        error: SimulationError,
+    },
+    /// The fee granter of the transaction has no allowance that pays for it, nothing was
+    /// broadcast
+    #[error("fee grant from {granter} does not cover the tx: {error}")]
+    FeeGrantNotUsable {
+        granter: String,
+        error: FeeGrantError,
+    },
+    /// The IBC packet was not received before its timeout, the timeout was relayed back in
+    /// tx_hash which refunded the funds of a transfer to the sender
+    #[error("IBC packet {sequence} on {channel} timed out, funds refunded in tx {tx_hash}")]
+    IbcPacketTimedOut {
+        channel: String,
+        sequence: u64,
+        tx_hash: String,
+    },
+    /// The validator failed a check of Contact::delegate_checked, nothing was broadcast
+    #[error("refusing to delegate to {validator}: {risk}")]
+    UnsafeValidator {
+        validator: String,
+        risk: ValidatorRisk,
+    },
+}
+
+/// Cosmos SDK ABCI error codes of the sdk codespace, see types/errors/errors.go
+const SDK_CODESPACE: &str = "sdk";
+const SDK_UNAUTHORIZED: u32 = 4;
+const SDK_INVALID_PUB_KEY: u32 = 8;
+const SDK_NO_SIGNATURES: u32 = 15;
+const SDK_MEMPOOL_IS_FULL: u32 = 20;
+const SDK_INVALID_SIGNER: u32 = 24;
+const SDK_INVALID_CHAIN_ID: u32 = 28;
+const SDK_WRONG_SEQUENCE: u32 = 32;
+
+impl CosmosGrpcError {
+    /// The gRPC status code if the node rejected the request
+    pub fn grpc_code(&self) -> Option<Code> {
+        match self {
+            CosmosGrpcError::RequestError { error, .. } => Some(error.code()),
+            _ => None,
+        }
+    }
 
-impl Display for CosmosGrpcError {
-    fn fmt(&self, f: &mut Formatter) -> Result {
+    /// The ABCI code of the sdk codespace if the chain rejected the transaction
+    fn sdk_code(&self) -> Option<u32> {
         match self {
-            CosmosGrpcError::NoToken => {
-                write!(f, "Account has no tokens! No details!")
-            }
-            CosmosGrpcError::BadResponse(val) => write!(f, "CosmosGrpc bad response {}", val),
-            CosmosGrpcError::BadStruct(val) => {
-                write!(f, "CosmosGrpc unexpected json returned {}", val)
-            }
-            CosmosGrpcError::BadInput(val) => write!(f, "CosmosGrpc bad input {}", val),
-            CosmosGrpcError::DecodeError { error: val } => {
-                write!(f, "CosmosGrpc bad any unpacking {}", val)
-            }
-            CosmosGrpcError::ConnectionError { error } => {
-                write!(f, "CosmosGrpc Connection error {} {:?}", error, error)
+            CosmosGrpcError::TransactionFailed { tx, .. }
+                if tx.code != 0 && (tx.codespace == SDK_CODESPACE || tx.codespace.is_empty()) =>
+            {
+                Some(tx.code)
             }
-            CosmosGrpcError::RequestError { error } => {
-                write!(f, "CosmosGrpc Request error {} {:?}", error, error)
-            }
-            CosmosGrpcError::ChainNotRunning => {
-                write!(f, "CosmosGrpc this node is waiting on a blockchain start")
-            }
-            CosmosGrpcError::NodeNotSynced => {
-                write!(f, "CosmosGrpc this node is syncing")
-            }
-            CosmosGrpcError::SigningError { error } => {
-                write!(f, "CosmosGrpc could not sign using private key {:?}", error)
-            }
-            CosmosGrpcError::NoBlockProduced { time } => {
-                write!(f, "CosmosGrpc NoBlockProduced in {}ms", time.as_millis())
-            }
-            CosmosGrpcError::InvalidPrefix => {
-                write!(f, "CosmosGrpc InvalidPrefix")
-            }
-            CosmosGrpcError::TransactionFailed { tx, time } => {
-                write!(
-                    f,
-                    "CosmosGrpc Transaction {:?} did not enter chain in {}ms",
-                    tx,
-                    time.as_millis()
-                )
+            _ => None,
+        }
+    }
+
+    /// True if the node could not be reached or the request was cut off, the same request
+    /// may succeed against the same or another endpoint
+    pub fn is_transient_network(&self) -> bool {
+        match self {
+            CosmosGrpcError::ConnectionError { .. }
+            | CosmosGrpcError::ConnectTimeout { .. }
+            | CosmosGrpcError::ConnectBackoff { .. }
+            | CosmosGrpcError::RequestTimeout { .. } => true,
+            CosmosGrpcError::RequestError { error, .. } => matches!(
+                error.code(),
+                Code::Unavailable | Code::DeadlineExceeded | Code::Cancelled
+            ),
+            _ => false,
+        }
+    }
+
+    /// True if retrying the same operation, possibly after re-fetching the account sequence,
+    /// can succeed. Transactions that were broadcast but not seen in a block are not retryable
+    /// as they may still be included.
+    pub fn is_retryable(&self) -> bool {
+        if self.is_transient_network() {
+            return true;
+        }
+        match self {
+            CosmosGrpcError::RequestError { error, .. } => {
+                matches!(error.code(), Code::ResourceExhausted | Code::Aborted)
             }
-            CosmosGrpcError::InsufficientFees { fee_info } => {
-                write!(f, "Insufficient fees or gas for transaction {:?}", fee_info)
+            CosmosGrpcError::NodeNotSynced
+            | CosmosGrpcError::ChainNotRunning
+            | CosmosGrpcError::NoBlockProduced { .. } => true,
+            CosmosGrpcError::TransactionFailed { .. } => matches!(
+                self.sdk_code(),
+                Some(SDK_WRONG_SEQUENCE) | Some(SDK_MEMPOOL_IS_FULL)
+            ),
+            _ => false,
+        }
+    }
+
+    /// True if the node rejected a request because the requested height has been pruned
+    pub fn is_pruned_height(&self) -> bool {
+        match self {
+            CosmosGrpcError::HeightPruned { .. } => true,
+            CosmosGrpcError::RequestError { error, .. } => {
+                let message = error.message();
+                parse_lowest_height(message).is_some()
+                    || message.contains("version does not exist")
+                    || message.contains("failed to load state at height")
+                    || message.contains("could not find results for height")
             }
+            _ => false,
         }
     }
-}
 
-impl Error for CosmosGrpcError {}
+    /// The sequence the node expected if it rejected the transaction for a sequence mismatch,
+    /// lower than the sequence of the transaction if an earlier one left the mempool
+    pub fn expected_sequence(&self) -> Option<u64> {
+        match self {
+            CosmosGrpcError::TransactionFailed { tx, .. } => parse_expected_sequence(&tx.raw_log),
+            _ => None,
+        }
+    }
 
-impl From<TonicError> for CosmosGrpcError {
-    fn from(error: TonicError) -> Self {
-        CosmosGrpcError::ConnectionError { error }
+    /// True if the transaction could not be signed or the chain rejected its signature,
+    /// signer or chain id. A sequence mismatch is also retryable as signing again with the
+    /// current sequence fixes it.
+    pub fn is_signing_error(&self) -> bool {
+        match self {
+            CosmosGrpcError::SigningError { .. } => true,
+            CosmosGrpcError::TransactionFailed { .. } => matches!(
+                self.sdk_code(),
+                Some(SDK_UNAUTHORIZED)
+                    | Some(SDK_INVALID_PUB_KEY)
+                    | Some(SDK_NO_SIGNATURES)
+                    | Some(SDK_INVALID_SIGNER)
+                    | Some(SDK_INVALID_CHAIN_ID)
+                    | Some(SDK_WRONG_SEQUENCE)
+            ),
+            _ => false,
+        }
     }
 }
 
-impl From<Status> for CosmosGrpcError {
-    fn from(error: Status) -> Self {
-        CosmosGrpcError::RequestError { error }
+fn sequence_context(sequence: &Option<u64>) -> String {
+    match sequence {
+        Some(sequence) => format!(" with sequence {}", sequence),
+        None => String::new(),
     }
 }
 
@@ -105,51 +322,150 @@ impl From<ArrayStringError> for CosmosGrpcError {
     }
 }
 
-impl From<DecodeError> for CosmosGrpcError {
-    fn from(error: DecodeError) -> Self {
-        CosmosGrpcError::DecodeError { error }
+/// A transaction exceeds one of the limits set by the chain parameters and would be
+/// rejected if broadcast
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum TxLimitError {
+    #[error("memo is {length} bytes, the maximum is {max}")]
+    MemoTooLong { length: u64, max: u64 },
+    #[error("tx has {count} signatures, the maximum is {max}")]
+    TooManySignatures { count: u64, max: u64 },
+    #[error("tx is {size} bytes, the maximum is {max}")]
+    TxTooLarge { size: u64, max: u64 },
+    #[error("tx could not be decoded {0}")]
+    InvalidTx(String),
+}
+
+/// Why simulating a transaction failed, the transaction would fail in the same way if it
+/// was broadcast, but no fee has been spent yet
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum SimulationError {
+    #[error(
+        "insufficient funds, {} {} required but only {} available",
+        .0.required,
+        .0.denom,
+        .0.available
+    )]
+    InsufficientFunds(FundsInfo),
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("invalid coins: {0}")]
+    InvalidCoins(String),
+    #[error("contract error: {0}")]
+    ContractError(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+impl SimulationError {
+    /// Classifies the error message returned by the node for a failed simulation
+    pub fn from_log(log: &str) -> Self {
+        // older nodes append the gas used and a generic error to the message
+        let log = match log.find(" With gas wanted") {
+            Some(idx) => &log[..idx],
+            None => log,
+        };
+        let message = log.trim().to_string();
+        if let Some(funds_info) = parse_insufficient_funds(log) {
+            SimulationError::InsufficientFunds(funds_info)
+        } else if log.contains("wasm contract failed") {
+            SimulationError::ContractError(message)
+        } else if log.ends_with(": unauthorized") {
+            SimulationError::Unauthorized(message)
+        } else if log.ends_with(": invalid coins") {
+            SimulationError::InvalidCoins(message)
+        } else {
+            SimulationError::Other(message)
+        }
     }
 }
 
-impl From<PrivateKeyError> for CosmosGrpcError {
-    fn from(error: PrivateKeyError) -> Self {
-        CosmosGrpcError::SigningError { error }
+/// Why a fee allowance does not pay the fee of a transaction, the chain would reject the
+/// transaction for the same reason
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum FeeGrantError {
+    #[error("no allowance from the granter to the signer")]
+    NoAllowance,
+    #[error("allowance has expired")]
+    Expired { expiration: SystemTime },
+    #[error("fee {fee} exceeds the remaining spend limit of {remaining}")]
+    SpendLimitExceeded { fee: Coin, remaining: Coin },
+    /// the fee fits once the period resets at period_reset
+    #[error("fee {fee} exceeds the {remaining} left in the current period")]
+    PeriodLimitExceeded {
+        fee: Coin,
+        remaining: Coin,
+        period_reset: Option<SystemTime>,
+    },
+    #[error("allowance does not allow {type_url} messages")]
+    MessageNotAllowed { type_url: String },
+    #[error("unsupported allowance type {type_url}")]
+    UnknownAllowance { type_url: String },
+    #[error("invalid allowance {0}")]
+    InvalidAllowance(String),
+}
+
+/// Something about a validator a delegator should know before delegating to it, see
+/// ValidatorCheck. Blocking risks are returned as errors by Contact::delegate_checked, the
+/// others are returned as warnings next to the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ValidatorRisk {
+    /// jailed validators earn no rewards until they unjail, until is None if the validator
+    /// has no signing info
+    #[error("validator is jailed")]
+    Jailed { until: Option<SystemTime> },
+    /// the validator double signed and can never be unjailed
+    #[error("validator is tombstoned")]
+    Tombstoned,
+    /// the validator is not in the active set and earns no rewards
+    #[error("validator is not bonded, status {status}")]
+    NotBonded { status: String },
+    /// the validator missed more than half of the blocks it may miss before it is jailed
+    #[error("validator missed {missed} of the last {window} blocks")]
+    MissingBlocks { missed: i64, window: i64 },
+    /// the commission rate was changed less than the policy window ago
+    #[error("commission changed to {rate} {}s ago", .age.as_secs())]
+    CommissionChanged { rate: Dec, age: Duration },
+    #[error("commission {rate} is above the limit of {limit}")]
+    CommissionAboveLimit { rate: Dec, limit: Dec },
+}
+
+impl ValidatorRisk {
+    /// Returns true if delegating should not go ahead, warnings return false
+    pub fn is_blocking(&self) -> bool {
+        matches!(
+            self,
+            ValidatorRisk::Jailed { .. }
+                | ValidatorRisk::Tombstoned
+                | ValidatorRisk::NotBonded { .. }
+                | ValidatorRisk::CommissionAboveLimit { .. }
+        )
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum AddressError {
+    #[error("bech32 address has the wrong length")]
     Bech32WrongLength,
+    #[error("bech32 address contains an invalid character")]
     Bech32InvalidBase32,
+    #[error("bech32 address has an invalid encoding or checksum")]
     Bech32InvalidEncoding,
-    HexDecodeError(ByteDecodeError),
+    #[error("invalid hex address")]
+    HexDecodeError(#[source] ByteDecodeError),
+    #[error("hex address has the wrong length")]
     HexDecodeErrorWrongLength,
-    PrefixTooLong(ArrayStringError),
+    #[error("address prefix too long")]
+    PrefixTooLong(#[from] ArrayStringError),
+    #[error("address bytes have the wrong length")]
     BytesDecodeErrorWrongLength,
 }
 
-impl fmt::Display for AddressError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            AddressError::Bech32WrongLength => write!(f, "Bech32WrongLength"),
-            AddressError::Bech32InvalidBase32 => write!(f, "Bech32InvalidBase32"),
-            AddressError::Bech32InvalidEncoding => write!(f, "Bech32InvalidEncoding"),
-            AddressError::HexDecodeError(val) => write!(f, "HexDecodeError {}", val),
-            AddressError::HexDecodeErrorWrongLength => write!(f, "HexDecodeError Wrong Length"),
-            AddressError::PrefixTooLong(val) => write!(f, "Prefix too long {}", val),
-            AddressError::BytesDecodeErrorWrongLength => write!(f, "BytesDecodeError Wrong Length"),
-        }
-    }
-}
-
-impl std::error::Error for AddressError {}
-
-impl From<ArrayStringError> for AddressError {
-    fn from(error: ArrayStringError) -> Self {
-        AddressError::PrefixTooLong(error)
-    }
-}
-
 impl From<bech32::Error> for AddressError {
     fn from(error: bech32::Error) -> Self {
         match error {
@@ -164,58 +480,34 @@ impl From<bech32::Error> for AddressError {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Error)]
+#[non_exhaustive]
 pub enum ByteDecodeError {
-    DecodeError(Utf8Error),
-    ParseError(ParseIntError),
-}
-
-impl Display for ByteDecodeError {
-    fn fmt(&self, f: &mut Formatter) -> FmtResult {
-        match self {
-            ByteDecodeError::DecodeError(val) => write!(f, "ByteDecodeError {}", val),
-            ByteDecodeError::ParseError(val) => write!(f, "ByteParseError {}", val),
-        }
-    }
+    #[error("invalid utf8 in hex string")]
+    DecodeError(#[from] Utf8Error),
+    #[error("invalid hex digit")]
+    ParseError(#[from] ParseIntError),
 }
 
-impl Error for ByteDecodeError {}
-
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum PublicKeyError {
+    #[error("bech32 public key has the wrong length")]
     Bech32WrongLength,
+    #[error("bech32 public key contains an invalid character")]
     Bech32InvalidBase32,
+    #[error("bech32 public key has an invalid encoding or checksum")]
     Bech32InvalidEncoding,
-    HexDecodeError(ByteDecodeError),
-    Base64DecodeError(Base64DecodeError),
+    #[error("invalid hex public key")]
+    HexDecodeError(#[source] ByteDecodeError),
+    #[error("invalid base64 public key")]
+    Base64DecodeError(#[source] Base64DecodeError),
+    #[error("hex public key has the wrong length")]
     HexDecodeErrorWrongLength,
+    #[error("public key bytes have the wrong length")]
     BytesDecodeErrorWrongLength,
-    PrefixTooLong(ArrayStringError),
-}
-
-impl fmt::Display for PublicKeyError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            PublicKeyError::Bech32WrongLength => write!(f, "Bech32WrongLength"),
-            PublicKeyError::Bech32InvalidBase32 => write!(f, "Bech32InvalidBase32"),
-            PublicKeyError::Bech32InvalidEncoding => write!(f, "Bech32InvalidEncoding"),
-            PublicKeyError::HexDecodeError(val) => write!(f, "HexDecodeError {}", val),
-            PublicKeyError::Base64DecodeError(val) => write!(f, "Base64DecodeError {}", val),
-            PublicKeyError::BytesDecodeErrorWrongLength => {
-                write!(f, "BytesDecodeError Wrong Length")
-            }
-            PublicKeyError::HexDecodeErrorWrongLength => write!(f, "HexDecodeError Wrong Length"),
-            PublicKeyError::PrefixTooLong(val) => write!(f, "Prefix too long {}", val),
-        }
-    }
-}
-
-impl std::error::Error for PublicKeyError {}
-
-impl From<ArrayStringError> for PublicKeyError {
-    fn from(error: ArrayStringError) -> Self {
-        PublicKeyError::PrefixTooLong(error)
-    }
+    #[error("public key prefix too long")]
+    PrefixTooLong(#[from] ArrayStringError),
 }
 
 impl From<bech32::Error> for PublicKeyError {
@@ -232,143 +524,238 @@ impl From<bech32::Error> for PublicKeyError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum PrivateKeyError {
-    HexDecodeError(ByteDecodeError),
+    #[error("invalid hex private key")]
+    HexDecodeError(#[from] ByteDecodeError),
+    #[error("hex private key has the wrong length")]
     HexDecodeErrorWrongLength,
-    CurveError(CurveError),
-    EncodeError(EncodeError),
-    PublicKeyError(PublicKeyError),
-    AddressError(AddressError),
-    HdWalletError(HdWalletError),
-}
-
-impl fmt::Display for PrivateKeyError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> FormatResult {
-        match self {
-            PrivateKeyError::HexDecodeError(val) => write!(f, "PrivateKeyError {}", val),
-            PrivateKeyError::HexDecodeErrorWrongLength => write!(f, "PrivateKeyError Wrong Length"),
-            PrivateKeyError::CurveError(val) => write!(f, "Secp256k1 Error {}", val),
-            PrivateKeyError::EncodeError(val) => write!(f, "Could not encode message {}", val),
-            PrivateKeyError::PublicKeyError(val) => write!(f, "{}", val),
-            PrivateKeyError::AddressError(val) => write!(f, "{}", val),
-            PrivateKeyError::HdWalletError(val) => write!(f, "{}", val),
-        }
-    }
-}
-
-impl std::error::Error for PrivateKeyError {}
-
-impl From<CurveError> for PrivateKeyError {
-    fn from(error: CurveError) -> Self {
-        PrivateKeyError::CurveError(error)
-    }
-}
-
-impl From<HdWalletError> for PrivateKeyError {
-    fn from(error: HdWalletError) -> Self {
-        PrivateKeyError::HdWalletError(error)
-    }
-}
-
-impl From<PublicKeyError> for PrivateKeyError {
-    fn from(error: PublicKeyError) -> Self {
-        PrivateKeyError::PublicKeyError(error)
-    }
-}
-
-impl From<AddressError> for PrivateKeyError {
-    fn from(error: AddressError) -> Self {
-        PrivateKeyError::AddressError(error)
-    }
-}
-
-impl From<EncodeError> for PrivateKeyError {
-    fn from(error: EncodeError) -> Self {
-        PrivateKeyError::EncodeError(error)
-    }
-}
-
-impl From<ByteDecodeError> for PrivateKeyError {
-    fn from(error: ByteDecodeError) -> Self {
-        PrivateKeyError::HexDecodeError(error)
-    }
-}
-
-#[derive(Debug)]
+    #[error("secp256k1 error")]
+    CurveError(#[from] CurveError),
+    #[error("could not encode message")]
+    EncodeError(#[from] EncodeError),
+    #[error(transparent)]
+    PublicKeyError(#[from] PublicKeyError),
+    #[error(transparent)]
+    AddressError(#[from] AddressError),
+    #[error(transparent)]
+    HdWalletError(#[from] HdWalletError),
+}
+
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum HdWalletError {
+    #[error(transparent)]
     Bip39Error(Bip39Error),
+    #[error("invalid hd wallet path {0}")]
     InvalidPathSpec(String),
 }
 
-impl fmt::Display for HdWalletError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> FormatResult {
-        match self {
-            HdWalletError::Bip39Error(val) => write!(f, "{}", val),
-            HdWalletError::InvalidPathSpec(val) => write!(f, "HDWalletError invalid path {}", val),
-        }
-    }
+/// An error confirming an address on a hardware wallet, see the hardware_wallet module
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum HardwareWalletError {
+    #[error("the address was rejected on the device")]
+    Rejected,
+    #[error("device error: {0}")]
+    Device(String),
+    #[error("hd path {0} can not be used with a hardware wallet")]
+    UnsupportedPath(String),
+    #[error("device returned an invalid public key")]
+    InvalidPublicKey(#[from] PublicKeyError),
+    #[error("device returned {device} which does not match {expected}")]
+    AddressMismatch { device: String, expected: String },
 }
 
-impl std::error::Error for HdWalletError {}
-
 /// A BIP39 error.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Error)]
+#[non_exhaustive]
 pub enum Bip39Error {
     /// Mnemonic has a word count that is not a multiple of 6.
+    #[error("mnemonic has a word count that is not a multiple of 6: {0}")]
     BadWordCount(usize),
     /// Mnemonic contains an unknown word.
+    #[error("mnemonic contains an unknown word: {0}")]
     UnknownWord(String),
     /// Entropy was not a multiple of 32 bits or between 128-256n bits in length.
+    #[error("entropy was not between 128-256 bits or not a multiple of 32 bits: {0} bits")]
     BadEntropyBitCount(usize),
     /// The mnemonic has an invalid checksum.
+    #[error("the mnemonic has an invalid checksum")]
     InvalidChecksum,
     /// The word list can be interpreted as multiple languages.
+    #[error("ambiguous word list: {0:?}")]
     AmbiguousWordList(Vec<Language>),
 }
 
-impl fmt::Display for Bip39Error {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            Bip39Error::BadWordCount(c) => write!(
-                f,
-                "mnemonic has a word count that is not a multiple of 6: {}",
-                c,
-            ),
-            Bip39Error::UnknownWord(ref w) => {
-                write!(f, "mnemonic contains an unknown word: {}", w,)
-            }
-            Bip39Error::BadEntropyBitCount(c) => write!(
-                f,
-                "entropy was not between 128-256 bits or not a multiple of 32 bits: {} bits",
-                c,
-            ),
-            Bip39Error::InvalidChecksum => write!(f, "the mnemonic has an invalid checksum"),
-            Bip39Error::AmbiguousWordList(ref langs) => {
-                write!(f, "ambiguous word list: {:?}", langs)
-            }
-        }
-    }
-}
 impl Debug for Bip39Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(self, f)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ArrayStringError {
+    #[error("string is longer than 32 bytes")]
     TooLong,
 }
 
-impl Display for ArrayStringError {
-    fn fmt(&self, f: &mut Formatter) -> Result {
-        match self {
-            ArrayStringError::TooLong => {
-                write!(f, "This string is too long!")
-            }
+/// An error converting between deep_space and cosmrs types, see the cosmrs module
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CosmrsConversionError {
+    #[error(transparent)]
+    AddressError(#[from] AddressError),
+    #[error(transparent)]
+    PublicKeyError(#[from] PublicKeyError),
+    #[error("only secp256k1 public keys are supported, not {0}")]
+    UnsupportedKey(String),
+    #[error("amount {0} does not fit in a u128")]
+    AmountTooLarge(String),
+    #[error("could not decode tx")]
+    DecodeError(#[from] DecodeError),
+    /// an error returned by cosmrs
+    #[error("cosmrs: {0}")]
+    Cosmrs(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as StdError;
+
+    #[test]
+    fn test_error_context() {
+        let error = CosmosGrpcError::RequestError {
+            endpoint: "http://localhost:9090".to_string(),
+            method: "/cosmos.bank.v1beta1.Query/Balance".to_string(),
+            error: Status::not_found("account not found"),
+        };
+        assert_eq!(error.grpc_code(), Some(Code::NotFound));
+        assert!(error
+            .to_string()
+            .contains("/cosmos.bank.v1beta1.Query/Balance to http://localhost:9090"));
+        assert!(!error.to_string().contains("account not found"));
+        assert!(error.source().is_some());
+
+        let error: CosmosGrpcError =
+            PrivateKeyError::from(HdWalletError::InvalidPathSpec("m/44".to_string())).into();
+        // the text of a source is not repeated by the error wrapping it
+        assert_eq!(error.to_string(), "could not sign using private key");
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "invalid hd wallet path m/44"
+        );
+        assert_eq!(error.grpc_code(), None);
+        assert!(error.is_signing_error());
+        assert!(!error.is_retryable());
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let unavailable = CosmosGrpcError::RequestError {
+            endpoint: "http://localhost:9090".to_string(),
+            method: "/cosmos.tx.v1beta1.Service/BroadcastTx".to_string(),
+            error: Status::unavailable("connection reset"),
+        };
+        assert!(unavailable.is_transient_network());
+        assert!(unavailable.is_retryable());
+        let not_found = CosmosGrpcError::RequestError {
+            endpoint: "http://localhost:9090".to_string(),
+            method: "/cosmos.tx.v1beta1.Service/GetTx".to_string(),
+            error: Status::not_found("tx not found"),
+        };
+        assert!(!not_found.is_retryable());
+
+        let failed = |code: u32, codespace: &str| CosmosGrpcError::TransactionFailed {
+            tx: TxResponse {
+                code,
+                codespace: codespace.to_string(),
+                ..TxResponse::default()
+            },
+            time: Duration::from_secs(0),
+            sequence: Some(7),
+        };
+        let sequence = failed(SDK_WRONG_SEQUENCE, "sdk");
+        assert!(sequence.is_retryable() && sequence.is_signing_error());
+        assert!(!sequence.is_transient_network());
+        let timeout = CosmosGrpcError::ConfirmationTimeout {
+            tx_hash: "AB".to_string(),
+            time: Duration::from_secs(60),
+        };
+        assert!(!timeout.is_retryable() && !timeout.is_transient_network());
+        assert!(CosmosGrpcError::ConnectTimeout {
+            endpoint: "http://localhost:9090".to_string(),
+            timeout: Duration::from_secs(5),
         }
+        .is_retryable());
+        assert!(failed(SDK_UNAUTHORIZED, "sdk").is_signing_error());
+        assert!(!failed(SDK_UNAUTHORIZED, "sdk").is_retryable());
+        assert!(failed(SDK_MEMPOOL_IS_FULL, "sdk").is_retryable());
+        // the same code in another module's codespace means something else
+        assert!(!failed(SDK_WRONG_SEQUENCE, "wasm").is_retryable());
+        // not included in time, it may still be included
+        assert!(!failed(0, "").is_retryable());
+        // already in the mempool
+        assert!(!failed(19, "sdk").is_retryable());
+    }
+
+    #[test]
+    fn test_broadcast_codes() {
+        use crate::client::send::check_broadcast_response;
+        let rejected = |code: u32, raw_log: &str| {
+            check_broadcast_response(
+                TxResponse {
+                    code,
+                    codespace: "sdk".to_string(),
+                    raw_log: raw_log.to_string(),
+                    ..TxResponse::default()
+                },
+                Some(7),
+            )
+            .unwrap_err()
+        };
+        let unauthorized = rejected(SDK_UNAUTHORIZED, "signature verification failed");
+        assert!(matches!(
+            unauthorized,
+            CosmosGrpcError::TransactionFailed {
+                sequence: Some(7),
+                ..
+            }
+        ));
+        assert!(unauthorized.is_signing_error());
+        assert!(rejected(SDK_MEMPOOL_IS_FULL, "mempool is full").is_retryable());
+        assert!(check_broadcast_response(TxResponse::default(), Some(7)).is_ok());
     }
-}
 
-impl Error for ArrayStringError {}
+    #[test]
+    fn test_simulation_error() {
+        let funds = SimulationError::from_log(
+            "failed to execute message; message index: 0: 90uatom is smaller than 100uatom: \
+             insufficient funds With gas wanted: '0' and gas used: '61000' : unknown request",
+        );
+        assert_eq!(
+            funds,
+            SimulationError::InsufficientFunds(FundsInfo {
+                denom: "uatom".to_string(),
+                required: 100u64.into(),
+                available: 90u64.into(),
+            })
+        );
+        let unauthorized = SimulationError::from_log(
+            "failed to execute message; message index: 0: authorization not found: unauthorized",
+        );
+        assert!(matches!(unauthorized, SimulationError::Unauthorized(_)));
+        let contract = SimulationError::from_log(
+            "failed to execute message; message index: 0: Unauthorized: execute wasm contract \
+             failed",
+        );
+        assert!(matches!(contract, SimulationError::ContractError(_)));
+        assert_eq!(
+            SimulationError::from_log("0uatom: invalid coins"),
+            SimulationError::InvalidCoins("0uatom: invalid coins".to_string())
+        );
+    }
+}
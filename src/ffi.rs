@@ -0,0 +1,305 @@
+//! C ABI bindings for key derivation, address encoding and sign doc signing, so that mobile
+//! apps and backends in other languages use exactly the same signing logic as this crate.
+//! Only compiled if the ffi feature is enabled, a C library can be built with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or staticlib).
+//!
+//! Every function returns one of the DEEP_SPACE_* status codes. Keys and signatures are
+//! written to caller provided buffers of the documented length. Strings are NUL terminated,
+//! strings returned by this module are owned by Rust and must be released with
+//! deep_space_string_free.
+#![allow(unsafe_code)]
+
+use crate::PrivateKey;
+use crate::PublicKey;
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::os::raw::c_int;
+use std::panic::catch_unwind;
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+use std::slice;
+
+pub const DEEP_SPACE_OK: c_int = 0;
+/// a required pointer argument was NULL
+pub const DEEP_SPACE_NULL_POINTER: c_int = 1;
+/// a string argument was not valid UTF-8
+pub const DEEP_SPACE_INVALID_STRING: c_int = 2;
+/// a private or public key was not a valid secp256k1 key
+pub const DEEP_SPACE_INVALID_KEY: c_int = 3;
+/// the mnemonic or HD path could not be parsed
+pub const DEEP_SPACE_INVALID_MNEMONIC: c_int = 4;
+/// the address prefix is not a valid bech32 prefix
+pub const DEEP_SPACE_INVALID_PREFIX: c_int = 5;
+/// an unexpected internal error, no output was written
+pub const DEEP_SPACE_INTERNAL_ERROR: c_int = 6;
+
+pub const DEEP_SPACE_PRIVATE_KEY_LEN: usize = 32;
+/// public keys are compressed secp256k1 keys
+pub const DEEP_SPACE_PUBLIC_KEY_LEN: usize = 33;
+/// signatures are compact secp256k1 signatures, r followed by s
+pub const DEEP_SPACE_SIGNATURE_LEN: usize = 64;
+
+/// Runs the body of an exported function, panics must not unwind into the caller
+fn guard(f: impl FnOnce() -> Result<(), c_int>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => DEEP_SPACE_OK,
+        Ok(Err(code)) => code,
+        Err(_) => DEEP_SPACE_INTERNAL_ERROR,
+    }
+}
+
+unsafe fn str_arg<'a>(ptr: *const c_char) -> Result<&'a str, c_int> {
+    if ptr.is_null() {
+        return Err(DEEP_SPACE_NULL_POINTER);
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| DEEP_SPACE_INVALID_STRING)
+}
+
+unsafe fn bytes_arg<'a>(ptr: *const u8, len: usize) -> Result<&'a [u8], c_int> {
+    if ptr.is_null() {
+        return Err(DEEP_SPACE_NULL_POINTER);
+    }
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+unsafe fn private_key_arg(ptr: *const u8) -> Result<PrivateKey, c_int> {
+    let mut bytes = [0; DEEP_SPACE_PRIVATE_KEY_LEN];
+    bytes.copy_from_slice(bytes_arg(ptr, DEEP_SPACE_PRIVATE_KEY_LEN)?);
+    PrivateKey::from_bytes(bytes).map_err(|_| DEEP_SPACE_INVALID_KEY)
+}
+
+unsafe fn write_bytes(out: *mut u8, bytes: &[u8]) -> Result<(), c_int> {
+    if out.is_null() {
+        return Err(DEEP_SPACE_NULL_POINTER);
+    }
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    Ok(())
+}
+
+unsafe fn write_string(out: *mut *mut c_char, value: String) -> Result<(), c_int> {
+    if out.is_null() {
+        return Err(DEEP_SPACE_NULL_POINTER);
+    }
+    let value = CString::new(value).map_err(|_| DEEP_SPACE_INTERNAL_ERROR)?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+/// Derives the private key for a BIP39 mnemonic, writing its 32 bytes to out_private_key.
+/// passphrase may be NULL for no passphrase and hd_path may be NULL for the default Cosmos
+/// path m/44'/118'/0'/0/0.
+///
+/// # Safety
+/// mnemonic, and passphrase and hd_path if not NULL, must be NUL terminated strings and
+/// out_private_key must point to DEEP_SPACE_PRIVATE_KEY_LEN writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_private_key_from_mnemonic(
+    mnemonic: *const c_char,
+    passphrase: *const c_char,
+    hd_path: *const c_char,
+    out_private_key: *mut u8,
+) -> c_int {
+    guard(|| {
+        let mnemonic = str_arg(mnemonic)?;
+        let passphrase = if passphrase.is_null() {
+            ""
+        } else {
+            str_arg(passphrase)?
+        };
+        let key = if hd_path.is_null() {
+            PrivateKey::from_phrase(mnemonic, passphrase)
+        } else {
+            PrivateKey::from_hd_wallet_path(str_arg(hd_path)?, mnemonic, passphrase)
+        }
+        .map_err(|_| DEEP_SPACE_INVALID_MNEMONIC)?;
+        write_bytes(out_private_key, &key.to_bytes())
+    })
+}
+
+/// Writes the compressed public key of a private key to out_public_key
+///
+/// # Safety
+/// private_key must point to DEEP_SPACE_PRIVATE_KEY_LEN readable bytes and out_public_key
+/// to DEEP_SPACE_PUBLIC_KEY_LEN writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_public_key(
+    private_key: *const u8,
+    out_public_key: *mut u8,
+) -> c_int {
+    guard(|| {
+        let key = private_key_arg(private_key)?;
+        let public_key = key
+            .to_public_key(PublicKey::DEFAULT_PREFIX)
+            .map_err(|_| DEEP_SPACE_INVALID_KEY)?;
+        write_bytes(out_public_key, public_key.as_bytes())
+    })
+}
+
+/// Encodes the address of a private key with the bech32 prefix, for example "cosmos", the
+/// address string is written to out_address
+///
+/// # Safety
+/// private_key must point to DEEP_SPACE_PRIVATE_KEY_LEN readable bytes, prefix must be a
+/// NUL terminated string and out_address must be a valid pointer. The returned string
+/// must be released with deep_space_string_free.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_address(
+    private_key: *const u8,
+    prefix: *const c_char,
+    out_address: *mut *mut c_char,
+) -> c_int {
+    guard(|| {
+        let key = private_key_arg(private_key)?;
+        let public_key = key
+            .to_public_key(PublicKey::DEFAULT_PREFIX)
+            .map_err(|_| DEEP_SPACE_INVALID_KEY)?;
+        let address = public_key
+            .to_address_with_prefix(str_arg(prefix)?)
+            .map_err(|_| DEEP_SPACE_INVALID_PREFIX)?;
+        write_string(out_address, address.to_string())
+    })
+}
+
+/// Encodes the address of a compressed public key with the bech32 prefix, the address
+/// string is written to out_address
+///
+/// # Safety
+/// public_key must point to DEEP_SPACE_PUBLIC_KEY_LEN readable bytes, prefix must be a NUL
+/// terminated string and out_address must be a valid pointer. The returned string must be
+/// released with deep_space_string_free.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_public_key_address(
+    public_key: *const u8,
+    prefix: *const c_char,
+    out_address: *mut *mut c_char,
+) -> c_int {
+    guard(|| {
+        let bytes = bytes_arg(public_key, DEEP_SPACE_PUBLIC_KEY_LEN)?;
+        let public_key = PublicKey::from_slice(bytes, PublicKey::DEFAULT_PREFIX)
+            .map_err(|_| DEEP_SPACE_INVALID_KEY)?;
+        let address = public_key
+            .to_address_with_prefix(str_arg(prefix)?)
+            .map_err(|_| DEEP_SPACE_INVALID_PREFIX)?;
+        write_string(out_address, address.to_string())
+    })
+}
+
+/// Signs the protobuf encoded SignDoc of a transaction, or any other message, writing the
+/// compact signature of its sha256 hash to out_signature
+///
+/// # Safety
+/// private_key must point to DEEP_SPACE_PRIVATE_KEY_LEN readable bytes, sign_doc to
+/// sign_doc_len readable bytes and out_signature to DEEP_SPACE_SIGNATURE_LEN writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_sign_bytes(
+    private_key: *const u8,
+    sign_doc: *const u8,
+    sign_doc_len: usize,
+    out_signature: *mut u8,
+) -> c_int {
+    guard(|| {
+        let key = private_key_arg(private_key)?;
+        let signature = key
+            .sign_bytes(bytes_arg(sign_doc, sign_doc_len)?)
+            .map_err(|_| DEEP_SPACE_INVALID_KEY)?;
+        write_bytes(out_signature, &signature)
+    })
+}
+
+/// Releases a string returned by this module, NULL is ignored
+///
+/// # Safety
+/// value must be NULL or a string returned by this module that has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn deep_space_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::sign_doc_vectors;
+
+    fn c_string(value: &str) -> CString {
+        CString::new(value).unwrap()
+    }
+
+    #[test]
+    fn test_ffi_signing() {
+        let vector = &sign_doc_vectors()[0];
+        let mnemonic = c_string(&vector.mnemonic);
+        let hd_path = c_string(&vector.hd_path);
+        let prefix = c_string(&vector.prefix);
+        unsafe {
+            let mut key = [0u8; DEEP_SPACE_PRIVATE_KEY_LEN];
+            let code = deep_space_private_key_from_mnemonic(
+                mnemonic.as_ptr(),
+                ptr::null(),
+                hd_path.as_ptr(),
+                key.as_mut_ptr(),
+            );
+            assert_eq!(code, DEEP_SPACE_OK);
+            assert_eq!(key, vector.private_key().to_bytes());
+
+            let mut address = ptr::null_mut();
+            let code = deep_space_address(key.as_ptr(), prefix.as_ptr(), &mut address);
+            assert_eq!(code, DEEP_SPACE_OK);
+            assert_eq!(CStr::from_ptr(address).to_str().unwrap(), vector.address);
+            deep_space_string_free(address);
+
+            let mut public_key = [0u8; DEEP_SPACE_PUBLIC_KEY_LEN];
+            assert_eq!(
+                deep_space_public_key(key.as_ptr(), public_key.as_mut_ptr()),
+                DEEP_SPACE_OK
+            );
+            let mut address = ptr::null_mut();
+            let code =
+                deep_space_public_key_address(public_key.as_ptr(), prefix.as_ptr(), &mut address);
+            assert_eq!(code, DEEP_SPACE_OK);
+            assert_eq!(CStr::from_ptr(address).to_str().unwrap(), vector.address);
+            deep_space_string_free(address);
+
+            let sign_doc = vector.sign_doc_bytes();
+            let mut signature = [0u8; DEEP_SPACE_SIGNATURE_LEN];
+            let code = deep_space_sign_bytes(
+                key.as_ptr(),
+                sign_doc.as_ptr(),
+                sign_doc.len(),
+                signature.as_mut_ptr(),
+            );
+            assert_eq!(code, DEEP_SPACE_OK);
+            assert_eq!(signature.to_vec(), vector.signature_bytes());
+        }
+    }
+
+    #[test]
+    fn test_ffi_errors() {
+        let invalid = c_string("not a mnemonic");
+        unsafe {
+            let mut key = [0u8; DEEP_SPACE_PRIVATE_KEY_LEN];
+            let code = deep_space_private_key_from_mnemonic(
+                invalid.as_ptr(),
+                ptr::null(),
+                ptr::null(),
+                key.as_mut_ptr(),
+            );
+            assert_eq!(code, DEEP_SPACE_INVALID_MNEMONIC);
+            let code = deep_space_private_key_from_mnemonic(
+                ptr::null(),
+                ptr::null(),
+                ptr::null(),
+                key.as_mut_ptr(),
+            );
+            assert_eq!(code, DEEP_SPACE_NULL_POINTER);
+            // zero is not a valid secp256k1 secret
+            let mut public_key = [0u8; DEEP_SPACE_PUBLIC_KEY_LEN];
+            let code = deep_space_public_key([0u8; 32].as_ptr(), public_key.as_mut_ptr());
+            assert_eq!(code, DEEP_SPACE_INVALID_KEY);
+        }
+    }
+}
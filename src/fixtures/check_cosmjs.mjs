@@ -0,0 +1,62 @@
+// Signs every vector in sign_docs.json with CosmJS and compares the result, run this after
+// adding or changing a vector:
+//
+//   npm install @cosmjs/crypto @cosmjs/encoding @cosmjs/proto-signing @cosmjs/stargate cosmjs-types
+//   node src/fixtures/check_cosmjs.mjs
+import { readFileSync } from "fs";
+import { stringToPath } from "@cosmjs/crypto";
+import { toHex } from "@cosmjs/encoding";
+import { DirectSecp256k1HdWallet, parseCoins } from "@cosmjs/proto-signing";
+import { SigningStargateClient } from "@cosmjs/stargate";
+import { TxRaw } from "cosmjs-types/cosmos/tx/v1beta1/tx.js";
+
+const vectors = JSON.parse(readFileSync(new URL("./sign_docs.json", import.meta.url)));
+let failed = false;
+for (const v of vectors) {
+  const wallet = await DirectSecp256k1HdWallet.fromMnemonic(v.mnemonic, {
+    hdPaths: [stringToPath(v.hd_path)],
+    prefix: v.prefix,
+  });
+  const [account] = await wallet.getAccounts();
+  const client = await SigningStargateClient.offline(wallet);
+  const send = {
+    typeUrl: "/cosmos.bank.v1beta1.MsgSend",
+    value: {
+      fromAddress: account.address,
+      toAddress: v.to_address,
+      amount: parseCoins(v.amount.join(",")),
+    },
+  };
+  const fee = { amount: parseCoins(v.fee.join(",")), gas: String(v.gas_limit) };
+  const signerData = {
+    accountNumber: v.account_number,
+    sequence: v.sequence,
+    chainId: v.chain_id,
+  };
+  const raw = await client.sign(
+    account.address,
+    [send],
+    fee,
+    v.memo,
+    signerData,
+    BigInt(v.timeout_height),
+  );
+  const checks = {
+    address: [account.address, v.address],
+    public_key: [toHex(account.pubkey), v.public_key],
+    body_bytes: [toHex(raw.bodyBytes), v.body_bytes],
+    auth_info_bytes: [toHex(raw.authInfoBytes), v.auth_info_bytes],
+    signature: [toHex(raw.signatures[0]), v.signature],
+    tx_bytes: [toHex(TxRaw.encode(raw).finish()), v.tx_bytes],
+  };
+  for (const [field, [actual, expected]] of Object.entries(checks)) {
+    if (actual !== expected) {
+      console.log(`${v.name}: ${field} is ${actual}, expected ${expected}`);
+      failed = true;
+    }
+  }
+}
+if (failed) {
+  process.exit(1);
+}
+console.log(`${vectors.length} vectors match CosmJS`);
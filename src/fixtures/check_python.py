@@ -0,0 +1,142 @@
+# Rebuilds every vector in sign_docs.json from its mnemonic with an implementation that
+# shares no code with deep_space, BIP39 / BIP32 / bech32 and the protobuf encoding are
+# written out here and signing uses the cryptography package. Run it after adding or
+# changing a vector:
+#
+#   pip install "cryptography>=44"
+#   python3 src/fixtures/check_python.py
+import hashlib
+import hmac
+import json
+import os
+import re
+import sys
+
+from cryptography.hazmat.primitives import hashes
+from cryptography.hazmat.primitives.asymmetric import ec
+from cryptography.hazmat.primitives.asymmetric.utils import decode_dss_signature
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+
+N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+
+
+def public_key(secret):
+    key = ec.derive_private_key(secret, ec.SECP256K1())
+    return key.public_key().public_bytes(Encoding.X962, PublicFormat.CompressedPoint)
+
+
+def derive(mnemonic, path):
+    seed = hashlib.pbkdf2_hmac("sha512", mnemonic.encode(), b"mnemonic", 2048)
+    digest = hmac.new(b"Bitcoin seed", seed, hashlib.sha512).digest()
+    secret, chain_code = int.from_bytes(digest[:32], "big"), digest[32:]
+    for part in path.split("/")[1:]:
+        index = int(part.rstrip("'"))
+        if part.endswith("'"):
+            data = b"\x00" + secret.to_bytes(32, "big") + (index + 2**31).to_bytes(4, "big")
+        else:
+            data = public_key(secret) + index.to_bytes(4, "big")
+        digest = hmac.new(chain_code, data, hashlib.sha512).digest()
+        secret = (int.from_bytes(digest[:32], "big") + secret) % N
+        chain_code = digest[32:]
+    return secret
+
+
+def bech32(prefix, data):
+    charset = "qpzry9x8gf2tvdw0s3jn54khce6mua7l"
+    words, acc, bits = [], 0, 0
+    for byte in data:
+        acc, bits = (acc << 8) | byte, bits + 8
+        while bits >= 5:
+            bits -= 5
+            words.append((acc >> bits) & 31)
+    if bits:
+        words.append((acc << (5 - bits)) & 31)
+    values = [ord(c) >> 5 for c in prefix] + [0] + [ord(c) & 31 for c in prefix] + words
+    checksum = 1
+    for value in values + [0] * 6:
+        top = checksum >> 25
+        checksum = (checksum & 0x1FFFFFF) << 5 ^ value
+        for i, g in enumerate([0x3B6A57B2, 0x26508E6D, 0x1EA119FA, 0x3D4233DD, 0x2A1462B3]):
+            if (top >> i) & 1:
+                checksum ^= g
+    checksum ^= 1
+    words += [(checksum >> 5 * (5 - i)) & 31 for i in range(6)]
+    return prefix + "1" + "".join(charset[w] for w in words)
+
+
+def varint(value):
+    out = b""
+    while value > 0x7F:
+        out += bytes([value & 0x7F | 0x80])
+        value >>= 7
+    return out + bytes([value])
+
+
+def field(number, value):
+    """A length delimited field, or a varint field for ints, empty values are omitted"""
+    if isinstance(value, int):
+        return varint(number << 3) + varint(value) if value else b""
+    if isinstance(value, str):
+        value = value.encode()
+    return varint(number << 3 | 2) + varint(len(value)) + value if value else b""
+
+
+def coins(number, values):
+    out = b""
+    for value in values:
+        amount, denom = re.fullmatch(r"(\d+)(.+)", value).groups()
+        out += field(number, field(1, denom) + field(2, amount))
+    return out
+
+
+def any_(type_url, value):
+    return field(1, type_url) + field(2, value)
+
+
+def check(v):
+    secret = derive(v["mnemonic"], v["hd_path"])
+    pubkey = public_key(secret)
+    sha = hashlib.sha256(pubkey).digest()
+    address = bech32(v["prefix"], hashlib.new("ripemd160", sha).digest())
+    send = field(1, address) + field(2, v["to_address"]) + coins(3, v["amount"])
+    body = (
+        field(1, any_("/cosmos.bank.v1beta1.MsgSend", send))
+        + field(2, v["memo"])
+        + field(3, v["timeout_height"])
+    )
+    key = any_("/cosmos.crypto.secp256k1.PubKey", field(1, pubkey))
+    # SIGN_MODE_DIRECT
+    signer_info = field(1, key) + field(2, field(1, field(1, 1))) + field(3, v["sequence"])
+    fee = coins(1, v["fee"]) + field(2, v["gas_limit"])
+    auth_info = field(1, signer_info) + field(2, fee)
+    sign_doc = field(1, body) + field(2, auth_info) + field(3, v["chain_id"])
+    sign_doc += field(4, v["account_number"])
+    key = ec.derive_private_key(secret, ec.SECP256K1())
+    der = key.sign(sign_doc, ec.ECDSA(hashes.SHA256(), deterministic_signing=True))
+    r, s = decode_dss_signature(der)
+    signature = r.to_bytes(32, "big") + min(s, N - s).to_bytes(32, "big")
+    tx = field(1, body) + field(2, auth_info) + field(3, signature)
+    checks = {
+        "private_key": secret.to_bytes(32, "big").hex(),
+        "public_key": pubkey.hex(),
+        "address": address,
+        "body_bytes": body.hex(),
+        "auth_info_bytes": auth_info.hex(),
+        "sign_doc_bytes": sign_doc.hex(),
+        "signature": signature.hex(),
+        "tx_bytes": tx.hex(),
+        "tx_hash": hashlib.sha256(tx).hexdigest().upper(),
+    }
+    ok = True
+    for name, actual in checks.items():
+        if actual != v[name]:
+            print(f"{v['name']}: {name} is {actual}, expected {v[name]}")
+            ok = False
+    return ok
+
+
+with open(os.path.join(os.path.dirname(__file__), "sign_docs.json")) as f:
+    vectors = json.load(f)
+if not all([check(v) for v in vectors]):
+    sys.exit(1)
+print(f"{len(vectors)} vectors match")
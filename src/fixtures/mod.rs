@@ -0,0 +1,170 @@
+//! Canonical signing test vectors, from mnemonic to keys, addresses, sign docs and
+//! signatures for single message bank sends. Custom key backends such as hardware wallets
+//! or remote signers can use these to check that they produce byte identical sign docs and
+//! signatures to deep_space.
+//!
+//! Signatures are deterministic RFC6979 secp256k1 signatures normalized to a low S value, so
+//! any correct signer produces the same bytes.
+//!
+//! The vectors were generated with deep_space and every field is checked against two
+//! implementations that share no code with it. check_python.py derives the keys and
+//! addresses, encodes the sign doc and TxRaw and signs with the Python cryptography
+//! package. check_cosmjs.mjs signs each vector with CosmJS and compares the keys, sign doc
+//! parts and TxRaw. Run both after adding or changing a vector, the commands are at the
+//! top of each script. Only compiled for tests or with the fixtures feature.
+
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::utils::hex_str_to_bytes;
+use crate::Coin;
+use crate::Fee;
+use crate::MessageArgs;
+use crate::Msg;
+use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+const SIGN_DOCS: &str = include_str!("sign_docs.json");
+
+/// A signed single message bank send, all byte values are hex encoded
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct SignDocVector {
+    pub name: String,
+    pub mnemonic: String,
+    pub hd_path: String,
+    pub prefix: String,
+    pub private_key: String,
+    /// the compressed secp256k1 public key
+    pub public_key: String,
+    pub address: String,
+    pub chain_id: String,
+    pub account_number: u64,
+    pub sequence: u64,
+    pub memo: String,
+    pub timeout_height: u64,
+    pub to_address: String,
+    pub amount: Vec<String>,
+    pub fee: Vec<String>,
+    pub gas_limit: u64,
+    pub body_bytes: String,
+    pub auth_info_bytes: String,
+    pub sign_doc_bytes: String,
+    /// the 64 byte compact signature of the sha256 hash of the sign doc
+    pub signature: String,
+    /// the encoded TxRaw
+    pub tx_bytes: String,
+    pub tx_hash: String,
+}
+
+fn parse_coins(coins: &[String]) -> Vec<Coin> {
+    coins.iter().map(|c| c.parse().unwrap()).collect()
+}
+
+impl SignDocVector {
+    pub fn private_key(&self) -> PrivateKey {
+        self.private_key.parse().unwrap()
+    }
+
+    /// The messages of the transaction, a single MsgSend
+    pub fn messages(&self) -> Vec<Msg> {
+        let send = MsgSend {
+            from_address: self.address.clone(),
+            to_address: self.to_address.clone(),
+            amount: parse_coins(&self.amount)
+                .into_iter()
+                .map(|c| c.into())
+                .collect(),
+        };
+        vec![Msg::new(MSG_SEND_TYPE_URL, send)]
+    }
+
+    /// The arguments to sign the messages with
+    pub fn message_args(&self) -> MessageArgs {
+        MessageArgs {
+            sequence: self.sequence,
+            account_number: self.account_number,
+            chain_id: self.chain_id.clone(),
+            fee: Fee {
+                amount: parse_coins(&self.fee),
+                gas_limit: self.gas_limit,
+                payer: None,
+                granter: None,
+            },
+            timeout_height: self.timeout_height,
+        }
+    }
+
+    pub fn sign_doc_bytes(&self) -> Vec<u8> {
+        hex_str_to_bytes(&self.sign_doc_bytes).unwrap()
+    }
+
+    pub fn signature_bytes(&self) -> Vec<u8> {
+        hex_str_to_bytes(&self.signature).unwrap()
+    }
+
+    pub fn tx_raw_bytes(&self) -> Vec<u8> {
+        hex_str_to_bytes(&self.tx_bytes).unwrap()
+    }
+}
+
+/// Returns the signing test vectors
+pub fn sign_doc_vectors() -> Vec<SignDocVector> {
+    serde_json::from_str(SIGN_DOCS).expect("Invalid sign doc fixtures")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::bytes_to_hex_str;
+    use crate::utils::tx_hash;
+    use crate::utils::tx_sequence;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::SignDoc;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+    use prost::Message;
+
+    #[test]
+    fn test_sign_doc_vectors() {
+        let vectors = sign_doc_vectors();
+        assert!(!vectors.is_empty());
+        for vector in vectors {
+            let key =
+                PrivateKey::from_hd_wallet_path(&vector.hd_path, &vector.mnemonic, "").unwrap();
+            assert_eq!(key, vector.private_key(), "{}", vector.name);
+            let public_key = key.to_public_key("cosmospub").unwrap();
+            assert_eq!(bytes_to_hex_str(public_key.as_bytes()), vector.public_key);
+            assert_eq!(
+                key.to_address(&vector.prefix)
+                    .unwrap()
+                    .to_bech32(&vector.prefix)
+                    .unwrap(),
+                vector.address
+            );
+
+            let tx_bytes = key
+                .sign_std_msg(
+                    &vector.messages(),
+                    vector.message_args(),
+                    vector.memo.clone(),
+                )
+                .unwrap();
+            assert_eq!(tx_bytes, vector.tx_raw_bytes(), "{}", vector.name);
+            assert_eq!(tx_hash(&tx_bytes), vector.tx_hash);
+            assert_eq!(tx_sequence(&tx_bytes), Some(vector.sequence));
+
+            let raw = TxRaw::decode(tx_bytes.as_slice()).unwrap();
+            assert_eq!(bytes_to_hex_str(&raw.body_bytes), vector.body_bytes);
+            assert_eq!(
+                bytes_to_hex_str(&raw.auth_info_bytes),
+                vector.auth_info_bytes
+            );
+            assert_eq!(raw.signatures, vec![vector.signature_bytes()]);
+            let sign_doc = SignDoc {
+                body_bytes: raw.body_bytes,
+                auth_info_bytes: raw.auth_info_bytes,
+                chain_id: vector.chain_id.clone(),
+                account_number: vector.account_number,
+            };
+            let mut sign_doc_bytes = Vec::new();
+            sign_doc.encode(&mut sign_doc_bytes).unwrap();
+            assert_eq!(sign_doc_bytes, vector.sign_doc_bytes());
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Address confirmation on hardware wallets. A deposit address provisioned by a host can be
+//! swapped by malware on that host, so before an address is handed out the device is asked
+//! to display the address it derives for the path and the user approves it there. The
+//! address the device returns is then checked against its public key and against the
+//! address the host is about to use.
+//!
+//! The transport is not part of this crate, implement HardwareWallet for the device, for
+//! example with the GetAddrSecp256k1 command of the Ledger Cosmos app.
+//!
+//! ```ignore
+//! let path = HdPath::new(118, 0, index);
+//! let confirmed = confirm_address(&ledger, &path, "cosmos", Some(stored_address)).await?;
+//! ```
+//!
+//! Only Cosmos secp256k1 keys are supported, the address of an Ethermint key derived with
+//! coin type 60 is not the one PublicKey::to_address returns.
+
+use crate::error::HardwareWalletError;
+use crate::Address;
+use crate::PublicKey;
+use futures::future::BoxFuture;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// A BIP44 path m/44'/coin_type'/account'/change/index, the only form hardware wallet apps
+/// accept. Key derivation helpers such as coin_type::hd_path return this type as well, its
+/// Display form is what PrivateKey::from_hd_wallet_path takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HdPath {
+    pub coin_type: u32,
+    pub account: u32,
+    pub change: u32,
+    pub index: u32,
+}
+
+impl HdPath {
+    /// The path of the key with the given account and index on the external chain
+    pub fn new(coin_type: u32, account: u32, index: u32) -> Self {
+        HdPath {
+            coin_type,
+            account,
+            change: 0,
+            index,
+        }
+    }
+
+    /// The five path components as a device expects them, with the hardened bit set on the
+    /// first three
+    pub fn components(&self) -> [u32; 5] {
+        [
+            44 | HARDENED,
+            self.coin_type | HARDENED,
+            self.account | HARDENED,
+            self.change,
+            self.index,
+        ]
+    }
+}
+
+impl Display for HdPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "m/44'/{}'/{}'/{}/{}",
+            self.coin_type, self.account, self.change, self.index
+        )
+    }
+}
+
+impl FromStr for HdPath {
+    type Err = HardwareWalletError;
+
+    fn from_str(path: &str) -> Result<HdPath, HardwareWalletError> {
+        let unsupported = || HardwareWalletError::UnsupportedPath(path.to_string());
+        let parts: Vec<&str> = path.split('/').collect();
+        if parts.len() != 6 || parts[0] != "m" {
+            return Err(unsupported());
+        }
+        let mut values = [0u32; 5];
+        for (i, part) in parts[1..].iter().enumerate() {
+            let hardened = part.ends_with('\'');
+            // purpose, coin type and account must be hardened, change and index must not be
+            if hardened != (i < 3) {
+                return Err(unsupported());
+            }
+            values[i] = match part.trim_end_matches('\'').parse::<u32>() {
+                Ok(value) if value < HARDENED => value,
+                _ => return Err(unsupported()),
+            };
+        }
+        if values[0] != 44 {
+            return Err(unsupported());
+        }
+        Ok(HdPath {
+            coin_type: values[1],
+            account: values[2],
+            change: values[3],
+            index: values[4],
+        })
+    }
+}
+
+/// What a device returns for a path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceAddress {
+    /// the 33 byte compressed secp256k1 public key
+    pub public_key: Vec<u8>,
+    /// the bech32 address the device derived, and displayed if asked to
+    pub address: String,
+}
+
+/// A hardware wallet holding Cosmos secp256k1 keys
+pub trait HardwareWallet: Send + Sync {
+    /// Returns the public key and address of the path. If display is set the device shows
+    /// the address and only returns once the user approved it, a rejection must be returned
+    /// as HardwareWalletError::Rejected.
+    fn get_address<'a>(
+        &'a self,
+        path: &'a HdPath,
+        prefix: &'a str,
+        display: bool,
+    ) -> BoxFuture<'a, Result<DeviceAddress, HardwareWalletError>>;
+}
+
+/// An address the user approved on the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmedAddress {
+    pub path: HdPath,
+    pub address: Address,
+    pub public_key: PublicKey,
+}
+
+/// Displays the address of the path on the device and waits for the user to approve it. The
+/// address is derived again from the public key the device returned and must equal the
+/// address it displayed, and expected if provided, which is the address the host stored or
+/// is about to hand out.
+pub async fn confirm_address(
+    device: &dyn HardwareWallet,
+    path: &HdPath,
+    prefix: &str,
+    expected: Option<Address>,
+) -> Result<ConfirmedAddress, HardwareWalletError> {
+    let returned = device.get_address(path, prefix, true).await?;
+    let public_key = PublicKey::from_slice(&returned.public_key, PublicKey::DEFAULT_PREFIX)?;
+    let mismatch = |expected: String| HardwareWalletError::AddressMismatch {
+        device: returned.address.clone(),
+        expected,
+    };
+    let address = public_key
+        .to_address_with_prefix(prefix)
+        .map_err(|e| HardwareWalletError::Device(e.to_string()))?;
+    if address.to_string() != returned.address {
+        return Err(mismatch(address.to_string()));
+    }
+    if let Some(expected) = expected {
+        if !expected.same_account(&address) || expected.get_prefix() != prefix {
+            return Err(mismatch(expected.to_string()));
+        }
+    }
+    Ok(ConfirmedAddress {
+        path: *path,
+        address,
+        public_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrivateKey;
+    use futures::FutureExt;
+
+    const PHRASE: &str = "purse sure leg gap above pull rescue glass circle attract erupt can \
+        sail gasp shy clarify inflict anger sketch hobby scare mad reject where";
+
+    /// Derives keys in software, swap_key derives a different key than it displays
+    struct TestDevice {
+        swap_key: bool,
+    }
+
+    impl HardwareWallet for TestDevice {
+        fn get_address<'a>(
+            &'a self,
+            path: &'a HdPath,
+            prefix: &'a str,
+            display: bool,
+        ) -> BoxFuture<'a, Result<DeviceAddress, HardwareWalletError>> {
+            assert!(display);
+            let derive = |path: &HdPath| {
+                PrivateKey::from_hd_wallet_path(&path.to_string(), PHRASE, "").unwrap()
+            };
+            let key = derive(path);
+            let address = key.to_address(prefix).unwrap().to_string();
+            let key = if self.swap_key {
+                derive(&HdPath::new(path.coin_type, path.account, path.index + 1))
+            } else {
+                key
+            };
+            let public_key = key.to_public_key("cosmospub").unwrap().to_vec();
+            futures::future::ready(Ok(DeviceAddress {
+                public_key,
+                address,
+            }))
+            .boxed()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_confirm_address() {
+        let path: HdPath = "m/44'/118'/0'/0/3".parse().unwrap();
+        assert_eq!(path, HdPath::new(118, 0, 3));
+        assert_eq!(path.to_string(), "m/44'/118'/0'/0/3");
+        assert_eq!(path.components()[1], 118 | HARDENED);
+        assert!("m/44'/118'/0'/0'/3".parse::<HdPath>().is_err());
+        assert!("m/44'/118'/0'/0".parse::<HdPath>().is_err());
+
+        let stored = PrivateKey::from_hd_wallet_path(&path.to_string(), PHRASE, "")
+            .unwrap()
+            .to_address("cosmos")
+            .unwrap();
+        let device = TestDevice { swap_key: false };
+        let confirmed = confirm_address(&device, &path, "cosmos", Some(stored))
+            .await
+            .unwrap();
+        assert_eq!(confirmed.address, stored);
+
+        // the host swapped the address it stored
+        let other = HdPath::new(118, 0, 4);
+        assert!(confirm_address(&device, &other, "cosmos", Some(stored))
+            .await
+            .is_err());
+        // the returned key does not belong to the displayed address
+        let device = TestDevice { swap_key: true };
+        match confirm_address(&device, &path, "cosmos", None).await {
+            Err(HardwareWalletError::AddressMismatch { device, .. }) => {
+                assert_eq!(device, stored.to_string())
+            }
+            other => panic!("Expected AddressMismatch, got {:?}", other),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 #![warn(clippy::all)]
 #![allow(clippy::pedantic)]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "ffi"), forbid(unsafe_code))]
+#![cfg_attr(feature = "ffi", deny(unsafe_code))]
 
 extern crate base64;
 extern crate bech32;
@@ -17,24 +18,53 @@ extern crate log;
 extern crate serde_derive;
 
 pub mod address;
+pub mod amino;
+pub mod any;
+pub mod canonical_json;
 pub mod client;
 pub mod coin;
+pub mod coin_type;
+pub mod cosmjs;
+#[cfg(feature = "cosmrs")]
+pub mod cosmrs;
+pub mod dec;
 pub mod decimal;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
+pub mod hardware_wallet;
 pub mod mnemonic;
 pub mod msg;
 pub mod private_key;
+pub mod proto;
 pub mod public_key;
+pub mod registry;
 pub mod signature;
+pub mod summary;
+#[cfg(feature = "tendermint")]
+pub mod tendermint;
+#[cfg(feature = "test-harness")]
+pub mod test_harness;
 pub mod utils;
 
 pub use address::Address;
+pub use any::AnyExt;
 pub use client::Contact;
 pub use coin::Coin;
+pub use coin::Coins;
 pub use coin::Fee;
+pub use coin_type::CoinTypeRegistry;
+pub use dec::Dec;
+pub use dec::DecCoin;
+pub use hardware_wallet::HdPath;
 pub use mnemonic::Mnemonic;
 pub use msg::Msg;
 pub use private_key::MessageArgs;
 pub use private_key::PrivateKey;
 pub use public_key::PublicKey;
+pub use registry::MsgRegistry;
 pub use signature::Signature;
+pub use summary::summarize_tx;
+pub use summary::TxSummary;
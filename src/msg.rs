@@ -21,6 +21,11 @@ impl Msg {
             value: buf.to_vec(),
         })
     }
+
+    /// Returns the type url of this message
+    pub fn type_url(&self) -> &str {
+        &self.0.type_url
+    }
 }
 
 impl From<Any> for Msg {
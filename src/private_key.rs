@@ -1,5 +1,8 @@
+use crate::coin_type::CoinTypeRegistry;
 use crate::mnemonic::Mnemonic;
 use crate::msg::Msg;
+#[cfg(feature = "sdk-0_53")]
+use crate::proto::cosmos::tx::v1beta1::TxBody as UnorderedTxBody;
 use crate::public_key::PublicKey;
 use crate::utils::bytes_to_hex_str;
 use crate::utils::encode_any;
@@ -14,12 +17,18 @@ use cosmos_sdk_proto::cosmos::tx::v1beta1::{
 use num_bigint::BigUint;
 use prost::Message;
 use secp256k1::constants::CURVE_ORDER as CurveN;
+use secp256k1::All;
 use secp256k1::Message as CurveMessage;
 use secp256k1::Secp256k1;
 use secp256k1::{PublicKey as PublicKeyEC, SecretKey};
 use sha2::Sha512;
 use sha2::{Digest, Sha256};
+use sha3::Keccak256;
 use std::str::FromStr;
+#[cfg(feature = "global-context")]
+use std::sync::OnceLock;
+#[cfg(feature = "sdk-0_53")]
+use std::time::SystemTime;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct MessageArgs {
@@ -86,6 +95,24 @@ impl PrivateKey {
         PrivateKey::from_hd_wallet_path("m/44'/118'/0'/0/0", phrase, passphrase)
     }
 
+    /// Imports the first key of a phrase for the chain using the address prefix, with the
+    /// coin type the registry has for that prefix, see the coin_type module
+    pub fn from_phrase_for_prefix(
+        phrase: &str,
+        passphrase: &str,
+        prefix: &str,
+        registry: &CoinTypeRegistry,
+    ) -> Result<PrivateKey, PrivateKeyError> {
+        if phrase.is_empty() {
+            return Err(HdWalletError::Bip39Error(Bip39Error::BadWordCount(0)).into());
+        }
+        PrivateKey::from_hd_wallet_path(
+            &registry.hd_path(prefix, 0).to_string(),
+            phrase,
+            passphrase,
+        )
+    }
+
     pub fn from_hd_wallet_path(
         path: &str,
         phrase: &str,
@@ -98,7 +125,7 @@ impl PrivateKey {
         // discard the m
         let _ = iterator.next();
 
-        let key_import = Mnemonic::from_str(phrase).unwrap();
+        let key_import = Mnemonic::from_str(phrase).map_err(HdWalletError::Bip39Error)?;
         let seed_bytes = key_import.to_seed(passphrase);
         let (master_secret_key, master_chain_code) = master_key_from_seed(&seed_bytes);
         let mut secret_key = master_secret_key;
@@ -121,11 +148,22 @@ impl PrivateKey {
         Ok(PrivateKey(secret_key))
     }
 
+    /// Creates a private key from its 32 byte secret, fails if the secret is not a valid
+    /// secp256k1 scalar
+    pub fn from_bytes(bytes: [u8; 32]) -> Result<PrivateKey, PrivateKeyError> {
+        SecretKey::from_slice(&bytes)?;
+        Ok(PrivateKey(bytes))
+    }
+
+    /// The 32 byte secret of this key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
     /// Obtain a public key for a given private key
     pub fn to_public_key(&self, prefix: &str) -> Result<PublicKey, PrivateKeyError> {
-        let secp256k1 = Secp256k1::new();
         let sk = SecretKey::from_slice(&self.0)?;
-        let pkey = PublicKeyEC::from_secret_key(&secp256k1, &sk);
+        let pkey = with_context(|secp256k1| PublicKeyEC::from_secret_key(secp256k1, &sk));
         let compressed = pkey.serialize();
         Ok(PublicKey::from_bytes(compressed, prefix)?)
     }
@@ -137,6 +175,52 @@ impl PrivateKey {
         Ok(address)
     }
 
+    /// The 20 bytes of the Ethereum address of this key, the last 20 bytes of the keccak256
+    /// hash of the uncompressed public key. Ethermint chains use the same bytes for the
+    /// bech32 address of an eth_secp256k1 key.
+    pub fn to_eth_address_bytes(&self) -> Result<[u8; 20], PrivateKeyError> {
+        let sk = SecretKey::from_slice(&self.0)?;
+        let pkey = with_context(|secp256k1| PublicKeyEC::from_secret_key(secp256k1, &sk));
+        let hash = Keccak256::digest(&pkey.serialize_uncompressed()[1..]);
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(&hash[12..]);
+        Ok(bytes)
+    }
+
+    /// The EIP-55 checksummed 0x address of this key
+    pub fn to_eth_address(&self) -> Result<String, PrivateKeyError> {
+        let hex = bytes_to_hex_str(&self.to_eth_address_bytes()?);
+        let hash = Keccak256::digest(hex.as_bytes());
+        let checksummed: String = hex
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        Ok(format!("0x{}", checksummed))
+    }
+
+    /// The bech32 address of this key as an Ethermint eth_secp256k1 key
+    pub fn to_ethermint_address(&self, prefix: &str) -> Result<Address, PrivateKeyError> {
+        Ok(Address::from_bytes(self.to_eth_address_bytes()?, prefix)?)
+    }
+
+    /// Signs the sha256 hash of the message, returning the 64 byte compact signature that
+    /// PublicKey::verify checks. This is how sign docs are signed.
+    pub fn sign_bytes(&self, message: &[u8]) -> Result<Vec<u8>, PrivateKeyError> {
+        let sk = SecretKey::from_slice(&self.0)?;
+        let digest = Sha256::digest(message);
+        let msg = CurveMessage::from_slice(&digest)?;
+        let signed = with_context(|secp256k1| secp256k1.sign(&msg, &sk));
+        Ok(signed.serialize_compact().to_vec())
+    }
+
     /// Internal function that that handles building a single message to sign
     /// returns an internal struct containing the parts of the built transaction
     /// in a way that's easy to mix and match for various uses and output types.
@@ -146,8 +230,6 @@ impl PrivateKey {
         args: MessageArgs,
         memo: impl Into<String>,
     ) -> Result<TxParts, PrivateKeyError> {
-        // prefix does not matter in this case, you could use a blank string
-        let our_pubkey = self.to_public_key(PublicKey::DEFAULT_PREFIX)?;
         // Create TxBody
         let body = TxBody {
             messages: messages.iter().map(|msg| msg.0.clone()).collect(),
@@ -161,6 +243,28 @@ impl PrivateKey {
         let mut body_buf = Vec::new();
         body.encode(&mut body_buf).unwrap();
 
+        let (auth_info, auth_buf, signatures) = self.sign_body(&body_buf, args)?;
+
+        Ok(TxParts {
+            body,
+            body_buf,
+            auth_info,
+            auth_buf,
+            signatures,
+        })
+    }
+
+    /// Builds the AuthInfo for an encoded TxBody and signs the resulting SignDoc, returns
+    /// the AuthInfo, its encoding and the signatures
+    #[allow(clippy::type_complexity)]
+    fn sign_body(
+        &self,
+        body_buf: &[u8],
+        args: MessageArgs,
+    ) -> Result<(AuthInfo, Vec<u8>, Vec<Vec<u8>>), PrivateKeyError> {
+        // prefix does not matter in this case, you could use a blank string
+        let our_pubkey = self.to_public_key(PublicKey::DEFAULT_PREFIX)?;
+
         let key = ProtoSecp256k1Pubkey {
             key: our_pubkey.to_vec(),
         };
@@ -189,7 +293,7 @@ impl PrivateKey {
         auth_info.encode(&mut auth_buf).unwrap();
 
         let sign_doc = SignDoc {
-            body_bytes: body_buf.clone(),
+            body_bytes: body_buf.to_vec(),
             auth_info_bytes: auth_buf.clone(),
             chain_id: args.chain_id.to_string(),
             account_number: args.account_number,
@@ -199,21 +303,10 @@ impl PrivateKey {
         let mut signdoc_buf = Vec::new();
         sign_doc.encode(&mut signdoc_buf).unwrap();
 
-        let secp256k1 = Secp256k1::new();
-        let sk = SecretKey::from_slice(&self.0)?;
-        let digest = Sha256::digest(&signdoc_buf);
-        let msg = CurveMessage::from_slice(&digest)?;
         // Sign the signdoc
-        let signed = secp256k1.sign(&msg, &sk);
-        let compact = signed.serialize_compact().to_vec();
+        let compact = self.sign_bytes(&signdoc_buf)?;
 
-        Ok(TxParts {
-            body,
-            body_buf,
-            auth_info,
-            auth_buf,
-            signatures: vec![compact],
-        })
+        Ok((auth_info, auth_buf, vec![compact]))
     }
 
     /// Signs a transaction that contains at least one message using a single
@@ -255,6 +348,41 @@ impl PrivateKey {
 
         Ok(txraw_buf)
     }
+
+    /// Signs an unordered transaction, returning the encoded TxRaw. Unordered transactions
+    /// are not checked against the account sequence, they are deduplicated by hash until
+    /// the timeout, which must be within the maximum the chain allows. Only chains on Cosmos
+    /// SDK v0.53 or later with unordered transactions enabled accept these.
+    #[cfg(feature = "sdk-0_53")]
+    pub fn sign_unordered_msg(
+        &self,
+        messages: &[Msg],
+        args: MessageArgs,
+        memo: impl Into<String>,
+        timeout: SystemTime,
+    ) -> Result<Vec<u8>, PrivateKeyError> {
+        let body = UnorderedTxBody {
+            messages: messages.iter().map(|msg| msg.0.clone()).collect(),
+            memo: memo.into(),
+            timeout_height: args.timeout_height,
+            unordered: true,
+            timeout_timestamp: Some(timeout.into()),
+            extension_options: Default::default(),
+            non_critical_extension_options: Default::default(),
+        };
+        let mut body_buf = Vec::new();
+        body.encode(&mut body_buf).unwrap();
+
+        let (_, auth_buf, signatures) = self.sign_body(&body_buf, args)?;
+        let tx_raw = TxRaw {
+            body_bytes: body_buf,
+            auth_info_bytes: auth_buf,
+            signatures,
+        };
+        let mut txraw_buf = Vec::new();
+        tx_raw.encode(&mut txraw_buf).unwrap();
+        Ok(txraw_buf)
+    }
 }
 
 impl FromStr for PrivateKey {
@@ -303,6 +431,21 @@ fn master_key_from_seed(seed_bytes: &[u8]) -> ([u8; 32], [u8; 32]) {
     (master_secret_key, master_chain_code)
 }
 
+/// Runs f with a signing context. Building a context computes the precomputed tables used for
+/// signing, which takes longer than the signature itself, so with the global-context feature a
+/// single context is built on first use and kept for the life of the process.
+#[cfg(feature = "global-context")]
+fn with_context<T>(f: impl FnOnce(&Secp256k1<All>) -> T) -> T {
+    static CONTEXT: OnceLock<Secp256k1<All>> = OnceLock::new();
+    f(CONTEXT.get_or_init(Secp256k1::new))
+}
+
+/// Runs f with a signing context, see the global-context feature to reuse a single context
+#[cfg(not(feature = "global-context"))]
+fn with_context<T>(f: impl FnOnce(&Secp256k1<All>) -> T) -> T {
+    f(&Secp256k1::new())
+}
+
 /// This keys the child key following the bip32 https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
 /// specified derivation method. This method is internal because you should really be using the public API that
 /// handles key path parsing.
@@ -323,9 +466,8 @@ fn get_child_key(
         hasher.update(&[0u8]);
         hasher.update(&k_parent);
     } else {
-        let scep = Secp256k1::new();
         let private_key = SecretKey::from_slice(&k_parent).unwrap();
-        let public_key = PublicKeyEC::from_secret_key(&scep, &private_key);
+        let public_key = with_context(|scep| PublicKeyEC::from_secret_key(scep, &private_key));
         hasher.update(&public_key.serialize());
     }
     hasher.update(&i.to_be_bytes());
@@ -393,6 +535,22 @@ fn test_secret() {
     );
 }
 
+#[test]
+fn test_eth_address() {
+    let phrase = "test test test test test test test test test test test junk";
+    let key = PrivateKey::from_hd_wallet_path("m/44'/60'/0'/0/0", phrase, "").unwrap();
+    assert_eq!(
+        key.to_eth_address().unwrap(),
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"
+    );
+    let address = key.to_ethermint_address("evmos").unwrap();
+    assert_eq!(address.get_prefix(), "evmos");
+    assert_eq!(
+        bytes_to_hex_str(address.as_bytes()),
+        "f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+    );
+}
+
 #[test]
 fn test_cosmos_key_derivation_manual() {
     let words = "purse sure leg gap above pull rescue glass circle attract erupt can sail gasp shy clarify inflict anger sketch hobby scare mad reject where";
@@ -553,3 +711,46 @@ fn test_many_key_generation() {
         let _cosmos_address = cosmos_key.to_public_key("cosmospub").unwrap().to_address();
     }
 }
+
+#[test]
+#[cfg(feature = "sdk-0_53")]
+fn test_sign_unordered_msg() {
+    use crate::fixtures::sign_doc_vectors;
+    use std::time::Duration;
+
+    let vector = &sign_doc_vectors()[0];
+    let key = vector.private_key();
+    let timeout = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    let bytes = key
+        .sign_unordered_msg(&vector.messages(), vector.message_args(), "", timeout)
+        .unwrap();
+    let raw = TxRaw::decode(bytes.as_slice()).unwrap();
+    let body = UnorderedTxBody::decode(raw.body_bytes.as_slice()).unwrap();
+    assert!(body.unordered);
+    assert_eq!(body.timeout_timestamp.unwrap().seconds, 1_700_000_000);
+
+    let sign_doc = SignDoc {
+        body_bytes: raw.body_bytes,
+        auth_info_bytes: raw.auth_info_bytes,
+        chain_id: vector.chain_id.clone(),
+        account_number: vector.account_number,
+    };
+    let mut sign_doc_bytes = Vec::new();
+    sign_doc.encode(&mut sign_doc_bytes).unwrap();
+    let public_key = key.to_public_key("cosmospub").unwrap();
+    assert!(public_key.verify(&sign_doc_bytes, &raw.signatures[0]));
+}
+
+#[test]
+fn test_from_phrase_for_prefix() {
+    let vector = &crate::fixtures::sign_doc_vectors()[0];
+    let phrase = vector.mnemonic.as_str();
+    let registry = CoinTypeRegistry::with_defaults();
+    let cosmos = PrivateKey::from_phrase_for_prefix(phrase, "", "cosmos", &registry).unwrap();
+    assert_eq!(cosmos, PrivateKey::from_phrase(phrase, "").unwrap());
+    let cro = PrivateKey::from_phrase_for_prefix(phrase, "", "cro", &registry).unwrap();
+    assert_eq!(
+        cro,
+        PrivateKey::from_hd_wallet_path("m/44'/394'/0'/0/0", phrase, "").unwrap()
+    );
+}
@@ -0,0 +1,10 @@
+/// Bech32PrefixRequest is the request type for Bech32Prefix rpc method.
+/// Added in Cosmos SDK v0.46.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bech32PrefixRequest {}
+/// Bech32PrefixResponse is the response type for Bech32Prefix rpc method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Bech32PrefixResponse {
+    #[prost(string, tag = "1")]
+    pub bech32_prefix: ::prost::alloc::string::String,
+}
@@ -0,0 +1,65 @@
+/// MsgData defines the data returned in a Result object during message
+/// execution.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgData {
+    #[prost(string, tag = "1")]
+    pub msg_type: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+/// TxMsgData defines a list of MsgData. A transaction will have a MsgData object
+/// for each message.
+///
+/// This includes the msg_responses field added in Cosmos SDK v0.46 which is not
+/// present in the version of cosmos-sdk-proto this crate depends on.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxMsgData {
+    /// data field is deprecated and not populated.
+    #[prost(message, repeated, tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<MsgData>,
+    /// msg_responses contains the Msg handler responses packed into Anys.
+    ///
+    /// Since: cosmos-sdk 0.46
+    #[prost(message, repeated, tag = "2")]
+    pub msg_responses: ::prost::alloc::vec::Vec<::prost_types::Any>,
+}
+/// TxResponse as of Cosmos SDK v0.50, which no longer fills logs and returns the events
+/// of the transaction in the events field instead. The TxResponse in cosmos-sdk-proto
+/// lacks the events field so it is only replaced with the sdk-0_50 feature.
+#[cfg(feature = "sdk-0_50")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxResponse {
+    #[prost(int64, tag = "1")]
+    pub height: i64,
+    #[prost(string, tag = "2")]
+    pub txhash: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub codespace: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "4")]
+    pub code: u32,
+    #[prost(string, tag = "5")]
+    pub data: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub raw_log: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "7")]
+    pub logs:
+        ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog>,
+    #[prost(string, tag = "8")]
+    pub info: ::prost::alloc::string::String,
+    #[prost(int64, tag = "9")]
+    pub gas_wanted: i64,
+    #[prost(int64, tag = "10")]
+    pub gas_used: i64,
+    #[prost(message, optional, tag = "11")]
+    pub tx: ::core::option::Option<::prost_types::Any>,
+    #[prost(string, tag = "12")]
+    pub timestamp: ::prost::alloc::string::String,
+    /// events of the whole transaction, including those of the ante handler which have
+    /// no msg_index attribute
+    ///
+    /// Since: cosmos-sdk 0.45
+    #[prost(message, repeated, tag = "13")]
+    pub events: ::prost::alloc::vec::Vec<tendermint_proto::abci::Event>,
+}
+#[cfg(not(feature = "sdk-0_50"))]
+pub use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
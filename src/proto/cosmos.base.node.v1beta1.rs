@@ -0,0 +1,12 @@
+/// ConfigRequest defines the request structure for the Config gRPC query.
+/// Added in Cosmos SDK v0.46.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfigRequest {}
+/// ConfigResponse defines the response structure for the Config gRPC query.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConfigResponse {
+    /// the minimum gas prices of the queried node as a comma separated list
+    /// of DecCoins, for example 0.025uatom,0.1ustake
+    #[prost(string, tag = "1")]
+    pub minimum_gas_price: ::prost::alloc::string::String,
+}
@@ -0,0 +1,11 @@
+/// QueryParamsRequest defines the request type for querying x/consensus parameters.
+/// Added in Cosmos SDK v0.47, older chains do not expose consensus params over gRPC.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryParamsRequest {}
+/// QueryParamsResponse defines the response type for querying x/consensus parameters.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryParamsResponse {
+    /// params are the tendermint consensus params stored in the consensus module.
+    #[prost(message, optional, tag = "1")]
+    pub params: ::core::option::Option<tendermint_proto::types::ConsensusParams>,
+}
@@ -0,0 +1,35 @@
+/// MsgSubmitProposal defines an sdk.Msg type that supports submitting arbitrary
+/// proposal Content.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitProposal {
+    /// messages are the arbitrary messages to be executed if proposal passes.
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<::prost_types::Any>,
+    /// initial_deposit is the deposit value that must be paid at proposal submission.
+    #[prost(message, repeated, tag = "2")]
+    pub initial_deposit:
+        ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+    /// proposer is the account address of the proposer.
+    #[prost(string, tag = "3")]
+    pub proposer: ::prost::alloc::string::String,
+    /// metadata is any arbitrary metadata attached to the proposal.
+    #[prost(string, tag = "4")]
+    pub metadata: ::prost::alloc::string::String,
+    /// title is the title of the proposal. Added in Cosmos SDK v0.47.
+    #[prost(string, tag = "5")]
+    pub title: ::prost::alloc::string::String,
+    /// summary is the summary of the proposal. Added in Cosmos SDK v0.47.
+    #[prost(string, tag = "6")]
+    pub summary: ::prost::alloc::string::String,
+}
+/// MsgExecLegacyContent is used to wrap the legacy content field into a message.
+/// This ensures backwards compatibility with v1beta1.MsgSubmitProposal.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExecLegacyContent {
+    /// content is the proposal's content.
+    #[prost(message, optional, tag = "1")]
+    pub content: ::core::option::Option<::prost_types::Any>,
+    /// authority must be the gov module address.
+    #[prost(string, tag = "2")]
+    pub authority: ::prost::alloc::string::String,
+}
@@ -0,0 +1,23 @@
+/// WeightedVoteOption defines a unit of vote for vote split. Added in Cosmos SDK v0.43.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedVoteOption {
+    #[prost(
+        enumeration = "cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption",
+        tag = "1"
+    )]
+    pub option: i32,
+    /// weight is an sdk.Dec encoded as its integer representation
+    #[prost(string, tag = "2")]
+    pub weight: ::prost::alloc::string::String,
+}
+/// MsgVoteWeighted defines a message to cast a vote, with an option to split
+/// the vote. Added in Cosmos SDK v0.43.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgVoteWeighted {
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    #[prost(string, tag = "2")]
+    pub voter: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub options: ::prost::alloc::vec::Vec<WeightedVoteOption>,
+}
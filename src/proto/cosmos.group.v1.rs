@@ -0,0 +1,216 @@
+/// MemberRequest represents a group member to be used in Msg server requests.
+/// Contrary to `Member`, it doesn't have any `added_at` field
+/// since this field cannot be set as part of requests.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MemberRequest {
+    /// address is the member's account address.
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    /// weight is the member's voting weight that should be greater than 0.
+    #[prost(string, tag = "2")]
+    pub weight: ::prost::alloc::string::String,
+    /// metadata is any arbitrary metadata attached to the member.
+    #[prost(string, tag = "3")]
+    pub metadata: ::prost::alloc::string::String,
+}
+/// ThresholdDecisionPolicy is a decision policy where a proposal passes when it
+/// satisfies the two following conditions:
+/// 1. The sum of all `YES` voter's weights is greater or equal than the defined
+///    `threshold`.
+/// 2. The voting and execution periods of the proposal respect the parameters
+///    given by `windows`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ThresholdDecisionPolicy {
+    /// threshold is the minimum weighted sum of `YES` votes that must be met or
+    /// exceeded for a proposal to succeed.
+    #[prost(string, tag = "1")]
+    pub threshold: ::prost::alloc::string::String,
+    /// windows defines the different windows for voting and execution.
+    #[prost(message, optional, tag = "2")]
+    pub windows: ::core::option::Option<DecisionPolicyWindows>,
+}
+/// PercentageDecisionPolicy is a decision policy where a proposal passes when
+/// it satisfies the two following conditions:
+/// 1. The percentage of all `YES` voters' weights out of the total group weight
+///    is greater or equal than the given `percentage`.
+/// 2. The voting and execution periods of the proposal respect the parameters
+///    given by `windows`.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PercentageDecisionPolicy {
+    /// percentage is the minimum percentage of the weighted sum of `YES` votes must
+    /// meet for a proposal to succeed.
+    #[prost(string, tag = "1")]
+    pub percentage: ::prost::alloc::string::String,
+    /// windows defines the different windows for voting and execution.
+    #[prost(message, optional, tag = "2")]
+    pub windows: ::core::option::Option<DecisionPolicyWindows>,
+}
+/// DecisionPolicyWindows defines the different windows for voting and execution.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecisionPolicyWindows {
+    /// voting_period is the duration from submission of a proposal to the end of voting period
+    /// Within this times votes can be submitted with MsgVote.
+    #[prost(message, optional, tag = "1")]
+    pub voting_period: ::core::option::Option<::prost_types::Duration>,
+    /// min_execution_period is the minimum duration after the proposal submission
+    /// where members can start sending MsgExec. This means that the window for
+    /// sending a MsgExec transaction is:
+    /// `[ submission + min_execution_period ; submission + voting_period + max_execution_period]`
+    /// where max_execution_period is a app-specific config, defined in the keeper.
+    /// If not set, min_execution_period will default to 0.
+    #[prost(message, optional, tag = "2")]
+    pub min_execution_period: ::core::option::Option<::prost_types::Duration>,
+}
+/// MsgCreateGroup is the Msg/CreateGroup request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreateGroup {
+    /// admin is the account address of the group admin.
+    #[prost(string, tag = "1")]
+    pub admin: ::prost::alloc::string::String,
+    /// members defines the group members.
+    #[prost(message, repeated, tag = "2")]
+    pub members: ::prost::alloc::vec::Vec<MemberRequest>,
+    /// metadata is any arbitrary metadata to attached to the group.
+    #[prost(string, tag = "3")]
+    pub metadata: ::prost::alloc::string::String,
+}
+/// MsgCreateGroupPolicy is the Msg/CreateGroupPolicy request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreateGroupPolicy {
+    /// admin is the account address of the group admin.
+    #[prost(string, tag = "1")]
+    pub admin: ::prost::alloc::string::String,
+    /// group_id is the unique ID of the group.
+    #[prost(uint64, tag = "2")]
+    pub group_id: u64,
+    /// metadata is any arbitrary metadata attached to the group policy.
+    #[prost(string, tag = "3")]
+    pub metadata: ::prost::alloc::string::String,
+    /// decision_policy specifies the group policy's decision policy.
+    #[prost(message, optional, tag = "4")]
+    pub decision_policy: ::core::option::Option<::prost_types::Any>,
+}
+/// MsgCreateGroupWithPolicy is the Msg/CreateGroupWithPolicy request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreateGroupWithPolicy {
+    /// admin is the account address of the group and group policy admin.
+    #[prost(string, tag = "1")]
+    pub admin: ::prost::alloc::string::String,
+    /// members defines the group members.
+    #[prost(message, repeated, tag = "2")]
+    pub members: ::prost::alloc::vec::Vec<MemberRequest>,
+    /// group_metadata is any arbitrary metadata attached to the group.
+    #[prost(string, tag = "3")]
+    pub group_metadata: ::prost::alloc::string::String,
+    /// group_policy_metadata is any arbitrary metadata attached to the group policy.
+    #[prost(string, tag = "4")]
+    pub group_policy_metadata: ::prost::alloc::string::String,
+    /// group_policy_as_admin is a boolean field, if set to true, the group policy account address will be used as group
+    /// and group policy admin.
+    #[prost(bool, tag = "5")]
+    pub group_policy_as_admin: bool,
+    /// decision_policy specifies the group policy's decision policy.
+    #[prost(message, optional, tag = "6")]
+    pub decision_policy: ::core::option::Option<::prost_types::Any>,
+}
+/// MsgSubmitProposal is the Msg/SubmitProposal request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitProposal {
+    /// group_policy_address is the account address of group policy.
+    #[prost(string, tag = "1")]
+    pub group_policy_address: ::prost::alloc::string::String,
+    /// proposers are the account addresses of the proposers.
+    /// Proposers signatures will be counted as yes votes.
+    #[prost(string, repeated, tag = "2")]
+    pub proposers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// metadata is any arbitrary metadata attached to the proposal.
+    #[prost(string, tag = "3")]
+    pub metadata: ::prost::alloc::string::String,
+    /// messages is a list of `sdk.Msg`s that will be executed if the proposal passes.
+    #[prost(message, repeated, tag = "4")]
+    pub messages: ::prost::alloc::vec::Vec<::prost_types::Any>,
+    /// exec defines the mode of execution of the proposal,
+    /// whether it should be executed immediately on creation or not.
+    /// If so, proposers signatures are considered as Yes votes.
+    #[prost(enumeration = "Exec", tag = "5")]
+    pub exec: i32,
+    /// title is the title of the proposal.
+    ///
+    /// Since: cosmos-sdk 0.47
+    #[prost(string, tag = "6")]
+    pub title: ::prost::alloc::string::String,
+    /// summary is the summary of the proposal.
+    ///
+    /// Since: cosmos-sdk 0.47
+    #[prost(string, tag = "7")]
+    pub summary: ::prost::alloc::string::String,
+}
+/// MsgVote is the Msg/Vote request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgVote {
+    /// proposal is the unique ID of the proposal.
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    /// voter is the voter account address.
+    #[prost(string, tag = "2")]
+    pub voter: ::prost::alloc::string::String,
+    /// option is the voter's choice on the proposal.
+    #[prost(enumeration = "VoteOption", tag = "3")]
+    pub option: i32,
+    /// metadata is any arbitrary metadata attached to the vote.
+    #[prost(string, tag = "4")]
+    pub metadata: ::prost::alloc::string::String,
+    /// exec defines whether the proposal should be executed
+    /// immediately after voting or not.
+    #[prost(enumeration = "Exec", tag = "5")]
+    pub exec: i32,
+}
+/// MsgExec is the Msg/Exec request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExec {
+    /// proposal is the unique ID of the proposal.
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    /// executor is the account address used to execute the proposal.
+    #[prost(string, tag = "2")]
+    pub executor: ::prost::alloc::string::String,
+}
+/// MsgWithdrawProposal is the Msg/WithdrawProposal request type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgWithdrawProposal {
+    /// proposal is the unique ID of the proposal.
+    #[prost(uint64, tag = "1")]
+    pub proposal_id: u64,
+    /// address is the admin of the group policy or one of the proposer of the proposal.
+    #[prost(string, tag = "2")]
+    pub address: ::prost::alloc::string::String,
+}
+/// VoteOption enumerates the valid vote options for a given proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum VoteOption {
+    /// VOTE_OPTION_UNSPECIFIED defines an unspecified vote option which will
+    /// return an error.
+    Unspecified = 0,
+    /// VOTE_OPTION_YES defines a yes vote option.
+    Yes = 1,
+    /// VOTE_OPTION_ABSTAIN defines an abstain vote option.
+    Abstain = 2,
+    /// VOTE_OPTION_NO defines a no vote option.
+    No = 3,
+    /// VOTE_OPTION_NO_WITH_VETO defines a no with veto vote option.
+    NoWithVeto = 4,
+}
+/// Exec defines modes of execution of a proposal on creation or on new vote.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Exec {
+    /// An empty value means that there should be a separate
+    /// MsgExec request for the proposal to execute.
+    Unspecified = 0,
+    /// Try to execute the proposal immediately.
+    /// If the proposal is not allowed per the DecisionPolicy,
+    /// the proposal will still be open and could
+    /// be executed at a later point.
+    Try = 1,
+}
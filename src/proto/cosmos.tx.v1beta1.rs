@@ -0,0 +1,111 @@
+/// SimulateRequest is the request type for the Service.Simulate
+/// RPC method. tx_bytes was added in Cosmos SDK v0.43 and tx is deprecated in
+/// its favor, older nodes only read tx so both should be set.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SimulateRequest {
+    /// tx is the transaction to simulate.
+    #[prost(message, optional, tag = "1")]
+    pub tx: ::core::option::Option<cosmos_sdk_proto::cosmos::tx::v1beta1::Tx>,
+    /// tx_bytes is the raw transaction.
+    #[prost(bytes = "vec", tag = "2")]
+    pub tx_bytes: ::prost::alloc::vec::Vec<u8>,
+}
+/// TxBody as of Cosmos SDK v0.53, which added unordered transactions, the TxBody in
+/// cosmos-sdk-proto lacks the unordered and timeout_timestamp fields. Older nodes reject
+/// these fields so this is only compiled with the sdk-0_53 feature.
+#[cfg(feature = "sdk-0_53")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TxBody {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<::prost_types::Any>,
+    #[prost(string, tag = "2")]
+    pub memo: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "3")]
+    pub timeout_height: u64,
+    /// unordered transactions are deduplicated by hash rather than by the signer
+    /// sequence and must set timeout_timestamp
+    #[prost(bool, tag = "4")]
+    pub unordered: bool,
+    #[prost(message, optional, tag = "5")]
+    pub timeout_timestamp: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, repeated, tag = "1023")]
+    pub extension_options: ::prost::alloc::vec::Vec<::prost_types::Any>,
+    #[prost(message, repeated, tag = "2047")]
+    pub non_critical_extension_options: ::prost::alloc::vec::Vec<::prost_types::Any>,
+}
+/// GetTxsEventRequest as of Cosmos SDK v0.50, which requires the query string and pages
+/// with page and limit instead of pagination
+#[cfg(feature = "sdk-0_50")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTxsEventRequest {
+    /// events is the list of transaction event type.
+    /// Deprecated post v0.47.x: use query instead, which should contain a valid
+    /// events query.
+    #[prost(string, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// pagination defines a pagination for the request.
+    /// Deprecated post v0.46.x: use page and limit instead.
+    #[prost(message, optional, tag = "2")]
+    pub pagination:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest>,
+    #[prost(int32, tag = "3")]
+    pub order_by: i32,
+    /// page is the page number to query, starts at 1. If not provided, will
+    /// default to first page.
+    #[prost(uint64, tag = "4")]
+    pub page: u64,
+    /// limit is the total number of results to be returned in the result page.
+    /// If left empty it will default to a value to be set by each app.
+    #[prost(uint64, tag = "5")]
+    pub limit: u64,
+    /// query defines the transaction event query that is proxied to Tendermint's
+    /// TxSearch RPC method. The query must be valid.
+    ///
+    /// Since cosmos-sdk 0.50
+    #[prost(string, tag = "6")]
+    pub query: ::prost::alloc::string::String,
+}
+/// GetTxResponse decoding the TxResponse with events, see TxResponse
+#[cfg(feature = "sdk-0_50")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTxResponse {
+    #[prost(message, optional, tag = "1")]
+    pub tx: ::core::option::Option<cosmos_sdk_proto::cosmos::tx::v1beta1::Tx>,
+    #[prost(message, optional, tag = "2")]
+    pub tx_response:
+        ::core::option::Option<crate::proto::cosmos::base::abci::v1beta1::TxResponse>,
+}
+/// BroadcastTxResponse decoding the TxResponse with events, see TxResponse
+#[cfg(feature = "sdk-0_50")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BroadcastTxResponse {
+    #[prost(message, optional, tag = "1")]
+    pub tx_response:
+        ::core::option::Option<crate::proto::cosmos::base::abci::v1beta1::TxResponse>,
+}
+/// GetTxsEventResponse decoding the TxResponses with events, see TxResponse
+#[cfg(feature = "sdk-0_50")]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetTxsEventResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub txs: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::tx::v1beta1::Tx>,
+    #[prost(message, repeated, tag = "2")]
+    pub tx_responses:
+        ::prost::alloc::vec::Vec<crate::proto::cosmos::base::abci::v1beta1::TxResponse>,
+    #[prost(message, optional, tag = "3")]
+    pub pagination:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse>,
+    /// total is total number of results available
+    ///
+    /// Since: cosmos-sdk 0.46
+    #[prost(uint64, tag = "4")]
+    pub total: u64,
+}
+#[cfg(not(feature = "sdk-0_50"))]
+pub use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxResponse;
+#[cfg(not(feature = "sdk-0_50"))]
+pub use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse;
+#[cfg(not(feature = "sdk-0_50"))]
+pub use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxsEventRequest;
+#[cfg(not(feature = "sdk-0_50"))]
+pub use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxsEventResponse;
@@ -0,0 +1,49 @@
+/// Period defines a length of time and amount of coins that will vest.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Period {
+    /// Period duration in seconds.
+    #[prost(int64, tag = "1")]
+    pub length: i64,
+    #[prost(message, repeated, tag = "2")]
+    pub amount: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+}
+/// MsgCreatePermanentLockedAccount defines a message that enables creating a permanent
+/// locked account.
+///
+/// Since: cosmos-sdk 0.46
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreatePermanentLockedAccount {
+    #[prost(string, tag = "1")]
+    pub from_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub to_address: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+}
+/// MsgCreateVestingAccount defines a message that enables creating a vesting
+/// account.
+///
+/// Since: cosmos-sdk 0.46
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgCreatePeriodicVestingAccount {
+    #[prost(string, tag = "1")]
+    pub from_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub to_address: ::prost::alloc::string::String,
+    /// start of vesting as unix time (in seconds).
+    #[prost(int64, tag = "3")]
+    pub start_time: i64,
+    #[prost(message, repeated, tag = "4")]
+    pub vesting_periods: ::prost::alloc::vec::Vec<Period>,
+}
+/// PermanentLockedAccount implements the VestingAccount interface. It does
+/// not ever release coins, locking them indefinitely. Coins in this account can
+/// still be used for delegating and for governance votes even while locked.
+///
+/// Since: cosmos-sdk 0.43
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PermanentLockedAccount {
+    #[prost(message, optional, tag = "1")]
+    pub base_vesting_account:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::vesting::v1beta1::BaseVestingAccount>,
+}
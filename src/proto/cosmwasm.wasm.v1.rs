@@ -0,0 +1,196 @@
+/// AccessConfig access control type.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccessConfig {
+    #[prost(enumeration = "AccessType", tag = "1")]
+    pub permission: i32,
+    #[prost(string, repeated, tag = "3")]
+    pub addresses: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// MsgStoreCode submit Wasm code to the system
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgStoreCode {
+    /// Sender is the actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// WASMByteCode can be raw or gzip compressed
+    #[prost(bytes = "vec", tag = "2")]
+    pub wasm_byte_code: ::prost::alloc::vec::Vec<u8>,
+    /// InstantiatePermission access control to apply on contract creation,
+    /// optional
+    #[prost(message, optional, tag = "5")]
+    pub instantiate_permission: ::core::option::Option<AccessConfig>,
+}
+/// MsgStoreCodeResponse returns store result data.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgStoreCodeResponse {
+    /// CodeID is the reference to the stored WASM code
+    #[prost(uint64, tag = "1")]
+    pub code_id: u64,
+    /// Checksum is the sha256 hash of the stored code
+    #[prost(bytes = "vec", tag = "2")]
+    pub checksum: ::prost::alloc::vec::Vec<u8>,
+}
+/// MsgInstantiateContract create a new smart contract instance for the given
+/// code id.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgInstantiateContract {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// Admin is an optional address that can execute migrations
+    #[prost(string, tag = "2")]
+    pub admin: ::prost::alloc::string::String,
+    /// CodeID is the reference to the stored WASM code
+    #[prost(uint64, tag = "3")]
+    pub code_id: u64,
+    /// Label is optional metadata to be stored with a contract instance.
+    #[prost(string, tag = "4")]
+    pub label: ::prost::alloc::string::String,
+    /// Msg json encoded message to be passed to the contract on instantiation
+    #[prost(bytes = "vec", tag = "5")]
+    pub msg: ::prost::alloc::vec::Vec<u8>,
+    /// Funds coins that are transferred to the contract on instantiation
+    #[prost(message, repeated, tag = "6")]
+    pub funds: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+}
+/// MsgInstantiateContract2 create a new smart contract instance for the given
+/// code id with a predicable address.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgInstantiateContract2 {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// Admin is an optional address that can execute migrations
+    #[prost(string, tag = "2")]
+    pub admin: ::prost::alloc::string::String,
+    /// CodeID is the reference to the stored WASM code
+    #[prost(uint64, tag = "3")]
+    pub code_id: u64,
+    /// Label is optional metadata to be stored with a contract instance.
+    #[prost(string, tag = "4")]
+    pub label: ::prost::alloc::string::String,
+    /// Msg json encoded message to be passed to the contract on instantiation
+    #[prost(bytes = "vec", tag = "5")]
+    pub msg: ::prost::alloc::vec::Vec<u8>,
+    /// Funds coins that are transferred to the contract on instantiation
+    #[prost(message, repeated, tag = "6")]
+    pub funds: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+    /// Salt is an arbitrary value provided by the sender. Size can be 1 to 64.
+    #[prost(bytes = "vec", tag = "7")]
+    pub salt: ::prost::alloc::vec::Vec<u8>,
+    /// FixMsg include the msg value into the hash for the predictable address.
+    /// Default is false
+    #[prost(bool, tag = "8")]
+    pub fix_msg: bool,
+}
+/// MsgInstantiateContractResponse return instantiation result data
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgInstantiateContractResponse {
+    /// Address is the bech32 address of the new contract instance.
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    /// Data contains bytes to returned from the contract
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+/// MsgExecuteContract submits the given message data to a smart contract
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExecuteContract {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// Contract is the address of the smart contract
+    #[prost(string, tag = "2")]
+    pub contract: ::prost::alloc::string::String,
+    /// Msg json encoded message to be passed to the contract
+    #[prost(bytes = "vec", tag = "3")]
+    pub msg: ::prost::alloc::vec::Vec<u8>,
+    /// Funds coins that are transferred to the contract on execution
+    #[prost(message, repeated, tag = "5")]
+    pub funds: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+}
+/// MsgExecuteContractResponse returns execution result data.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgExecuteContractResponse {
+    /// Data contains bytes to returned from the contract
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+/// MsgMigrateContract runs a code upgrade/ downgrade for a smart contract
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgMigrateContract {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// Contract is the address of the smart contract
+    #[prost(string, tag = "2")]
+    pub contract: ::prost::alloc::string::String,
+    /// CodeID references the new WASM code
+    #[prost(uint64, tag = "3")]
+    pub code_id: u64,
+    /// Msg json encoded message to be passed to the contract on migration
+    #[prost(bytes = "vec", tag = "4")]
+    pub msg: ::prost::alloc::vec::Vec<u8>,
+}
+/// MsgUpdateAdmin sets a new admin for a smart contract
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgUpdateAdmin {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// NewAdmin address to be set
+    #[prost(string, tag = "2")]
+    pub new_admin: ::prost::alloc::string::String,
+    /// Contract is the address of the smart contract
+    #[prost(string, tag = "3")]
+    pub contract: ::prost::alloc::string::String,
+}
+/// MsgClearAdmin removes any admin stored for a smart contract
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgClearAdmin {
+    /// Sender is the that actor that signed the messages
+    #[prost(string, tag = "1")]
+    pub sender: ::prost::alloc::string::String,
+    /// Contract is the address of the smart contract
+    #[prost(string, tag = "3")]
+    pub contract: ::prost::alloc::string::String,
+}
+/// QueryContractsByCodeRequest is the request type for the Query/ContractsByCode
+/// RPC method
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryContractsByCodeRequest {
+    /// grpc-gateway_out does not support Go style CodID
+    #[prost(uint64, tag = "1")]
+    pub code_id: u64,
+    /// pagination defines an optional pagination for the request.
+    #[prost(message, optional, tag = "2")]
+    pub pagination: ::core::option::Option<
+        cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest,
+    >,
+}
+/// QueryContractsByCodeResponse is the response type for the
+/// Query/ContractsByCode RPC method
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryContractsByCodeResponse {
+    /// contracts are a set of contract addresses
+    #[prost(string, repeated, tag = "1")]
+    pub contracts: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// pagination defines the pagination in the response.
+    #[prost(message, optional, tag = "2")]
+    pub pagination: ::core::option::Option<
+        cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse,
+    >,
+}
+/// AccessType permission types
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AccessType {
+    /// AccessTypeUnspecified placeholder for empty value
+    Unspecified = 0,
+    /// AccessTypeNobody forbidden
+    Nobody = 1,
+    /// AccessTypeEverybody unrestricted
+    Everybody = 3,
+    /// AccessTypeAnyOfAddresses allow any of the addresses
+    AnyOfAddresses = 4,
+}
@@ -0,0 +1,102 @@
+/// MsgEthereumTx encapsulates an Ethereum transaction as an SDK message.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgEthereumTx {
+    /// data is inner transaction data of the Ethereum transaction
+    #[prost(message, optional, tag = "1")]
+    pub data: ::core::option::Option<::prost_types::Any>,
+    /// size is the encoded storage size of the transaction (DEPRECATED)
+    #[prost(double, tag = "2")]
+    pub size: f64,
+    /// hash of the transaction in hex format
+    #[prost(string, tag = "3")]
+    pub hash: ::prost::alloc::string::String,
+    /// from is the ethereum signer address in hex format. This address value is checked
+    /// against the address derived from the signature (V, R, S) using the
+    /// secp256k1 elliptic curve
+    #[prost(string, tag = "4")]
+    pub from: ::prost::alloc::string::String,
+}
+/// LegacyTx is the transaction data of regular Ethereum transactions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LegacyTx {
+    /// nonce corresponds to the account nonce (transaction sequence).
+    #[prost(uint64, tag = "1")]
+    pub nonce: u64,
+    /// gas_price defines the value for each gas unit
+    #[prost(string, tag = "2")]
+    pub gas_price: ::prost::alloc::string::String,
+    /// gas defines the gas limit defined for the transaction.
+    #[prost(uint64, tag = "3")]
+    pub gas: u64,
+    /// to is the hex formatted address of the recipient
+    #[prost(string, tag = "4")]
+    pub to: ::prost::alloc::string::String,
+    /// value defines the unsigned integer value of the transaction amount.
+    #[prost(string, tag = "5")]
+    pub value: ::prost::alloc::string::String,
+    /// data is the data payload bytes of the transaction.
+    #[prost(bytes = "vec", tag = "6")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// v defines the signature value
+    #[prost(bytes = "vec", tag = "7")]
+    pub v: ::prost::alloc::vec::Vec<u8>,
+    /// r defines the signature value
+    #[prost(bytes = "vec", tag = "8")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    /// s define the signature value
+    #[prost(bytes = "vec", tag = "9")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+}
+/// AccessTuple is the element type of an access list.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccessTuple {
+    /// address is a hex formatted ethereum address
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    /// storage_keys are hex formatted hashes of the storage keys
+    #[prost(string, repeated, tag = "2")]
+    pub storage_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// DynamicFeeTx is the data of EIP-1559 dinamic fee transactions.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DynamicFeeTx {
+    /// chain_id of the destination EVM chain
+    #[prost(string, tag = "1")]
+    pub chain_id: ::prost::alloc::string::String,
+    /// nonce corresponds to the account nonce (transaction sequence).
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    /// gas_tip_cap defines the max value for the gas tip
+    #[prost(string, tag = "3")]
+    pub gas_tip_cap: ::prost::alloc::string::String,
+    /// gas_fee_cap defines the max value for the gas fee
+    #[prost(string, tag = "4")]
+    pub gas_fee_cap: ::prost::alloc::string::String,
+    /// gas defines the gas limit defined for the transaction.
+    #[prost(uint64, tag = "5")]
+    pub gas: u64,
+    /// to is the hex formatted address of the recipient
+    #[prost(string, tag = "6")]
+    pub to: ::prost::alloc::string::String,
+    /// value defines the the transaction amount.
+    #[prost(string, tag = "7")]
+    pub value: ::prost::alloc::string::String,
+    /// data is the data payload bytes of the transaction.
+    #[prost(bytes = "vec", tag = "8")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// accesses is an array of access tuples
+    #[prost(message, repeated, tag = "9")]
+    pub accesses: ::prost::alloc::vec::Vec<AccessTuple>,
+    /// v defines the signature value
+    #[prost(bytes = "vec", tag = "10")]
+    pub v: ::prost::alloc::vec::Vec<u8>,
+    /// r defines the signature value
+    #[prost(bytes = "vec", tag = "11")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    /// s define the signature value
+    #[prost(bytes = "vec", tag = "12")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+}
+/// ExtensionOptionsEthereumTx is an extension option for ethereum transactions
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExtensionOptionsEthereumTx {}
@@ -0,0 +1,10 @@
+/// EthAccount implements the authtypes.AccountI interface and embeds an
+/// authtypes.BaseAccount type. It is compatible with the auth AccountKeeper.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EthAccount {
+    #[prost(message, optional, tag = "1")]
+    pub base_account:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::auth::v1beta1::BaseAccount>,
+    #[prost(string, tag = "2")]
+    pub code_hash: ::prost::alloc::string::String,
+}
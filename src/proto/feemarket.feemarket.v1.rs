@@ -0,0 +1,25 @@
+/// GasPriceRequest is the request type for the Query/GasPrice RPC method of the
+/// Skip feemarket module.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceRequest {
+    /// denom we are querying gas price in
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+}
+/// GasPriceResponse is the response type for the Query/GasPrice RPC method.
+/// Returns a gas price in specified denom.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPriceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub price: ::core::option::Option<cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin>,
+}
+/// GasPricesRequest is the request type for the Query/GasPrices RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesRequest {}
+/// GasPricesResponse is the response type for the Query/GasPrices RPC method.
+/// Returns a gas price in all available denoms.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPricesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub prices: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin>,
+}
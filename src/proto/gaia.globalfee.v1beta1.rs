@@ -0,0 +1,12 @@
+/// QueryMinimumGasPricesRequest is the request type for the
+/// Query/MinimumGasPrices RPC method of the Gaia globalfee module.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryMinimumGasPricesRequest {}
+/// QueryMinimumGasPricesResponse is the response type for the
+/// Query/MinimumGasPrices RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryMinimumGasPricesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub minimum_gas_prices:
+        ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin>,
+}
@@ -0,0 +1,196 @@
+/// Fee defines the ICS29 receive, acknowledgement and timeout fees
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Fee {
+    /// the packet receive fee
+    #[prost(message, repeated, tag = "1")]
+    pub recv_fee: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+    /// the packet acknowledgement fee
+    #[prost(message, repeated, tag = "2")]
+    pub ack_fee: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+    /// the packet timeout fee
+    #[prost(message, repeated, tag = "3")]
+    pub timeout_fee: ::prost::alloc::vec::Vec<cosmos_sdk_proto::cosmos::base::v1beta1::Coin>,
+}
+/// PacketFee contains ICS29 relayer fees, refund address and optional list of permitted relayers
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PacketFee {
+    /// fee encapsulates the recv, ack and timeout fees associated with an IBC packet
+    #[prost(message, optional, tag = "1")]
+    pub fee: ::core::option::Option<Fee>,
+    /// the refund address for unspent fees
+    #[prost(string, tag = "2")]
+    pub refund_address: ::prost::alloc::string::String,
+    /// optional list of relayers permitted to receive fees
+    #[prost(string, repeated, tag = "3")]
+    pub relayers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// IdentifiedPacketFees contains a list of type PacketFee and associated PacketId
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdentifiedPacketFees {
+    /// unique packet identifier comprised of the channel ID, port ID and sequence
+    #[prost(message, optional, tag = "1")]
+    pub packet_id: ::core::option::Option<super::super::super::core::channel::v1::PacketId>,
+    /// list of packet fees
+    #[prost(message, repeated, tag = "2")]
+    pub packet_fees: ::prost::alloc::vec::Vec<PacketFee>,
+}
+/// MsgRegisterPayee defines the request type for the RegisterPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterPayee {
+    /// unique port identifier
+    #[prost(string, tag = "1")]
+    pub port_id: ::prost::alloc::string::String,
+    /// unique channel identifier
+    #[prost(string, tag = "2")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// the relayer address
+    #[prost(string, tag = "3")]
+    pub relayer: ::prost::alloc::string::String,
+    /// the payee address
+    #[prost(string, tag = "4")]
+    pub payee: ::prost::alloc::string::String,
+}
+/// MsgRegisterCounterpartyPayee defines the request type for the RegisterCounterpartyPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterCounterpartyPayee {
+    /// unique port identifier
+    #[prost(string, tag = "1")]
+    pub port_id: ::prost::alloc::string::String,
+    /// unique channel identifier
+    #[prost(string, tag = "2")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// the relayer address
+    #[prost(string, tag = "3")]
+    pub relayer: ::prost::alloc::string::String,
+    /// the counterparty payee address
+    #[prost(string, tag = "4")]
+    pub counterparty_payee: ::prost::alloc::string::String,
+}
+/// MsgPayPacketFee defines the request type for the PayPacketFee rpc
+/// This Msg can be used to pay for a packet at the next sequence send & should be combined with the Msg that will be
+/// paid for
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFee {
+    /// fee encapsulates the recv, ack and timeout fees associated with an IBC packet
+    #[prost(message, optional, tag = "1")]
+    pub fee: ::core::option::Option<Fee>,
+    /// the source port unique identifier
+    #[prost(string, tag = "2")]
+    pub source_port_id: ::prost::alloc::string::String,
+    /// the source channel unique identifer
+    #[prost(string, tag = "3")]
+    pub source_channel_id: ::prost::alloc::string::String,
+    /// account address to refund fee if necessary
+    #[prost(string, tag = "4")]
+    pub signer: ::prost::alloc::string::String,
+    /// optional list of relayers permitted to the receive packet fees
+    #[prost(string, repeated, tag = "5")]
+    pub relayers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// MsgPayPacketFeeAsync defines the request type for the PayPacketFeeAsync rpc
+/// This Msg can be used to pay for a packet at a specified sequence (instead of the next sequence send)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgPayPacketFeeAsync {
+    /// unique packet identifier comprised of the channel ID, port ID and sequence
+    #[prost(message, optional, tag = "1")]
+    pub packet_id: ::core::option::Option<super::super::super::core::channel::v1::PacketId>,
+    /// the packet fee associated with a particular IBC packet
+    #[prost(message, optional, tag = "2")]
+    pub packet_fee: ::core::option::Option<PacketFee>,
+}
+/// QueryIncentivizedPacketRequest defines the request type for the IncentivizedPacket rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryIncentivizedPacketRequest {
+    /// unique packet identifier comprised of channel ID, port ID and sequence
+    #[prost(message, optional, tag = "1")]
+    pub packet_id: ::core::option::Option<super::super::super::core::channel::v1::PacketId>,
+    /// block height at which to query
+    #[prost(uint64, tag = "2")]
+    pub query_height: u64,
+}
+/// QueryIncentivizedPacketResponse defines the response type for the IncentivizedPacket rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryIncentivizedPacketResponse {
+    /// the identified fees for the incentivized packet
+    #[prost(message, optional, tag = "1")]
+    pub incentivized_packet: ::core::option::Option<IdentifiedPacketFees>,
+}
+/// QueryIncentivizedPacketsForChannelRequest defines the request type for querying for all incentivized packets
+/// for a specific channel
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryIncentivizedPacketsForChannelRequest {
+    /// pagination defines an optional pagination for the request.
+    #[prost(message, optional, tag = "1")]
+    pub pagination:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest>,
+    #[prost(string, tag = "2")]
+    pub port_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// Height to query at
+    #[prost(uint64, tag = "4")]
+    pub query_height: u64,
+}
+/// QueryIncentivizedPacketsForChannelResponse defines the response type for querying for all incentivized packets
+/// for a specific channel
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryIncentivizedPacketsForChannelResponse {
+    /// Map of all incentivized_packets
+    #[prost(message, repeated, tag = "1")]
+    pub incentivized_packets: ::prost::alloc::vec::Vec<IdentifiedPacketFees>,
+    /// pagination defines the pagination in the response.
+    #[prost(message, optional, tag = "2")]
+    pub pagination:
+        ::core::option::Option<cosmos_sdk_proto::cosmos::base::query::v1beta1::PageResponse>,
+}
+/// QueryPayeeRequest defines the request type for the Payee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPayeeRequest {
+    /// unique channel identifier
+    #[prost(string, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// the relayer address to which the distribution address is registered
+    #[prost(string, tag = "2")]
+    pub relayer: ::prost::alloc::string::String,
+}
+/// QueryPayeeResponse defines the response type for the Payee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryPayeeResponse {
+    /// the payee address to which packet fees are paid out
+    #[prost(string, tag = "1")]
+    pub payee_address: ::prost::alloc::string::String,
+}
+/// QueryCounterpartyPayeeRequest defines the request type for the CounterpartyPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryCounterpartyPayeeRequest {
+    /// unique channel identifier
+    #[prost(string, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    /// the relayer address to which the counterparty is registered
+    #[prost(string, tag = "2")]
+    pub relayer: ::prost::alloc::string::String,
+}
+/// QueryCounterpartyPayeeResponse defines the response type for the CounterpartyPayee rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryCounterpartyPayeeResponse {
+    /// the counterparty payee address used to compensate forward relaying
+    #[prost(string, tag = "1")]
+    pub counterparty_payee: ::prost::alloc::string::String,
+}
+/// QueryFeeEnabledChannelRequest defines the request type for the FeeEnabledChannel rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryFeeEnabledChannelRequest {
+    /// unique port identifier
+    #[prost(string, tag = "1")]
+    pub port_id: ::prost::alloc::string::String,
+    /// unique channel identifier
+    #[prost(string, tag = "2")]
+    pub channel_id: ::prost::alloc::string::String,
+}
+/// QueryFeeEnabledChannelResponse defines the response type for the FeeEnabledChannel rpc
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryFeeEnabledChannelResponse {
+    /// boolean flag representing the fee enabled channel status
+    #[prost(bool, tag = "1")]
+    pub fee_enabled: bool,
+}
@@ -0,0 +1,52 @@
+/// MsgRegisterInterchainAccount defines the payload for Msg/RegisterAccount
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterInterchainAccount {
+    #[prost(string, tag = "1")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub connection_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub version: ::prost::alloc::string::String,
+}
+/// MsgRegisterInterchainAccountResponse defines the response for Msg/RegisterAccount
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgRegisterInterchainAccountResponse {
+    #[prost(string, tag = "1")]
+    pub channel_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub port_id: ::prost::alloc::string::String,
+}
+/// MsgSendTx defines the payload for Msg/SendTx
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSendTx {
+    #[prost(string, tag = "1")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub connection_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub packet_data: ::core::option::Option<super::super::v1::InterchainAccountPacketData>,
+    /// Relative timeout timestamp provided will be added to the current block time during transaction execution.
+    /// The timeout timestamp must be non-zero.
+    #[prost(uint64, tag = "4")]
+    pub relative_timeout: u64,
+}
+/// MsgSendTxResponse defines the response for MsgSendTx
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSendTxResponse {
+    #[prost(uint64, tag = "1")]
+    pub sequence: u64,
+}
+/// QueryInterchainAccountRequest is the request type for the Query/InterchainAccount RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryInterchainAccountRequest {
+    #[prost(string, tag = "1")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub connection_id: ::prost::alloc::string::String,
+}
+/// QueryInterchainAccountResponse the response type for the Query/InterchainAccount RPC method.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryInterchainAccountResponse {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+}
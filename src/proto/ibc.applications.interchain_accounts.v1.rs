@@ -0,0 +1,26 @@
+/// InterchainAccountPacketData is comprised of a raw transaction, type of transaction and optional memo field.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InterchainAccountPacketData {
+    #[prost(enumeration = "Type", tag = "1")]
+    pub r#type: i32,
+    #[prost(bytes = "vec", tag = "2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub memo: ::prost::alloc::string::String,
+}
+/// CosmosTx contains a list of sdk.Msg's. It should be used when sending transactions to an SDK host chain.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CosmosTx {
+    #[prost(message, repeated, tag = "1")]
+    pub messages: ::prost::alloc::vec::Vec<::prost_types::Any>,
+}
+/// Type defines a classification of message issued from a controller chain to its associated interchain accounts
+/// host
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Type {
+    /// Default zero value enumeration
+    Unspecified = 0,
+    /// Execute a transaction on an interchain accounts host chain
+    ExecuteTx = 1,
+}
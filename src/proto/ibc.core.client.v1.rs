@@ -0,0 +1,82 @@
+/// Height is a monotonically increasing data type
+/// that can be compared against another Height for the purposes of updating and
+/// freezing clients
+///
+/// Normally the RevisionHeight is incremented at each height while keeping RevisionNumber
+/// the same. However some consensus algorithms may choose to reset the
+/// height in certain conditions e.g. hard forks, state-machine breaking changes
+/// In these cases, the RevisionNumber is incremented so that height continues to
+/// be monitonically increasing even as the RevisionHeight gets reset
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Height {
+    /// the revision that the client is currently on
+    #[prost(uint64, tag = "1")]
+    pub revision_number: u64,
+    /// the height within the given revision
+    #[prost(uint64, tag = "2")]
+    pub revision_height: u64,
+}
+/// IdentifiedClientState defines a client state with an additional client
+/// identifier field.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdentifiedClientState {
+    /// client identifier
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    /// client state
+    #[prost(message, optional, tag = "2")]
+    pub client_state: ::core::option::Option<::prost_types::Any>,
+}
+/// MsgUpdateClient define an sdk.Msg to update a IBC client state using
+/// the given client message.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgUpdateClient {
+    /// client unique identifier
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    /// client message to update the light client, named header before ibc-go v7
+    #[prost(message, optional, tag = "2")]
+    pub client_message: ::core::option::Option<::prost_types::Any>,
+    /// signer address
+    #[prost(string, tag = "3")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgUpgradeClient defines an sdk.Msg to upgrade an IBC client to a new client
+/// state
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgUpgradeClient {
+    /// client unique identifier
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    /// upgraded client state
+    #[prost(message, optional, tag = "2")]
+    pub client_state: ::core::option::Option<::prost_types::Any>,
+    /// upgraded consensus state, only contains enough information to serve as a
+    /// basis of trust in update logic
+    #[prost(message, optional, tag = "3")]
+    pub consensus_state: ::core::option::Option<::prost_types::Any>,
+    /// proof that old chain committed to new client
+    #[prost(bytes = "vec", tag = "4")]
+    pub proof_upgrade_client: ::prost::alloc::vec::Vec<u8>,
+    /// proof that old chain committed to new consensus state
+    #[prost(bytes = "vec", tag = "5")]
+    pub proof_upgrade_consensus_state: ::prost::alloc::vec::Vec<u8>,
+    /// signer address
+    #[prost(string, tag = "6")]
+    pub signer: ::prost::alloc::string::String,
+}
+/// MsgSubmitMisbehaviour defines an sdk.Msg type that submits Evidence for
+/// light client misbehaviour.
+/// Deprecated in ibc-go v7, misbehaviour can be submitted with MsgUpdateClient.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MsgSubmitMisbehaviour {
+    /// client unique identifier
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    /// misbehaviour used for freezing the light client
+    #[prost(message, optional, tag = "2")]
+    pub misbehaviour: ::core::option::Option<::prost_types::Any>,
+    /// signer address
+    #[prost(string, tag = "3")]
+    pub signer: ::prost::alloc::string::String,
+}
@@ -0,0 +1,51 @@
+/// ClientState from Tendermint tracks the current validator set, latest height,
+/// and a possible frozen height.
+///
+/// Only the fields needed by deep_space are defined here, the remaining fields
+/// are skipped when decoding.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientState {
+    #[prost(string, tag = "1")]
+    pub chain_id: ::prost::alloc::string::String,
+    /// Block height when the client was frozen due to a misbehaviour
+    #[prost(message, optional, tag = "6")]
+    pub frozen_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    /// Latest height the client was updated to
+    #[prost(message, optional, tag = "7")]
+    pub latest_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+}
+/// Header defines the Tendermint client consensus Header.
+/// It encapsulates all the information necessary to update from a trusted
+/// Tendermint ConsensusState. The inclusion of TrustedHeight and
+/// TrustedValidators allows this update to process correctly, so long as the
+/// ConsensusState for the TrustedHeight exists, this removes race conditions
+/// among relayers. The SignedHeader and ValidatorSet are the new untrusted update
+/// fields for the client. The TrustedHeight is the height of a stored
+/// ConsensusState on the client that will be used to verify the new untrusted
+/// header. The Trusted ConsensusState must be within the unbonding period of
+/// current time in order to correctly verify, and the TrustedValidators must
+/// hash to TrustedConsensusState.NextValidatorsHash since that is the last
+/// trusted validator set at the TrustedHeight.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Header {
+    #[prost(message, optional, tag = "1")]
+    pub signed_header: ::core::option::Option<tendermint_proto::types::SignedHeader>,
+    #[prost(message, optional, tag = "2")]
+    pub validator_set: ::core::option::Option<tendermint_proto::types::ValidatorSet>,
+    #[prost(message, optional, tag = "3")]
+    pub trusted_height: ::core::option::Option<super::super::super::core::client::v1::Height>,
+    #[prost(message, optional, tag = "4")]
+    pub trusted_validators: ::core::option::Option<tendermint_proto::types::ValidatorSet>,
+}
+/// Misbehaviour is a wrapper over two conflicting Headers
+/// that implements Misbehaviour interface expected by ICS-02
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Misbehaviour {
+    /// ClientID is deprecated
+    #[prost(string, tag = "1")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub header_1: ::core::option::Option<Header>,
+    #[prost(message, optional, tag = "3")]
+    pub header_2: ::core::option::Option<Header>,
+}
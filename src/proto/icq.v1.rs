@@ -0,0 +1,30 @@
+/// InterchainQueryPacketData is comprised of raw query.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InterchainQueryPacketData {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// optional memo
+    #[prost(string, tag = "2")]
+    pub memo: ::prost::alloc::string::String,
+}
+/// InterchainQueryPacketAck is comprised of an ABCI query response with non-deterministic
+/// fields left empty (e.g. Codespace, Log, Info and ...).
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InterchainQueryPacketAck {
+    #[prost(bytes = "vec", tag = "1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+/// CosmosQuery contains a list of tendermint ABCI query requests. It should be
+/// used when sending queries to an SDK host chain.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CosmosQuery {
+    #[prost(message, repeated, tag = "1")]
+    pub requests: ::prost::alloc::vec::Vec<tendermint_proto::abci::RequestQuery>,
+}
+/// CosmosResponse contains a list of tendermint ABCI query responses. It should
+/// be used when receiving responses from an SDK host chain.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CosmosResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub responses: ::prost::alloc::vec::Vec<tendermint_proto::abci::ResponseQuery>,
+}
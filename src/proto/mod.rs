@@ -0,0 +1,182 @@
+//! Protobuf definitions for services and modules that are not included in the version of
+//! cosmos-sdk-proto this crate depends on. These are written by hand to mirror the upstream
+//! .proto files and are laid out using the same package paths so that they can be swapped
+//! for generated code without changing any callers.
+
+pub mod cosmos {
+    pub mod auth {
+        pub mod v1beta1 {
+            include!("cosmos.auth.v1beta1.rs");
+        }
+    }
+    pub mod authz {
+        pub mod v1beta1 {
+            include!("cosmos.authz.v1beta1.rs");
+        }
+    }
+    pub mod bank {
+        pub mod v1beta1 {
+            include!("cosmos.bank.v1beta1.rs");
+        }
+    }
+    pub mod base {
+        pub mod abci {
+            pub mod v1beta1 {
+                include!("cosmos.base.abci.v1beta1.rs");
+            }
+        }
+        pub mod node {
+            pub mod v1beta1 {
+                include!("cosmos.base.node.v1beta1.rs");
+            }
+        }
+    }
+    #[cfg(feature = "sdk-0_47")]
+    pub mod consensus {
+        pub mod v1 {
+            include!("cosmos.consensus.v1.rs");
+        }
+    }
+    pub mod feegrant {
+        pub mod v1beta1 {
+            include!("cosmos.feegrant.v1beta1.rs");
+        }
+    }
+    pub mod gov {
+        #[cfg(feature = "sdk-0_46")]
+        pub mod v1 {
+            include!("cosmos.gov.v1.rs");
+        }
+        pub mod v1beta1 {
+            include!("cosmos.gov.v1beta1.rs");
+        }
+    }
+    #[cfg(feature = "sdk-0_46")]
+    pub mod group {
+        pub mod v1 {
+            include!("cosmos.group.v1.rs");
+        }
+    }
+    pub mod staking {
+        pub mod v1beta1 {
+            include!("cosmos.staking.v1beta1.rs");
+        }
+    }
+    pub mod tx {
+        pub mod v1beta1 {
+            include!("cosmos.tx.v1beta1.rs");
+        }
+    }
+    pub mod vesting {
+        pub mod v1beta1 {
+            include!("cosmos.vesting.v1beta1.rs");
+        }
+    }
+}
+
+#[cfg(feature = "cosmwasm")]
+pub mod cosmwasm {
+    pub mod wasm {
+        pub mod v1 {
+            include!("cosmwasm.wasm.v1.rs");
+        }
+    }
+}
+
+pub mod ethermint {
+    #[cfg(feature = "ethermint")]
+    pub mod evm {
+        pub mod v1 {
+            include!("ethermint.evm.v1.rs");
+        }
+    }
+    pub mod types {
+        pub mod v1 {
+            include!("ethermint.types.v1.rs");
+        }
+    }
+}
+
+pub mod feemarket {
+    pub mod feemarket {
+        pub mod v1 {
+            include!("feemarket.feemarket.v1.rs");
+        }
+    }
+}
+
+pub mod gaia {
+    pub mod globalfee {
+        pub mod v1beta1 {
+            include!("gaia.globalfee.v1beta1.rs");
+        }
+    }
+}
+
+pub mod grpc {
+    pub mod reflection {
+        pub mod v1alpha {
+            include!("grpc.reflection.v1alpha.rs");
+        }
+    }
+}
+
+pub mod ibc {
+    pub mod applications {
+        pub mod fee {
+            pub mod v1 {
+                include!("ibc.applications.fee.v1.rs");
+            }
+        }
+        pub mod interchain_accounts {
+            pub mod controller {
+                pub mod v1 {
+                    include!("ibc.applications.interchain_accounts.controller.v1.rs");
+                }
+            }
+            pub mod v1 {
+                include!("ibc.applications.interchain_accounts.v1.rs");
+            }
+        }
+        pub mod transfer {
+            pub mod v1 {
+                include!("ibc.applications.transfer.v1.rs");
+            }
+        }
+    }
+    pub mod core {
+        pub mod channel {
+            pub mod v1 {
+                include!("ibc.core.channel.v1.rs");
+            }
+        }
+        pub mod client {
+            pub mod v1 {
+                include!("ibc.core.client.v1.rs");
+            }
+        }
+    }
+    pub mod lightclients {
+        pub mod tendermint {
+            pub mod v1 {
+                include!("ibc.lightclients.tendermint.v1.rs");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "icq")]
+pub mod icq {
+    pub mod v1 {
+        include!("icq.v1.rs");
+    }
+}
+
+#[cfg(feature = "tokenfactory")]
+pub mod osmosis {
+    pub mod tokenfactory {
+        pub mod v1beta1 {
+            include!("osmosis.tokenfactory.v1beta1.rs");
+        }
+    }
+}
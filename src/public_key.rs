@@ -4,10 +4,16 @@ use crate::{address::Address, utils::ArrayString};
 use bech32::Variant;
 use bech32::{self, FromBase32, ToBase32};
 use ripemd160::Ripemd160;
+use secp256k1::Message as CurveMessage;
+use secp256k1::PublicKey as PublicKeyEC;
+use secp256k1::Secp256k1;
+use secp256k1::Signature as CurveSignature;
+use secp256k1::Verification;
 use sha2::{Digest, Sha256};
 use std::fmt::{self, Display, Formatter};
 use std::hash::Hash;
 use std::str::FromStr;
+use std::thread;
 
 /// Represents a public key of a given private key in the Cosmos Network.
 #[derive(PartialEq, Eq, Copy, Clone, Hash)]
@@ -112,6 +118,31 @@ impl PublicKey {
         Ok(bech32)
     }
 
+    /// Checks a 64 byte compact signature of the sha256 hash of the message, such as the
+    /// signature of a sign doc. Like the Cosmos SDK only low S signatures are accepted.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        self.verify_with(&Secp256k1::verification_only(), message, signature)
+    }
+
+    fn verify_with<C: Verification>(
+        &self,
+        secp256k1: &Secp256k1<C>,
+        message: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        let key = match PublicKeyEC::from_slice(&self.bytes) {
+            Ok(key) => key,
+            Err(_) => return false,
+        };
+        let signature = match CurveSignature::from_compact(signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        let digest = Sha256::digest(message);
+        let message = CurveMessage::from_slice(&digest).unwrap();
+        secp256k1.verify(&message, &signature, &key).is_ok()
+    }
+
     /// Parse a bech32 encoded public key
     ///
     /// * `s` - A bech32 encoded public key
@@ -135,6 +166,42 @@ impl PublicKey {
     }
 }
 
+/// Verifies many (message, signature, public key) items at once, such as all the signatures
+/// of the transactions in a block. The items are split between all available cores, this is
+/// several times faster than calling verify in a loop on a multi core machine. Returns whether
+/// each signature is valid, in the order of the items.
+pub fn verify_batch<M, S>(items: &[(M, S, PublicKey)]) -> Vec<bool>
+where
+    M: AsRef<[u8]> + Sync,
+    S: AsRef<[u8]> + Sync,
+{
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    // small batches are not worth the thread start up cost
+    let chunk_size = (items.len() / threads).max(64);
+    let secp256k1 = Secp256k1::verification_only();
+    let verify_chunk = |chunk: &[(M, S, PublicKey)]| {
+        chunk
+            .iter()
+            .map(|(message, signature, key)| {
+                key.verify_with(&secp256k1, message.as_ref(), signature.as_ref())
+            })
+            .collect::<Vec<bool>>()
+    };
+    if items.len() <= chunk_size {
+        return verify_chunk(items);
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || verify_chunk(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Verification thread panicked"))
+            .collect()
+    })
+}
+
 impl FromStr for PublicKey {
     type Err = PublicKeyError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -228,3 +295,34 @@ fn parse_base64_pubkey() {
 fn test_default_prefix() {
     PublicKey::from_bytes([0; 33], PublicKey::DEFAULT_PREFIX).unwrap();
 }
+
+#[test]
+fn test_verify_batch() {
+    use crate::fixtures::sign_doc_vectors;
+    let mut items = Vec::new();
+    for vector in sign_doc_vectors() {
+        let key = vector.private_key().to_public_key("cosmospub").unwrap();
+        assert!(key.verify(&vector.sign_doc_bytes(), &vector.signature_bytes()));
+        items.push((vector.sign_doc_bytes(), vector.signature_bytes(), key));
+    }
+    // a tampered sign doc and a signature by another key
+    let mut tampered = items[0].clone();
+    tampered.0[0] ^= 1;
+    let mut wrong_key = items[0].clone();
+    wrong_key.2 = PublicKey::from_bytes([2; 33], PublicKey::DEFAULT_PREFIX).unwrap();
+    items.push(tampered);
+    items.push(wrong_key);
+    // enough items to be split between threads
+    let items: Vec<_> = items
+        .iter()
+        .cycle()
+        .take(items.len() * 50)
+        .cloned()
+        .collect();
+    let expected: Vec<bool> = items
+        .iter()
+        .map(|(message, signature, key)| key.verify(message, signature))
+        .collect();
+    assert!(expected.contains(&false));
+    assert_eq!(verify_batch(&items), expected);
+}
@@ -0,0 +1,279 @@
+//! A registry of message types keyed by type url, this allows messages from chain specific
+//! modules that deep_space knows nothing about to be decoded from transactions and other
+//! Any encoded payloads without forking this crate.
+
+use crate::amino;
+use crate::client::bank::MSG_MULTI_SEND_TYPE_URL;
+use crate::client::bank::MSG_SEND_TYPE_URL;
+use crate::client::distribution::MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL;
+use crate::client::gov::MSG_DEPOSIT_TYPE_URL;
+use crate::client::gov::MSG_SUBMIT_PROPOSAL_TYPE_URL;
+use crate::client::gov::MSG_VOTE_TYPE_URL;
+use crate::client::staking::MSG_BEGIN_REDELEGATE_TYPE_URL;
+use crate::client::staking::MSG_DELEGATE_TYPE_URL;
+use crate::client::staking::MSG_UNDELEGATE_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::Msg;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use prost::DecodeError;
+use prost_types::Any;
+use serde_json::json;
+use serde_json::Value;
+use std::any::Any as StdAny;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A decoded message, value can be downcast to the registered prost type
+pub struct DecodedMsg {
+    pub type_url: String,
+    pub value: Box<dyn StdAny + Send + Sync>,
+    /// The Debug representation of the decoded value
+    pub description: String,
+}
+
+impl Debug for DecodedMsg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.type_url, self.description)
+    }
+}
+
+impl DecodedMsg {
+    /// Returns the decoded message if it is of type T
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.value.downcast_ref()
+    }
+}
+
+type Decoder = fn(&[u8]) -> Result<(Box<dyn StdAny + Send + Sync>, String), DecodeError>;
+
+fn decode_as<T>(bytes: &[u8]) -> Result<(Box<dyn StdAny + Send + Sync>, String), DecodeError>
+where
+    T: prost::Message + Default + Debug + Send + Sync + 'static,
+{
+    let value = T::decode(bytes)?;
+    let description = format!("{:?}", value);
+    Ok((Box::new(value), description))
+}
+
+/// Renders an encoded message as Amino JSON
+type AminoJsonEncoder = Arc<dyn Fn(&[u8]) -> Result<Value, DecodeError> + Send + Sync>;
+
+#[derive(Clone)]
+struct RegistryEntry {
+    amino_name: Option<String>,
+    decoder: Decoder,
+    amino_json: Option<AminoJsonEncoder>,
+}
+
+/// Maps type urls to prost types and optionally their legacy amino names
+#[derive(Clone, Default)]
+pub struct MsgRegistry {
+    entries: HashMap<String, RegistryEntry>,
+}
+
+impl Debug for MsgRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut type_urls: Vec<&String> = self.entries.keys().collect();
+        type_urls.sort();
+        f.debug_struct("MsgRegistry")
+            .field("type_urls", &type_urls)
+            .finish()
+    }
+}
+
+impl MsgRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        MsgRegistry::default()
+    }
+
+    /// Creates a registry containing the common Cosmos SDK messages
+    pub fn with_defaults() -> Self {
+        let mut registry = MsgRegistry::new();
+        registry.register_amino_json::<MsgSend>(
+            MSG_SEND_TYPE_URL,
+            "cosmos-sdk/MsgSend",
+            amino::msg_send_json,
+        );
+        registry.register_amino_json::<MsgMultiSend>(
+            MSG_MULTI_SEND_TYPE_URL,
+            "cosmos-sdk/MsgMultiSend",
+            amino::msg_multi_send_json,
+        );
+        registry.register_amino_json::<MsgDelegate>(
+            MSG_DELEGATE_TYPE_URL,
+            "cosmos-sdk/MsgDelegate",
+            amino::msg_delegate_json,
+        );
+        registry.register_amino_json::<MsgUndelegate>(
+            MSG_UNDELEGATE_TYPE_URL,
+            "cosmos-sdk/MsgUndelegate",
+            amino::msg_undelegate_json,
+        );
+        registry.register_amino_json::<MsgBeginRedelegate>(
+            MSG_BEGIN_REDELEGATE_TYPE_URL,
+            "cosmos-sdk/MsgBeginRedelegate",
+            amino::msg_begin_redelegate_json,
+        );
+        registry.register_amino_json::<MsgWithdrawDelegatorReward>(
+            MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL,
+            "cosmos-sdk/MsgWithdrawDelegationReward",
+            amino::msg_withdraw_delegator_reward_json,
+        );
+        // the proposal content is an Any, rendering it needs the content types as well
+        registry.register::<MsgSubmitProposal>(
+            MSG_SUBMIT_PROPOSAL_TYPE_URL,
+            Some("cosmos-sdk/MsgSubmitProposal"),
+        );
+        registry.register_amino_json::<MsgDeposit>(
+            MSG_DEPOSIT_TYPE_URL,
+            "cosmos-sdk/MsgDeposit",
+            amino::msg_deposit_json,
+        );
+        registry.register_amino_json::<MsgVote>(
+            MSG_VOTE_TYPE_URL,
+            "cosmos-sdk/MsgVote",
+            amino::msg_vote_json,
+        );
+        registry
+    }
+
+    /// Registers a prost type under the provided type url, replacing any existing entry
+    pub fn register<T>(&mut self, type_url: impl Into<String>, amino_name: Option<&str>)
+    where
+        T: prost::Message + Default + Debug + Send + Sync + 'static,
+    {
+        self.entries.insert(
+            type_url.into(),
+            RegistryEntry {
+                amino_name: amino_name.map(|n| n.to_string()),
+                decoder: decode_as::<T>,
+                amino_json: None,
+            },
+        );
+    }
+
+    /// Registers a prost type like register, along with the function rendering its value as
+    /// legacy Amino JSON. This is required to render transactions containing the type with
+    /// amino::std_tx_json.
+    pub fn register_amino_json<T>(
+        &mut self,
+        type_url: impl Into<String>,
+        amino_name: &str,
+        to_json: fn(&T) -> Value,
+    ) where
+        T: prost::Message + Default + Debug + Send + Sync + 'static,
+    {
+        let encoder: AminoJsonEncoder = Arc::new(move |bytes| Ok(to_json(&T::decode(bytes)?)));
+        self.entries.insert(
+            type_url.into(),
+            RegistryEntry {
+                amino_name: Some(amino_name.to_string()),
+                decoder: decode_as::<T>,
+                amino_json: Some(encoder),
+            },
+        );
+    }
+
+    /// Returns true if a type is registered for the provided type url
+    pub fn is_registered(&self, type_url: &str) -> bool {
+        self.entries.contains_key(type_url)
+    }
+
+    /// Returns the legacy amino name registered for the provided type url
+    pub fn amino_name(&self, type_url: &str) -> Option<&str> {
+        self.entries
+            .get(type_url)
+            .and_then(|e| e.amino_name.as_deref())
+    }
+
+    /// Decodes an Any using the type registered for its type url
+    pub fn decode(&self, any: &Any) -> Result<DecodedMsg, CosmosGrpcError> {
+        match self.entries.get(&any.type_url) {
+            Some(entry) => {
+                let (value, description) = (entry.decoder)(&any.value)
+                    .map_err(|error| CosmosGrpcError::DecodeError { error })?;
+                Ok(DecodedMsg {
+                    type_url: any.type_url.clone(),
+                    value,
+                    description,
+                })
+            }
+            None => Err(CosmosGrpcError::BadInput(format!(
+                "No type registered for {}",
+                any.type_url
+            ))),
+        }
+    }
+
+    /// Renders an Any as legacy Amino JSON, an object of the amino name and the value
+    pub fn amino_json(&self, any: &Any) -> Result<Value, CosmosGrpcError> {
+        let entry = self.entries.get(&any.type_url);
+        match entry.and_then(|e| Some((e.amino_name.as_ref()?, e.amino_json.as_ref()?))) {
+            Some((amino_name, encoder)) => Ok(json!({
+                "type": amino_name,
+                "value": encoder(&any.value)?,
+            })),
+            None => Err(CosmosGrpcError::BadInput(format!(
+                "No amino JSON encoder registered for {}",
+                any.type_url
+            ))),
+        }
+    }
+
+    /// Decodes a Msg using the type registered for its type url
+    pub fn decode_msg(&self, msg: &Msg) -> Result<DecodedMsg, CosmosGrpcError> {
+        self.decode(&msg.0)
+    }
+
+    /// Decodes every message in the body of a transaction, failing if any message type
+    /// is not registered
+    pub fn decode_tx(&self, tx: &Tx) -> Result<Vec<DecodedMsg>, CosmosGrpcError> {
+        match &tx.body {
+            Some(body) => body.messages.iter().map(|m| self.decode(m)).collect(),
+            None => Err(CosmosGrpcError::BadResponse("Tx has no body?".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    struct MsgCustom {
+        #[prost(string, tag = "1")]
+        pub value: String,
+    }
+
+    #[test]
+    fn test_registry_decode() {
+        let mut registry = MsgRegistry::with_defaults();
+        let msg = Msg::new(
+            "/custom.v1.MsgCustom",
+            MsgCustom {
+                value: "hello".to_string(),
+            },
+        );
+        assert!(registry.decode_msg(&msg).is_err());
+
+        registry.register::<MsgCustom>("/custom.v1.MsgCustom", Some("custom/MsgCustom"));
+        assert_eq!(
+            registry.amino_name("/custom.v1.MsgCustom"),
+            Some("custom/MsgCustom")
+        );
+        let decoded = registry.decode_msg(&msg).unwrap();
+        assert_eq!(decoded.downcast_ref::<MsgCustom>().unwrap().value, "hello");
+        assert!(decoded.downcast_ref::<MsgSend>().is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! Compact human readable summaries of transactions, intended for logging and for operator
+//! dashboards that want to show what a transaction does without dumping the full protobuf
+
+use crate::any::AnyExt;
+use crate::registry::MsgRegistry;
+use crate::Coin;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgMultiSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::MsgWithdrawDelegatorReward;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgDelegate;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
+use prost_types::Any;
+use std::fmt;
+
+/// A summary of a single transaction message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MsgSummary {
+    pub type_url: String,
+    /// a one line description, such as `cosmos1... -> cosmos1...`, empty for message
+    /// types that are not known
+    pub description: String,
+    /// the coins moved by this message
+    pub amounts: Vec<Coin>,
+}
+
+impl fmt::Display for MsgSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the last segment of the type url is enough to identify a message when reading
+        let name = self.type_url.rsplit('.').next().unwrap_or(&self.type_url);
+        write!(f, "{}", name)?;
+        if !self.amounts.is_empty() {
+            write!(f, " {}", Coin::display_list(&self.amounts))?;
+        }
+        if !self.description.is_empty() {
+            write!(f, " {}", self.description)?;
+        }
+        Ok(())
+    }
+}
+
+/// A summary of a transaction, see summarize_tx
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TxSummary {
+    /// signer addresses taken from the messages, in order of first appearance. Signers
+    /// of message types that are not known are not included.
+    pub signers: Vec<String>,
+    pub messages: Vec<MsgSummary>,
+    pub fee: Vec<Coin>,
+    pub gas_limit: u64,
+    /// the fee granter if the fee is paid through a fee grant
+    pub fee_granter: Option<String>,
+    pub memo: String,
+}
+
+impl fmt::Display for TxSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "signers: [{}]", self.signers.join(", "))?;
+        let messages: Vec<String> = self.messages.iter().map(|m| m.to_string()).collect();
+        write!(f, " messages: [{}]", messages.join("; "))?;
+        write!(
+            f,
+            " fee: {} gas: {}",
+            Coin::display_list(&self.fee),
+            self.gas_limit
+        )?;
+        if let Some(granter) = &self.fee_granter {
+            write!(f, " granter: {}", granter)?;
+        }
+        if !self.memo.is_empty() {
+            write!(f, " memo: {:?}", self.memo)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts proto coins skipping any with invalid amounts, a summary should never fail
+/// because of a malformed coin
+fn coins(input: &[ProtoCoin]) -> Vec<Coin> {
+    input
+        .iter()
+        .filter_map(|c| Coin::from_proto(c.clone()).ok())
+        .collect()
+}
+
+fn optional_coin(input: &Option<ProtoCoin>) -> Vec<Coin> {
+    match input {
+        Some(coin) => coins(std::slice::from_ref(coin)),
+        None => Vec::new(),
+    }
+}
+
+/// Summarizes a message, returning the summary and the signers of the message
+fn summarize_msg(registry: &MsgRegistry, any: &Any) -> (MsgSummary, Vec<String>) {
+    let mut summary = MsgSummary {
+        type_url: any.type_url.clone(),
+        description: String::new(),
+        amounts: Vec::new(),
+    };
+    let decoded = match any.unpack_dynamic(registry) {
+        Ok(decoded) => decoded,
+        Err(_) => return (summary, Vec::new()),
+    };
+    let signers = if let Some(msg) = decoded.downcast_ref::<MsgSend>() {
+        summary.description = format!("{} -> {}", msg.from_address, msg.to_address);
+        summary.amounts = coins(&msg.amount);
+        vec![msg.from_address.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgMultiSend>() {
+        let inputs: Vec<&str> = msg.inputs.iter().map(|i| i.address.as_str()).collect();
+        let outputs: Vec<&str> = msg.outputs.iter().map(|o| o.address.as_str()).collect();
+        summary.description = format!("{} -> {}", inputs.join(","), outputs.join(","));
+        summary.amounts = msg.inputs.iter().flat_map(|i| coins(&i.coins)).collect();
+        msg.inputs.iter().map(|i| i.address.clone()).collect()
+    } else if let Some(msg) = decoded.downcast_ref::<MsgDelegate>() {
+        summary.description = format!("{} -> {}", msg.delegator_address, msg.validator_address);
+        summary.amounts = optional_coin(&msg.amount);
+        vec![msg.delegator_address.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgUndelegate>() {
+        summary.description = format!("{} <- {}", msg.delegator_address, msg.validator_address);
+        summary.amounts = optional_coin(&msg.amount);
+        vec![msg.delegator_address.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgBeginRedelegate>() {
+        summary.description = format!(
+            "{} {} -> {}",
+            msg.delegator_address, msg.validator_src_address, msg.validator_dst_address
+        );
+        summary.amounts = optional_coin(&msg.amount);
+        vec![msg.delegator_address.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgWithdrawDelegatorReward>() {
+        summary.description = format!("{} <- {}", msg.delegator_address, msg.validator_address);
+        vec![msg.delegator_address.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgSubmitProposal>() {
+        summary.description = msg.proposer.clone();
+        summary.amounts = coins(&msg.initial_deposit);
+        vec![msg.proposer.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgDeposit>() {
+        summary.description = format!("{} proposal {}", msg.depositor, msg.proposal_id);
+        summary.amounts = coins(&msg.amount);
+        vec![msg.depositor.clone()]
+    } else if let Some(msg) = decoded.downcast_ref::<MsgVote>() {
+        let option = match VoteOption::from_i32(msg.option) {
+            Some(option) => format!("{:?}", option),
+            None => msg.option.to_string(),
+        };
+        summary.description = format!("{} proposal {} {}", msg.voter, msg.proposal_id, option);
+        vec![msg.voter.clone()]
+    } else {
+        Vec::new()
+    };
+    (summary, signers)
+}
+
+/// Summarizes the signers, messages, fee and memo of a transaction. The common Cosmos SDK
+/// message types are described in full when they are in the registry, see
+/// MsgRegistry::with_defaults, other messages are listed by type url only.
+pub fn summarize_tx(registry: &MsgRegistry, tx: &Tx) -> TxSummary {
+    let mut summary = TxSummary::default();
+    if let Some(body) = &tx.body {
+        for any in body.messages.iter() {
+            let (msg, signers) = summarize_msg(registry, any);
+            for signer in signers {
+                if !signer.is_empty() && !summary.signers.contains(&signer) {
+                    summary.signers.push(signer);
+                }
+            }
+            summary.messages.push(msg);
+        }
+        summary.memo = body.memo.clone();
+    }
+    if let Some(fee) = tx.auth_info.as_ref().and_then(|a| a.fee.as_ref()) {
+        summary.fee = coins(&fee.amount);
+        summary.gas_limit = fee.gas_limit;
+        if !fee.granter.is_empty() {
+            summary.fee_granter = Some(fee.granter.clone());
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::bank::MSG_SEND_TYPE_URL;
+    use crate::Msg;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::AuthInfo;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee as ProtoFee;
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::TxBody;
+
+    fn proto_coin(amount: &str, denom: &str) -> ProtoCoin {
+        ProtoCoin {
+            amount: amount.to_string(),
+            denom: denom.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_tx() {
+        let send = Msg::new(
+            MSG_SEND_TYPE_URL,
+            MsgSend {
+                from_address: "cosmos1from".to_string(),
+                to_address: "cosmos1to".to_string(),
+                amount: vec![proto_coin("100", "uatom")],
+            },
+        );
+        let unknown = Msg::new("/custom.v1.MsgCustom", MsgSend::default());
+        let tx = Tx {
+            body: Some(TxBody {
+                messages: vec![send.0.clone(), unknown.0, send.0],
+                memo: "payroll".to_string(),
+                timeout_height: 0,
+                extension_options: Vec::new(),
+                non_critical_extension_options: Vec::new(),
+            }),
+            auth_info: Some(AuthInfo {
+                signer_infos: Vec::new(),
+                fee: Some(ProtoFee {
+                    amount: vec![proto_coin("500", "uatom")],
+                    gas_limit: 200_000,
+                    payer: String::new(),
+                    granter: String::new(),
+                }),
+            }),
+            signatures: Vec::new(),
+        };
+        let summary = summarize_tx(&MsgRegistry::with_defaults(), &tx);
+        assert_eq!(summary.signers, vec!["cosmos1from".to_string()]);
+        assert_eq!(summary.messages.len(), 3);
+        assert_eq!(
+            summary.messages[0].amounts,
+            vec!["100uatom".parse().unwrap()]
+        );
+        assert!(summary.messages[1].description.is_empty());
+        assert_eq!(
+            summary.to_string(),
+            "signers: [cosmos1from] messages: [MsgSend 100uatom cosmos1from -> cosmos1to; \
+             MsgCustom; MsgSend 100uatom cosmos1from -> cosmos1to] fee: 500uatom gas: 200000 \
+             memo: \"payroll\""
+        );
+        // messages missing from the registry are listed by type url only
+        let summary = summarize_tx(&MsgRegistry::new(), &tx);
+        assert!(summary.signers.is_empty());
+        assert!(summary.messages[0].description.is_empty());
+    }
+}
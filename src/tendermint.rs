@@ -0,0 +1,89 @@
+//! Conversions from the raw Tendermint protobuf blocks and headers returned by Contact to
+//! tendermint-rs domain types, so that its verification and serialization logic can be used
+//! on them. Only compiled if the tendermint feature is enabled.
+//!
+//! The tendermint version matches the tendermint-proto version deep_space is built on, so the
+//! conversions are the ones tendermint-rs provides and validate the same way.
+
+use crate::client::types::LatestBlock;
+use crate::error::CosmosGrpcError;
+use crate::Contact;
+use std::convert::TryFrom;
+use tendermint::block::Header as TendermintHeader;
+use tendermint::Block as TendermintBlock;
+use tendermint::Error as TendermintError;
+use tendermint_proto::types::Block;
+use tendermint_proto::types::Header;
+
+/// Converts a block, fails if the block is not valid, for example a missing header
+pub fn to_tendermint_block(block: Block) -> Result<TendermintBlock, TendermintError> {
+    TendermintBlock::try_from(block)
+}
+
+/// Converts a block header, fails if a hash or address has the wrong length
+pub fn to_tendermint_header(header: Header) -> Result<TendermintHeader, TendermintError> {
+    TendermintHeader::try_from(header)
+}
+
+/// Converts a block back to the protobuf type used by the rest of this crate
+pub fn from_tendermint_block(block: TendermintBlock) -> Block {
+    block.into()
+}
+
+fn invalid_block(error: TendermintError) -> CosmosGrpcError {
+    CosmosGrpcError::BadResponse(format!("Invalid block: {}", error))
+}
+
+impl Contact {
+    /// Gets the block at the provided height as a tendermint-rs Block
+    pub async fn get_tendermint_block_by_height(
+        &self,
+        height: u64,
+    ) -> Result<TendermintBlock, CosmosGrpcError> {
+        let block = self.get_block_by_height(height).await?;
+        to_tendermint_block(block).map_err(invalid_block)
+    }
+
+    /// Gets the latest block as a tendermint-rs Block, errors if the node is syncing or the
+    /// chain has not started
+    pub async fn get_latest_tendermint_block(&self) -> Result<TendermintBlock, CosmosGrpcError> {
+        match self.get_latest_block().await? {
+            LatestBlock::Latest { block } => to_tendermint_block(block).map_err(invalid_block),
+            LatestBlock::Syncing { .. } => Err(CosmosGrpcError::NodeNotSynced),
+            LatestBlock::WaitingToStart => Err(CosmosGrpcError::ChainNotRunning),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::Timestamp;
+    use tendermint_proto::version::Consensus;
+
+    #[test]
+    fn test_header_conversion() {
+        let header = Header {
+            version: Some(Consensus { block: 11, app: 0 }),
+            chain_id: "cosmoshub-4".to_string(),
+            height: 42,
+            time: Some(Timestamp {
+                seconds: 1_600_000_000,
+                nanos: 0,
+            }),
+            proposer_address: vec![7; 20],
+            ..Header::default()
+        };
+        let converted = to_tendermint_header(header.clone()).unwrap();
+        assert_eq!(converted.chain_id.as_str(), "cosmoshub-4");
+        assert_eq!(converted.height.value(), 42);
+
+        let bad = Header {
+            proposer_address: vec![7; 3],
+            ..header
+        };
+        assert!(to_tendermint_header(bad).is_err());
+        // a block has to have a header
+        assert!(to_tendermint_block(Block::default()).is_err());
+    }
+}
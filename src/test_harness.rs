@@ -0,0 +1,274 @@
+//! Integration test support, launches a single node chain in docker with a set of funded
+//! test keys and provides a ready Contact. Only compiled if the test-harness feature is
+//! enabled and requires the docker cli to be installed.
+//!
+//! ```ignore
+//! let localnet = Localnet::start(LocalnetConfig::simd()).await?;
+//! let contact = localnet.contact();
+//! let key = localnet.keys()[0];
+//! ```
+//!
+//! The container is removed when the Localnet is dropped.
+
+use crate::client::runtime::sleep;
+use crate::client::ChainStatus;
+use crate::error::CosmosGrpcError;
+use crate::Contact;
+use crate::PrivateKey;
+use std::net::TcpListener;
+use std::process::Command;
+use std::time::Duration;
+use std::time::Instant;
+
+/// A widely used BIP39 test mnemonic, never use it for real funds
+pub const DEFAULT_TEST_MNEMONIC: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// The gRPC port inside the container
+const CONTAINER_GRPC_PORT: u16 = 9090;
+
+/// The chain and funding setup of a localnet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalnetConfig {
+    /// the docker image to run, it must contain the chain binary and sh
+    pub image: String,
+    /// the chain binary inside the image, such as gaiad or simd
+    pub binary: String,
+    pub chain_id: String,
+    pub prefix: String,
+    /// the staking and fee denom
+    pub denom: String,
+    /// the genesis mnemonic, the validator uses the first key and the funded test keys
+    /// the following key_count keys of the m/44'/118'/0'/0/n path
+    pub mnemonic: String,
+    pub key_count: u32,
+    /// the genesis balance of the validator and every test key
+    pub balance: u128,
+    /// how long to wait for the chain to produce blocks
+    pub startup_timeout: Duration,
+}
+
+impl LocalnetConfig {
+    fn new(image: &str, binary: &str, prefix: &str, denom: &str) -> Self {
+        LocalnetConfig {
+            image: image.to_string(),
+            binary: binary.to_string(),
+            chain_id: "localnet-1".to_string(),
+            prefix: prefix.to_string(),
+            denom: denom.to_string(),
+            mnemonic: DEFAULT_TEST_MNEMONIC.to_string(),
+            key_count: 5,
+            balance: 1_000_000_000_000_000,
+            startup_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// A Cosmos Hub node
+    pub fn gaia() -> Self {
+        LocalnetConfig::new("ghcr.io/cosmos/gaia:v14.1.0", "gaiad", "cosmos", "uatom")
+    }
+
+    /// A Cosmos SDK simapp node
+    pub fn simd() -> Self {
+        LocalnetConfig::new("ghcr.io/cosmos/simapp:v0.47.5", "simd", "cosmos", "stake")
+    }
+
+    /// A Cronos node. Cronos only accepts Ethermint keys, the test keys are funded but
+    /// transactions signed with a PrivateKey will be rejected.
+    pub fn cronos() -> Self {
+        LocalnetConfig::new(
+            "ghcr.io/crypto-org-chain/cronos:v1.0.15",
+            "cronosd",
+            "crc",
+            "basetcro",
+        )
+    }
+
+    /// The funded test keys
+    pub fn keys(&self) -> Result<Vec<PrivateKey>, CosmosGrpcError> {
+        let mut keys = Vec::new();
+        for n in 1..=self.key_count {
+            let path = format!("m/44'/118'/0'/0/{}", n);
+            keys.push(PrivateKey::from_hd_wallet_path(&path, &self.mnemonic, "")?);
+        }
+        Ok(keys)
+    }
+}
+
+/// Builds the shell script that creates the genesis and starts the node, the mnemonic is
+/// passed as the MNEMONIC environment variable so it does not need to be quoted
+fn genesis_script(config: &LocalnetConfig, funded: &[String]) -> String {
+    let bin = format!("{} --home /chain", config.binary);
+    let amount = format!("{}{}", config.balance, config.denom);
+    let stake = format!("{}{}", config.balance / 2, config.denom);
+    // Cosmos SDK v0.47 moved the genesis commands under the genesis subcommand, the
+    // older form is tried if the new one does not exist
+    format!(
+        r#"set -e
+{bin} init localnet --chain-id {chain_id} > /dev/null 2>&1
+sed -i 's/"stake"/"{denom}"/g' /chain/config/genesis.json
+echo "$MNEMONIC" | {bin} keys add validator --recover --keyring-backend test > /dev/null 2>&1
+for ADDR in $({bin} keys show validator -a --keyring-backend test) {funded}; do
+  {bin} genesis add-genesis-account $ADDR {amount} 2> /dev/null || {bin} add-genesis-account $ADDR {amount}
+done
+{bin} genesis gentx validator {stake} --chain-id {chain_id} --keyring-backend test 2> /dev/null || {bin} gentx validator {stake} --chain-id {chain_id} --keyring-backend test
+{bin} genesis collect-gentxs > /dev/null 2>&1 || {bin} collect-gentxs > /dev/null 2>&1
+sed -i 's/timeout_commit = "5s"/timeout_commit = "1s"/' /chain/config/config.toml
+exec {bin} start --minimum-gas-prices 0{denom} --grpc.address 0.0.0.0:{port}
+"#,
+        bin = bin,
+        chain_id = config.chain_id,
+        denom = config.denom,
+        funded = funded.join(" "),
+        amount = amount,
+        stake = stake,
+        port = CONTAINER_GRPC_PORT,
+    )
+}
+
+/// Runs a docker command returning its trimmed stdout
+fn docker(args: &[&str]) -> Result<String, CosmosGrpcError> {
+    let output = Command::new("docker")
+        .args(args)
+        .output()
+        .map_err(|e| CosmosGrpcError::BadInput(format!("Failed to run docker: {}", e)))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(CosmosGrpcError::BadResponse(format!(
+            "docker {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Finds a free local port for the gRPC endpoint
+fn free_port() -> Result<u16, CosmosGrpcError> {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| CosmosGrpcError::BadInput(format!("No free port: {}", e)))
+}
+
+/// A running single node chain, removed when dropped
+pub struct Localnet {
+    config: LocalnetConfig,
+    container_id: String,
+    contact: Contact,
+    keys: Vec<PrivateKey>,
+}
+
+impl Localnet {
+    /// Starts the chain and waits until it has produced a block
+    pub async fn start(config: LocalnetConfig) -> Result<Localnet, CosmosGrpcError> {
+        let keys = config.keys()?;
+        let mut funded = Vec::new();
+        for key in keys.iter() {
+            funded.push(key.to_address(&config.prefix)?.to_string());
+        }
+        let port = free_port()?;
+        let url = format!("http://127.0.0.1:{}", port);
+        let contact = Contact::new(&url, Duration::from_secs(30), &config.prefix)?;
+        let script = genesis_script(&config, &funded);
+        let container_id = docker(&[
+            "run",
+            "-d",
+            "--rm",
+            "-p",
+            &format!("127.0.0.1:{}:{}", port, CONTAINER_GRPC_PORT),
+            "-e",
+            &format!("MNEMONIC={}", config.mnemonic),
+            "--entrypoint",
+            "sh",
+            &config.image,
+            "-c",
+            &script,
+        ])?;
+
+        let localnet = Localnet {
+            contact,
+            config,
+            container_id,
+            keys,
+        };
+        // dropping the localnet on error removes the container
+        localnet.wait_for_start().await?;
+        Ok(localnet)
+    }
+
+    async fn wait_for_start(&self) -> Result<(), CosmosGrpcError> {
+        let start = Instant::now();
+        while Instant::now() - start < self.config.startup_timeout {
+            if let Ok(ChainStatus::Moving { block_height }) = self.contact.get_chain_status().await
+            {
+                if block_height >= 1 {
+                    return Ok(());
+                }
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        Err(CosmosGrpcError::NoBlockProduced {
+            time: self.config.startup_timeout,
+        })
+    }
+
+    /// A Contact connected to the node
+    pub fn contact(&self) -> &Contact {
+        &self.contact
+    }
+
+    /// The funded test keys, the validator key is not included
+    pub fn keys(&self) -> &[PrivateKey] {
+        &self.keys
+    }
+
+    /// The key of the only validator, derived from the first key of the mnemonic
+    pub fn validator_key(&self) -> Result<PrivateKey, CosmosGrpcError> {
+        Ok(PrivateKey::from_phrase(&self.config.mnemonic, "")?)
+    }
+
+    pub fn config(&self) -> &LocalnetConfig {
+        &self.config
+    }
+
+    /// The logs of the node, useful when a test fails
+    pub fn logs(&self) -> Result<String, CosmosGrpcError> {
+        let output = Command::new("docker")
+            .args(&["logs", &self.container_id])
+            .output()
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Failed to run docker: {}", e)))?;
+        Ok(format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+impl Drop for Localnet {
+    fn drop(&mut self) {
+        if let Err(e) = docker(&["rm", "-f", &self.container_id]) {
+            error!("Failed to remove localnet container {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genesis_script() {
+        let config = LocalnetConfig::gaia();
+        let keys = config.keys().unwrap();
+        assert_eq!(keys.len(), 5);
+        let address = keys[0].to_address("cosmos").unwrap().to_string();
+        let script = genesis_script(&config, &[address.clone()]);
+        assert!(script.contains(&address));
+        assert!(script.contains("--chain-id localnet-1"));
+        assert!(script.contains("exec gaiad --home /chain start --minimum-gas-prices 0uatom"));
+        // the mnemonic is passed through the environment
+        assert!(!script.contains(&config.mnemonic));
+    }
+}
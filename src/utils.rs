@@ -1,7 +1,14 @@
 use crate::error::{ArrayStringError, ByteDecodeError};
+use crate::proto::cosmos::base::abci::v1beta1::TxResponse;
 use crate::Coin;
-use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use crate::Coins;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::AuthInfo;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee as ProtoFee;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+use num256::Uint256;
+use prost::Message;
 use prost_types::Any;
+use sha2::{Digest, Sha256};
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
@@ -32,6 +39,24 @@ pub fn bytes_to_hex_str(bytes: &[u8]) -> String {
         .fold(String::new(), |acc, x| acc + &x)
 }
 
+/// Returns the hash of encoded transaction bytes, as used by the chain to identify the tx
+pub fn tx_hash(tx_bytes: &[u8]) -> String {
+    bytes_to_hex_str(&Sha256::digest(tx_bytes)).to_uppercase()
+}
+
+/// The sequence of the first signer of an encoded TxRaw, None if it can not be decoded
+pub fn tx_sequence(tx_bytes: &[u8]) -> Option<u64> {
+    let raw = TxRaw::decode(tx_bytes).ok()?;
+    let auth_info = AuthInfo::decode(raw.auth_info_bytes.as_slice()).ok()?;
+    auth_info.signer_infos.first().map(|signer| signer.sequence)
+}
+
+/// The fee of an encoded TxRaw, None if it can not be decoded
+pub fn tx_fee(tx_bytes: &[u8]) -> Option<ProtoFee> {
+    let raw = TxRaw::decode(tx_bytes).ok()?;
+    AuthInfo::decode(raw.auth_info_bytes.as_slice()).ok()?.fee
+}
+
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Deserialize, Serialize)]
 pub struct ArrayString {
     chars: [Option<char>; ArrayString::MAX_LEN],
@@ -82,11 +107,33 @@ pub fn contains_non_hex_chars(input: &str) -> bool {
     false
 }
 
+/// Gas and fee details of a transaction, from a simulation or a rejected broadcast
+#[derive(PartialEq, Eq, Clone, Hash, Deserialize, Serialize, Debug, Default)]
+pub struct GasDetails {
+    pub gas_wanted: u64,
+    pub gas_used: u64,
+    /// the gas used by each message when simulated on its own, including the fixed
+    /// per transaction overhead. Empty if not measured.
+    pub msg_gas: Vec<u64>,
+    /// the fee required by the node's minimum gas prices, one coin per accepted
+    /// fee denom. Empty if unknown.
+    pub required_fees: Vec<Coin>,
+}
+
 /// An enum
+///
+/// Both variants carry the GasDetails of the rejected transaction. This changed the shape of
+/// the variants and breaks code that constructs them or matches them without `..`.
 #[derive(PartialEq, Eq, Clone, Hash, Deserialize, Serialize, Debug)]
 pub enum FeeInfo {
-    InsufficientFees { min_fees: Vec<Coin> },
-    InsufficientGas { amount: u64 },
+    InsufficientFees {
+        min_fees: Vec<Coin>,
+        details: GasDetails,
+    },
+    InsufficientGas {
+        amount: u64,
+        details: GasDetails,
+    },
 }
 
 /// Returns what fee related problem is keeping your tx from running, you may need
@@ -104,7 +151,16 @@ pub fn determine_min_fees_and_gas(input: &TxResponse) -> Option<FeeInfo> {
                     coins.push(coin);
                 }
             }
-            Some(FeeInfo::InsufficientFees { min_fees: coins })
+            let details = GasDetails {
+                gas_wanted: input.gas_wanted as u64,
+                gas_used: input.gas_used as u64,
+                msg_gas: Vec::new(),
+                required_fees: coins.clone(),
+            };
+            Some(FeeInfo::InsufficientFees {
+                min_fees: coins,
+                details,
+            })
         } else {
             error!("Failed parsing insufficient fee error, probably changed gRPC error message response");
             None
@@ -112,12 +168,85 @@ pub fn determine_min_fees_and_gas(input: &TxResponse) -> Option<FeeInfo> {
     } else if input.gas_used > input.gas_wanted {
         Some(FeeInfo::InsufficientGas {
             amount: input.gas_used as u64,
+            details: GasDetails {
+                gas_wanted: input.gas_wanted as u64,
+                gas_used: input.gas_used as u64,
+                ..GasDetails::default()
+            },
         })
     } else {
         None
     }
 }
 
+/// The denom a transaction ran out of, with the amount it needed and the amount the
+/// account could spend
+#[derive(PartialEq, Eq, Clone, Hash, Debug)]
+pub struct FundsInfo {
+    pub denom: String,
+    pub required: Uint256,
+    pub available: Uint256,
+}
+
+/// Parses an insufficient funds error out of a failed transaction, either from a message
+/// such as a send, `90uatom is smaller than 100uatom: insufficient funds`, or from paying
+/// the fee, `insufficient funds to pay for fees; 90uatom < 100uatom: insufficient funds`.
+/// Returns None if the transaction did not fail for lack of funds.
+pub fn determine_insufficient_funds(input: &TxResponse) -> Option<FundsInfo> {
+    parse_insufficient_funds(&input.raw_log)
+}
+
+/// Parses an insufficient funds error out of a log or error message, see
+/// determine_insufficient_funds for the formats understood
+pub fn parse_insufficient_funds(log: &str) -> Option<FundsInfo> {
+    let log = &log[..log.rfind(": insufficient funds")?];
+    let (available, required) = if let Some(idx) = log.find("insufficient funds to pay for fees;") {
+        let amounts = &log[idx + "insufficient funds to pay for fees;".len()..];
+        let mut split = amounts.split(" < ");
+        (split.next()?, split.next()?)
+    } else {
+        let mut split = log.split(" is smaller than ");
+        let available = split.next()?;
+        // strip wrapping such as "failed to execute message; message index: 0:" and the
+        // "spendable balance" prefix of newer Cosmos SDK versions
+        let available = available.rsplit(':').next()?.trim();
+        let available = available.trim_start_matches("spendable balance");
+        (available, split.next()?)
+    };
+    let available: Coins = available.trim().parse().ok()?;
+    let required: Coins = required.trim().parse().ok()?;
+    required
+        .iter()
+        .find(|coin| available.amount_of(&coin.denom) < coin.amount)
+        .map(|coin| FundsInfo {
+            denom: coin.denom.clone(),
+            required: coin.amount.clone(),
+            available: available.amount_of(&coin.denom),
+        })
+}
+
+/// Parses the lowest stored height out of the error Tendermint returns for blocks the node
+/// has pruned, "height 5 is not available, lowest height is 1000"
+pub fn parse_lowest_height(message: &str) -> Option<u64> {
+    let idx = message.find("is not available, lowest height is ")?;
+    let lowest = &message[idx + "is not available, lowest height is ".len()..];
+    let digits = lowest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or_else(|| lowest.len());
+    lowest[..digits].parse().ok()
+}
+
+/// Parses the sequence the node expected out of a sequence mismatch error, "account sequence
+/// mismatch, expected 5, got 7: incorrect account sequence"
+pub fn parse_expected_sequence(message: &str) -> Option<u64> {
+    let idx = message.find("account sequence mismatch, expected ")?;
+    let expected = &message[idx + "account sequence mismatch, expected ".len()..];
+    let digits = expected
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or_else(|| expected.len());
+    expected[..digits].parse().ok()
+}
+
 /// Checks a tx response raw_log for known issues returns true if tx is good, false if the tx
 /// has some known error
 pub fn check_tx_response(input: &TxResponse) -> bool {
@@ -147,6 +276,75 @@ mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    #[test]
+    fn test_determine_insufficient_funds() {
+        let response = |raw_log: &str| TxResponse {
+            code: 5,
+            codespace: "sdk".to_string(),
+            raw_log: raw_log.to_string(),
+            ..TxResponse::default()
+        };
+        let expected = FundsInfo {
+            denom: "uatom".to_string(),
+            required: 100u64.into(),
+            available: 90u64.into(),
+        };
+        let logs = [
+            "failed to execute message; message index: 0: 90uatom,5ufoo is smaller than 100uatom: insufficient funds",
+            "spendable balance 90uatom is smaller than 100uatom: insufficient funds",
+            "insufficient funds to pay for fees; 90uatom < 100uatom: insufficient funds",
+            "5ufoo,90uatom is smaller than 1ufoo,100uatom: insufficient funds",
+        ];
+        for log in logs.iter() {
+            assert_eq!(
+                determine_insufficient_funds(&response(log)),
+                Some(expected.clone()),
+                "{}",
+                log
+            );
+        }
+        // nothing of the denom held
+        assert_eq!(
+            determine_insufficient_funds(&response(
+                "spendable balance  is smaller than 100uatom: insufficient funds"
+            ))
+            .unwrap()
+            .available,
+            0u64.into()
+        );
+        assert_eq!(
+            determine_insufficient_funds(&response("account sequence mismatch")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_lowest_height() {
+        assert_eq!(
+            parse_lowest_height("height 5 is not available, lowest height is 1000"),
+            Some(1000)
+        );
+        assert_eq!(
+            parse_lowest_height("rpc error: height 5 is not available, lowest height is 77: x"),
+            Some(77)
+        );
+        assert_eq!(
+            parse_lowest_height("height 5 must be less than or equal to 4"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_expected_sequence() {
+        assert_eq!(
+            parse_expected_sequence(
+                "account sequence mismatch, expected 5, got 7: incorrect account sequence"
+            ),
+            Some(5)
+        );
+        assert_eq!(parse_expected_sequence("incorrect account sequence"), None);
+    }
+
     #[test]
     fn test_determine_fees() {
         let below_min_fees_tx_response = TxResponse {
@@ -160,24 +358,65 @@ mod tests {
             info: String::new(),
             gas_used: 0,
             gas_wanted: 0,
-            tx: None,
-            timestamp: String::new(),
+            ..TxResponse::default()
         };
-        let correct_output = Some(FeeInfo::InsufficientFees {
-            min_fees: vec![
-                Coin {
-                    denom: "ualtg".to_string(),
-                    amount: 50000u64.into(),
-                },
-                Coin {
-                    denom: "ufootoken".to_string(),
-                    amount: 250000u64.into(),
+        let correct_output = vec![
+            Coin {
+                denom: "ualtg".to_string(),
+                amount: 50000u64.into(),
+            },
+            Coin {
+                denom: "ufootoken".to_string(),
+                amount: 250000u64.into(),
+            },
+        ];
+        match determine_min_fees_and_gas(&below_min_fees_tx_response) {
+            Some(FeeInfo::InsufficientFees { min_fees, .. }) => {
+                assert_eq!(min_fees, correct_output)
+            }
+            other => panic!("Unexpected fee info {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_determine_fees_details() {
+        let response = TxResponse {
+            codespace: "sdk".to_string(),
+            code: 13,
+            raw_log: "insufficient fees; got: 1uatom required: 5000uatom: insufficient fee"
+                .to_string(),
+            gas_wanted: 200_000,
+            ..TxResponse::default()
+        };
+        let min_fees = vec!["5000uatom".parse::<Coin>().unwrap()];
+        assert_eq!(
+            determine_min_fees_and_gas(&response),
+            Some(FeeInfo::InsufficientFees {
+                min_fees: min_fees.clone(),
+                details: GasDetails {
+                    gas_wanted: 200_000,
+                    gas_used: 0,
+                    msg_gas: Vec::new(),
+                    required_fees: min_fees,
                 },
-            ],
-        });
+            })
+        );
+
+        let out_of_gas = TxResponse {
+            gas_wanted: 100_000,
+            gas_used: 120_000,
+            ..TxResponse::default()
+        };
         assert_eq!(
-            determine_min_fees_and_gas(&below_min_fees_tx_response),
-            correct_output
+            determine_min_fees_and_gas(&out_of_gas),
+            Some(FeeInfo::InsufficientGas {
+                amount: 120_000,
+                details: GasDetails {
+                    gas_wanted: 100_000,
+                    gas_used: 120_000,
+                    ..GasDetails::default()
+                },
+            })
         );
     }
 }